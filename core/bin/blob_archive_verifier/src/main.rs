@@ -0,0 +1,109 @@
+//! Tool verifying EIP-4844 blob sidecars archived by the eth sender against KZG commitments
+//! included in L1 commit transactions.
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::ObservabilityConfig, ETHClientConfig, ObjectStoreConfig, PostgresConfig,
+};
+use zksync_core::eth_sender::{BlobArchiveStatus, BlobArchiveVerifier};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_env_config::FromEnv;
+use zksync_eth_client::clients::l1_client;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Verifier of archived EIP-4844 blob sidecars",
+    long_about = None
+)]
+struct Cli {
+    /// First L1 batch to verify.
+    #[arg(long = "from-l1-batch")]
+    from_l1_batch: u32,
+    /// Last L1 batch to verify (inclusive). If not specified, all batches up to the last batch
+    /// committed on L1 are verified.
+    #[arg(long = "to-l1-batch")]
+    to_l1_batch: Option<u32>,
+}
+
+impl Cli {
+    async fn run(
+        self,
+        pool: ConnectionPool<Core>,
+        verifier: &BlobArchiveVerifier,
+    ) -> anyhow::Result<()> {
+        let to_l1_batch = if let Some(number) = self.to_l1_batch {
+            L1BatchNumber(number)
+        } else {
+            let mut storage = pool.connection().await?;
+            let last_committed = storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_committed_on_eth()
+                .await?;
+            let Some(last_committed) = last_committed else {
+                tracing::info!("No L1 batches are committed on L1, skipping");
+                return Ok(());
+            };
+            last_committed
+        };
+
+        let mut failed_count = 0;
+        for number in self.from_l1_batch..=to_l1_batch.0 {
+            let l1_batch_number = L1BatchNumber(number);
+            let status = verifier.verify_l1_batch(l1_batch_number).await?;
+            match &status {
+                BlobArchiveStatus::Verified { .. } | BlobArchiveStatus::NotCommitted => {
+                    tracing::info!("L1 batch #{l1_batch_number}: {status}");
+                }
+                BlobArchiveStatus::MissingSidecar | BlobArchiveStatus::Mismatch(_) => {
+                    tracing::error!("L1 batch #{l1_batch_number}: {status}");
+                    failed_count += 1;
+                }
+            }
+        }
+        anyhow::ensure!(
+            failed_count == 0,
+            "Blob archive verification failed for {failed_count} L1 batch(es)"
+        );
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let object_store_config =
+        ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
+    let eth_client_config = ETHClientConfig::from_env().context("ETHClientConfig::from_env()")?;
+
+    let pool = ConnectionPool::<Core>::singleton(postgres_config.replica_url()?)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    let blob_store = ObjectStoreFactory::new(object_store_config)
+        .create_store()
+        .await;
+    let l1_client = l1_client(&eth_client_config).context("l1_client()")?;
+    let verifier = BlobArchiveVerifier::new(pool.clone(), blob_store, l1_client)?;
+
+    Cli::parse().run(pool, &verifier).await
+}
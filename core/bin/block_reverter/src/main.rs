@@ -3,13 +3,21 @@ use clap::{Parser, Subcommand};
 use tokio::io::{self, AsyncReadExt};
 use zksync_config::{
     configs::ObservabilityConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig,
-    PostgresConfig,
+    ObjectStoreConfig, PostgresConfig,
 };
-use zksync_core::block_reverter::{
-    BlockReverter, BlockReverterEthConfig, BlockReverterFlags, L1ExecutedBatchesRevert, NodeRole,
+use zksync_core::{
+    block_reverter::{
+        BlockReverter, BlockReverterEthConfig, BlockReverterFlags, L1ExecutedBatchesRevert,
+        NodeRole,
+    },
+    eth_watch::client::EthHttpQueryClient,
+    metadata_calculator::{export_tree_checkpoint, TreeCheckpointStorage},
+    new_horizen::NhAttestationReconciler,
 };
 use zksync_dal::{ConnectionPool, Core};
 use zksync_env_config::FromEnv;
+use zksync_eth_client::clients::l1_client;
+use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{L1BatchNumber, U256};
 
 #[derive(Debug, Parser)]
@@ -67,6 +75,26 @@ enum Command {
     /// Clears failed L1 transactions.
     #[command(name = "clear-failed-transactions")]
     ClearFailedL1Transactions,
+
+    /// Restores NewHorizen attestations missed by the Ethereum watcher by re-scanning L1 logs.
+    #[command(name = "reconcile-nh-attestations")]
+    ReconcileNhAttestations {
+        /// First L1 block to scan for `AttestationPosted` events.
+        #[arg(long)]
+        from_block: u64,
+        /// Last L1 block (inclusive) to scan for `AttestationPosted` events.
+        #[arg(long)]
+        to_block: u64,
+    },
+
+    /// Exports a Merkle tree checkpoint to the checkpoint storage specified in the Merkle tree config.
+    /// The tree must not be used by a running node.
+    #[command(name = "export-tree-checkpoint")]
+    ExportTreeCheckpoint {
+        /// L1 batch to export the checkpoint for. If not specified, the latest L1 batch processed by the tree is used.
+        #[arg(long)]
+        l1_batch_number: Option<u32>,
+    },
 }
 
 #[tokio::main]
@@ -89,11 +117,14 @@ async fn main() -> anyhow::Result<()> {
 
     let eth_sender = ETHSenderConfig::from_env().context("ETHSenderConfig::from_env()")?;
     let db_config = DBConfig::from_env().context("DBConfig::from_env()")?;
+    let merkle_tree_config = db_config.merkle_tree.clone();
     let eth_client = ETHClientConfig::from_env().context("ETHClientConfig::from_env()")?;
     let default_priority_fee_per_gas =
         U256::from(eth_sender.gas_adjuster.default_priority_fee_per_gas);
     let contracts = ContractsConfig::from_env().context("ContractsConfig::from_env()")?;
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let nh_verifier_addr = contracts.nh_verifier_addr;
+    let diamond_proxy_addr = contracts.diamond_proxy_addr;
     let config = BlockReverterEthConfig::new(eth_sender, contracts, eth_client.web3_url.clone());
 
     let connection_pool = ConnectionPool::<Core>::builder(
@@ -108,7 +139,7 @@ async fn main() -> anyhow::Result<()> {
         db_config.state_keeper_db_path,
         db_config.merkle_tree.path,
         Some(config),
-        connection_pool,
+        connection_pool.clone(),
         L1ExecutedBatchesRevert::Disallowed,
     );
 
@@ -189,6 +220,67 @@ async fn main() -> anyhow::Result<()> {
                 .await
         }
         Command::ClearFailedL1Transactions => block_reverter.clear_failed_l1_transactions().await,
+        Command::ReconcileNhAttestations {
+            from_block,
+            to_block,
+        } => {
+            anyhow::ensure!(
+                from_block <= to_block,
+                "Invalid L1 block range: {from_block}..={to_block}"
+            );
+            let query_client = l1_client(&eth_client).context("failed creating L1 client")?;
+            let eth_client = EthHttpQueryClient::new(
+                query_client,
+                diamond_proxy_addr,
+                None,
+                None,
+                nh_verifier_addr,
+            );
+            let reconciler = NhAttestationReconciler::new(connection_pool, Box::new(eth_client));
+            let reconciliation = reconciler.reconcile(from_block, to_block).await?;
+
+            println!(
+                "Missing attestations: {:?}",
+                reconciliation.missing_attestation_ids
+            );
+            for attestation in &reconciliation.restored_attestations {
+                println!(
+                    "Restored attestation #{}: {:?}",
+                    attestation.attestation_id, attestation.proofs_attestation
+                );
+            }
+            let unresolved: Vec<_> = reconciliation.unresolved_attestation_ids().collect();
+            if !unresolved.is_empty() {
+                println!(
+                    "Attestations not found in L1 blocks {from_block}..={to_block}: {unresolved:?}"
+                );
+            }
+        }
+        Command::ExportTreeCheckpoint { l1_batch_number } => {
+            let checkpoint_path = merkle_tree_config
+                .checkpoint_path
+                .clone()
+                .context("Merkle tree checkpoint path is not configured")?;
+            let object_store = if merkle_tree_config.checkpoints_in_object_store {
+                let object_store_config =
+                    ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
+                Some(
+                    ObjectStoreFactory::new(object_store_config)
+                        .create_store()
+                        .await,
+                )
+            } else {
+                None
+            };
+            let storage = TreeCheckpointStorage::new(checkpoint_path.into(), object_store);
+            let manifest = export_tree_checkpoint(
+                &merkle_tree_config,
+                &storage,
+                l1_batch_number.map(L1BatchNumber),
+            )
+            .await?;
+            println!("Exported Merkle tree checkpoint: {manifest:#?}");
+        }
     }
     Ok(())
 }
@@ -154,8 +154,22 @@ pub struct OptionalENConfig {
     /// values cache will be disabled.
     #[serde(default = "OptionalENConfig::default_latest_values_cache_size_mb")]
     latest_values_cache_size_mb: usize,
+    /// Path to the RocksDB directory used as a secondary disk-backed tier for the API server caches.
+    /// If not set, the disk tier is disabled.
+    pub vm_storage_disk_cache_path: Option<String>,
+    /// Maximum size of the disk-backed tier for the API server caches in MiBs. Once it's exceeded, cached data
+    /// is evicted from disk. Default value is 4,096 MiB.
+    #[serde(default = "OptionalENConfig::default_vm_storage_disk_cache_size_mb")]
+    vm_storage_disk_cache_size_mb: usize,
     /// Enabled JSON RPC API namespaces.
     api_namespaces: Option<Vec<Namespace>>,
+    /// Comma-separated addresses of additional Ethereum node APIs. If specified, L1 requests are distributed
+    /// among `EN_ETH_CLIENT_URL` and these URLs, failing over to another endpoint if the current one is unavailable.
+    #[serde(default)]
+    eth_client_fallback_urls: Vec<String>,
+    /// Minimum number of Ethereum node APIs that must return matching responses when reading transaction statuses
+    /// and logs. Can only be set together with `eth_client_fallback_urls`.
+    pub eth_client_read_quorum: Option<usize>,
     /// Whether to support HTTP methods that install filters and query filter changes.
     /// WS methods are unaffected.
     ///
@@ -227,6 +241,21 @@ pub struct OptionalENConfig {
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     #[serde(default = "OptionalENConfig::default_merkle_tree_stalled_writes_timeout_sec")]
     merkle_tree_stalled_writes_timeout_sec: u64,
+    /// Whether the Merkle tree should persist results for an L1 batch concurrently with processing the next L1 batch.
+    /// Can speed up tree updates if the tree lags behind the rest of the node. Uses an additional Postgres connection.
+    #[serde(default)]
+    pub merkle_tree_concurrent_persistence: bool,
+    /// Path to the directory with Merkle tree checkpoints. If set, an empty tree is bootstrapped from the latest
+    /// checkpoint instead of being built from scratch. If checkpoints are stored in the object store,
+    /// the directory is used to stage exported checkpoints.
+    pub merkle_tree_checkpoint_path: Option<String>,
+    /// Whether Merkle tree checkpoints are stored in the object store configured
+    /// with `EN_TREE_CHECKPOINTS_OBJECT_STORE_` env variables rather than in the checkpoint directory.
+    #[serde(default)]
+    pub merkle_tree_checkpoints_in_object_store: bool,
+    /// Interval (in L1 batches) between Merkle tree checkpoints exported by the node. If not set,
+    /// checkpoints are not exported.
+    pub merkle_tree_checkpoint_export_interval: Option<u32>,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -342,6 +371,10 @@ impl OptionalENConfig {
         128
     }
 
+    const fn default_vm_storage_disk_cache_size_mb() -> usize {
+        4_096
+    }
+
     const fn default_merkle_tree_multi_get_chunk_size() -> usize {
         500
     }
@@ -448,6 +481,20 @@ impl OptionalENConfig {
             .unwrap_or_else(|| Namespace::DEFAULT.to_vec())
     }
 
+    pub fn eth_client_fallback_urls(&self) -> anyhow::Result<Vec<String>> {
+        self.eth_client_fallback_urls
+            .iter()
+            .map(|url| {
+                RequiredENConfig::get_url(url)
+                    .with_context(|| format!("Could not parse fallback L1 client URL `{url}`"))
+            })
+            .collect()
+    }
+
+    pub fn vm_storage_disk_cache_size(&self) -> usize {
+        self.vm_storage_disk_cache_size_mb * BYTES_IN_MEGABYTE
+    }
+
     pub fn max_response_body_size(&self) -> usize {
         self.max_response_body_size_mb * BYTES_IN_MEGABYTE
     }
@@ -558,6 +605,14 @@ pub(crate) fn read_snapshots_recovery_config() -> anyhow::Result<SnapshotsRecove
     })
 }
 
+/// Reads the config for the object store with Merkle tree checkpoints. Loaded optionally, only if
+/// [`OptionalENConfig::merkle_tree_checkpoints_in_object_store`] is set.
+pub(crate) fn read_tree_checkpoints_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_TREE_CHECKPOINTS_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("failed loading tree checkpoints object store config from env variables")
+}
+
 /// External Node Config contains all the configuration required for the EN operation.
 /// It is split into three parts: required, optional and remote for easier navigation.
 #[derive(Debug, Clone)]
@@ -19,11 +19,19 @@ fn parsing_optional_config_from_empty_env() {
     assert_eq!(config.vm_concurrency_limit, 2_048);
     assert_eq!(config.factory_deps_cache_size(), 128 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 128 * BYTES_IN_MEGABYTE);
+    assert_eq!(
+        config.vm_storage_disk_cache_size(),
+        4_096 * BYTES_IN_MEGABYTE
+    );
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 500);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
         128 * BYTES_IN_MEGABYTE
     );
+    assert_eq!(config.merkle_tree_checkpoint_path, None);
+    assert!(config.eth_client_fallback_urls().unwrap().is_empty());
+    assert_eq!(config.eth_client_read_quorum, None);
+    assert_eq!(config.merkle_tree_checkpoint_export_interval, None);
     assert_eq!(config.max_response_body_size(), 10 * BYTES_IN_MEGABYTE);
     assert_eq!(
         config.l1_batch_commit_data_generator_mode,
@@ -46,8 +54,17 @@ fn parsing_optional_config_from_env() {
         ("EN_VM_CONCURRENCY_LIMIT", "1000"),
         ("EN_FACTORY_DEPS_CACHE_SIZE_MB", "64"),
         ("EN_LATEST_VALUES_CACHE_SIZE_MB", "50"),
+        ("EN_VM_STORAGE_DISK_CACHE_SIZE_MB", "1024"),
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
+        ("EN_MERKLE_TREE_CHECKPOINT_PATH", "/db/tree_checkpoints"),
+        (
+            "EN_ETH_CLIENT_FALLBACK_URLS",
+            "http://127.0.0.1:8546,https://l1.example.com/",
+        ),
+        ("EN_ETH_CLIENT_READ_QUORUM", "2"),
+        ("EN_MERKLE_TREE_CHECKPOINTS_IN_OBJECT_STORE", "true"),
+        ("EN_MERKLE_TREE_CHECKPOINT_EXPORT_INTERVAL", "1000"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
         ("EN_L1_BATCH_COMMIT_DATA_GENERATOR_MODE", "Validium"),
     ];
@@ -71,11 +88,26 @@ fn parsing_optional_config_from_env() {
     assert_eq!(config.vm_concurrency_limit, 1_000);
     assert_eq!(config.factory_deps_cache_size(), 64 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 50 * BYTES_IN_MEGABYTE);
+    assert_eq!(
+        config.vm_storage_disk_cache_size(),
+        1_024 * BYTES_IN_MEGABYTE
+    );
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 1_000);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
         32 * BYTES_IN_MEGABYTE
     );
+    assert_eq!(
+        config.merkle_tree_checkpoint_path.as_deref(),
+        Some("/db/tree_checkpoints")
+    );
+    assert!(config.merkle_tree_checkpoints_in_object_store);
+    assert_eq!(
+        config.eth_client_fallback_urls().unwrap(),
+        ["http://127.0.0.1:8546/", "https://l1.example.com:443/"]
+    );
+    assert_eq!(config.eth_client_read_quorum, Some(2));
+    assert_eq!(config.merkle_tree_checkpoint_export_interval, Some(1_000));
     assert_eq!(config.max_response_body_size(), BYTES_IN_MEGABYTE);
     assert_eq!(
         config.l1_batch_commit_data_generator_mode,
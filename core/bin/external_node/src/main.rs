@@ -9,8 +9,11 @@ use tokio::{
     task::{self, JoinHandle},
 };
 use zksync_concurrency::{ctx, limiter, scope, time};
-use zksync_config::configs::{
-    api::MerkleTreeApiConfig, chain::L1BatchCommitDataGeneratorMode, database::MerkleTreeMode,
+use zksync_config::{
+    configs::{
+        api::MerkleTreeApiConfig, chain::L1BatchCommitDataGeneratorMode, database::MerkleTreeMode,
+    },
+    ETHClientConfig,
 };
 use zksync_core::{
     api_server::{
@@ -29,7 +32,7 @@ use zksync_core::{
         ValidiumModeL1BatchCommitDataGenerator,
     },
     l1_gas_price::MainNodeFeeParamsFetcher,
-    metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig},
+    metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig, TreeCheckpointStorage},
     reorg_detector::{self, ReorgDetector},
     setup_sigint_handler,
     state_keeper::{
@@ -46,8 +49,9 @@ use zksync_dal::{metrics::PostgresMetrics, ConnectionPool, Core, CoreDal};
 use zksync_db_connection::{
     connection_pool::ConnectionPoolBuilder, healthcheck::ConnectionPoolHealthCheck,
 };
-use zksync_eth_client::clients::QueryClient;
+use zksync_eth_client::clients::l1_client;
 use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
+use zksync_object_store::ObjectStoreFactory;
 use zksync_state::PostgresStorageCaches;
 use zksync_storage::RocksDB;
 use zksync_types::L2ChainId;
@@ -55,7 +59,10 @@ use zksync_utils::wait_for_tasks::ManagedTasks;
 use zksync_web3_decl::jsonrpsee::http_client::HttpClient;
 
 use crate::{
-    config::{observability::observability_config_from_env, ExternalNodeConfig},
+    config::{
+        observability::observability_config_from_env, read_tree_checkpoints_object_store_config,
+        ExternalNodeConfig,
+    },
     helpers::MainNodeHealthCheck,
     init::ensure_storage_initialized,
 };
@@ -138,10 +145,29 @@ async fn run_tree(
         block_cache_capacity: config.optional.merkle_tree_block_cache_size(),
         memtable_capacity: config.optional.merkle_tree_memtable_capacity(),
         stalled_writes_timeout: config.optional.merkle_tree_stalled_writes_timeout(),
+        archive_mode: false,
+        archive_checkpoint_interval: 1_000,
+        pruning_retained_versions: None,
+        concurrent_persistence: config.optional.merkle_tree_concurrent_persistence,
+        checkpoint_export_interval: config.optional.merkle_tree_checkpoint_export_interval,
     };
-    let metadata_calculator = MetadataCalculator::new(metadata_calculator_config, None)
+    let mut metadata_calculator = MetadataCalculator::new(metadata_calculator_config, None)
         .await
         .context("failed initializing metadata calculator")?;
+    if let Some(checkpoint_path) = &config.optional.merkle_tree_checkpoint_path {
+        let object_store = if config.optional.merkle_tree_checkpoints_in_object_store {
+            let object_store_config = read_tree_checkpoints_object_store_config()?;
+            Some(
+                ObjectStoreFactory::new(object_store_config)
+                    .create_store()
+                    .await,
+            )
+        } else {
+            None
+        };
+        let checkpoint_storage = TreeCheckpointStorage::new(checkpoint_path.into(), object_store);
+        metadata_calculator = metadata_calculator.with_checkpoint_storage(checkpoint_storage);
+    }
     let tree_reader = Arc::new(metadata_calculator.tree_reader());
     app_health.insert_component(metadata_calculator.tree_health_check());
 
@@ -286,7 +312,13 @@ async fn run_core(
         .required
         .eth_client_url()
         .context("L1 client URL is incorrect")?;
-    let eth_client = QueryClient::new(&eth_client_url).unwrap();
+    let eth_client_config = ETHClientConfig {
+        chain_id: config.remote.l1_chain_id.0,
+        web3_url: eth_client_url,
+        fallback_web3_urls: config.optional.eth_client_fallback_urls()?,
+        read_quorum: config.optional.eth_client_read_quorum,
+    };
+    let eth_client = l1_client(&eth_client_config).context("failed creating L1 client")?;
 
     ensure_l1_batch_commit_data_generation_mode(
         config.optional.l1_batch_commit_data_generator_mode,
@@ -306,7 +338,7 @@ async fn run_core(
     };
 
     let consistency_checker = ConsistencyChecker::new(
-        Arc::new(eth_client),
+        eth_client,
         10, // TODO (BFT-97): Make it a part of a proper EN config
         singleton_pool_builder
             .build()
@@ -412,6 +444,16 @@ async fn run_api(
             config.optional.factory_deps_cache_size() as u64,
             config.optional.initial_writes_cache_size() as u64,
         );
+        if let Some(path) = &config.optional.vm_storage_disk_cache_path {
+            storage_caches
+                .configure_disk_cache(
+                    path.into(),
+                    config.optional.vm_storage_disk_cache_size() as u64,
+                    &connection_pool,
+                )
+                .await
+                .context("failed configuring VM storage disk cache")?;
+        }
         let latest_values_cache_size = config.optional.latest_values_cache_size() as u64;
         let cache_update_handle = (latest_values_cache_size > 0).then(|| {
             task::spawn(
@@ -529,8 +571,13 @@ async fn init_tasks(
 
     let singleton_pool_builder = ConnectionPool::singleton(&config.postgres.database_url);
 
-    // Run the components.
-    let tree_pool = singleton_pool_builder
+    // Run the components. The tree needs an additional connection to persist results concurrently with tree updates.
+    let tree_pool_size = if config.optional.merkle_tree_concurrent_persistence {
+        2
+    } else {
+        1
+    };
+    let tree_pool = ConnectionPool::builder(&config.postgres.database_url, tree_pool_size)
         .build()
         .await
         .context("failed to build a tree_pool")?;
@@ -11,8 +11,9 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, NewHorizenConfig,
-        ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig, WitnessGeneratorConfig,
+        DADispatcherConfig, FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig,
+        NewHorizenConfig, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
+        RemoteSignerConfig, WitnessGeneratorConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
     GasAdjusterConfig, GenesisConfig, ObjectStoreConfig, PostgresConfig,
@@ -140,6 +141,8 @@ async fn main() -> anyhow::Result<()> {
             object_store_config: ObjectStoreConfig::from_env().ok(),
             consensus_config: config::read_consensus_config().context("read_consensus_config()")?,
             new_horizen_config: NewHorizenConfig::from_env().ok(),
+            da_dispatcher_config: DADispatcherConfig::from_env().ok(),
+            remote_signer_config: RemoteSignerConfig::from_env().ok(),
         },
     };
     let secrets: Secrets = match opt.secrets_path {
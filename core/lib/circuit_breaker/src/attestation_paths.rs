@@ -0,0 +1,35 @@
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Trips if an NH attestation Merkle path for an L1 batch proof doesn't match the attestation root
+/// posted on L1, which would make the corresponding `proveBatches` transaction revert.
+#[derive(Debug)]
+pub struct AttestationPathMismatchChecker {
+    pub pool: ConnectionPool<Core>,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for AttestationPathMismatchChecker {
+    fn name(&self) -> &'static str {
+        "attestation_path_mismatch"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let number_of_mismatches = self
+            .pool
+            .connection_tagged("circuit_breaker")
+            .await?
+            .nh_proof_submissions_dal()
+            .get_number_of_path_mismatches()
+            .await
+            .context("cannot get number of NH attestation path mismatches")?;
+        if number_of_mismatches > 0 {
+            return Err(CircuitBreakerError::AttestationPathMismatch(
+                number_of_mismatches,
+            ));
+        }
+        Ok(())
+    }
+}
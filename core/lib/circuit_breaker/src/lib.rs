@@ -3,6 +3,7 @@ use std::{fmt, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::sync::{watch, Mutex};
 
+pub mod attestation_paths;
 pub mod l1_txs;
 mod metrics;
 pub mod replication_lag;
@@ -35,6 +36,8 @@ pub enum CircuitBreakerError {
     FailedL1Transaction,
     #[error("Replication lag ({0}) is above the threshold ({1})")]
     ReplicationLag(u32, u32),
+    #[error("NH attestation paths for {0} L1 batch(es) don't match attestations on L1")]
+    AttestationPathMismatch(i64),
     #[error("Internal error running circuit breaker checks")]
     Internal(#[from] anyhow::Error),
 }
@@ -85,6 +85,13 @@ pub struct Web3JsonRpcConfig {
     /// Latest values cache size in MiBs. The default value is 128 MiB. If set to 0, the latest
     /// values cache will be disabled.
     pub latest_values_cache_size_mb: Option<usize>,
+    /// Path to the RocksDB directory used as a secondary disk-backed tier for the caches above. The disk tier
+    /// persists across restarts, so that the API server doesn't start with cold caches. If not set, the disk tier
+    /// is disabled.
+    pub vm_storage_disk_cache_path: Option<String>,
+    /// Maximum size of the disk-backed cache tier in MiBs. Once it's exceeded, cached data is evicted from disk.
+    /// The default value is 4,096 MiB.
+    pub vm_storage_disk_cache_size_mb: Option<usize>,
     /// Limit for fee history block range.
     pub fee_history_limit: Option<u64>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
@@ -132,6 +139,8 @@ impl Web3JsonRpcConfig {
             factory_deps_cache_size_mb: Default::default(),
             initial_writes_cache_size_mb: Default::default(),
             latest_values_cache_size_mb: Default::default(),
+            vm_storage_disk_cache_path: Default::default(),
+            vm_storage_disk_cache_size_mb: Default::default(),
             fee_history_limit: Default::default(),
             max_batch_request_size: Default::default(),
             max_response_body_size_mb: Default::default(),
@@ -196,6 +205,11 @@ impl Web3JsonRpcConfig {
         self.latest_values_cache_size_mb.unwrap_or(128) * super::BYTES_IN_MEGABYTE
     }
 
+    /// Returns the maximum size of the disk-backed cache tier in bytes.
+    pub fn vm_storage_disk_cache_size(&self) -> usize {
+        self.vm_storage_disk_cache_size_mb.unwrap_or(4_096) * super::BYTES_IN_MEGABYTE
+    }
+
     pub fn fee_history_limit(&self) -> u64 {
         self.fee_history_limit.unwrap_or(1024)
     }
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Data availability (DA) layer to which pubdata of L1 batches is posted in Validium mode.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum DataAvailabilityMode {
+    /// Pubdata is stored in the object store configured for the server. Should only be used in tests
+    /// and local setups.
+    ObjectStore,
+    /// Pubdata is posted as blobs to the Celestia network via a Celestia light node.
+    Celestia,
+}
+
+/// Configuration of the DA dispatcher. If this config is present in Validium mode, L1 batches are only committed
+/// after their pubdata is confirmed to be available on the DA layer.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DADispatcherConfig {
+    pub mode: DataAvailabilityMode,
+    /// URL of the Celestia light node JSON-RPC API. Required in the Celestia mode.
+    pub celestia_api_node_url: Option<String>,
+    /// Authentication token for the Celestia light node API.
+    pub celestia_auth_token: Option<String>,
+    /// Hex-encoded 10-byte ID of the Celestia namespace to which blobs are posted. Required in the Celestia mode.
+    pub celestia_namespace: Option<String>,
+    /// Polling interval for sealed L1 batches and for the inclusion of dispatched blobs.
+    pub polling_interval_ms: Option<u64>,
+    /// Maximum number of L1 batches dispatched in a single iteration.
+    pub max_batches_to_dispatch: Option<u32>,
+    /// Initial delay before retrying a failed dispatch. The delay is doubled on each subsequent failure.
+    pub retry_backoff_ms: Option<u64>,
+}
+
+impl DADispatcherConfig {
+    /// Maximum multiplier applied to [`Self::retry_backoff()`].
+    const MAX_BACKOFF_MULTIPLIER: u32 = 64;
+
+    pub fn polling_interval(&self) -> Duration {
+        Duration::from_millis(self.polling_interval_ms.unwrap_or(5_000))
+    }
+
+    pub fn max_batches_to_dispatch(&self) -> usize {
+        self.max_batches_to_dispatch.unwrap_or(10).max(1) as usize
+    }
+
+    pub fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms.unwrap_or(5_000))
+    }
+
+    /// Returns the delay before the next dispatch attempt after `failures` consecutive failed ones.
+    pub fn retry_delay(&self, failures: u32) -> Duration {
+        let multiplier = 1_u32
+            .checked_shl(failures.saturating_sub(1))
+            .unwrap_or(u32::MAX)
+            .min(Self::MAX_BACKOFF_MULTIPLIER);
+        self.retry_backoff() * multiplier
+    }
+}
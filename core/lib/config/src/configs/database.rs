@@ -44,6 +44,37 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[serde(default = "MerkleTreeConfig::default_max_l1_batches_per_iter")]
     pub max_l1_batches_per_iter: usize,
+    /// Whether the Merkle tree should run in the archive mode. In this mode, the tree records a diff log
+    /// for each processed L1 batch, so that proofs for L1 batches pruned from the tree can be rebuilt on demand.
+    #[serde(default)]
+    pub archive_mode: bool,
+    /// Interval (in L1 batches) between checkpoint tree versions retained by the pruner in the archive mode.
+    /// Proofs for a pruned L1 batch are rebuilt from the preceding checkpoint, so this value also bounds
+    /// the number of L1 batches replayed to serve a single request. Ignored if the archive mode is disabled.
+    #[serde(default = "MerkleTreeConfig::default_archive_checkpoint_interval")]
+    pub archive_checkpoint_interval: u64,
+    /// Number of latest tree versions (= L1 batches) to retain when pruning the tree. If not set,
+    /// the tree is not pruned.
+    #[serde(default)]
+    pub pruning_retained_versions: Option<u64>,
+    /// Whether the Merkle tree should persist the results for an L1 batch (i.e., save witness inputs to the object store
+    /// and metadata to Postgres) concurrently with computing the tree update for the next L1 batch. Tree updates
+    /// for different L1 batches are still computed sequentially. The tree uses an additional Postgres connection if enabled.
+    #[serde(default)]
+    pub concurrent_persistence: bool,
+    /// Path to the directory with Merkle tree checkpoints. If set, an empty tree is bootstrapped from the latest
+    /// checkpoint instead of being recovered from a snapshot or built from scratch. If checkpoints are stored
+    /// in the object store, the directory is used to stage exported checkpoints; it should be on the same filesystem
+    /// as the tree RocksDB.
+    #[serde(default)]
+    pub checkpoint_path: Option<String>,
+    /// Whether Merkle tree checkpoints are stored in the object store rather than in `checkpoint_path`.
+    #[serde(default)]
+    pub checkpoints_in_object_store: bool,
+    /// Interval (in L1 batches) between Merkle tree checkpoints exported by the tree. If not set, checkpoints
+    /// are not exported. Ignored if `checkpoint_path` is not set.
+    #[serde(default)]
+    pub checkpoint_export_interval: Option<u32>,
 }
 
 impl Default for MerkleTreeConfig {
@@ -56,6 +87,13 @@ impl Default for MerkleTreeConfig {
             memtable_capacity_mb: Self::default_memtable_capacity_mb(),
             stalled_writes_timeout_sec: Self::default_stalled_writes_timeout_sec(),
             max_l1_batches_per_iter: Self::default_max_l1_batches_per_iter(),
+            archive_mode: false,
+            archive_checkpoint_interval: Self::default_archive_checkpoint_interval(),
+            pruning_retained_versions: None,
+            concurrent_persistence: false,
+            checkpoint_path: None,
+            checkpoints_in_object_store: false,
+            checkpoint_export_interval: None,
         }
     }
 }
@@ -85,6 +123,10 @@ impl MerkleTreeConfig {
         20
     }
 
+    const fn default_archive_checkpoint_interval() -> u64 {
+        1_000
+    }
+
     /// Returns the size of block cache size for Merkle tree in bytes.
     pub fn block_cache_size(&self) -> usize {
         self.block_cache_size_mb * super::BYTES_IN_MEGABYTE
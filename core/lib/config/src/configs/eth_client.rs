@@ -8,4 +8,12 @@ pub struct ETHClientConfig {
     pub chain_id: u64,
     /// Address of the Ethereum node API.
     pub web3_url: String,
+    /// Addresses of additional Ethereum node APIs. If specified, L1 requests are distributed among
+    /// `web3_url` and these URLs, failing over to another endpoint if the current one is unavailable.
+    #[serde(default)]
+    pub fallback_web3_urls: Vec<String>,
+    /// Minimum number of Ethereum node APIs that must return matching responses when reading
+    /// transaction statuses and logs. If not specified, these reads are served by a single node.
+    /// Can only be set together with `fallback_web3_urls`, and must not exceed the total number of URLs.
+    pub read_quorum: Option<usize>,
 }
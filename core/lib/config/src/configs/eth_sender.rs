@@ -10,6 +10,9 @@ pub struct ETHSenderConfig {
     pub sender: SenderConfig,
     /// Options related to the `GasAdjuster` submodule.
     pub gas_adjuster: GasAdjusterConfig,
+    /// Strategies choosing fees for resending L1 transactions.
+    #[serde(default)]
+    pub fee_bump: FeeBumpConfig,
 }
 
 impl ETHSenderConfig {
@@ -36,6 +39,7 @@ impl ETHSenderConfig {
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
                 pubdata_sending_mode: PubdataSendingMode::Calldata,
+                proof_verification_layer: ProofVerificationLayerMode::NewHorizen,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -50,6 +54,7 @@ impl ETHSenderConfig {
                 internal_pubdata_pricing_multiplier: 1.0,
                 max_blob_base_fee: None,
             },
+            fee_bump: FeeBumpConfig::default(),
         }
     }
 }
@@ -74,6 +79,26 @@ pub enum PubdataSendingMode {
     Blobs,
 }
 
+/// Layer verifying L1 batch proofs before they are sent to L1.
+///
+/// There is intentionally no mock mode: the in-process mock layer in `zksync_core` is test-only, since its attestations
+/// are only posted on a mock L1 client, so a node configured with it would never send proofs to L1.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ProofVerificationLayerMode {
+    /// Proofs are verified by the NewHorizen chain; L1 receives attestation inclusion paths.
+    #[default]
+    NewHorizen,
+    /// Proofs are sent to L1 directly and verified by the L1 verifier contract.
+    L1,
+}
+
+impl ProofVerificationLayerMode {
+    /// Returns `true` if proofs are submitted to a separate layer, which attests them on L1.
+    pub fn attests_proofs(self) -> bool {
+        matches!(self, Self::NewHorizen)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SenderConfig {
     pub aggregated_proof_sizes: Vec<usize>,
@@ -110,6 +135,10 @@ pub struct SenderConfig {
 
     /// The mode in which we send pubdata, either Calldata or Blobs
     pub pubdata_sending_mode: PubdataSendingMode,
+
+    /// The layer verifying proofs, either NewHorizen or L1 itself.
+    #[serde(default)]
+    pub proof_verification_layer: ProofVerificationLayerMode,
 }
 
 impl SenderConfig {
@@ -188,3 +217,68 @@ impl GasAdjusterConfig {
         1.0
     }
 }
+
+/// Strategies choosing fees for resending L1 transactions, configured per operation type.
+/// Operations without a configured strategy use the default linear strategy.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct FeeBumpConfig {
+    pub commit: Option<FeeBumpStrategyConfig>,
+    pub prove: Option<FeeBumpStrategyConfig>,
+    pub execute: Option<FeeBumpStrategyConfig>,
+}
+
+/// Kind of the fee bump strategy.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum FeeBumpStrategyKind {
+    /// Increases the priority fee by a fixed percentage on each resend.
+    #[default]
+    Linear,
+    /// Multiplies suggested fees by `multiplier^n`, where `n` is the number of L1 blocks spent in the mempool.
+    Exponential,
+    /// Multiplies suggested fees by a factor growing linearly with the operation age up to the deadline.
+    DeadlineAware,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct FeeBumpStrategyConfig {
+    pub strategy: FeeBumpStrategyKind,
+    /// Priority fee increase on each resend in percent; used by the `Linear` strategy.
+    #[serde(default = "FeeBumpStrategyConfig::default_priority_fee_increase_percent")]
+    pub priority_fee_increase_percent: u64,
+    /// Fee multiplier per L1 block spent in the mempool; used by the `Exponential` strategy.
+    #[serde(default = "FeeBumpStrategyConfig::default_multiplier")]
+    pub multiplier: f64,
+    /// Maximum fee multiplier; used by the `Exponential` and `DeadlineAware` strategies.
+    #[serde(default = "FeeBumpStrategyConfig::default_max_multiplier")]
+    pub max_multiplier: f64,
+    /// Time since the operation creation in seconds, by which it should be included on L1.
+    /// Required by the `DeadlineAware` strategy.
+    pub deadline_sec: Option<u64>,
+    /// Time since the operation creation in seconds, after which the operation is abandoned
+    /// by replacing its transaction with a 0-value transfer. If not set, operations are never abandoned.
+    pub abandon_after_sec: Option<u64>,
+}
+
+impl FeeBumpStrategyConfig {
+    pub const fn default_priority_fee_increase_percent() -> u64 {
+        20
+    }
+
+    pub const fn default_multiplier() -> f64 {
+        1.125
+    }
+
+    pub const fn default_max_multiplier() -> f64 {
+        3.0
+    }
+
+    /// Converts `self.deadline_sec` into `Duration`.
+    pub fn deadline(&self) -> Option<Duration> {
+        self.deadline_sec.map(Duration::from_secs)
+    }
+
+    /// Converts `self.abandon_after_sec` into `Duration`.
+    pub fn abandon_after(&self) -> Option<Duration> {
+        self.abandon_after_sec.map(Duration::from_secs)
+    }
+}
@@ -19,6 +19,11 @@ pub struct HouseKeeperConfig {
     // TODO(PLA-862): Make these 2 variables required
     pub fri_prover_job_archiver_reporting_interval_ms: Option<u64>,
     pub fri_prover_job_archiver_archiving_interval_secs: Option<u64>,
+    /// Interval between object store garbage collection runs. Garbage collection is disabled if this
+    /// or [`Self::object_store_gc_retention_secs`] is not set.
+    pub object_store_gc_interval_ms: Option<u64>,
+    /// Minimum age of blobs for proven L1 batches to be removed by the object store garbage collector.
+    pub object_store_gc_retention_secs: Option<u64>,
 }
 
 impl HouseKeeperConfig {
@@ -28,4 +33,8 @@ impl HouseKeeperConfig {
                 .fri_prover_job_archiver_archiving_interval_secs
                 .is_some()
     }
+
+    pub fn object_store_gc_enabled(&self) -> bool {
+        self.object_store_gc_interval_ms.is_some() && self.object_store_gc_retention_secs.is_some()
+    }
 }
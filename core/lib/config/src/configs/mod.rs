@@ -4,6 +4,7 @@ pub use self::{
     api::ApiConfig,
     contract_verifier::ContractVerifierConfig,
     contracts::ContractsConfig,
+    da_dispatcher::{DADispatcherConfig, DataAvailabilityMode},
     database::{DBConfig, PostgresConfig},
     eth_client::ETHClientConfig,
     eth_sender::{ETHSenderConfig, GasAdjusterConfig},
@@ -14,10 +15,11 @@ pub use self::{
     fri_witness_generator::FriWitnessGeneratorConfig,
     fri_witness_vector_generator::FriWitnessVectorGeneratorConfig,
     genesis::GenesisConfig,
-    new_horizen::NewHorizenConfig,
+    new_horizen::{NewHorizenConfig, NhSignerConfig},
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::ProofDataHandlerConfig,
+    remote_signer::RemoteSignerConfig,
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
     witness_generator::WitnessGeneratorConfig,
@@ -28,6 +30,7 @@ pub mod api;
 pub mod chain;
 pub mod contract_verifier;
 pub mod contracts;
+pub mod da_dispatcher;
 pub mod database;
 pub mod eth_client;
 pub mod eth_sender;
@@ -44,6 +47,7 @@ pub mod new_horizen;
 pub mod object_store;
 pub mod observability;
 pub mod proof_data_handler;
+pub mod remote_signer;
 pub mod snapshots_creator;
 pub mod utils;
 pub mod witness_generator;
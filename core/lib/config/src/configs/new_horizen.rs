@@ -1,7 +1,101 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
+/// Signer of extrinsics submitted to the NH chain. Only sr25519 keys are supported.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum NhSignerConfig {
+    /// Key derived from a secret URI, i.e., a mnemonic or a hex-encoded seed with an optional derivation path.
+    /// The secret is kept in plaintext, so this signer should only be used in tests and local setups.
+    Local { secret_uri: String },
+    /// Key stored in a keystore file encrypted with a passphrase.
+    Keystore {
+        keystore_path: String,
+        passphrase: String,
+    },
+    /// Remote signer accessed via JSON-RPC. The signer must hold the key for the specified account.
+    JsonRpc {
+        url: String,
+        /// SS58-encoded or hex-encoded ID of the signing account.
+        account_id: String,
+    },
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct NewHorizenConfig {
     pub url: String,
-    pub seed_phrase: String,
+    /// Signer of proof submission extrinsics.
+    #[serde(skip)]
+    // ^ Filled in separately in `Self::from_env()`. We cannot use `serde(flatten)` because it
+    // doesn't work with 'envy`.
+    pub signer: Option<NhSignerConfig>,
+    /// Polling interval for the proof submission queue.
+    pub submission_polling_interval_ms: Option<u64>,
+    /// Timeout for a single proof submission attempt, including waiting for NH block finalization.
+    pub submission_timeout_sec: Option<u64>,
+    /// Maximum number of attempts to submit a proof before marking the submission as failed.
+    pub submission_max_attempts: Option<u32>,
+    /// Initial delay before retrying a failed proof submission. The delay is doubled on each
+    /// subsequent attempt.
+    pub submission_retry_backoff_ms: Option<u64>,
+    /// Maximum number of proofs for consecutive L1 batches submitted at once. Proofs in a batch are submitted
+    /// without waiting for each other to be included into NH blocks.
+    pub submission_batch_size: Option<u32>,
+    /// Maximum time a finalized proof submission may wait to be included into an attestation posted on L1.
+    /// If exceeded, the NH health check reports the component as affected.
+    pub max_attestation_delay_sec: Option<u64>,
+    /// Interval between runs of the attestation reconciliation task, which fetches `AttestationPosted` events
+    /// missed by the Ethereum watcher.
+    pub attestation_reconciliation_interval_sec: Option<u64>,
+    /// Number of L1 blocks (counting back from the last finalized one) scanned by the attestation
+    /// reconciliation task.
+    pub attestation_reconciliation_block_range: Option<u64>,
+}
+
+impl NewHorizenConfig {
+    /// Maximum multiplier applied to [`Self::submission_retry_backoff()`].
+    const MAX_BACKOFF_MULTIPLIER: u32 = 64;
+
+    pub fn submission_polling_interval(&self) -> Duration {
+        Duration::from_millis(self.submission_polling_interval_ms.unwrap_or(1_000))
+    }
+
+    pub fn submission_timeout(&self) -> Duration {
+        Duration::from_secs(self.submission_timeout_sec.unwrap_or(300))
+    }
+
+    pub fn submission_max_attempts(&self) -> u32 {
+        self.submission_max_attempts.unwrap_or(10)
+    }
+
+    pub fn submission_retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.submission_retry_backoff_ms.unwrap_or(5_000))
+    }
+
+    pub fn submission_batch_size(&self) -> usize {
+        self.submission_batch_size.unwrap_or(10).max(1) as usize
+    }
+
+    pub fn max_attestation_delay(&self) -> Duration {
+        Duration::from_secs(self.max_attestation_delay_sec.unwrap_or(3_600))
+    }
+
+    pub fn attestation_reconciliation_interval(&self) -> Duration {
+        Duration::from_secs(self.attestation_reconciliation_interval_sec.unwrap_or(600))
+    }
+
+    pub fn attestation_reconciliation_block_range(&self) -> u64 {
+        self.attestation_reconciliation_block_range
+            .unwrap_or(50_000)
+    }
+
+    /// Returns the delay before the next submission attempt after `attempts` failed ones.
+    pub fn submission_retry_delay(&self, attempts: u32) -> Duration {
+        let multiplier = 1_u32
+            .checked_shl(attempts.saturating_sub(1))
+            .unwrap_or(u32::MAX)
+            .min(Self::MAX_BACKOFF_MULTIPLIER);
+        self.submission_retry_backoff() * multiplier
+    }
 }
@@ -7,12 +7,29 @@ pub struct ObjectStoreConfig {
     pub mode: ObjectStoreMode,
     #[serde(default = "ObjectStoreConfig::default_max_retries")]
     pub max_retries: u16,
+    /// Names of buckets (e.g., `witness_inputs`) in which new objects are compressed using zstd.
+    /// Compressed objects are transparently decompressed on reads regardless of this setting.
+    #[serde(default)]
+    pub compressed_buckets: Vec<String>,
+    /// Names of buckets in which new objects are stored content-addressed, so that identical objects
+    /// are stored only once.
+    #[serde(default)]
+    pub deduplicated_buckets: Vec<String>,
+    /// Path to a local directory used as a read-through cache for fetched objects. If not set,
+    /// objects are not cached locally.
+    pub local_cache_path: Option<String>,
+    /// Maximum total size of objects in the local cache in bytes.
+    pub local_cache_max_size_bytes: Option<u64>,
 }
 
 impl ObjectStoreConfig {
     const fn default_max_retries() -> u16 {
         5
     }
+
+    pub fn local_cache_max_size_bytes(&self) -> u64 {
+        self.local_cache_max_size_bytes.unwrap_or(10 << 30)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -31,4 +48,23 @@ pub enum ObjectStoreMode {
     FileBacked {
         file_backed_base_path: String,
     },
+    /// S3-compatible storage (AWS S3, MinIO, Cloudflare R2 etc.) accessed using path-style URLs.
+    S3 {
+        /// Storage endpoint, e.g. `https://s3.us-east-1.amazonaws.com` or `http://localhost:9000` for MinIO.
+        s3_endpoint: String,
+        s3_region: String,
+        /// Name of the S3 bucket. Objects are placed in the bucket under the `{bucket}/{key}` keys.
+        s3_bucket: String,
+        s3_access_key_id: String,
+        s3_secret_access_key: String,
+    },
+    AzureBlob {
+        azure_account_name: String,
+        /// Name of the blob container. Objects are placed in the container under the `{bucket}/{key}` names.
+        azure_container: String,
+        /// Base64-encoded shared account key.
+        azure_access_key: String,
+        /// Blob service endpoint. If not specified, set to `https://{azure_account_name}.blob.core.windows.net`.
+        azure_endpoint: Option<String>,
+    },
 }
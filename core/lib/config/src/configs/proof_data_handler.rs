@@ -2,10 +2,27 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+/// Handling of L1 batches for which proof generation was skipped, with regard to the proof verification layer.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SkippedProofMode {
+    /// No proof is submitted to the proof verification layer. Such batches can only be proven on L1
+    /// with dummy proofs.
+    #[default]
+    Skip,
+    /// A deterministic fake proof derived from the L1 batch number is submitted to the proof verification layer.
+    /// Only accepted by a test verifier.
+    FakeProof,
+    /// Requests to skip proof generation are rejected.
+    Refuse,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ProofDataHandlerConfig {
     pub http_port: u16,
     pub proof_generation_timeout_in_secs: u16,
+    /// Handling of L1 batches for which proof generation was skipped.
+    #[serde(default)]
+    pub skipped_proof_mode: SkippedProofMode,
 }
 
 impl ProofDataHandlerConfig {
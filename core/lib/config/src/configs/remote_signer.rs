@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use zksync_basic_types::Address;
+
+/// Configuration of a remote signer (Web3Signer) holding operator keys. If present, the eth sender signs
+/// L1 transactions via the remote signer instead of the private keys from the environment.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RemoteSignerConfig {
+    /// Base URL of the Web3Signer instance, e.g. `http://127.0.0.1:9000`.
+    pub url: String,
+    /// Address of the operator account used to commit, prove and execute L1 batches.
+    pub operator_address: Address,
+    /// Address of the operator account used to send EIP-4844 blob transactions, if blobs are sent from
+    /// a separate account.
+    pub blobs_operator_address: Option<Address>,
+    /// Timeout for a single request to the remote signer.
+    pub request_timeout_ms: Option<u64>,
+}
+
+impl RemoteSignerConfig {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms.unwrap_or(10_000))
+    }
+}
@@ -81,6 +81,8 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             factory_deps_cache_size_mb: self.sample(rng),
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
+            vm_storage_disk_cache_path: self.sample(rng),
+            vm_storage_disk_cache_size_mb: self.sample(rng),
             fee_history_limit: self.sample(rng),
             max_batch_request_size: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
@@ -293,6 +295,13 @@ impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
             memtable_capacity_mb: self.sample(rng),
             stalled_writes_timeout_sec: self.sample(rng),
             max_l1_batches_per_iter: self.sample(rng),
+            archive_mode: self.sample(rng),
+            archive_checkpoint_interval: self.sample(rng),
+            pruning_retained_versions: self.sample(rng),
+            concurrent_persistence: self.sample(rng),
+            checkpoint_path: self.sample(rng),
+            checkpoints_in_object_store: self.sample(rng),
+            checkpoint_export_interval: self.sample(rng),
         }
     }
 }
@@ -327,6 +336,8 @@ impl Distribution<configs::ETHClientConfig> for EncodeDist {
         configs::ETHClientConfig {
             chain_id: self.sample(rng),
             web3_url: self.sample(rng),
+            fallback_web3_urls: self.sample_collect(rng),
+            read_quorum: self.sample(rng),
         }
     }
 }
@@ -336,6 +347,7 @@ impl Distribution<configs::ETHSenderConfig> for EncodeDist {
         configs::ETHSenderConfig {
             sender: self.sample(rng),
             gas_adjuster: self.sample(rng),
+            fee_bump: self.sample(rng),
         }
     }
 }
@@ -371,6 +383,54 @@ impl Distribution<configs::eth_sender::PubdataSendingMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::eth_sender::ProofVerificationLayerMode> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::eth_sender::ProofVerificationLayerMode {
+        type T = configs::eth_sender::ProofVerificationLayerMode;
+        match rng.gen_range(0..2) {
+            0 => T::NewHorizen,
+            _ => T::L1,
+        }
+    }
+}
+
+impl Distribution<configs::DataAvailabilityMode> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::DataAvailabilityMode {
+        type T = configs::DataAvailabilityMode;
+        match rng.gen_range(0..2) {
+            0 => T::ObjectStore,
+            _ => T::Celestia,
+        }
+    }
+}
+
+impl Distribution<configs::DADispatcherConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::DADispatcherConfig {
+        configs::DADispatcherConfig {
+            mode: self.sample(rng),
+            celestia_api_node_url: self.sample(rng),
+            celestia_auth_token: self.sample(rng),
+            celestia_namespace: self.sample(rng),
+            polling_interval_ms: self.sample(rng),
+            max_batches_to_dispatch: self.sample(rng),
+            retry_backoff_ms: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::RemoteSignerConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::RemoteSignerConfig {
+        configs::RemoteSignerConfig {
+            url: self.sample(rng),
+            operator_address: rng.gen(),
+            blobs_operator_address: self.sample_opt(|| rng.gen()),
+            request_timeout_ms: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::SenderConfig {
         configs::eth_sender::SenderConfig {
@@ -392,6 +452,7 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             max_acceptable_priority_fee_in_gwei: self.sample(rng),
             proof_loading_mode: self.sample(rng),
             pubdata_sending_mode: PubdataSendingMode::Calldata,
+            proof_verification_layer: self.sample(rng),
         }
     }
 }
@@ -414,6 +475,40 @@ impl Distribution<configs::eth_sender::GasAdjusterConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::eth_sender::FeeBumpConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::FeeBumpConfig {
+        configs::eth_sender::FeeBumpConfig {
+            commit: self.sample_opt(|| self.sample(rng)),
+            prove: self.sample_opt(|| self.sample(rng)),
+            execute: self.sample_opt(|| self.sample(rng)),
+        }
+    }
+}
+
+impl Distribution<configs::eth_sender::FeeBumpStrategyKind> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::FeeBumpStrategyKind {
+        type T = configs::eth_sender::FeeBumpStrategyKind;
+        match rng.gen_range(0..3) {
+            0 => T::Linear,
+            1 => T::Exponential,
+            _ => T::DeadlineAware,
+        }
+    }
+}
+
+impl Distribution<configs::eth_sender::FeeBumpStrategyConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::FeeBumpStrategyConfig {
+        configs::eth_sender::FeeBumpStrategyConfig {
+            strategy: self.sample(rng),
+            priority_fee_increase_percent: self.sample(rng),
+            multiplier: self.sample(rng),
+            max_multiplier: self.sample(rng),
+            deadline_sec: self.sample(rng),
+            abandon_after_sec: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::ETHWatchConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ETHWatchConfig {
         configs::ETHWatchConfig {
@@ -602,6 +697,8 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
             fri_proof_compressor_stats_reporting_interval_ms: self.sample(rng),
             fri_prover_job_archiver_reporting_interval_ms: self.sample(rng),
             fri_prover_job_archiver_archiving_interval_secs: self.sample(rng),
+            object_store_gc_interval_ms: self.sample(rng),
+            object_store_gc_retention_secs: self.sample(rng),
         }
     }
 }
@@ -609,7 +706,7 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
 impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreMode {
         type T = configs::object_store::ObjectStoreMode;
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..6) {
             0 => T::GCS {
                 bucket_base_url: self.sample(rng),
             },
@@ -620,6 +717,19 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
             2 => T::FileBacked {
                 file_backed_base_path: self.sample(rng),
             },
+            3 => T::S3 {
+                s3_endpoint: self.sample(rng),
+                s3_region: self.sample(rng),
+                s3_bucket: self.sample(rng),
+                s3_access_key_id: self.sample(rng),
+                s3_secret_access_key: self.sample(rng),
+            },
+            4 => T::AzureBlob {
+                azure_account_name: self.sample(rng),
+                azure_container: self.sample(rng),
+                azure_access_key: self.sample(rng),
+                azure_endpoint: self.sample(rng),
+            },
             _ => T::GCSAnonymousReadOnly {
                 bucket_base_url: self.sample(rng),
             },
@@ -632,6 +742,24 @@ impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
         configs::ObjectStoreConfig {
             mode: self.sample(rng),
             max_retries: self.sample(rng),
+            compressed_buckets: self.sample_collect(rng),
+            deduplicated_buckets: self.sample_collect(rng),
+            local_cache_path: self.sample(rng),
+            local_cache_max_size_bytes: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::proof_data_handler::SkippedProofMode> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::proof_data_handler::SkippedProofMode {
+        type T = configs::proof_data_handler::SkippedProofMode;
+        match rng.gen_range(0..3) {
+            0 => T::Skip,
+            1 => T::FakeProof,
+            _ => T::Refuse,
         }
     }
 }
@@ -641,6 +769,7 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
         configs::ProofDataHandlerConfig {
             http_port: self.sample(rng),
             proof_generation_timeout_in_secs: self.sample(rng),
+            skipped_proof_mode: self.sample(rng),
         }
     }
 }
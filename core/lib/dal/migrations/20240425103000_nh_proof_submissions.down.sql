@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_nh_proof_submissions_status_next_attempt_at;
+
+DROP TABLE IF EXISTS nh_proof_submissions;
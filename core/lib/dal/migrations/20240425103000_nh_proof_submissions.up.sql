@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS nh_proof_submissions
+(
+    l1_batch_number     BIGINT NOT NULL PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    status              TEXT NOT NULL,
+    proof               BYTEA NOT NULL,
+    public_inputs       BYTEA NOT NULL,
+    attempts            INT NOT NULL DEFAULT 0,
+    error               TEXT,
+    tx_hash             BYTEA,
+    block_hash          BYTEA,
+    attestation_id      NUMERIC(80),
+    attestation_element BYTEA,
+    next_attempt_at     TIMESTAMP NOT NULL,
+    created_at          TIMESTAMP NOT NULL,
+    updated_at          TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_nh_proof_submissions_status_next_attempt_at
+    ON nh_proof_submissions (status, next_attempt_at);
@@ -0,0 +1 @@
+ALTER TABLE proof_generation_details DROP COLUMN IF EXISTS skipped_proof_handling;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS data_availability
+(
+    l1_batch_number BIGINT NOT NULL PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    blob_id         TEXT   NOT NULL,
+    inclusion_data  BYTEA,
+    sent_at         TIMESTAMP NOT NULL,
+    created_at      TIMESTAMP NOT NULL,
+    updated_at      TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE eth_txs_history ADD COLUMN IF NOT EXISTS is_cancellation BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,298 @@
+use sqlx::types::chrono::NaiveDateTime;
+use zksync_db_connection::connection::Connection;
+use zksync_types::L1BatchNumber;
+
+use crate::{Core, SqlxError};
+
+#[derive(Debug)]
+pub struct DataAvailabilityDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+/// Pubdata blob of an L1 batch dispatched to the data availability (DA) layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataAvailabilityBlob {
+    pub l1_batch_number: L1BatchNumber,
+    /// ID of the blob assigned by the DA layer.
+    pub blob_id: String,
+    /// Data proving the blob inclusion on the DA layer. `None` if the inclusion is not confirmed yet.
+    pub inclusion_data: Option<Vec<u8>>,
+    pub sent_at: NaiveDateTime,
+}
+
+/// Pubdata of an L1 batch ready to be dispatched to the DA layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1BatchPubdata {
+    pub l1_batch_number: L1BatchNumber,
+    pub pubdata: Vec<u8>,
+}
+
+impl DataAvailabilityDal<'_, '_> {
+    /// Records that the pubdata of the specified L1 batch was dispatched to the DA layer.
+    pub async fn insert_l1_batch_da(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        blob_id: &str,
+        sent_at: NaiveDateTime,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                data_availability (
+                    l1_batch_number,
+                    blob_id,
+                    sent_at,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, NOW(), NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+            blob_id,
+            sent_at,
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Saves the inclusion data for a dispatched blob. Errors if the blob doesn't exist
+    /// or already has inclusion data.
+    pub async fn save_l1_batch_inclusion_data(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        inclusion_data: &[u8],
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE data_availability
+            SET
+                inclusion_data = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND inclusion_data IS NULL
+            "#,
+            inclusion_data,
+            i64::from(l1_batch_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected()
+        .eq(&1)
+        .then_some(())
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Returns the dispatched blob with the lowest L1 batch number that doesn't have inclusion data yet.
+    pub async fn get_first_da_blob_awaiting_inclusion(
+        &mut self,
+    ) -> sqlx::Result<Option<DataAvailabilityBlob>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                blob_id,
+                inclusion_data,
+                sent_at
+            FROM
+                data_availability
+            WHERE
+                inclusion_data IS NULL
+            ORDER BY
+                l1_batch_number
+            LIMIT
+                1
+            "#,
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| DataAvailabilityBlob {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            blob_id: row.blob_id,
+            inclusion_data: row.inclusion_data,
+            sent_at: row.sent_at,
+        }))
+    }
+
+    /// Returns up to `limit` oldest sealed L1 batches that are not committed to L1 and are not dispatched
+    /// to the DA layer yet, together with their pubdata. L1 batches without persisted pubdata
+    /// (i.e., ones produced by older protocol versions) are skipped.
+    pub async fn get_ready_for_da_dispatch_l1_batches(
+        &mut self,
+        limit: usize,
+    ) -> sqlx::Result<Vec<L1BatchPubdata>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                pubdata_input AS "pubdata_input!"
+            FROM
+                l1_batches
+                LEFT JOIN data_availability ON data_availability.l1_batch_number = l1_batches.number
+            WHERE
+                eth_commit_tx_id IS NULL
+                AND number != 0
+                AND data_availability.blob_id IS NULL
+                AND pubdata_input IS NOT NULL
+            ORDER BY
+                number
+            LIMIT
+                $1
+            "#,
+            limit as i64,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchPubdata {
+                l1_batch_number: L1BatchNumber(row.number as u32),
+                pubdata: row.pubdata_input,
+            })
+            .collect())
+    }
+
+    /// Returns the first L1 batch starting from `from_l1_batch` which pubdata is not confirmed
+    /// to be available on the DA layer, or `None` if all such L1 batches are confirmed. Like in
+    /// [`Self::get_ready_for_da_dispatch_l1_batches()`], L1 batches without persisted pubdata are skipped
+    /// since they are never dispatched.
+    pub async fn get_first_l1_batch_without_inclusion_data(
+        &mut self,
+        from_l1_batch: L1BatchNumber,
+    ) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(number) AS "number"
+            FROM
+                l1_batches
+                LEFT JOIN data_availability ON data_availability.l1_batch_number = l1_batches.number
+            WHERE
+                number >= $1
+                AND data_availability.inclusion_data IS NULL
+                AND pubdata_input IS NOT NULL
+            "#,
+            i64::from(from_l1_batch.0),
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{block::L1BatchHeader, ProtocolVersion, ProtocolVersionId};
+
+    use super::*;
+    use crate::{ConnectionPool, Core, CoreDal};
+
+    async fn insert_l1_batch(
+        conn: &mut Connection<'_, Core>,
+        number: L1BatchNumber,
+        pubdata: Option<Vec<u8>>,
+    ) {
+        let mut header = L1BatchHeader::new(
+            number,
+            100,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.pubdata_input = pubdata;
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn data_availability_lifecycle() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        insert_l1_batch(&mut conn, L1BatchNumber(0), None).await;
+        insert_l1_batch(&mut conn, L1BatchNumber(1), Some(vec![1; 32])).await;
+        insert_l1_batch(&mut conn, L1BatchNumber(2), Some(vec![2; 32])).await;
+        insert_l1_batch(&mut conn, L1BatchNumber(3), None).await;
+
+        let mut dal = conn.data_availability_dal();
+        let ready = dal.get_ready_for_da_dispatch_l1_batches(10).await.unwrap();
+        assert_eq!(
+            ready,
+            [
+                L1BatchPubdata {
+                    l1_batch_number: L1BatchNumber(1),
+                    pubdata: vec![1; 32],
+                },
+                L1BatchPubdata {
+                    l1_batch_number: L1BatchNumber(2),
+                    pubdata: vec![2; 32],
+                },
+            ]
+        );
+        assert_eq!(
+            dal.get_first_l1_batch_without_inclusion_data(L1BatchNumber(1))
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(1))
+        );
+
+        let sent_at = NaiveDateTime::from_timestamp_opt(1_700_000_000, 0).unwrap();
+        dal.insert_l1_batch_da(L1BatchNumber(1), "blob_1", sent_at)
+            .await
+            .unwrap();
+        let ready = dal.get_ready_for_da_dispatch_l1_batches(10).await.unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].l1_batch_number, L1BatchNumber(2));
+
+        let blob = dal.get_first_da_blob_awaiting_inclusion().await.unwrap();
+        assert_eq!(
+            blob,
+            Some(DataAvailabilityBlob {
+                l1_batch_number: L1BatchNumber(1),
+                blob_id: "blob_1".to_owned(),
+                inclusion_data: None,
+                sent_at,
+            })
+        );
+
+        dal.save_l1_batch_inclusion_data(L1BatchNumber(1), &[42; 8])
+            .await
+            .unwrap();
+        // Inclusion data cannot be overwritten.
+        dal.save_l1_batch_inclusion_data(L1BatchNumber(1), &[0; 8])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            dal.get_first_da_blob_awaiting_inclusion().await.unwrap(),
+            None
+        );
+        assert_eq!(
+            dal.get_first_l1_batch_without_inclusion_data(L1BatchNumber(1))
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(2))
+        );
+
+        dal.insert_l1_batch_da(L1BatchNumber(2), "blob_2", sent_at)
+            .await
+            .unwrap();
+        dal.save_l1_batch_inclusion_data(L1BatchNumber(2), &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_first_l1_batch_without_inclusion_data(L1BatchNumber(1))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
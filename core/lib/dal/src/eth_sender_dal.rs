@@ -229,6 +229,7 @@ impl EthSenderDal<'_, '_> {
         blob_base_fee_per_gas: Option<u64>,
         tx_hash: H256,
         raw_signed_tx: &[u8],
+        is_cancellation: bool,
     ) -> anyhow::Result<Option<u32>> {
         let priority_fee_per_gas =
             i64::try_from(priority_fee_per_gas).context("Can't convert u64 to i64")?;
@@ -247,10 +248,11 @@ impl EthSenderDal<'_, '_> {
                     signed_raw_tx,
                     created_at,
                     updated_at,
-                    blob_base_fee_per_gas
+                    blob_base_fee_per_gas,
+                    is_cancellation
                 )
             VALUES
-                ($1, $2, $3, $4, $5, NOW(), NOW(), $6)
+                ($1, $2, $3, $4, $5, NOW(), NOW(), $6, $7)
             ON CONFLICT (tx_hash) DO NOTHING
             RETURNING
                 id
@@ -261,6 +263,7 @@ impl EthSenderDal<'_, '_> {
             tx_hash,
             raw_signed_tx,
             blob_base_fee_per_gas.map(|v| v as i64),
+            is_cancellation,
         )
         .fetch_optional(self.storage.conn())
         .await?
@@ -493,6 +496,25 @@ impl EthSenderDal<'_, '_> {
         Ok(tx_history.into_iter().map(|tx| tx.into()).collect())
     }
 
+    /// Checks whether the specified transaction cancels its operation.
+    pub async fn is_cancellation_tx(&mut self, tx_hash: H256) -> sqlx::Result<bool> {
+        let tx_hash = format!("{:#x}", tx_hash);
+        let is_cancellation = sqlx::query_scalar!(
+            r#"
+            SELECT
+                is_cancellation
+            FROM
+                eth_txs_history
+            WHERE
+                tx_hash = $1
+            "#,
+            tx_hash
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(is_cancellation.unwrap_or(false))
+    }
+
     pub async fn get_block_number_on_first_sent_attempt(
         &mut self,
         eth_tx_id: u32,
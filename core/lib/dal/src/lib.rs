@@ -10,9 +10,10 @@ pub use zksync_db_connection::{connection::Connection, connection_pool::Connecti
 use crate::{
     basic_witness_input_producer_dal::BasicWitnessInputProducerDal, blocks_dal::BlocksDal,
     blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
-    contract_verification_dal::ContractVerificationDal, eth_sender_dal::EthSenderDal,
-    events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    nh_dal::NewHorizenDal, proof_generation_dal::ProofGenerationDal,
+    contract_verification_dal::ContractVerificationDal, data_availability_dal::DataAvailabilityDal,
+    eth_sender_dal::EthSenderDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
+    factory_deps_dal::FactoryDepsDal, nh_dal::NewHorizenDal,
+    nh_proof_submissions_dal::NhProofSubmissionsDal, proof_generation_dal::ProofGenerationDal,
     protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
@@ -28,12 +29,14 @@ pub mod blocks_dal;
 pub mod blocks_web3_dal;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
+pub mod data_availability_dal;
 pub mod eth_sender_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
 mod models;
 pub mod nh_dal;
+pub mod nh_proof_submissions_dal;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
@@ -120,6 +123,10 @@ where
     fn snapshot_recovery_dal(&mut self) -> SnapshotRecoveryDal<'_, 'a>;
 
     fn nh_dal(&mut self) -> NewHorizenDal<'_, 'a>;
+
+    fn nh_proof_submissions_dal(&mut self) -> NhProofSubmissionsDal<'_, 'a>;
+
+    fn data_availability_dal(&mut self) -> DataAvailabilityDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -234,4 +241,12 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn nh_dal(&mut self) -> NewHorizenDal<'_, 'a> {
         NewHorizenDal { storage: self }
     }
+
+    fn nh_proof_submissions_dal(&mut self) -> NhProofSubmissionsDal<'_, 'a> {
+        NhProofSubmissionsDal { storage: self }
+    }
+
+    fn data_availability_dal(&mut self) -> DataAvailabilityDal<'_, 'a> {
+        DataAvailabilityDal { storage: self }
+    }
 }
@@ -66,6 +66,7 @@ pub struct StorageTxHistory {
     // Format a `bincode`-encoded `EthTxBlobSidecar` enum.
     pub blob_sidecar: Option<Vec<u8>>,
     pub blob_base_fee_per_gas: Option<i64>,
+    pub is_cancellation: bool,
 }
 
 impl From<StorageEthTx> for EthTx {
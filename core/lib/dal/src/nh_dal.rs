@@ -1,7 +1,7 @@
 use bigdecimal::BigDecimal;
 use zksync_basic_types::H256;
 use zksync_db_connection::connection::Connection;
-use zksync_types::{l1::NHAttestation, L1BatchNumber};
+use zksync_types::{l1::NHAttestation, L1BatchNumber, U256};
 use zksync_utils::{bigdecimal_to_u256, u256_to_big_decimal};
 
 use crate::Core;
@@ -77,4 +77,68 @@ impl NewHorizenDal<'_, '_> {
 
         nh_attestation
     }
+
+    /// Returns IDs of attestations that include L1 batch proofs, but are not stored yet, in the ascending order.
+    pub async fn get_missing_nh_attestation_ids(&mut self) -> sqlx::Result<Vec<U256>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                attestation_id AS "attestation_id!"
+            FROM
+                proof_generation_details
+            WHERE
+                attestation_id IS NOT NULL
+                AND attestation_id NOT IN (
+                    SELECT
+                        attestation_id
+                    FROM
+                        new_horizen_attestation
+                )
+            ORDER BY
+                attestation_id
+            "#
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| bigdecimal_to_u256(row.attestation_id))
+            .collect())
+    }
+
+    /// Returns the latest L1 batch with the proof finalized on NH.
+    pub async fn get_last_l1_batch_with_nh_proof(&mut self) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "number"
+            FROM
+                proof_generation_details
+            WHERE
+                attestation_element IS NOT NULL
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns the latest L1 batch with the proof included into an attestation posted on L1.
+    pub async fn get_last_attested_l1_batch(&mut self) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(proof_generation_details.l1_batch_number) AS "number"
+            FROM
+                proof_generation_details
+                JOIN new_horizen_attestation ON new_horizen_attestation.attestation_id = proof_generation_details.attestation_id
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
 }
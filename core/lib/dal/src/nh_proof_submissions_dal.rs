@@ -0,0 +1,634 @@
+#![doc = include_str!("../doc/NhProofSubmissionsDal.md")]
+use std::time::Duration;
+
+use sqlx::types::chrono::NaiveDateTime;
+use strum::{Display, EnumString};
+use zksync_db_connection::{connection::Connection, utils::pg_interval_from_duration};
+use zksync_types::{L1BatchNumber, H256, U256};
+use zksync_utils::u256_to_big_decimal;
+
+use crate::{Core, SqlxError};
+
+#[derive(Debug)]
+pub struct NhProofSubmissionsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+/// Status of an L1 batch proof submission to the NewHorizen chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum NhProofSubmissionStatus {
+    /// Waiting to be (re)submitted.
+    #[strum(serialize = "pending")]
+    Pending,
+    /// Submission extrinsic is included in a NH block, but the block isn't finalized yet.
+    #[strum(serialize = "in_block")]
+    InBlock,
+    /// Submission extrinsic is finalized; the attestation ID and element are known.
+    #[strum(serialize = "finalized")]
+    Finalized,
+    /// Submission has failed after exhausting all attempts.
+    #[strum(serialize = "failed")]
+    Failed,
+    /// Submission is finalized, but the attestation Merkle path returned by NH doesn't lead
+    /// to the attestation root posted on L1. Requires manual intervention.
+    #[strum(serialize = "path_mismatch")]
+    PathMismatch,
+}
+
+/// Proof submission picked from the queue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NhProofSubmission {
+    pub l1_batch_number: L1BatchNumber,
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<u8>,
+    /// Number of attempts including the current one.
+    pub attempts: u32,
+}
+
+/// Proof submission included in a (possibly non-finalized) NH block, picked to check its finality.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NhIncludedProofSubmission {
+    pub l1_batch_number: L1BatchNumber,
+    /// Hash of the submission extrinsic.
+    pub tx_hash: H256,
+    /// Hash of the block including the submission extrinsic.
+    pub block_hash: H256,
+    /// Number of submission attempts made so far.
+    pub attempts: u32,
+}
+
+impl NhProofSubmissionsDal<'_, '_> {
+    pub async fn insert_proof_submission(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        proof: &[u8],
+        public_inputs: &[u8],
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                nh_proof_submissions (
+                    l1_batch_number,
+                    status,
+                    proof,
+                    public_inputs,
+                    next_attempt_at,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, NOW(), NOW(), NOW())
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            i64::from(l1_batch_number.0),
+            NhProofSubmissionStatus::Pending.to_string(),
+            proof,
+            public_inputs,
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Picks up to `limit` oldest pending submissions that should be (re)submitted, ordered by L1 batch number.
+    /// Picked submissions are leased for `processing_timeout`; if a submission isn't included in a block, rescheduled
+    /// or failed during this time, it will be picked again. Submissions already included in a block are never picked
+    /// for resubmission; see [`Self::get_included_proof_submissions()`].
+    pub async fn get_next_proof_submissions(
+        &mut self,
+        processing_timeout: Duration,
+        limit: usize,
+    ) -> sqlx::Result<Vec<NhProofSubmission>> {
+        let processing_timeout = pg_interval_from_duration(processing_timeout);
+        let rows = sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                attempts = attempts + 1,
+                next_attempt_at = NOW() + $1::INTERVAL,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number IN (
+                    SELECT
+                        l1_batch_number
+                    FROM
+                        nh_proof_submissions
+                    WHERE
+                        status = 'pending'
+                        AND next_attempt_at <= NOW()
+                    ORDER BY
+                        l1_batch_number ASC
+                    LIMIT
+                        $2
+                    FOR UPDATE
+                        SKIP LOCKED
+                )
+            RETURNING
+                nh_proof_submissions.l1_batch_number,
+                nh_proof_submissions.proof,
+                nh_proof_submissions.public_inputs,
+                nh_proof_submissions.attempts
+            "#,
+            &processing_timeout,
+            limit as i64,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let mut submissions: Vec<_> = rows
+            .into_iter()
+            .map(|row| NhProofSubmission {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                proof: row.proof,
+                public_inputs: row.public_inputs,
+                attempts: row.attempts as u32,
+            })
+            .collect();
+        // `RETURNING` doesn't guarantee any particular order.
+        submissions.sort_unstable_by_key(|submission| submission.l1_batch_number);
+        Ok(submissions)
+    }
+
+    /// Picks up to `limit` oldest submissions included in a block, but not finalized yet, ordered by L1 batch number.
+    /// Picked submissions are leased for `processing_timeout`; after this time, their finality will be checked again.
+    pub async fn get_included_proof_submissions(
+        &mut self,
+        processing_timeout: Duration,
+        limit: usize,
+    ) -> sqlx::Result<Vec<NhIncludedProofSubmission>> {
+        let processing_timeout = pg_interval_from_duration(processing_timeout);
+        let rows = sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                next_attempt_at = NOW() + $1::INTERVAL,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number IN (
+                    SELECT
+                        l1_batch_number
+                    FROM
+                        nh_proof_submissions
+                    WHERE
+                        status = 'in_block'
+                        AND next_attempt_at <= NOW()
+                    ORDER BY
+                        l1_batch_number ASC
+                    LIMIT
+                        $2
+                    FOR UPDATE
+                        SKIP LOCKED
+                )
+            RETURNING
+                nh_proof_submissions.l1_batch_number,
+                nh_proof_submissions.tx_hash AS "tx_hash!",
+                nh_proof_submissions.block_hash AS "block_hash!",
+                nh_proof_submissions.attempts
+            "#,
+            &processing_timeout,
+            limit as i64,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let mut submissions: Vec<_> = rows
+            .into_iter()
+            .map(|row| NhIncludedProofSubmission {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                tx_hash: H256::from_slice(&row.tx_hash),
+                block_hash: H256::from_slice(&row.block_hash),
+                attempts: row.attempts as u32,
+            })
+            .collect();
+        // `RETURNING` doesn't guarantee any particular order.
+        submissions.sort_unstable_by_key(|submission| submission.l1_batch_number);
+        Ok(submissions)
+    }
+
+    pub async fn mark_proof_submission_as_in_block(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        tx_hash: H256,
+        block_hash: H256,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                status = $1,
+                tx_hash = $2,
+                block_hash = $3,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $4
+            "#,
+            NhProofSubmissionStatus::InBlock.to_string(),
+            tx_hash.as_bytes(),
+            block_hash.as_bytes(),
+            i64::from(l1_batch_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected()
+        .eq(&1)
+        .then_some(())
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Marks the submission as finalized. The caller is responsible for propagating the attestation
+    /// to `proof_generation_details` in the same DB transaction.
+    pub async fn mark_proof_submission_as_finalized(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        block_hash: H256,
+        attestation_id: u64,
+        attestation_element: H256,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                status = $1,
+                block_hash = $2,
+                attestation_id = $3,
+                attestation_element = $4,
+                error = NULL,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $5
+            "#,
+            NhProofSubmissionStatus::Finalized.to_string(),
+            block_hash.as_bytes(),
+            u256_to_big_decimal(U256::from(attestation_id)),
+            attestation_element.as_bytes(),
+            i64::from(l1_batch_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected()
+        .eq(&1)
+        .then_some(())
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Returns the submission to the queue so that it's retried after `retry_after`.
+    pub async fn reschedule_proof_submission(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        error: &str,
+        retry_after: Duration,
+    ) -> Result<(), SqlxError> {
+        let retry_after = pg_interval_from_duration(retry_after);
+        sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                status = $1,
+                error = $2,
+                next_attempt_at = NOW() + $3::INTERVAL,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $4
+            "#,
+            NhProofSubmissionStatus::Pending.to_string(),
+            error,
+            &retry_after,
+            i64::from(l1_batch_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected()
+        .eq(&1)
+        .then_some(())
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn mark_proof_submission_as_failed(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        error: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                status = $1,
+                error = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $3
+            "#,
+            NhProofSubmissionStatus::Failed.to_string(),
+            error,
+            i64::from(l1_batch_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected()
+        .eq(&1)
+        .then_some(())
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Marks a finalized submission as having an attestation Merkle path that doesn't match the attestation
+    /// posted on L1. Does nothing if the submission doesn't exist.
+    pub async fn mark_proof_submission_as_path_mismatch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        error: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE nh_proof_submissions
+            SET
+                status = $1,
+                error = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $3
+            "#,
+            NhProofSubmissionStatus::PathMismatch.to_string(),
+            error,
+            i64::from(l1_batch_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_number_of_path_mismatches(&mut self) -> sqlx::Result<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                nh_proof_submissions
+            WHERE
+                status = $1
+            "#,
+            NhProofSubmissionStatus::PathMismatch.to_string(),
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.count)
+    }
+
+    /// Returns the finalization time of the oldest finalized submission whose attestation is not posted
+    /// on L1 yet.
+    pub async fn get_oldest_unattested_submission_time(
+        &mut self,
+    ) -> sqlx::Result<Option<NaiveDateTime>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(updated_at) AS "finalized_at"
+            FROM
+                nh_proof_submissions
+            WHERE
+                status = $1
+                AND attestation_id NOT IN (
+                    SELECT
+                        attestation_id
+                    FROM
+                        new_horizen_attestation
+                )
+            "#,
+            NhProofSubmissionStatus::Finalized.to_string(),
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(row.finalized_at)
+    }
+
+    pub async fn get_proof_submission_status(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Option<NhProofSubmissionStatus>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                status
+            FROM
+                nh_proof_submissions
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| {
+            row.status
+                .parse()
+                .unwrap_or_else(|_| panic!("Unknown NH proof submission status: {}", row.status))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{block::L1BatchHeader, ProtocolVersion, ProtocolVersionId};
+
+    use super::*;
+    use crate::{ConnectionPool, Core, CoreDal};
+
+    async fn insert_l1_batch(conn: &mut Connection<'_, Core>, number: L1BatchNumber) {
+        let header = L1BatchHeader::new(
+            number,
+            100,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn proof_submission_lifecycle() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        insert_l1_batch(&mut conn, L1BatchNumber(1)).await;
+        insert_l1_batch(&mut conn, L1BatchNumber(2)).await;
+
+        let mut dal = conn.nh_proof_submissions_dal();
+        dal.insert_proof_submission(L1BatchNumber(1), &[1; 16], &[2; 4])
+            .await
+            .unwrap();
+        dal.insert_proof_submission(L1BatchNumber(2), &[3; 16], &[4; 4])
+            .await
+            .unwrap();
+
+        let timeout = Duration::from_secs(60);
+        let submissions = dal.get_next_proof_submissions(timeout, 1).await.unwrap();
+        assert_eq!(
+            submissions,
+            [NhProofSubmission {
+                l1_batch_number: L1BatchNumber(1),
+                proof: vec![1; 16],
+                public_inputs: vec![2; 4],
+                attempts: 1,
+            }]
+        );
+        // The first submission is leased, so the second one should be picked.
+        let submissions = dal.get_next_proof_submissions(timeout, 10).await.unwrap();
+        assert_eq!(submissions.len(), 1);
+        assert_eq!(submissions[0].l1_batch_number, L1BatchNumber(2));
+        let submissions = dal.get_next_proof_submissions(timeout, 10).await.unwrap();
+        assert_eq!(submissions, []);
+
+        dal.mark_proof_submission_as_in_block(L1BatchNumber(1), H256::repeat_byte(1), H256::zero())
+            .await
+            .unwrap();
+        dal.mark_proof_submission_as_finalized(
+            L1BatchNumber(1),
+            H256::repeat_byte(2),
+            42,
+            H256::repeat_byte(3),
+        )
+        .await
+        .unwrap();
+        let status = dal
+            .get_proof_submission_status(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(status, Some(NhProofSubmissionStatus::Finalized));
+        assert_eq!(dal.get_number_of_path_mismatches().await.unwrap(), 0);
+
+        dal.mark_proof_submission_as_path_mismatch(L1BatchNumber(1), "root mismatch")
+            .await
+            .unwrap();
+        let status = dal
+            .get_proof_submission_status(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(status, Some(NhProofSubmissionStatus::PathMismatch));
+        assert_eq!(dal.get_number_of_path_mismatches().await.unwrap(), 1);
+
+        dal.reschedule_proof_submission(L1BatchNumber(2), "timeout", Duration::ZERO)
+            .await
+            .unwrap();
+        let submissions = dal.get_next_proof_submissions(timeout, 10).await.unwrap();
+        assert_eq!(submissions.len(), 1);
+        assert_eq!(submissions[0].l1_batch_number, L1BatchNumber(2));
+        assert_eq!(submissions[0].attempts, 2);
+
+        dal.mark_proof_submission_as_failed(L1BatchNumber(2), "timeout")
+            .await
+            .unwrap();
+        let status = dal
+            .get_proof_submission_status(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(status, Some(NhProofSubmissionStatus::Failed));
+        let submissions = dal.get_next_proof_submissions(timeout, 10).await.unwrap();
+        assert_eq!(submissions, []);
+    }
+
+    #[tokio::test]
+    async fn included_proof_submissions_are_not_resubmitted() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        insert_l1_batch(&mut conn, L1BatchNumber(1)).await;
+        insert_l1_batch(&mut conn, L1BatchNumber(2)).await;
+
+        let mut dal = conn.nh_proof_submissions_dal();
+        for number in [1, 2] {
+            dal.insert_proof_submission(L1BatchNumber(number), &[1; 16], &[2; 4])
+                .await
+                .unwrap();
+        }
+        let submissions = dal
+            .get_next_proof_submissions(Duration::ZERO, 10)
+            .await
+            .unwrap();
+        assert_eq!(submissions.len(), 2);
+        let tx_hash = H256::repeat_byte(1);
+        let block_hash = H256::repeat_byte(2);
+        dal.mark_proof_submission_as_in_block(L1BatchNumber(1), tx_hash, block_hash)
+            .await
+            .unwrap();
+
+        // The lease has expired, but the included submission must not be picked for resubmission.
+        let submissions = dal
+            .get_next_proof_submissions(Duration::from_secs(60), 10)
+            .await
+            .unwrap();
+        let numbers: Vec<_> = submissions.iter().map(|s| s.l1_batch_number.0).collect();
+        assert_eq!(numbers, [2]);
+
+        let timeout = Duration::from_secs(60);
+        let included = dal
+            .get_included_proof_submissions(timeout, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            included,
+            [NhIncludedProofSubmission {
+                l1_batch_number: L1BatchNumber(1),
+                tx_hash,
+                block_hash,
+                attempts: 1,
+            }]
+        );
+        // The included submission is leased.
+        let included = dal
+            .get_included_proof_submissions(timeout, 10)
+            .await
+            .unwrap();
+        assert!(included.is_empty());
+
+        dal.mark_proof_submission_as_finalized(
+            L1BatchNumber(1),
+            block_hash,
+            42,
+            H256::repeat_byte(3),
+        )
+        .await
+        .unwrap();
+        let included = dal
+            .get_included_proof_submissions(Duration::ZERO, 10)
+            .await
+            .unwrap();
+        assert!(included.is_empty());
+    }
+
+    #[tokio::test]
+    async fn picking_multiple_proof_submissions() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        for number in 1..=5 {
+            insert_l1_batch(&mut conn, L1BatchNumber(number)).await;
+            conn.nh_proof_submissions_dal()
+                .insert_proof_submission(L1BatchNumber(number), &[number as u8; 16], &[])
+                .await
+                .unwrap();
+        }
+
+        let mut dal = conn.nh_proof_submissions_dal();
+        let timeout = Duration::from_secs(60);
+        let submissions = dal.get_next_proof_submissions(timeout, 3).await.unwrap();
+        let numbers: Vec<_> = submissions.iter().map(|s| s.l1_batch_number.0).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+
+        dal.reschedule_proof_submission(L1BatchNumber(2), "timeout", Duration::ZERO)
+            .await
+            .unwrap();
+        let submissions = dal.get_next_proof_submissions(timeout, 3).await.unwrap();
+        let numbers: Vec<_> = submissions.iter().map(|s| s.l1_batch_number.0).collect();
+        assert_eq!(numbers, [2, 4, 5]);
+    }
+}
@@ -25,6 +25,17 @@ enum ProofGenerationJobStatus {
     Skipped,
 }
 
+/// How the proof for an L1 batch with skipped proof generation is handled by the proof verification layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum SkippedProofHandling {
+    /// No proof is submitted to the proof verification layer.
+    #[strum(serialize = "not_submitted")]
+    NotSubmitted,
+    /// A deterministic fake proof is submitted to the proof verification layer.
+    #[strum(serialize = "fake_proof")]
+    FakeProof,
+}
+
 impl ProofGenerationDal<'_, '_> {
     pub async fn get_next_block_to_be_proven(
         &mut self,
@@ -74,8 +85,6 @@ impl ProofGenerationDal<'_, '_> {
         &mut self,
         block_number: L1BatchNumber,
         proof_blob_url: &str,
-        attestation_id: u64,
-        attestation_element: H256,
     ) -> Result<(), SqlxError> {
         sqlx::query!(
             r#"
@@ -83,16 +92,12 @@ impl ProofGenerationDal<'_, '_> {
             SET
                 status = 'generated',
                 proof_blob_url = $1,
-                updated_at = NOW(),
-                attestation_id = $3,
-                attestation_element = $4
+                updated_at = NOW()
             WHERE
                 l1_batch_number = $2
             "#,
             proof_blob_url,
             i64::from(block_number.0),
-            u256_to_big_decimal(U256::from(attestation_id)),
-            attestation_element.as_bytes(),
         )
         .execute(self.storage.conn())
         .await?
@@ -125,25 +130,51 @@ impl ProofGenerationDal<'_, '_> {
 
     pub async fn mark_proof_generation_job_as_skipped(
         &mut self,
-        batch_number: L1BatchNumber,
-        attestation_id: u64,
-        attestation_element: H256,
+        block_number: L1BatchNumber,
+        skipped_proof_handling: SkippedProofHandling,
     ) -> Result<(), SqlxError> {
         sqlx::query!(
             r#"
             UPDATE proof_generation_details
             SET
                 status = $1,
-                updated_at = NOW(),
-                attestation_id = $3,
-                attestation_element = $4
+                skipped_proof_handling = $2,
+                updated_at = NOW()
             WHERE
-                l1_batch_number = $2
+                l1_batch_number = $3
             "#,
             ProofGenerationJobStatus::Skipped.to_string(),
-            i64::from(batch_number.0),
+            skipped_proof_handling.to_string(),
+            i64::from(block_number.0),
+        )
+        .execute(self.storage.conn())
+        .await?
+        .rows_affected()
+        .eq(&1)
+        .then_some(())
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Saves the NewHorizen attestation data for a batch once its proof submission is finalized.
+    pub async fn save_nh_attestation_element(
+        &mut self,
+        block_number: L1BatchNumber,
+        attestation_id: u64,
+        attestation_element: H256,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                attestation_id = $1,
+                attestation_element = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $3
+            "#,
             u256_to_big_decimal(U256::from(attestation_id)),
             attestation_element.as_bytes(),
+            i64::from(block_number.0),
         )
         .execute(self.storage.conn())
         .await?
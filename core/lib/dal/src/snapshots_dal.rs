@@ -184,6 +184,29 @@ impl SnapshotsDal<'_, '_> {
 
         row.map(TryFrom::try_from).transpose()
     }
+
+    /// Removes snapshots for the specified L1 batches. Snapshot files in the object store are not affected.
+    pub async fn delete_snapshots(
+        &mut self,
+        l1_batch_numbers: &[L1BatchNumber],
+    ) -> sqlx::Result<()> {
+        let l1_batch_numbers: Vec<_> = l1_batch_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        sqlx::query!(
+            r#"
+            DELETE FROM snapshots
+            WHERE
+                l1_batch_number = ANY ($1)
+            "#,
+            &l1_batch_numbers
+        )
+        .instrument("delete_snapshots")
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +258,10 @@ mod tests {
             .expect("Failed to retrieve snapshot")
             .unwrap();
         assert_eq!(snapshot_metadata.l1_batch_number, l1_batch_number);
+
+        dal.delete_snapshots(&[l1_batch_number]).await.unwrap();
+        let snapshot_metadata = dal.get_snapshot_metadata(l1_batch_number).await.unwrap();
+        assert!(snapshot_metadata.is_none());
     }
 
     #[tokio::test]
@@ -0,0 +1,48 @@
+use zksync_config::configs::DADispatcherConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for DADispatcherConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("da_dispatcher", "DA_DISPATCHER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::DataAvailabilityMode;
+
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    fn expected_config() -> DADispatcherConfig {
+        DADispatcherConfig {
+            mode: DataAvailabilityMode::Celestia,
+            celestia_api_node_url: Some("http://127.0.0.1:26658".to_owned()),
+            celestia_auth_token: Some("secret".to_owned()),
+            celestia_namespace: Some("000000000000007a6b73".to_owned()),
+            polling_interval_ms: Some(1_000),
+            max_batches_to_dispatch: Some(5),
+            retry_backoff_ms: Some(2_000),
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+            DA_DISPATCHER_MODE="Celestia"
+            DA_DISPATCHER_CELESTIA_API_NODE_URL="http://127.0.0.1:26658"
+            DA_DISPATCHER_CELESTIA_AUTH_TOKEN="secret"
+            DA_DISPATCHER_CELESTIA_NAMESPACE="000000000000007a6b73"
+            DA_DISPATCHER_POLLING_INTERVAL_MS=1000
+            DA_DISPATCHER_MAX_BATCHES_TO_DISPATCH=5
+            DA_DISPATCHER_RETRY_BACKOFF_MS=2000
+        "#;
+        let mut lock = MUTEX.lock();
+        lock.set_env(config);
+        let actual = DADispatcherConfig::from_env().unwrap();
+        assert_eq!(actual, expected_config());
+    }
+}
@@ -81,6 +81,13 @@ mod tests {
             DATABASE_MERKLE_TREE_MEMTABLE_CAPACITY_MB=512
             DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC=60
             DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER=50
+            DATABASE_MERKLE_TREE_ARCHIVE_MODE=true
+            DATABASE_MERKLE_TREE_ARCHIVE_CHECKPOINT_INTERVAL=100
+            DATABASE_MERKLE_TREE_PRUNING_RETAINED_VERSIONS=10
+            DATABASE_MERKLE_TREE_CONCURRENT_PERSISTENCE=true
+            DATABASE_MERKLE_TREE_CHECKPOINT_PATH="/db/tree_checkpoints"
+            DATABASE_MERKLE_TREE_CHECKPOINTS_IN_OBJECT_STORE=true
+            DATABASE_MERKLE_TREE_CHECKPOINT_EXPORT_INTERVAL=1000
         "#;
         lock.set_env(config);
 
@@ -92,6 +99,19 @@ mod tests {
         assert_eq!(db_config.merkle_tree.max_l1_batches_per_iter, 50);
         assert_eq!(db_config.merkle_tree.memtable_capacity_mb, 512);
         assert_eq!(db_config.merkle_tree.stalled_writes_timeout_sec, 60);
+        assert!(db_config.merkle_tree.archive_mode);
+        assert_eq!(db_config.merkle_tree.archive_checkpoint_interval, 100);
+        assert_eq!(db_config.merkle_tree.pruning_retained_versions, Some(10));
+        assert!(db_config.merkle_tree.concurrent_persistence);
+        assert_eq!(
+            db_config.merkle_tree.checkpoint_path.as_deref(),
+            Some("/db/tree_checkpoints")
+        );
+        assert!(db_config.merkle_tree.checkpoints_in_object_store);
+        assert_eq!(
+            db_config.merkle_tree.checkpoint_export_interval,
+            Some(1_000)
+        );
     }
 
     #[test]
@@ -107,6 +127,13 @@ mod tests {
             "DATABASE_MERKLE_TREE_MEMTABLE_CAPACITY_MB",
             "DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC",
             "DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER",
+            "DATABASE_MERKLE_TREE_ARCHIVE_MODE",
+            "DATABASE_MERKLE_TREE_ARCHIVE_CHECKPOINT_INTERVAL",
+            "DATABASE_MERKLE_TREE_PRUNING_RETAINED_VERSIONS",
+            "DATABASE_MERKLE_TREE_CONCURRENT_PERSISTENCE",
+            "DATABASE_MERKLE_TREE_CHECKPOINT_PATH",
+            "DATABASE_MERKLE_TREE_CHECKPOINTS_IN_OBJECT_STORE",
+            "DATABASE_MERKLE_TREE_CHECKPOINT_EXPORT_INTERVAL",
         ]);
 
         let db_config = DBConfig::from_env().unwrap();
@@ -118,6 +145,12 @@ mod tests {
         assert_eq!(db_config.merkle_tree.block_cache_size_mb, 128);
         assert_eq!(db_config.merkle_tree.memtable_capacity_mb, 256);
         assert_eq!(db_config.merkle_tree.stalled_writes_timeout_sec, 30);
+        assert!(!db_config.merkle_tree.archive_mode);
+        assert_eq!(db_config.merkle_tree.archive_checkpoint_interval, 1_000);
+        assert_eq!(db_config.merkle_tree.pruning_retained_versions, None);
+        assert!(!db_config.merkle_tree.concurrent_persistence);
+        assert_eq!(db_config.merkle_tree.checkpoint_path, None);
+        assert_eq!(db_config.merkle_tree.checkpoint_export_interval, None);
 
         // Check that new env variable for Merkle tree path is supported
         lock.set_env("DATABASE_MERKLE_TREE_PATH=/db/tree/main");
@@ -26,6 +26,11 @@ mod tests {
         ETHClientConfig {
             chain_id: 9,
             web3_url: "http://127.0.0.1:8545".into(),
+            fallback_web3_urls: vec![
+                "http://127.0.0.1:8546".into(),
+                "http://127.0.0.1:8547".into(),
+            ],
+            read_quorum: Some(2),
         }
     }
 
@@ -35,6 +40,8 @@ mod tests {
         let config = r#"
             ETH_CLIENT_CHAIN_ID="9"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_FALLBACK_WEB3_URLS="http://127.0.0.1:8546,http://127.0.0.1:8547"
+            ETH_CLIENT_READ_QUORUM="2"
         "#;
         lock.set_env(config);
 
@@ -1,5 +1,8 @@
 use anyhow::Context as _;
-use zksync_config::{configs::eth_sender::SenderConfig, ETHSenderConfig, GasAdjusterConfig};
+use zksync_config::{
+    configs::eth_sender::{FeeBumpConfig, FeeBumpStrategyConfig, SenderConfig},
+    ETHSenderConfig, GasAdjusterConfig,
+};
 
 use crate::{envy_load, FromEnv};
 
@@ -8,6 +11,7 @@ impl FromEnv for ETHSenderConfig {
         Ok(Self {
             sender: SenderConfig::from_env().context("SenderConfig")?,
             gas_adjuster: GasAdjusterConfig::from_env().context("GasAdjusterConfig")?,
+            fee_bump: FeeBumpConfig::from_env().context("FeeBumpConfig")?,
         })
     }
 }
@@ -24,10 +28,32 @@ impl FromEnv for GasAdjusterConfig {
     }
 }
 
+impl FromEnv for FeeBumpConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        Ok(Self {
+            commit: load_fee_bump_strategy("commit")?,
+            prove: load_fee_bump_strategy("prove")?,
+            execute: load_fee_bump_strategy("execute")?,
+        })
+    }
+}
+
+/// Loads the strategy for the specified operation type; the strategy is only configured
+/// if the corresponding `..._STRATEGY` variable is set.
+fn load_fee_bump_strategy(action: &str) -> anyhow::Result<Option<FeeBumpStrategyConfig>> {
+    let prefix = format!("ETH_SENDER_FEE_BUMP_{}_", action.to_uppercase());
+    if std::env::var(format!("{prefix}STRATEGY")).is_err() {
+        return Ok(None);
+    }
+    let name = format!("eth_sender.fee_bump.{action}");
+    envy_load(&name, &prefix).map(Some)
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_config::configs::eth_sender::{
-        ProofLoadingMode, ProofSendingMode, PubdataSendingMode,
+        FeeBumpStrategyKind, ProofLoadingMode, ProofSendingMode, ProofVerificationLayerMode,
+        PubdataSendingMode,
     };
 
     use super::*;
@@ -57,6 +83,7 @@ mod tests {
                 max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
                 pubdata_sending_mode: PubdataSendingMode::Calldata,
+                proof_verification_layer: ProofVerificationLayerMode::L1,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 20000000000,
@@ -71,6 +98,25 @@ mod tests {
                 internal_pubdata_pricing_multiplier: 1.0,
                 max_blob_base_fee: None,
             },
+            fee_bump: FeeBumpConfig {
+                commit: None,
+                prove: Some(FeeBumpStrategyConfig {
+                    strategy: FeeBumpStrategyKind::DeadlineAware,
+                    priority_fee_increase_percent: 20,
+                    multiplier: 1.125,
+                    max_multiplier: 5.0,
+                    deadline_sec: Some(3_600),
+                    abandon_after_sec: None,
+                }),
+                execute: Some(FeeBumpStrategyConfig {
+                    strategy: FeeBumpStrategyKind::Exponential,
+                    priority_fee_increase_percent: 20,
+                    multiplier: 1.5,
+                    max_multiplier: 3.0,
+                    deadline_sec: None,
+                    abandon_after_sec: Some(7_200),
+                }),
+            },
         }
     }
 
@@ -107,6 +153,13 @@ mod tests {
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PROOF_LOADING_MODE="OldProofFromDb"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_PROOF_VERIFICATION_LAYER="L1"
+            ETH_SENDER_FEE_BUMP_PROVE_STRATEGY="DeadlineAware"
+            ETH_SENDER_FEE_BUMP_PROVE_MAX_MULTIPLIER="5.0"
+            ETH_SENDER_FEE_BUMP_PROVE_DEADLINE_SEC="3600"
+            ETH_SENDER_FEE_BUMP_EXECUTE_STRATEGY="Exponential"
+            ETH_SENDER_FEE_BUMP_EXECUTE_MULTIPLIER="1.5"
+            ETH_SENDER_FEE_BUMP_EXECUTE_ABANDON_AFTER_SEC="7200"
         "#;
         lock.set_env(config);
 
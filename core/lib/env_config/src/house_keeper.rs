@@ -32,6 +32,8 @@ mod tests {
             fri_proof_compressor_stats_reporting_interval_ms: 30_000,
             fri_prover_job_archiver_reporting_interval_ms: Some(1_800_000),
             fri_prover_job_archiver_archiving_interval_secs: Some(172_800),
+            object_store_gc_interval_ms: Some(3_600_000),
+            object_store_gc_retention_secs: Some(604_800),
         }
     }
 
@@ -54,6 +56,8 @@ mod tests {
             HOUSE_KEEPER_FRI_PROOF_COMPRESSOR_JOB_RETRYING_INTERVAL_MS="30000"
             HOUSE_KEEPER_FRI_PROVER_JOB_ARCHIVER_REPORTING_INTERVAL_MS="1800000"
             HOUSE_KEEPER_FRI_PROVER_JOB_ARCHIVER_ARCHIVING_INTERVAL_SECS="172800"
+            HOUSE_KEEPER_OBJECT_STORE_GC_INTERVAL_MS="3600000"
+            HOUSE_KEEPER_OBJECT_STORE_GC_RETENTION_SECS="604800"
         "#;
         lock.set_env(config);
 
@@ -4,12 +4,17 @@ use crate::{envy_load, FromEnv};
 
 impl FromEnv for NewHorizenConfig {
     fn from_env() -> anyhow::Result<Self> {
-        envy_load("new_horizen", "NEW_HORIZEN_")
+        Ok(Self {
+            signer: Some(envy_load("new_horizen_signer", "NEW_HORIZEN_SIGNER_")?),
+            ..envy_load("new_horizen", "NEW_HORIZEN_")?
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use zksync_config::configs::new_horizen::NhSignerConfig;
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -18,7 +23,18 @@ mod tests {
     fn expected_config() -> NewHorizenConfig {
         NewHorizenConfig {
             url: "ws://127.0.0.1:9944".to_string(),
-            seed_phrase: "test test test".to_string(),
+            signer: Some(NhSignerConfig::Keystore {
+                keystore_path: "/etc/nh/keystore.json".to_string(),
+                passphrase: "correct horse battery staple".to_string(),
+            }),
+            submission_polling_interval_ms: Some(500),
+            submission_timeout_sec: Some(120),
+            submission_max_attempts: Some(5),
+            submission_retry_backoff_ms: Some(2000),
+            submission_batch_size: Some(4),
+            max_attestation_delay_sec: Some(1800),
+            attestation_reconciliation_interval_sec: Some(300),
+            attestation_reconciliation_block_range: Some(10000),
         }
     }
 
@@ -26,7 +42,17 @@ mod tests {
     fn from_env() {
         let config = r#"
             NEW_HORIZEN_URL="ws://127.0.0.1:9944"
-            NEW_HORIZEN_SEED_PHRASE="test test test"
+            NEW_HORIZEN_SIGNER_TYPE="Keystore"
+            NEW_HORIZEN_SIGNER_KEYSTORE_PATH="/etc/nh/keystore.json"
+            NEW_HORIZEN_SIGNER_PASSPHRASE="correct horse battery staple"
+            NEW_HORIZEN_SUBMISSION_POLLING_INTERVAL_MS=500
+            NEW_HORIZEN_SUBMISSION_TIMEOUT_SEC=120
+            NEW_HORIZEN_SUBMISSION_MAX_ATTEMPTS=5
+            NEW_HORIZEN_SUBMISSION_RETRY_BACKOFF_MS=2000
+            NEW_HORIZEN_SUBMISSION_BATCH_SIZE=4
+            NEW_HORIZEN_MAX_ATTESTATION_DELAY_SEC=1800
+            NEW_HORIZEN_ATTESTATION_RECONCILIATION_INTERVAL_SEC=300
+            NEW_HORIZEN_ATTESTATION_RECONCILIATION_BLOCK_RANGE=10000
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -56,6 +56,10 @@ mod tests {
                 gcs_credential_file_path: "/path/to/credentials.json".to_owned(),
             },
             max_retries: 5,
+            compressed_buckets: vec![],
+            deduplicated_buckets: vec![],
+            local_cache_path: None,
+            local_cache_max_size_bytes: None,
         }
     }
 
@@ -90,6 +94,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn s3_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="S3"
+            OBJECT_STORE_S3_ENDPOINT="http://localhost:9000"
+            OBJECT_STORE_S3_REGION="us-east-1"
+            OBJECT_STORE_S3_BUCKET="zksync"
+            OBJECT_STORE_S3_ACCESS_KEY_ID="minioadmin"
+            OBJECT_STORE_S3_SECRET_ACCESS_KEY="minioadmin"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(
+            actual.mode,
+            ObjectStoreMode::S3 {
+                s3_endpoint: "http://localhost:9000".to_owned(),
+                s3_region: "us-east-1".to_owned(),
+                s3_bucket: "zksync".to_owned(),
+                s3_access_key_id: "minioadmin".to_owned(),
+                s3_secret_access_key: "minioadmin".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn azure_blob_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="AzureBlob"
+            OBJECT_STORE_AZURE_ACCOUNT_NAME="account"
+            OBJECT_STORE_AZURE_CONTAINER="zksync"
+            OBJECT_STORE_AZURE_ACCESS_KEY="a2V5"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(
+            actual.mode,
+            ObjectStoreMode::AzureBlob {
+                azure_account_name: "account".to_owned(),
+                azure_container: "zksync".to_owned(),
+                azure_access_key: "a2V5".to_owned(),
+                azure_endpoint: None,
+            }
+        );
+    }
+
+    #[test]
+    fn compression_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="FileBacked"
+            OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            OBJECT_STORE_COMPRESSED_BUCKETS="witness_inputs,prover_jobs_fri"
+            OBJECT_STORE_DEDUPLICATED_BUCKETS="prover_jobs_fri"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(
+            actual.compressed_buckets,
+            ["witness_inputs", "prover_jobs_fri"]
+        );
+        assert_eq!(actual.deduplicated_buckets, ["prover_jobs_fri"]);
+    }
+
+    #[test]
+    fn local_cache_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="GCSAnonymousReadOnly"
+            OBJECT_STORE_BUCKET_BASE_URL="/base/url"
+            OBJECT_STORE_LOCAL_CACHE_PATH="./object_cache"
+            OBJECT_STORE_LOCAL_CACHE_MAX_SIZE_BYTES="1048576"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(actual.local_cache_path.as_deref(), Some("./object_cache"));
+        assert_eq!(actual.local_cache_max_size_bytes(), 1 << 20);
+    }
+
     #[test]
     fn public_bucket_config_from_env() {
         let mut lock = MUTEX.lock();
@@ -10,6 +10,8 @@ impl FromEnv for ProofDataHandlerConfig {
 
 #[cfg(test)]
 mod tests {
+    use zksync_config::configs::proof_data_handler::SkippedProofMode;
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -19,6 +21,7 @@ mod tests {
         ProofDataHandlerConfig {
             http_port: 3320,
             proof_generation_timeout_in_secs: 18000,
+            skipped_proof_mode: SkippedProofMode::FakeProof,
         }
     }
 
@@ -27,6 +30,7 @@ mod tests {
         let config = r#"
             PROOF_DATA_HANDLER_PROOF_GENERATION_TIMEOUT_IN_SECS="18000"
             PROOF_DATA_HANDLER_HTTP_PORT="3320"
+            PROOF_DATA_HANDLER_SKIPPED_PROOF_MODE="FakeProof"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
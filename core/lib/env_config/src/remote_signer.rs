@@ -0,0 +1,40 @@
+use zksync_config::configs::RemoteSignerConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for RemoteSignerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("remote_signer", "ETH_SENDER_REMOTE_SIGNER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{addr, EnvMutex};
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    fn expected_config() -> RemoteSignerConfig {
+        RemoteSignerConfig {
+            url: "http://127.0.0.1:9000".to_owned(),
+            operator_address: addr("de03a0b5963f75f1c8485b355ff6d30f3093bde7"),
+            blobs_operator_address: Some(addr("a61464658afeaf65cccaafd3a512b69a83b77618")),
+            request_timeout_ms: Some(5_000),
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+            ETH_SENDER_REMOTE_SIGNER_URL="http://127.0.0.1:9000"
+            ETH_SENDER_REMOTE_SIGNER_OPERATOR_ADDRESS="0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+            ETH_SENDER_REMOTE_SIGNER_BLOBS_OPERATOR_ADDRESS="0xa61464658afeaf65cccaafd3a512b69a83b77618"
+            ETH_SENDER_REMOTE_SIGNER_REQUEST_TIMEOUT_MS=5000
+        "#;
+        let mut lock = MUTEX.lock();
+        lock.set_env(config);
+        let actual = RemoteSignerConfig::from_env().unwrap();
+        assert_eq!(actual, expected_config());
+    }
+}
@@ -1,15 +1,18 @@
 use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
-use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_config::{
+    configs::RemoteSignerConfig, ContractsConfig, ETHClientConfig, ETHSenderConfig,
+};
 use zksync_contracts::zksync_contract;
-use zksync_eth_signer::{raw_ethereum_tx::TransactionParameters, EthereumSigner, PrivateKeySigner};
+use zksync_eth_signer::{
+    raw_ethereum_tx::TransactionParameters, EthereumSigner, PrivateKeySigner, Web3Signer,
+};
 use zksync_types::{
     web3::{
         self,
         contract::tokens::Detokenize,
         ethabi,
-        transports::Http,
         types::{
             Address, BlockId, BlockNumber, Filter, Log, Transaction, TransactionReceipt, H160,
             H256, U256, U64,
@@ -18,8 +21,9 @@ use zksync_types::{
     L1ChainId, PackedEthSignature, EIP_4844_TX_TYPE,
 };
 
-use super::{query::QueryClient, Method, LATENCIES};
+use super::{Method, LATENCIES};
 use crate::{
+    clients::l1_client,
     types::{encode_blob_tx_with_sidecar, Error, ExecutedTxStatus, FailureInfo, SignedCallResult},
     Block, BoundEthInterface, CallFunctionArgs, ContractCall, EthInterface, Options,
     RawTransactionBytes,
@@ -73,19 +77,18 @@ impl PKSigningClient {
         eth_client: &ETHClientConfig,
         operator_private_key: H256,
     ) -> Self {
-        let main_node_url = &eth_client.web3_url;
         let diamond_proxy_addr = contracts_config.diamond_proxy_addr;
         let default_priority_fee_per_gas = eth_sender.gas_adjuster.default_priority_fee_per_gas;
         let l1_chain_id = eth_client.chain_id;
 
-        let transport = Http::new(main_node_url).expect("Failed to create transport");
+        let query_client = l1_client(eth_client).expect("Failed to create L1 client");
         let operator_address = PackedEthSignature::address_from_private_key(&operator_private_key)
             .expect("Failed to get address from private key");
 
         tracing::info!("Operator address: {:?}", operator_address);
 
         SigningClient::new(
-            transport,
+            query_client,
             zksync_contract(),
             operator_address,
             PrivateKeySigner::new(operator_private_key),
@@ -96,6 +99,81 @@ impl PKSigningClient {
     }
 }
 
+/// HTTP-based Ethereum client, backed by a remote Web3Signer instance to sign transactions.
+/// Operator keys are not stored on the node in this case.
+pub type Web3SignerClient = SigningClient<Web3Signer>;
+
+impl Web3SignerClient {
+    pub async fn from_config(
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+        remote_signer: &RemoteSignerConfig,
+    ) -> Result<Self, Error> {
+        Self::from_config_inner(
+            eth_sender,
+            contracts_config,
+            eth_client,
+            remote_signer,
+            remote_signer.operator_address,
+        )
+        .await
+    }
+
+    /// Create a signing client for the blobs account. Returns `Ok(None)` if the blobs account is not configured.
+    pub async fn from_config_blobs(
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+        remote_signer: &RemoteSignerConfig,
+    ) -> Result<Option<Self>, Error> {
+        let Some(operator_address) = remote_signer.blobs_operator_address else {
+            return Ok(None);
+        };
+        let client = Self::from_config_inner(
+            eth_sender,
+            contracts_config,
+            eth_client,
+            remote_signer,
+            operator_address,
+        )
+        .await?;
+        Ok(Some(client))
+    }
+
+    async fn from_config_inner(
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+        remote_signer: &RemoteSignerConfig,
+        operator_address: Address,
+    ) -> Result<Self, Error> {
+        let diamond_proxy_addr = contracts_config.diamond_proxy_addr;
+        let default_priority_fee_per_gas = eth_sender.gas_adjuster.default_priority_fee_per_gas;
+        let l1_chain_id = eth_client.chain_id;
+
+        let query_client = l1_client(eth_client)?;
+        let signer = Web3Signer::new(
+            remote_signer.url.clone(),
+            operator_address,
+            remote_signer.request_timeout(),
+        )
+        .await?;
+
+        tracing::info!("Operator address: {operator_address:?} (signing via Web3Signer)");
+
+        Ok(SigningClient::new(
+            query_client,
+            zksync_contract(),
+            operator_address,
+            signer,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas.into(),
+            L1ChainId(l1_chain_id),
+        ))
+    }
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -107,7 +185,7 @@ const FALLBACK_GAS_LIMIT: u64 = 3_000_000;
 #[derive(Clone)]
 pub struct SigningClient<S: EthereumSigner> {
     inner: Arc<ETHDirectClientInner<S>>,
-    query_client: QueryClient,
+    query_client: Arc<dyn EthInterface>,
 }
 
 struct ETHDirectClientInner<S: EthereumSigner> {
@@ -351,7 +429,7 @@ impl<S: EthereumSigner> BoundEthInterface for SigningClient<S> {
 
 impl<S: EthereumSigner> SigningClient<S> {
     pub fn new(
-        transport: Http,
+        query_client: Arc<dyn EthInterface>,
         contract: ethabi::Contract,
         operator_eth_addr: H160,
         eth_signer: S,
@@ -368,7 +446,7 @@ impl<S: EthereumSigner> SigningClient<S> {
                 contract,
                 default_priority_fee_per_gas,
             }),
-            query_client: transport.into(),
+            query_client,
         }
     }
 }
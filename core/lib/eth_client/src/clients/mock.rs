@@ -78,6 +78,7 @@ struct MockEthereumInner {
     current_nonce: u64,
     pending_nonce: u64,
     nonces: BTreeMap<u64, u64>,
+    logs: Vec<Log>,
 }
 
 impl MockEthereumInner {
@@ -244,6 +245,14 @@ impl MockEthereum {
         ))
     }
 
+    /// Emits a log in the current block. Emitted logs are returned by [`EthInterface::logs()`]
+    /// regardless of the filter.
+    pub fn emit_log(&self, mut log: Log) {
+        let mut inner = self.inner.write().unwrap();
+        log.block_number = Some(inner.block_number.into());
+        inner.logs.push(log);
+    }
+
     pub fn advance_block_number(&self, val: u64) -> u64 {
         let mut inner = self.inner.write().unwrap();
         inner.block_number += val;
@@ -394,7 +403,7 @@ impl EthInterface for MockEthereum {
     }
 
     async fn logs(&self, _filter: Filter, _component: &'static str) -> Result<Vec<Log>, Error> {
-        unimplemented!("Not needed right now")
+        Ok(self.inner.read().unwrap().logs.clone())
     }
 
     async fn block(
@@ -484,6 +493,8 @@ impl BoundEthInterface for MockEthereum {
 
 #[cfg(test)]
 mod tests {
+    use zksync_types::web3::types::FilterBuilder;
+
     use super::*;
 
     #[tokio::test]
@@ -497,6 +508,38 @@ mod tests {
         assert_eq!(block_number, 5.into());
     }
 
+    #[tokio::test]
+    async fn managing_logs() {
+        let client = MockEthereum::default();
+        client.advance_block_number(3);
+        let log = Log {
+            address: Address::repeat_byte(1),
+            topics: vec![H256::repeat_byte(2)],
+            data: vec![].into(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        client.emit_log(log.clone());
+
+        let logs = client
+            .logs(FilterBuilder::default().build(), "test")
+            .await
+            .unwrap();
+        assert_eq!(
+            logs,
+            [Log {
+                block_number: Some(3.into()),
+                ..log
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn managing_transactions() {
         let client = MockEthereum::default().with_non_ordering_confirmation(true);
@@ -3,8 +3,10 @@
 mod generic;
 mod http;
 mod mock;
+mod multi;
 
 pub use self::{
-    http::{PKSigningClient, QueryClient, SigningClient},
+    http::{PKSigningClient, QueryClient, SigningClient, Web3SignerClient},
     mock::MockEthereum,
+    multi::{l1_client, MultiEndpointClient},
 };
@@ -15,7 +15,7 @@ use zksync_types::{
 };
 
 /// Wrapper for `Vec<ethabi::Token>` that doesn't wrap them in an additional array in `Tokenize` implementation.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct RawTokens(pub Vec<ethabi::Token>);
 
 impl Tokenize for RawTokens {
@@ -31,7 +31,7 @@ impl Detokenize for RawTokens {
 }
 
 /// Arguments for calling a function in an unspecified Ethereum smart contract.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CallFunctionArgs {
     pub(crate) name: String,
     pub(crate) from: Option<Address>,
@@ -76,7 +76,7 @@ impl CallFunctionArgs {
 
 /// Information sufficient for calling a function in a specific Ethereum smart contract. Instantiated
 /// using [`CallFunctionArgs::for_contract()`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ContractCall {
     pub(crate) contract_address: Address,
     pub(crate) contract_abi: ethabi::Contract,
@@ -121,6 +121,32 @@ pub enum Error {
     /// EIP4844 transaction lacks `blob_versioned_hashes` field
     #[error("EIP4844 transaction lacks blob_versioned_hashes field")]
     Eip4844MissingBlobVersionedHashes,
+    /// Not enough L1 endpoints returned matching responses for a quorum read.
+    #[error("Quorum not reached for `{method}`: {matching} out of {total} endpoints returned matching responses, while {quorum} are required")]
+    QuorumNotReached {
+        method: &'static str,
+        quorum: usize,
+        matching: usize,
+        total: usize,
+    },
+    /// Misconfigured L1 client.
+    #[error("Invalid L1 client configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl Error {
+    /// Checks whether the error is caused by a transient network issue, i.e. the request may succeed
+    /// if it's retried (possibly via another L1 endpoint).
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::EthereumGateway(
+                zksync_types::web3::Error::Unreachable
+                    | zksync_types::web3::Error::Transport(_)
+                    | zksync_types::web3::Error::Io(_)
+            )
+        )
+    }
 }
 
 /// Raw transaction bytes.
@@ -230,7 +256,7 @@ pub fn encode_blob_tx_with_sidecar(raw_tx: &[u8], sidecar: &EthTxBlobSidecar) ->
 }
 
 /// State of the executed Ethereum transaction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExecutedTxStatus {
     /// The hash of the executed L1 transaction.
     pub tx_hash: H256,
@@ -52,28 +52,10 @@ impl EthereumSigner for PrivateKeySigner {
         raw_tx: TransactionParameters,
     ) -> Result<Vec<u8>, SignerError> {
         let key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
 
-        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
-        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
-        let gas_price = raw_tx.max_fee_per_gas;
-
-        let max_priority_fee_per_gas = raw_tx.max_priority_fee_per_gas;
-
-        let tx = Transaction {
-            to: raw_tx.to,
-            nonce: raw_tx.nonce,
-            gas: raw_tx.gas,
-            gas_price,
-            value: raw_tx.value,
-            data: raw_tx.data,
-            transaction_type: raw_tx.transaction_type,
-            access_list: raw_tx.access_list.unwrap_or_default(),
-            max_priority_fee_per_gas,
-            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas,
-            blob_versioned_hashes: raw_tx.blob_versioned_hashes,
-        };
-
-        let signed = tx.sign(&key, raw_tx.chain_id);
+        let signed = tx.sign(&key, chain_id);
         Ok(signed.raw_transaction.0)
     }
 }
@@ -73,6 +73,28 @@ pub struct Transaction {
     pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
+impl From<TransactionParameters> for Transaction {
+    fn from(raw_tx: TransactionParameters) -> Self {
+        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+        let gas_price = raw_tx.max_fee_per_gas;
+
+        Self {
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            gas_price,
+            value: raw_tx.value,
+            data: raw_tx.data,
+            transaction_type: raw_tx.transaction_type,
+            access_list: raw_tx.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: raw_tx.blob_versioned_hashes,
+        }
+    }
+}
+
 impl Transaction {
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
@@ -243,14 +265,34 @@ impl Transaction {
         }
     }
 
-    /// Sign and return a raw signed transaction.
-    pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
+    fn is_legacy(&self) -> bool {
+        matches!(
             self.transaction_type.map(|t| t.as_u64()),
             Some(LEGACY_TX_ID) | None
-        );
+        )
+    }
+
+    /// Returns the payload which keccak256 hash must be signed to produce a signed transaction.
+    pub fn signing_payload(&self, chain_id: u64) -> Vec<u8> {
+        self.encode(chain_id, None)
+    }
+
+    /// Encodes the transaction with a signature over [`Self::signing_payload()`] produced elsewhere,
+    /// e.g. by a remote signer. `recovery_id` must be 0 or 1.
+    pub fn encode_signed(&self, chain_id: u64, recovery_id: u8, r: H256, s: H256) -> Vec<u8> {
+        let v = if self.is_legacy() {
+            u64::from(recovery_id) + 35 + chain_id * 2
+        } else {
+            u64::from(recovery_id)
+        };
+        self.encode(chain_id, Some(&Signature { v, r, s }))
+    }
+
+    /// Sign and return a raw signed transaction.
+    pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
+        let adjust_v_value = self.is_legacy();
 
-        let encoded = self.encode(chain_id, None);
+        let encoded = self.signing_payload(chain_id);
 
         let hash = signing::keccak256(encoded.as_ref());
 
@@ -0,0 +1,385 @@
+//! Signer delegating signing to a remote [Web3Signer](https://docs.web3signer.consensys.io/) instance.
+//!
+//! Unlike [`JsonRpcSigner`](crate::JsonRpcSigner), this signer does not rely on `eth_signTransaction`.
+//! Transactions (including EIP-1559 and EIP-4844 ones) are RLP-encoded locally, and only the signing payload
+//! is sent to the Web3Signer `eth1/sign` endpoint. The returned signature is checked against the expected address
+//! before being used.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use reqwest::StatusCode;
+use serde::Serialize;
+use zksync_types::{
+    tx::primitives::PackedEthSignature, web3::signing::keccak256, Address, EIP712TypedStructure,
+    Eip712Domain, H256,
+};
+
+use crate::{
+    json_rpc_signer::is_signature_from_address,
+    raw_ethereum_tx::{Transaction, TransactionParameters},
+    EthereumSigner, SignerError,
+};
+
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    data: String,
+}
+
+/// Signer backed by a remote Web3Signer instance. The signer holds no key material; keys are identified
+/// by the Ethereum address they correspond to.
+///
+/// # Key rotation
+///
+/// Web3Signer identifies keys by their public keys, which are resolved from the configured address and cached.
+/// If the key becomes unavailable (e.g., its keystore was rotated or moved to another vault), the signer asks
+/// Web3Signer to reload its keys, resolves the key identifier again, and retries signing once.
+#[derive(Debug, Clone)]
+pub struct Web3Signer {
+    url: String,
+    client: reqwest::Client,
+    address: Address,
+    /// Web3Signer identifier of the key corresponding to `address`; `None` if it should be (re)resolved.
+    key_identifier: Arc<RwLock<Option<String>>>,
+}
+
+impl Web3Signer {
+    /// Creates a signer for the specified `address`. Fails if Web3Signer does not have a key for the address.
+    pub async fn new(
+        url: impl Into<String>,
+        address: Address,
+        request_timeout: Duration,
+    ) -> Result<Self, SignerError> {
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .map_err(|err| SignerError::CustomError(err.to_string()))?;
+        let url: String = url.into();
+        let signer = Self {
+            url: url.trim_end_matches('/').to_owned(),
+            client,
+            address,
+            key_identifier: Arc::default(),
+        };
+        signer.resolve_key_identifier().await?;
+        Ok(signer)
+    }
+
+    /// Returns the Ethereum address of the signing key.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Makes Web3Signer reload its keys, e.g. after the signing key was rotated.
+    pub async fn reload_keys(&self) -> Result<(), SignerError> {
+        let response = self
+            .client
+            .post(format!("{}/reload", self.url))
+            .send()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if !response.status().is_success() {
+            let err = format!("reloading keys failed with status {}", response.status());
+            return Err(SignerError::SigningFailed(err));
+        }
+        *self.key_identifier.write().unwrap() = None;
+        Ok(())
+    }
+
+    async fn key_identifier(&self) -> Result<String, SignerError> {
+        let cached = self.key_identifier.read().unwrap().clone();
+        match cached {
+            Some(identifier) => Ok(identifier),
+            None => self.resolve_key_identifier().await,
+        }
+    }
+
+    async fn resolve_key_identifier(&self) -> Result<String, SignerError> {
+        let response = self
+            .client
+            .get(format!("{}/api/v1/eth1/publicKeys", self.url))
+            .send()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if !response.status().is_success() {
+            let err = format!("listing keys failed with status {}", response.status());
+            return Err(SignerError::SigningFailed(err));
+        }
+        let public_keys: Vec<String> = response
+            .json()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        for public_key in public_keys {
+            if address_from_public_key(&public_key)? == self.address {
+                *self.key_identifier.write().unwrap() = Some(public_key.clone());
+                return Ok(public_key);
+            }
+        }
+        Err(SignerError::DefineAddress)
+    }
+
+    /// Signs the keccak256 hash of `data`. Retries once after reloading keys if the key is not found.
+    async fn sign_data(&self, data: &[u8]) -> Result<PackedEthSignature, SignerError> {
+        let identifier = self.key_identifier().await?;
+        let signature = match self.request_signature(&identifier, data).await? {
+            Some(signature) => signature,
+            None => {
+                // The key may have been rotated; make Web3Signer reload keys and retry.
+                self.reload_keys().await?;
+                let identifier = self.resolve_key_identifier().await?;
+                self.request_signature(&identifier, data)
+                    .await?
+                    .ok_or(SignerError::DefineAddress)?
+            }
+        };
+
+        let signed_bytes = H256(keccak256(data));
+        if is_signature_from_address(&signature, &signed_bytes, self.address)? {
+            Ok(signature)
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from Web3Signer".to_owned(),
+            ))
+        }
+    }
+
+    /// Returns `Ok(None)` if Web3Signer does not have the key with the specified identifier.
+    async fn request_signature(
+        &self,
+        identifier: &str,
+        data: &[u8],
+    ) -> Result<Option<PackedEthSignature>, SignerError> {
+        let request = SignRequest {
+            data: format!("0x{}", hex::encode(data)),
+        };
+        let response = self
+            .client
+            .post(format!("{}/api/v1/eth1/sign/{identifier}", self.url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        match response.status() {
+            StatusCode::NOT_FOUND => return Ok(None),
+            status if !status.is_success() => {
+                let err = format!("signing failed with status {status}");
+                return Err(SignerError::SigningFailed(err));
+            }
+            _ => { /* continue processing */ }
+        }
+
+        let signature = response
+            .text()
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let signature = hex::decode(signature.trim().trim_start_matches("0x"))
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        PackedEthSignature::deserialize_packed(&signature)
+            .map(Some)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))
+    }
+}
+
+/// Computes the Ethereum address for a hex-encoded uncompressed secp256k1 public key
+/// (with or without the `0x04` prefix).
+fn address_from_public_key(public_key: &str) -> Result<Address, SignerError> {
+    let bytes = hex::decode(public_key.trim_start_matches("0x"))
+        .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+    let bytes = match bytes.len() {
+        64 => &bytes[..],
+        65 if bytes[0] == 4 => &bytes[1..],
+        len => {
+            let err = format!("unexpected public key length: {len} bytes");
+            return Err(SignerError::SigningFailed(err));
+        }
+    };
+    Ok(Address::from_slice(&keccak256(bytes)[12..]))
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for Web3Signer {
+    /// Signs typed struct by EIP-712 signature standard.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let mut data = b"\x19\x01".to_vec();
+        data.extend_from_slice(domain.hash_struct().as_bytes());
+        data.extend_from_slice(typed_struct.hash_struct().as_bytes());
+        self.sign_data(&data).await
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+        let signature = self.sign_data(&tx.signing_payload(chain_id)).await?;
+        Ok(tx.encode_signed(
+            chain_id,
+            signature.v(),
+            H256::from_slice(signature.r()),
+            H256::from_slice(signature.s()),
+        ))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::IntoFuture,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use axum::{
+        extract::{Json, Path, State},
+        http::StatusCode as HttpStatusCode,
+        routing::{get, post},
+        Router,
+    };
+    use secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use zksync_types::{U256, U64};
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    #[derive(Debug)]
+    struct ServerState {
+        private_key: H256,
+        key_loaded: AtomicBool,
+    }
+
+    impl ServerState {
+        fn public_key(&self) -> String {
+            let secret_key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
+            let public_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+            format!(
+                "0x{}",
+                hex::encode(&public_key.serialize_uncompressed()[1..])
+            )
+        }
+    }
+
+    async fn public_keys(State(state): State<Arc<ServerState>>) -> Json<Vec<String>> {
+        Json(vec![state.public_key()])
+    }
+
+    async fn reload(State(state): State<Arc<ServerState>>) -> HttpStatusCode {
+        state.key_loaded.store(true, Ordering::SeqCst);
+        HttpStatusCode::OK
+    }
+
+    async fn sign(
+        State(state): State<Arc<ServerState>>,
+        Path(identifier): Path<String>,
+        Json(request): Json<serde_json::Value>,
+    ) -> Result<String, HttpStatusCode> {
+        if identifier != state.public_key() || !state.key_loaded.load(Ordering::SeqCst) {
+            return Err(HttpStatusCode::NOT_FOUND);
+        }
+        let data = request["data"].as_str().unwrap().trim_start_matches("0x");
+        let hash = H256(keccak256(&hex::decode(data).unwrap()));
+        let signature = PackedEthSignature::sign_raw(&state.private_key, &hash).unwrap();
+        Ok(format!("0x{}", hex::encode(signature.serialize_packed())))
+    }
+
+    async fn run_server(state: Arc<ServerState>) -> String {
+        let app = Router::new()
+            .route("/api/v1/eth1/publicKeys", get(public_keys))
+            .route("/api/v1/eth1/sign/:identifier", post(sign))
+            .route("/reload", post(reload))
+            .with_state(state);
+        let server = axum::Server::bind(&([127, 0, 0, 1], 0).into()).serve(app.into_make_service());
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server.into_future());
+        url
+    }
+
+    fn test_transactions() -> Vec<TransactionParameters> {
+        let eip1559_tx = TransactionParameters {
+            nonce: U256::from(1),
+            to: Some(Address::repeat_byte(0x11)),
+            gas: U256::from(100_000),
+            max_fee_per_gas: U256::from(2_000_000_000_u64),
+            max_priority_fee_per_gas: U256::from(1_000_000_000),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type: Some(U64::from(2)),
+            ..TransactionParameters::default()
+        };
+        let eip4844_tx = TransactionParameters {
+            transaction_type: Some(U64::from(3)),
+            max_fee_per_blob_gas: Some(U256::from(10)),
+            blob_versioned_hashes: Some(vec![H256::repeat_byte(1), H256::repeat_byte(2)]),
+            ..eip1559_tx.clone()
+        };
+        vec![eip1559_tx, eip4844_tx]
+    }
+
+    #[tokio::test]
+    async fn signing_transactions() {
+        let private_key = H256::repeat_byte(0x17);
+        let state = Arc::new(ServerState {
+            private_key,
+            key_loaded: AtomicBool::new(true),
+        });
+        let url = run_server(state).await;
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let signer = Web3Signer::new(url, address, Duration::from_secs(5))
+            .await
+            .unwrap();
+        let pk_signer = PrivateKeySigner::new(private_key);
+
+        for tx in test_transactions() {
+            let signed_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+            let expected_signed_tx = pk_signer.sign_transaction(tx).await.unwrap();
+            assert_eq!(signed_tx, expected_signed_tx);
+        }
+    }
+
+    #[tokio::test]
+    async fn creating_signer_for_unknown_address() {
+        let state = Arc::new(ServerState {
+            private_key: H256::repeat_byte(0x17),
+            key_loaded: AtomicBool::new(true),
+        });
+        let url = run_server(state).await;
+        let err = Web3Signer::new(url, Address::repeat_byte(1), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(err, SignerError::DefineAddress);
+    }
+
+    #[tokio::test]
+    async fn reloading_keys_on_missing_key() {
+        let private_key = H256::repeat_byte(0x17);
+        let state = Arc::new(ServerState {
+            private_key,
+            key_loaded: AtomicBool::new(false),
+        });
+        let url = run_server(state.clone()).await;
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let signer = Web3Signer::new(url, address, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let tx = test_transactions().pop().unwrap();
+        let signed_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+        assert!(state.key_loaded.load(Ordering::SeqCst));
+        let expected_signed_tx = PrivateKeySigner::new(private_key)
+            .sign_transaction(tx)
+            .await
+            .unwrap();
+        assert_eq!(signed_tx, expected_signed_tx);
+    }
+}
@@ -10,7 +10,7 @@ use kzg::{
             CurveAffine,
         },
         eip_4844::{
-            bitreverse, fft,
+            bitreverse, ethereum_4844_data_into_zksync_pubdata, fft,
             input::{BLOB_CHUNK_SIZE, ELEMENTS_PER_4844_BLOCK},
             zksync_pubdata_into_ethereum_4844_data, zksync_pubdata_into_monomial_form_poly,
         },
@@ -267,6 +267,27 @@ impl KzgInfo {
     }
 }
 
+/// Recomputes the KZG commitment to a 4844 blob produced by [`KzgInfo::new()`]. Returns `None` if the blob
+/// is malformed, i.e., has an unexpected length or cannot be produced from zkSync pubdata.
+pub fn compute_blob_commitment(blob: &[u8]) -> Option<[u8; 48]> {
+    let blob: &[u8; EIP_4844_BYTES_PER_BLOB] = blob.try_into().ok()?;
+    let zksync_blob = ethereum_4844_data_into_zksync_pubdata(blob);
+    // The conversion above ignores the most significant byte of each field element,
+    // so we check that it round-trips.
+    if zksync_pubdata_into_ethereum_4844_data(&zksync_blob)[..] != blob[..] {
+        return None;
+    }
+
+    let mut poly = zksync_pubdata_into_monomial_form_poly(&zksync_blob);
+    fft(&mut poly);
+    bitreverse(&mut poly);
+    let kzg_commitment = compute_commitment(&KZG_SETTINGS, &poly);
+
+    let mut commitment = [0u8; 48];
+    commitment.copy_from_slice(kzg_commitment.into_compressed().as_ref());
+    Some(commitment)
+}
+
 pub fn pubdata_to_blob_commitments(pubdata_input: &[u8]) -> [H256; 2] {
     assert!(
         pubdata_input.len() <= 2 * ZK_SYNC_BYTES_PER_BLOB,
@@ -133,3 +133,23 @@ fn bytes_test() {
     let decoded_kzg_info = KzgInfo::from_slice(&encoded_info);
     assert_eq!(kzg_info, decoded_kzg_info);
 }
+
+#[test]
+fn recomputing_blob_commitment() {
+    let kzg_test: KzgTest = serde_json::from_str(KZG_TEST_JSON).unwrap();
+    let kzg_info = KzgInfo::new(&kzg_test.pubdata);
+
+    let commitment = compute_blob_commitment(&kzg_info.blob).unwrap();
+    assert_eq!(commitment, kzg_info.kzg_commitment);
+
+    let mut corrupted_blob = kzg_info.blob;
+    corrupted_blob[1] ^= 1;
+    let commitment = compute_blob_commitment(&corrupted_blob).unwrap();
+    assert_ne!(commitment, kzg_info.kzg_commitment);
+
+    // The most significant byte of each field element must be zero.
+    let mut malformed_blob = kzg_info.blob;
+    malformed_blob[0] = 1;
+    assert_eq!(compute_blob_commitment(&malformed_blob), None);
+    assert_eq!(compute_blob_commitment(&kzg_info.blob[1..]), None);
+}
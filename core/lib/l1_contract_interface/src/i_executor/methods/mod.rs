@@ -3,7 +3,7 @@
 pub use self::{
     commit_batches::{CommitBatchesRollup, CommitBatchesValidium},
     execute_batches::ExecuteBatches,
-    prove_batches::{NewHorizenProof, ProveBatches},
+    prove_batches::{NewHorizenProof, Proof, ProveBatches},
 };
 
 mod commit_batches;
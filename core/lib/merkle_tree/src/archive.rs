@@ -0,0 +1,258 @@
+//! Rebuilding pruned versions of the Merkle tree in the archive mode.
+
+use crate::{
+    errors::DeserializeError,
+    storage::{Database, NodeKeys, PatchSet, Patched, Storage},
+    types::{Manifest, Node, NodeKey, Root, TreeEntry, TreeEntryWithProof, TreeRangeWithProof},
+    HashTree, Key, MerkleTree, NoVersionError,
+};
+
+/// Readonly view of a [`Database`]. Used as a base for [`Patched`] when rebuilding tree versions;
+/// all changes are kept in the in-memory patch and are never applied to the view.
+#[derive(Debug)]
+struct ReadonlyDatabase<'a, DB>(&'a DB);
+
+impl<DB: Database> Database for ReadonlyDatabase<'_, DB> {
+    fn try_manifest(&self) -> Result<Option<Manifest>, DeserializeError> {
+        self.0.try_manifest()
+    }
+
+    fn try_root(&self, version: u64) -> Result<Option<Root>, DeserializeError> {
+        self.0.try_root(version)
+    }
+
+    fn try_tree_node(
+        &self,
+        key: &NodeKey,
+        is_leaf: bool,
+    ) -> Result<Option<Node>, DeserializeError> {
+        self.0.try_tree_node(key, is_leaf)
+    }
+
+    fn tree_nodes(&self, keys: &NodeKeys) -> Vec<Option<Node>> {
+        self.0.tree_nodes(keys)
+    }
+
+    fn try_diff_log(&self, version: u64) -> Result<Option<Vec<TreeEntry>>, DeserializeError> {
+        self.0.try_diff_log(version)
+    }
+
+    fn apply_patch(&mut self, _patch: PatchSet) {
+        unreachable!("Rebuilt tree versions are never flushed to the database");
+    }
+}
+
+impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
+    pub(crate) const DEFAULT_MAX_REPLAYED_VERSIONS: u64 = 1_000;
+
+    /// Reads entries together with Merkle proofs with the specified keys from the tree. Unlike
+    /// [`Self::entries_with_proofs()`], this method can serve versions removed by the [`MerkleTreePruner`]
+    /// if the tree was in the [archive mode](Self::set_archive_mode()). Such versions are rebuilt in memory
+    /// from the latest preceding retained version (a checkpoint) and diff logs of the following versions.
+    /// The rebuilt versions are not persisted, so rebuilding is repeated on each call; the number of replayed
+    /// versions is bounded by [`Self::set_max_replayed_versions()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing and cannot be rebuilt, e.g. because the diff logs
+    /// were not recorded for it, or because the preceding checkpoint is too far from it.
+    ///
+    /// [`MerkleTreePruner`]: crate::MerkleTreePruner
+    pub fn historical_entries_with_proofs(
+        &self,
+        version: u64,
+        leaf_keys: &[Key],
+    ) -> Result<Vec<TreeEntryWithProof>, NoVersionError> {
+        if self.db.root(version).is_some() {
+            return self.entries_with_proofs(version, leaf_keys);
+        }
+        let rebuilt_tree = self.rebuild_version(version)?;
+        rebuilt_tree.entries_with_proofs(version, leaf_keys)
+    }
+
+    /// Reads a key range together with a Merkle proof from the tree. Like [`Self::historical_entries_with_proofs()`],
+    /// this method can serve versions pruned in the archive mode; see [`Self::range_with_proof()`] for the description
+    /// of arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing and cannot be rebuilt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`, or if `max_entries` is zero.
+    pub fn historical_range_with_proof(
+        &self,
+        version: u64,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeWithProof, NoVersionError> {
+        if self.db.root(version).is_some() {
+            return self.range_with_proof(version, start_key, end_key, max_entries);
+        }
+        let rebuilt_tree = self.rebuild_version(version)?;
+        rebuilt_tree.range_with_proof(version, start_key, end_key, max_entries)
+    }
+
+    fn rebuild_version(
+        &self,
+        version: u64,
+    ) -> Result<MerkleTree<Patched<ReadonlyDatabase<'_, DB>>, &H>, NoVersionError> {
+        let mut manifest = self.db.manifest().unwrap_or_default();
+        let version_count = manifest.version_count;
+        let no_version_err = || NoVersionError {
+            missing_version: version,
+            version_count,
+        };
+        if version >= version_count || self.db.diff_log(version).is_none() {
+            return Err(no_version_err());
+        }
+        let min_checkpoint = version.saturating_sub(self.max_replayed_versions);
+        let checkpoint = (min_checkpoint..version)
+            .rev()
+            .find(|&prev_version| self.db.root(prev_version).is_some())
+            .ok_or_else(no_version_err)?;
+        tracing::debug!("Rebuilding tree version {version} from checkpoint version {checkpoint}");
+
+        let mut db = Patched::new(ReadonlyDatabase(&self.db));
+        // Truncate the tree to the checkpoint, so that the (possibly partially pruned) newer versions
+        // are shadowed by the rebuilt ones.
+        manifest.version_count = checkpoint + 1;
+        db.apply_patch(PatchSet::from_manifest(manifest));
+
+        for rebuilt_version in (checkpoint + 1)..=version {
+            let diff_log = self
+                .db
+                .diff_log(rebuilt_version)
+                .ok_or_else(no_version_err)?;
+            let storage = Storage::new(&db, &self.hasher, rebuilt_version, true);
+            let (_, patch) = storage.extend(diff_log);
+            db.apply_patch(patch);
+        }
+
+        Ok(MerkleTree {
+            db,
+            hasher: &self.hasher,
+            archive_mode: false,
+            max_replayed_versions: self.max_replayed_versions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MerkleTreePruner, ValueHash};
+
+    fn generate_entries(version: u64) -> Vec<TreeEntry> {
+        (0..10)
+            .map(|i| {
+                let key = Key::from(version * 5 + i); // keys are partially overwritten by each version
+                let value = ValueHash::from_low_u64_be(version * 100 + i);
+                TreeEntry::new(key, version * 5 + i + 1, value)
+            })
+            .collect()
+    }
+
+    fn test_rebuilding_pruned_versions(checkpoint_interval: u64) {
+        const VERSION_COUNT: u64 = 10;
+
+        let mut db = PatchSet::default();
+        let mut tree = MerkleTree::new(&mut db);
+        tree.set_archive_mode(true);
+        for version in 0..VERSION_COUNT {
+            tree.extend(generate_entries(version));
+        }
+        let all_keys: Vec<_> = (0..60).map(Key::from).collect();
+        let expected_entries: Vec<_> = (0..VERSION_COUNT)
+            .map(|version| tree.entries_with_proofs(version, &all_keys).unwrap())
+            .collect();
+        let root_hashes: Vec<_> = (0..VERSION_COUNT)
+            .map(|version| tree.root_hash(version).unwrap())
+            .collect();
+
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db, 0);
+        pruner.set_archive_checkpoint_interval(checkpoint_interval);
+        pruner.run_once().unwrap();
+
+        let tree = MerkleTree::new(&mut db);
+        for version in 0..VERSION_COUNT {
+            let is_retained = version % checkpoint_interval == 0 || version == VERSION_COUNT - 1;
+            assert_eq!(tree.root(version).is_some(), is_retained, "{version}");
+            if !is_retained {
+                tree.entries_with_proofs(version, &all_keys).unwrap_err();
+            }
+
+            let entries = tree
+                .historical_entries_with_proofs(version, &all_keys)
+                .unwrap();
+            let expected = &expected_entries[version as usize];
+            for (entry, expected) in entries.iter().zip(expected) {
+                assert_eq!(entry.base, expected.base);
+                assert_eq!(entry.merkle_path, expected.merkle_path);
+                entry.verify(&tree.hasher, root_hashes[version as usize]);
+            }
+
+            let range = tree
+                .historical_range_with_proof(version, Key::zero(), Key::from(60), usize::MAX)
+                .unwrap();
+            range.verify(&tree.hasher, root_hashes[version as usize]);
+        }
+
+        let err = tree
+            .historical_entries_with_proofs(VERSION_COUNT, &all_keys)
+            .unwrap_err();
+        assert_eq!(err.missing_version, VERSION_COUNT);
+    }
+
+    #[test]
+    fn rebuilding_pruned_versions() {
+        for checkpoint_interval in [1, 3, 4, 100] {
+            test_rebuilding_pruned_versions(checkpoint_interval);
+        }
+    }
+
+    #[test]
+    fn pruned_versions_cannot_be_rebuilt_without_diff_logs() {
+        let mut db = PatchSet::default();
+        let mut tree = MerkleTree::new(&mut db);
+        for version in 0..5 {
+            tree.extend(generate_entries(version));
+        }
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db, 0);
+        pruner.set_archive_checkpoint_interval(2);
+        pruner.run_once().unwrap();
+
+        let tree = MerkleTree::new(&mut db);
+        let keys = [Key::from(0)];
+        tree.historical_entries_with_proofs(2, &keys).unwrap();
+        let err = tree.historical_entries_with_proofs(3, &keys).unwrap_err();
+        assert_eq!(err.missing_version, 3);
+        assert_eq!(err.version_count, 5);
+    }
+
+    #[test]
+    fn rebuilding_is_bounded_by_max_replayed_versions() {
+        let mut db = PatchSet::default();
+        let mut tree = MerkleTree::new(&mut db);
+        tree.set_archive_mode(true);
+        for version in 0..10 {
+            tree.extend(generate_entries(version));
+        }
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db, 0);
+        pruner.set_archive_checkpoint_interval(5);
+        pruner.run_once().unwrap();
+
+        let mut tree = MerkleTree::new(&mut db);
+        tree.set_max_replayed_versions(2);
+        let keys = [Key::from(0)];
+        tree.historical_entries_with_proofs(6, &keys).unwrap();
+        tree.historical_entries_with_proofs(7, &keys).unwrap();
+        let err = tree.historical_entries_with_proofs(8, &keys).unwrap_err();
+        assert_eq!(err.missing_version, 8);
+
+        tree.set_max_replayed_versions(3);
+        tree.historical_entries_with_proofs(8, &keys).unwrap();
+    }
+}
@@ -1,8 +1,11 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::path::Path;
+
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_prover_interface::inputs::{PrepareBasicCircuitsJob, StorageLogMetadata};
+use zksync_storage::rocksdb;
 use zksync_types::{
     writes::{InitialStorageWrite, RepeatedStorageWrite},
     L1BatchNumber, StorageKey,
@@ -11,10 +14,10 @@ use zksync_types::{
 use crate::{
     storage::{PatchSet, Patched, RocksDBWrapper},
     types::{
-        Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash,
-        TREE_DEPTH,
+        Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry,
+        TreeRangeWithProof, ValueHash, TREE_DEPTH,
     },
-    BlockOutput, HashTree, MerkleTree, NoVersionError,
+    BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
 };
 
 /// Metadata for the current tree state.
@@ -126,6 +129,22 @@ impl ZkSyncTree {
             .set_multi_get_chunk_size(chunk_size);
     }
 
+    /// Sets the archive mode for the tree. In this mode, the tree records a diff log for each processed L1 batch,
+    /// which allows [`ZkSyncTreeReader`] to serve proofs for L1 batches pruned from the tree.
+    pub fn set_archive_mode(&mut self, archive_mode: bool) {
+        self.tree.set_archive_mode(archive_mode);
+    }
+
+    /// Creates a pruner for this tree that retains the specified number of latest L1 batches.
+    /// The pruner should be [run](MerkleTreePruner::run()) on a separate thread.
+    pub fn pruner(
+        &self,
+        past_versions_to_keep: u64,
+    ) -> (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle) {
+        let db = self.tree.db.inner().clone();
+        MerkleTreePruner::new(db, past_versions_to_keep)
+    }
+
     /// Signals that the tree should use a dedicated `rayon` thread pool for parallel operations
     /// (for now, hash computations).
     ///
@@ -406,11 +425,29 @@ pub struct ZkSyncTreeReader(MerkleTree<RocksDBWrapper>);
 // While cloning `MerkleTree` is logically unsound, cloning a reader is reasonable since it is readonly.
 impl Clone for ZkSyncTreeReader {
     fn clone(&self) -> Self {
-        Self(MerkleTree::new(self.0.db.clone()))
+        let mut tree = MerkleTree::new(self.0.db.clone());
+        tree.set_max_replayed_versions(self.0.max_replayed_versions);
+        Self(tree)
     }
 }
 
 impl ZkSyncTreeReader {
+    /// Sets the maximum number of L1 batches replayed from a checkpoint when serving proofs
+    /// for pruned L1 batches; see [`MerkleTree::set_max_replayed_versions()`].
+    pub fn set_max_replayed_versions(&mut self, max_replayed_versions: u64) {
+        self.0.set_max_replayed_versions(max_replayed_versions);
+    }
+
+    /// Creates a consistent RocksDB checkpoint of the tree in the specified directory, which must not exist.
+    /// The checkpoint contains all L1 batches flushed to RocksDB at the moment of the call.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.0.db.create_checkpoint(path)
+    }
+
     /// Returns the current root hash of this tree.
     pub fn root_hash(&self) -> ValueHash {
         self.0.latest_root_hash()
@@ -431,17 +468,42 @@ impl ZkSyncTreeReader {
     }
 
     /// Reads entries together with Merkle proofs with the specified keys from the tree. The entries are returned
-    /// in the same order as requested.
+    /// in the same order as requested. If the L1 batch was pruned from the tree, but the tree was in the archive mode
+    /// when processing it, the proofs are rebuilt from the preceding checkpoint.
     ///
     /// # Errors
     ///
-    /// Returns an error if the tree `version` is missing.
+    /// Returns an error if the tree `version` is missing and cannot be rebuilt.
     pub fn entries_with_proofs(
         &self,
         l1_batch_number: L1BatchNumber,
         keys: &[Key],
     ) -> Result<Vec<TreeEntryWithProof>, NoVersionError> {
         let version = u64::from(l1_batch_number.0);
-        self.0.entries_with_proofs(version, keys)
+        self.0.historical_entries_with_proofs(version, keys)
+    }
+
+    /// Reads the key range `start_key..=end_key` together with a Merkle range proof. If the range contains
+    /// more than `max_entries` existing entries, it is truncated; see [`MerkleTree::range_with_proof()`]
+    /// for details. Like [`Self::entries_with_proofs()`], this works for pruned L1 batches processed
+    /// in the archive mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing and cannot be rebuilt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`, or if `max_entries` is zero.
+    pub fn range_with_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeWithProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0
+            .historical_range_with_proof(version, start_key, end_key, max_entries)
     }
 }
@@ -62,6 +62,8 @@ pub enum ErrorContext {
     Leaf(NodeKey),
     /// Internal node with the specified storage key.
     InternalNode(NodeKey),
+    /// Diff log for the specified tree version.
+    DiffLog(u64),
     /// Hash value of a child reference in an internal tree node.
     ChildRefHash,
     /// Mask in an internal node specifying children existence and type.
@@ -82,6 +84,7 @@ impl fmt::Display for ErrorContext {
             Self::Root(version) => write!(formatter, "root at version {version}"),
             Self::Leaf(key) => write!(formatter, "leaf at `{key}`"),
             Self::InternalNode(key) => write!(formatter, "internal node at `{key}`"),
+            Self::DiffLog(version) => write!(formatter, "diff log for version {version}"),
             Self::ChildRefHash => formatter.write_str("hash value of a child reference"),
             Self::ChildrenMask => formatter.write_str("children mask"),
             Self::LeafCount => formatter.write_str("number of leaf nodes"),
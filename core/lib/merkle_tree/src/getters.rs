@@ -4,7 +4,10 @@ use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, TreeEntry, TreeEntryWithProof},
+    types::{
+        Nibbles, Node, Root, TreeEntry, TreeEntryWithProof, TreeRangeWithProof, KEY_SIZE,
+        TREE_DEPTH,
+    },
     Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
 };
 
@@ -55,6 +58,127 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Reads a key range `start_key..=end_key` together with a Merkle proof that the range contains
+    /// the returned entries and no other entries. The proof can be checked using [`TreeRangeWithProof::verify()`].
+    ///
+    /// If the range contains more than `max_entries` existing entries, it is truncated so that it ends
+    /// at the `max_entries`th existing entry; the proven range can be determined from the end key
+    /// of the returned proof. This allows to request large ranges in chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`, or if `max_entries` is zero.
+    pub fn range_with_proof(
+        &self,
+        version: u64,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeWithProof, NoVersionError> {
+        assert!(start_key <= end_key, "Range start is greater than its end");
+        assert!(
+            max_entries > 0,
+            "Maximum number of entries must be positive"
+        );
+
+        let root = self.db.root(version).ok_or_else(|| {
+            let manifest = self.db.manifest().unwrap_or_default();
+            NoVersionError {
+                missing_version: version,
+                version_count: manifest.version_count,
+            }
+        })?;
+        let existing_entries =
+            load_entries_in_range(&self.db, root, start_key, end_key, max_entries);
+        let end_key = if existing_entries.len() == max_entries {
+            existing_entries.last().map_or(end_key, |entry| entry.key)
+        } else {
+            end_key
+        };
+
+        let mut boundaries = self.entries_with_proofs(version, &[start_key, end_key])?;
+        let end = boundaries.pop().unwrap();
+        let start = boundaries.pop().unwrap();
+        // ^ `unwrap()`s are safe by construction
+        let entries = existing_entries
+            .into_iter()
+            .filter(|entry| entry.key > start_key && entry.key < end_key)
+            .collect();
+        Ok(TreeRangeWithProof {
+            start,
+            entries,
+            end,
+        })
+    }
+}
+
+/// Returns the range of keys covered by a subtree with the specified nibble prefix.
+fn key_range(prefix: &Nibbles) -> (Key, Key) {
+    let min_key = Key::from_big_endian(prefix.bytes());
+    let free_bits = 4 * (2 * KEY_SIZE - prefix.nibble_count());
+    let max_key = if free_bits == TREE_DEPTH {
+        Key::MAX
+    } else {
+        min_key | ((Key::one() << free_bits) - 1)
+    };
+    (min_key, max_key)
+}
+
+/// Loads up to `max_count` existing entries with keys in `start_key..=end_key`, ordered by key.
+/// Only subtrees intersecting with the range are traversed.
+fn load_entries_in_range(
+    db: &impl Database,
+    root: Root,
+    start_key: Key,
+    end_key: Key,
+    max_count: usize,
+) -> Vec<TreeEntry> {
+    let Root::Filled { node, .. } = root else {
+        return vec![];
+    };
+    let mut entries = vec![];
+    // Stack of nodes to visit; the node with the smallest keys is on top.
+    let mut stack = vec![(Nibbles::EMPTY, node)];
+    while let Some((prefix, node)) = stack.pop() {
+        let node = match node {
+            Node::Leaf(leaf) => {
+                if (start_key..=end_key).contains(&leaf.full_key) {
+                    entries.push(leaf.into());
+                    if entries.len() == max_count {
+                        break;
+                    }
+                }
+                continue;
+            }
+            Node::Internal(node) => node,
+        };
+
+        let child_prefixes: Vec<_> = node
+            .children()
+            .filter_map(|(nibble, child_ref)| {
+                let child_prefix = prefix.push(nibble)?;
+                let (min_key, max_key) = key_range(&child_prefix);
+                let intersects = min_key <= end_key && max_key >= start_key;
+                intersects.then_some((child_prefix, child_ref.version, child_ref.is_leaf))
+            })
+            .collect();
+        let node_keys: Vec<_> = child_prefixes
+            .iter()
+            .map(|&(child_prefix, version, is_leaf)| (child_prefix.with_version(version), is_leaf))
+            .collect();
+        let children = db.tree_nodes(&node_keys);
+        let children = child_prefixes.into_iter().zip(children).zip(&node_keys);
+        for (((child_prefix, ..), child), (node_key, _)) in children.rev() {
+            let child = child.unwrap_or_else(|| panic!("Node {node_key} is missing in the tree"));
+            stack.push((child_prefix, child));
+        }
+    }
+    entries
 }
 
 fn load_and_transform_entries<T>(
@@ -149,4 +273,92 @@ mod tests {
         assert!(entries[1].base.is_empty());
         entries[1].verify(&tree.hasher, output.root_hash);
     }
+
+    #[test]
+    fn key_ranges_for_prefixes() {
+        assert_eq!(key_range(&Nibbles::EMPTY), (Key::zero(), Key::MAX));
+
+        let prefix = Nibbles::single(0xa);
+        let (min_key, max_key) = key_range(&prefix);
+        assert_eq!(min_key, Key::from(0xa) << 252);
+        assert_eq!(max_key, (Key::from(0xb) << 252) - 1);
+
+        let key = Key::from(0x1234);
+        let full_prefix = Nibbles::new(&key, 2 * KEY_SIZE);
+        assert_eq!(key_range(&full_prefix), (key, key));
+    }
+
+    #[test]
+    fn range_proofs() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        let entries: Vec<_> = (1..=50_u64)
+            .map(|i| TreeEntry::new(Key::from(i * 1_000), i, ValueHash::from_low_u64_be(i)))
+            .collect();
+        let output = tree.extend(entries.clone());
+
+        let ranges = [
+            (0, 100, usize::MAX),
+            (0, 1_000_000, usize::MAX),
+            (1_000, 1_000, usize::MAX),
+            (1_000, 50_000, usize::MAX),
+            (1_500, 10_500, usize::MAX),
+            (0, 1_000_000, 10),
+            (1_000, 50_000, 1),
+            (1_001, 50_000, 1),
+        ];
+        for (start, end, max_entries) in ranges {
+            let (start, end) = (Key::from(start), Key::from(end));
+            let proof = tree.range_with_proof(0, start, end, max_entries).unwrap();
+            proof.verify(&tree.hasher, output.root_hash);
+
+            assert_eq!(proof.start.base.key, start);
+            let proven_end = proof.end.base.key;
+            assert!(proven_end <= end);
+            let expected_entries: Vec<_> = entries
+                .iter()
+                .filter(|entry| entry.key > start && entry.key < proven_end)
+                .copied()
+                .collect();
+            assert_eq!(proof.entries, expected_entries);
+            let existing_count = entries
+                .iter()
+                .filter(|entry| (start..=proven_end).contains(&entry.key))
+                .count();
+            assert!(existing_count <= max_entries);
+            if proven_end < end {
+                assert_eq!(existing_count, max_entries);
+                assert!(!proof.end.base.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn range_proof_in_empty_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(vec![]);
+        let proof = tree
+            .range_with_proof(0, Key::zero(), Key::MAX, 100)
+            .unwrap();
+        assert!(proof.entries.is_empty());
+        assert!(proof.start.base.is_empty());
+        assert!(proof.end.base.is_empty());
+        proof.verify(&tree.hasher, tree.hasher.empty_tree_hash());
+    }
+
+    #[test]
+    #[should_panic(expected = "Root hash mismatch")]
+    fn range_proof_with_omitted_entry() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        let entries: Vec<_> = (1..=10_u64)
+            .map(|i| TreeEntry::new(Key::from(i), i, ValueHash::from_low_u64_be(i)))
+            .collect();
+        let output = tree.extend(entries);
+
+        let mut proof = tree
+            .range_with_proof(0, Key::zero(), Key::from(100), 100)
+            .unwrap();
+        assert_eq!(proof.entries.len(), 10);
+        proof.entries.remove(5);
+        proof.verify(&tree.hasher, output.root_hash);
+    }
 }
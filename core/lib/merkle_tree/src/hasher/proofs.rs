@@ -6,7 +6,7 @@ use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
         BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        TreeLogEntry, TreeRangeWithProof, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -78,6 +78,44 @@ impl TreeEntryWithProof {
     }
 }
 
+impl TreeRangeWithProof {
+    /// Verifies this range proof.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proof doesn't verify.
+    pub fn verify(&self, hasher: &dyn HashTree, trusted_root_hash: ValueHash) {
+        let start_key = self.start.base.key;
+        let end_key = self.end.base.key;
+        assert!(start_key <= end_key, "Range start is greater than its end");
+        self.start.verify(hasher, trusted_root_hash);
+        if start_key == end_key {
+            assert!(
+                self.entries.is_empty(),
+                "Single-key range cannot contain intermediate entries"
+            );
+            assert_eq!(self.start.base, self.end.base, "Range boundaries mismatch");
+            return;
+        }
+        self.end.verify(hasher, trusted_root_hash);
+
+        let mut digest = TreeRangeDigest::new(hasher, start_key, &self.start);
+        for &entry in &self.entries {
+            assert!(
+                entry.key < end_key,
+                "Intermediate entry is outside the proven range"
+            );
+            assert!(
+                entry.leaf_index > 0,
+                "Intermediate entries must be present in the tree"
+            );
+            digest.update(entry);
+        }
+        let root_hash = digest.finalize(&self.end);
+        assert_eq!(root_hash, trusted_root_hash, "Root hash mismatch");
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
@@ -58,11 +58,12 @@ pub use crate::{
     },
     types::{
         BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        TreeLogEntry, TreeLogEntryWithProof, TreeRangeWithProof, ValueHash,
     },
 };
 use crate::{hasher::HasherWithStats, storage::Storage, types::Root};
 
+mod archive;
 mod consistency;
 pub mod domain;
 mod errors;
@@ -131,6 +132,8 @@ pub mod unstable {
 pub struct MerkleTree<DB, H = Blake2Hasher> {
     db: DB,
     hasher: H,
+    archive_mode: bool,
+    max_replayed_versions: u64,
 }
 
 impl<DB: Database> MerkleTree<DB> {
@@ -159,7 +162,32 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         // If there are currently no tags in the tree, we consider that it fits
         // for backward compatibility. The tags will be added the next time the tree is saved.
 
-        Self { db, hasher }
+        Self {
+            db,
+            hasher,
+            archive_mode: false,
+            max_replayed_versions: Self::DEFAULT_MAX_REPLAYED_VERSIONS,
+        }
+    }
+
+    /// Sets the archive mode for this tree. In the archive mode, the tree records a diff log (i.e., all writes
+    /// applied to the tree) for each new version. Together with checkpoints retained by
+    /// the [`MerkleTreePruner`], diff logs allow rebuilding pruned tree versions on demand;
+    /// see [`Self::historical_entries_with_proofs()`].
+    ///
+    /// The archive mode is disabled by default.
+    pub fn set_archive_mode(&mut self, archive_mode: bool) {
+        self.archive_mode = archive_mode;
+    }
+
+    /// Sets the maximum number of versions replayed from a checkpoint when rebuilding a pruned version
+    /// in [`Self::historical_entries_with_proofs()`] and [`Self::historical_range_with_proof()`].
+    /// Versions farther from the preceding checkpoint are treated as missing. This value should be
+    /// at least the archive checkpoint interval used by the [`MerkleTreePruner`].
+    ///
+    /// The default value is 1,000.
+    pub fn set_max_replayed_versions(&mut self, max_replayed_versions: u64) {
+        self.max_replayed_versions = max_replayed_versions;
     }
 
     /// Returns the root hash of a tree at the specified `version`, or `None` if the version
@@ -216,8 +244,12 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
     /// Returns information about the update such as the final tree hash.
     pub fn extend(&mut self, entries: Vec<TreeEntry>) -> BlockOutput {
         let next_version = self.db.manifest().unwrap_or_default().version_count;
+        let diff_log = self.archive_mode.then(|| entries.clone());
         let storage = Storage::new(&self.db, &self.hasher, next_version, true);
-        let (output, patch) = storage.extend(entries);
+        let (output, mut patch) = storage.extend(entries);
+        if let Some(diff_log) = diff_log {
+            patch.set_diff_log(next_version, diff_log);
+        }
         self.db.apply_patch(patch);
         output
     }
@@ -234,8 +266,20 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         instructions: Vec<TreeInstruction>,
     ) -> BlockOutputWithProofs {
         let next_version = self.db.manifest().unwrap_or_default().version_count;
+        let diff_log = self.archive_mode.then(|| {
+            let writes = instructions
+                .iter()
+                .filter_map(|instruction| match instruction {
+                    TreeInstruction::Write(entry) => Some(*entry),
+                    TreeInstruction::Read(_) => None,
+                });
+            writes.collect()
+        });
         let storage = Storage::new(&self.db, &self.hasher, next_version, true);
-        let (output, patch) = storage.extend_with_proofs(instructions);
+        let (output, mut patch) = storage.extend_with_proofs(instructions);
+        if let Some(diff_log) = diff_log {
+            patch.set_diff_log(next_version, diff_log);
+        }
         self.db.apply_patch(patch);
         output
     }
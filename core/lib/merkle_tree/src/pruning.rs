@@ -5,6 +5,7 @@ use std::{fmt, sync::mpsc, time::Duration};
 use crate::{
     metrics::{PruningStats, PRUNING_TIMINGS},
     storage::{PruneDatabase, PrunePatchSet},
+    types::NodeKey,
 };
 
 /// Handle for a [`MerkleTreePruner`] allowing to abort its operation.
@@ -37,9 +38,14 @@ impl MerkleTreePrunerHandle {
 /// (in RocksDB, this uses simple pointwise `delete_cf()` operations). The range of versions
 /// depends on pruning policies; for now, it's "remove versions older than `latest_version - N`",
 /// where `N` is a configurable number set when the pruner [is created](Self::new()).
+///
+/// If the [archive checkpoint interval](Self::set_archive_checkpoint_interval()) is set, the pruner
+/// additionally retains all nodes of *checkpoint* versions. Together with diff logs recorded by the tree
+/// in the archive mode, checkpoints allow rebuilding pruned versions on demand.
 pub struct MerkleTreePruner<DB> {
     db: DB,
     past_versions_to_keep: u64,
+    archive_checkpoint_interval: Option<u64>,
     target_pruned_key_count: usize,
     poll_interval: Duration,
     aborted_receiver: mpsc::Receiver<()>,
@@ -50,6 +56,10 @@ impl<DB> fmt::Debug for MerkleTreePruner<DB> {
         formatter
             .debug_struct("MerkleTreePruner")
             .field("past_versions_to_keep", &self.past_versions_to_keep)
+            .field(
+                "archive_checkpoint_interval",
+                &self.archive_checkpoint_interval,
+            )
             .field("target_pruned_key_count", &self.target_pruned_key_count)
             .field("poll_interval", &self.poll_interval)
             .finish_non_exhaustive()
@@ -70,6 +80,7 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         let this = Self {
             db,
             past_versions_to_keep,
+            archive_checkpoint_interval: None,
             target_pruned_key_count: 500_000,
             poll_interval: Duration::from_secs(60),
             aborted_receiver,
@@ -94,6 +105,27 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         self.poll_interval = poll_interval;
     }
 
+    /// Sets the interval between checkpoint versions that are never pruned. E.g., if the interval is 1,000,
+    /// versions 0, 1000, 2000, etc. are fully retained in the database.
+    ///
+    /// By default, checkpoints are not retained.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn set_archive_checkpoint_interval(&mut self, interval: u64) {
+        assert!(interval > 0, "Checkpoint interval must be positive");
+        self.archive_checkpoint_interval = Some(interval);
+    }
+
+    /// Checks whether the node with the specified `key` replaced in `stale_version` is a part
+    /// of a checkpoint version, i.e. whether there is a checkpoint in `key.version..stale_version`.
+    fn is_retained_for_checkpoint(&self, key: &NodeKey, stale_version: u64) -> bool {
+        self.archive_checkpoint_interval.map_or(false, |interval| {
+            key.version.div_ceil(interval) * interval < stale_version
+        })
+    }
+
     fn target_retained_version(&self) -> Option<u64> {
         let manifest = self.db.manifest()?;
         let latest_version = manifest.version_count.checked_sub(1)?;
@@ -110,23 +142,30 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
 
         let load_stale_keys_latency = PRUNING_TIMINGS.load_stale_keys.start();
         let mut pruned_keys = vec![];
+        let mut stale_key_count = 0;
         let mut max_stale_key_version = min_stale_key_version;
         for version in stale_key_new_versions {
             max_stale_key_version = version;
-            pruned_keys.extend_from_slice(&self.db.stale_keys(version));
-            if pruned_keys.len() >= self.target_pruned_key_count {
+            let stale_keys = self.db.stale_keys(version);
+            stale_key_count += stale_keys.len();
+            let stale_keys = stale_keys
+                .into_iter()
+                .filter(|key| !self.is_retained_for_checkpoint(key, version));
+            pruned_keys.extend(stale_keys);
+            if stale_key_count >= self.target_pruned_key_count {
                 break;
             }
         }
         load_stale_keys_latency.observe();
 
-        if pruned_keys.is_empty() {
+        if stale_key_count == 0 {
             tracing::info!("No stale keys to remove; skipping");
             return None;
         }
         let deleted_stale_key_versions = min_stale_key_version..(max_stale_key_version + 1);
         tracing::info!(
-            "Collected {} stale keys with new versions in {deleted_stale_key_versions:?}",
+            "Collected {stale_key_count} stale keys with new versions in {deleted_stale_key_versions:?}; \
+             {} keys will be pruned",
             pruned_keys.len()
         );
 
@@ -185,10 +224,7 @@ mod tests {
     use std::{collections::HashSet, thread, time::Instant};
 
     use super::*;
-    use crate::{
-        types::{Node, NodeKey},
-        Database, Key, MerkleTree, PatchSet, TreeEntry, ValueHash,
-    };
+    use crate::{types::Node, Database, Key, MerkleTree, PatchSet, TreeEntry, ValueHash};
 
     fn create_db() -> PatchSet {
         let mut db = PatchSet::default();
@@ -233,6 +269,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pruner_with_archive_checkpoints() {
+        let mut db = create_db();
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db, 0);
+        pruner.set_archive_checkpoint_interval(2);
+
+        let stats = pruner.run_once().unwrap();
+        assert!(stats.pruned_key_count > 0);
+        assert_eq!(stats.deleted_stale_key_versions, 1..5);
+        assert_eq!(db.min_stale_key_version(), None);
+
+        for version in 0..5 {
+            assert_eq!(db.root(version).is_some(), version % 2 == 0 || version == 4);
+        }
+        let tree = MerkleTree::new(&mut db);
+        for version in [0, 2, 4] {
+            tree.verify_consistency(version, true).unwrap();
+        }
+    }
+
     #[test]
     fn pruner_is_aborted_immediately_when_requested() {
         let (mut pruner, pruner_handle) = MerkleTreePruner::new(PatchSet::default(), 0);
@@ -5,7 +5,7 @@ use std::ops;
 use crate::{
     errors::DeserializeError,
     storage::patch::PatchSet,
-    types::{Manifest, Node, NodeKey, Root},
+    types::{Manifest, Node, NodeKey, Root, TreeEntry},
 };
 
 /// Slice of node keys together with an indicator whether a node at the requested key is a leaf.
@@ -77,6 +77,27 @@ pub trait Database: Send + Sync {
             .unwrap_or_else(|err| panic!("{err}"))
     }
 
+    /// Tries to read the diff log (i.e., writes applied to the tree) for the specified `version`.
+    /// Diff logs are only recorded if the tree is in the archive mode.
+    ///
+    /// The default implementation returns `Ok(None)`, i.e., signals that the database doesn't store diff logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if any.
+    fn try_diff_log(&self, _version: u64) -> Result<Option<Vec<TreeEntry>>, DeserializeError> {
+        Ok(None)
+    }
+    /// Reads the diff log for the specified `version` of the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics on deserialization errors.
+    fn diff_log(&self, version: u64) -> Option<Vec<TreeEntry>> {
+        self.try_diff_log(version)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
     /// Applies changes in the `patch` to this database. This operation should be atomic.
     fn apply_patch(&mut self, patch: PatchSet);
 }
@@ -98,6 +119,10 @@ impl<DB: Database + ?Sized> Database for &mut DB {
         (**self).try_tree_node(key, is_leaf)
     }
 
+    fn try_diff_log(&self, version: u64) -> Result<Option<Vec<TreeEntry>>, DeserializeError> {
+        (**self).try_diff_log(version)
+    }
+
     fn apply_patch(&mut self, patch: PatchSet) {
         (**self).apply_patch(patch);
     }
@@ -135,6 +160,10 @@ impl Database for PatchSet {
         Ok(Some(node))
     }
 
+    fn try_diff_log(&self, version: u64) -> Result<Option<Vec<TreeEntry>>, DeserializeError> {
+        Ok(self.diff_logs.get(&version).cloned())
+    }
+
     fn apply_patch(&mut self, mut other: PatchSet) {
         if let Some(other_updated_version) = other.updated_version {
             if let Some(updated_version) = self.updated_version {
@@ -162,9 +191,12 @@ impl Database for PatchSet {
             // Remove obsolete sub-patches from the patch.
             self.patches_by_version
                 .retain(|&version, _| version < new_version_count);
+            self.diff_logs
+                .retain(|&version, _| version < new_version_count);
         }
         self.manifest = other.manifest;
         self.patches_by_version.extend(other.patches_by_version);
+        self.diff_logs.extend(other.diff_logs);
         for (version, stale_keys) in other.stale_keys_by_version {
             self.stale_keys_by_version
                 .entry(version)
@@ -289,6 +321,15 @@ impl<DB: Database> Database for Patched<DB> {
         }
     }
 
+    fn try_diff_log(&self, version: u64) -> Result<Option<Vec<TreeEntry>>, DeserializeError> {
+        if let Some(patch) = &self.patch {
+            if patch.is_new_version(version) {
+                return patch.try_diff_log(version);
+            }
+        }
+        self.inner.try_diff_log(version)
+    }
+
     fn tree_nodes(&self, keys: &NodeKeys) -> Vec<Option<Node>> {
         if self.patch.is_none() {
             return self.inner.tree_nodes(keys);
@@ -14,7 +14,7 @@ use crate::{
     storage::{proofs::SUBTREE_COUNT, Operation, SortedKeys, TraverseOutcome},
     types::{
         ChildRef, InternalNode, Key, LeafNode, Manifest, Nibbles, NibblesBytes, Node, NodeKey,
-        Root, ValueHash, KEY_SIZE,
+        Root, TreeEntry, ValueHash, KEY_SIZE,
     },
     utils, Database,
 };
@@ -46,6 +46,8 @@ pub struct PatchSet {
     /// is smaller than all other keys in `patches_by_version`.
     pub(super) updated_version: Option<u64>,
     pub(super) stale_keys_by_version: HashMap<u64, Vec<NodeKey>>,
+    /// Writes applied in each version of the tree. Only recorded in the archive mode.
+    pub(super) diff_logs: HashMap<u64, Vec<TreeEntry>>,
 }
 
 impl PatchSet {
@@ -55,6 +57,7 @@ impl PatchSet {
             patches_by_version: HashMap::new(),
             updated_version: None,
             stale_keys_by_version: HashMap::new(),
+            diff_logs: HashMap::new(),
         }
     }
 
@@ -101,9 +104,15 @@ impl PatchSet {
             patches_by_version: HashMap::from([(version, partial_patch)]),
             updated_version,
             stale_keys_by_version: HashMap::from([(version, stale_keys)]),
+            diff_logs: HashMap::new(),
         }
     }
 
+    /// Records the diff log for the specified `version` of the tree.
+    pub(crate) fn set_diff_log(&mut self, version: u64, entries: Vec<TreeEntry>) {
+        self.diff_logs.insert(version, entries);
+    }
+
     pub(super) fn is_new_version(&self, version: u64) -> bool {
         version >= self.manifest.version_count // this patch truncates `version`
             || (self.updated_version != Some(version) && self.patches_by_version.contains_key(&version))
@@ -10,9 +10,12 @@ use crate::{
     metrics::ApplyPatchStats,
     storage::{
         database::{PruneDatabase, PrunePatchSet},
+        serialization::{deserialize_diff_log, serialize_diff_log},
         Database, NodeKeys, PatchSet,
     },
-    types::{InternalNode, LeafNode, Manifest, Nibbles, Node, NodeKey, Root, StaleNodeKey},
+    types::{
+        InternalNode, LeafNode, Manifest, Nibbles, Node, NodeKey, Root, StaleNodeKey, TreeEntry,
+    },
 };
 
 /// RocksDB column families used by the tree.
@@ -23,16 +26,20 @@ pub enum MerkleTreeColumnFamily {
     Tree,
     /// Column family containing stale node keys that are eventually removed by the pruning logic.
     StaleKeys,
+    /// Column family containing diff logs (i.e., writes applied to the tree) keyed by the tree version.
+    /// Diff logs are only recorded in the archive mode.
+    DiffLog,
 }
 
 impl NamedColumnFamily for MerkleTreeColumnFamily {
     const DB_NAME: &'static str = "merkle_tree";
-    const ALL: &'static [Self] = &[Self::Tree, Self::StaleKeys];
+    const ALL: &'static [Self] = &[Self::Tree, Self::StaleKeys, Self::DiffLog];
 
     fn name(&self) -> &'static str {
         match self {
             Self::Tree => "default",
             Self::StaleKeys => "stale_keys",
+            Self::DiffLog => "diff_log",
         }
     }
 
@@ -133,6 +140,17 @@ impl RocksDBWrapper {
         })
     }
 
+    /// Creates a consistent checkpoint of the tree database in the specified directory, which must not exist.
+    /// The checkpoint contains all tree versions flushed to the database at the moment of the call
+    /// and can be opened using [`Self::new()`].
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.db.create_checkpoint(path)
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -178,6 +196,19 @@ impl Database for RocksDBWrapper {
         Self::deserialize_node(&raw_node, key, is_leaf).map(Some)
     }
 
+    fn try_diff_log(&self, version: u64) -> Result<Option<Vec<TreeEntry>>, DeserializeError> {
+        let raw_diff_log = self
+            .db
+            .get_cf(MerkleTreeColumnFamily::DiffLog, &version.to_be_bytes())
+            .expect("Failed reading from RocksDB");
+        let Some(raw_diff_log) = raw_diff_log else {
+            return Ok(None);
+        };
+        deserialize_diff_log(&raw_diff_log)
+            .map(Some)
+            .map_err(|err| err.with_context(ErrorContext::DiffLog(version)))
+    }
+
     fn tree_nodes(&self, keys: &NodeKeys) -> Vec<Option<Node>> {
         let raw_nodes = self.raw_nodes(keys).into_iter().zip(keys);
 
@@ -202,6 +233,8 @@ impl Database for RocksDBWrapper {
         patch.manifest.serialize(&mut node_bytes);
         write_batch.put_cf(tree_cf, Self::MANIFEST_KEY, &node_bytes);
 
+        let diff_log_cf = MerkleTreeColumnFamily::DiffLog;
+        let mut diff_logs = patch.diff_logs;
         for (version, sub_patch) in patch.patches_by_version {
             let is_update = patch.updated_version == Some(version);
             let root_key = NodeKey::empty(version);
@@ -211,6 +244,15 @@ impl Database for RocksDBWrapper {
                 let next_root_key = NodeKey::empty(version + 1);
                 let keys_to_delete = &*root_key.to_db_key()..&*next_root_key.to_db_key();
                 write_batch.delete_range_cf(tree_cf, keys_to_delete);
+
+                // Same goes for the diff log; it's overwritten below if it's recorded for the version.
+                if let Some(diff_log) = diff_logs.remove(&version) {
+                    node_bytes.clear();
+                    serialize_diff_log(&diff_log, &mut node_bytes);
+                    write_batch.put_cf(diff_log_cf, &version.to_be_bytes(), &node_bytes);
+                } else {
+                    write_batch.delete_cf(diff_log_cf, &version.to_be_bytes());
+                }
             }
 
             if let Some(root) = sub_patch.root {
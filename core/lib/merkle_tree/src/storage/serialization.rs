@@ -5,8 +5,8 @@ use std::str;
 use crate::{
     errors::{DeserializeError, DeserializeErrorKind, ErrorContext},
     types::{
-        ChildRef, InternalNode, Key, LeafNode, Manifest, Node, Root, TreeTags, ValueHash,
-        HASH_SIZE, KEY_SIZE,
+        ChildRef, InternalNode, Key, LeafNode, Manifest, Node, Root, TreeEntry, TreeTags,
+        ValueHash, HASH_SIZE, KEY_SIZE,
     },
 };
 
@@ -15,15 +15,19 @@ use crate::{
 const LEB128_SIZE_ESTIMATE: usize = 3;
 
 impl LeafNode {
-    pub(super) fn deserialize(bytes: &[u8]) -> Result<Self, DeserializeError> {
+    pub(super) fn deserialize(mut bytes: &[u8]) -> Result<Self, DeserializeError> {
+        Self::deserialize_from(&mut bytes)
+    }
+
+    fn deserialize_from(bytes: &mut &[u8]) -> Result<Self, DeserializeError> {
         if bytes.len() < KEY_SIZE + HASH_SIZE {
             return Err(DeserializeErrorKind::UnexpectedEof.into());
         }
         let full_key = Key::from_big_endian(&bytes[..KEY_SIZE]);
         let value_hash = ValueHash::from_slice(&bytes[KEY_SIZE..(KEY_SIZE + HASH_SIZE)]);
 
-        let mut bytes = &bytes[(KEY_SIZE + HASH_SIZE)..];
-        let leaf_index = leb128::read::unsigned(&mut bytes).map_err(|err| {
+        *bytes = &bytes[(KEY_SIZE + HASH_SIZE)..];
+        let leaf_index = leb128::read::unsigned(bytes).map_err(|err| {
             DeserializeErrorKind::Leb128(err).with_context(ErrorContext::LeafIndex)
         })?;
         Ok(Self {
@@ -43,6 +47,23 @@ impl LeafNode {
     }
 }
 
+/// Diff logs are serialized as a concatenation of entries, each of which is serialized
+/// in the same way as a [`LeafNode`].
+pub(super) fn serialize_diff_log(entries: &[TreeEntry], buffer: &mut Vec<u8>) {
+    for entry in entries {
+        LeafNode::new(*entry).serialize(buffer);
+    }
+}
+
+pub(super) fn deserialize_diff_log(mut bytes: &[u8]) -> Result<Vec<TreeEntry>, DeserializeError> {
+    let mut entries = vec![];
+    while !bytes.is_empty() {
+        let leaf = LeafNode::deserialize_from(&mut bytes)?;
+        entries.push(leaf.into());
+    }
+    Ok(entries)
+}
+
 #[derive(Debug, Clone, Copy)]
 #[repr(u32)]
 enum ChildKind {
@@ -303,7 +324,6 @@ mod tests {
     use zksync_types::H256;
 
     use super::*;
-    use crate::types::TreeEntry;
 
     #[test]
     fn serializing_manifest() {
@@ -388,6 +408,24 @@ mod tests {
         assert_eq!(leaf_copy, leaf);
     }
 
+    #[test]
+    fn serializing_diff_log() {
+        let entries = [
+            TreeEntry::new(513.into(), 42, H256([4; 32])),
+            TreeEntry::new(Key::MAX, 1_000, H256::zero()),
+        ];
+        let mut buffer = vec![];
+        serialize_diff_log(&entries, &mut buffer);
+        assert_eq!(buffer.len(), 65 + 66);
+
+        let entries_copy = deserialize_diff_log(&buffer).unwrap();
+        assert_eq!(entries_copy, entries);
+
+        buffer.pop();
+        let err = deserialize_diff_log(&buffer).unwrap_err().to_string();
+        assert!(err.contains("leaf index"), "{err}");
+    }
+
     fn create_internal_node() -> InternalNode {
         let mut node = InternalNode::default();
         node.insert_child_ref(1, ChildRef::internal(3));
@@ -125,6 +125,22 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Key range in a Merkle tree together with a proof that the range contains the specified entries
+/// and no other entries. The proven range is `start.base.key..=end.base.key`.
+///
+/// Besides proving the range contents, this allows proving that a certain key is absent from the tree
+/// without specifying the key precisely (e.g., that there are no keys with a certain prefix).
+#[derive(Debug, Clone)]
+pub struct TreeRangeWithProof {
+    /// Start of the range together with a Merkle proof. The entry may be [empty](TreeEntry::is_empty()).
+    pub start: TreeEntryWithProof,
+    /// Existing entries with keys strictly between the start and end keys, ordered by key.
+    pub entries: Vec<TreeEntry>,
+    /// End of the range together with a Merkle proof. The entry may be [empty](TreeEntry::is_empty()).
+    /// If the range consists of a single key, this entry is equal to [`Self::start`].
+    pub end: TreeEntryWithProof,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
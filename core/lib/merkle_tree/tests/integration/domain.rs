@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 use serde_with::{hex::Hex, serde_as};
 use tempfile::TempDir;
 use zksync_crypto::hasher::blake2::Blake2Hasher;
-use zksync_merkle_tree::{domain::ZkSyncTree, HashTree, TreeEntry, TreeInstruction};
+use zksync_merkle_tree::{
+    domain::ZkSyncTree, HashTree, MerkleTreePruner, RocksDBWrapper, TreeEntry, TreeInstruction,
+};
 use zksync_prover_interface::inputs::StorageLogMetadata;
 use zksync_storage::RocksDB;
 use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
@@ -110,6 +112,45 @@ fn basic_workflow_multiblock() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(12));
 }
 
+#[test]
+fn historical_proofs_in_archive_mode() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let db = RocksDBWrapper::from(RocksDB::new(temp_dir.as_ref()).unwrap());
+    let logs = gen_storage_logs();
+    let keys: Vec<_> = logs.iter().map(|log| log.key().hashed_key_u256()).collect();
+
+    let mut tree = ZkSyncTree::new(db.clone());
+    tree.set_archive_mode(true);
+    for block in logs.chunks(9) {
+        tree.process_l1_batch(block);
+    }
+    tree.save();
+    let reader = tree.reader();
+    let l1_batch_count = reader.next_l1_batch_number().0;
+    let expected_proofs: Vec<_> = (0..l1_batch_count)
+        .map(|number| {
+            reader
+                .entries_with_proofs(L1BatchNumber(number), &keys)
+                .unwrap()
+        })
+        .collect();
+
+    let (mut pruner, _handle) = MerkleTreePruner::new(db, 0);
+    pruner.set_archive_checkpoint_interval(5);
+    let stats = pruner.run_once().unwrap();
+    assert!(stats.pruned_key_count > 0);
+
+    for (number, expected_proofs) in (0..l1_batch_count).zip(&expected_proofs) {
+        let proofs = reader
+            .entries_with_proofs(L1BatchNumber(number), &keys)
+            .unwrap();
+        for (proof, expected) in proofs.iter().zip(expected_proofs) {
+            assert_eq!(proof.base, expected.base);
+            assert_eq!(proof.merkle_path, expected.merkle_path);
+        }
+    }
+}
+
 #[test]
 fn filtering_out_no_op_writes() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
@@ -0,0 +1,437 @@
+//! [`ObjectStore`] implementation for Azure Blob Storage.
+
+use std::{fmt, ops::Range};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::Utc;
+use reqwest::{Client, Method, RequestBuilder, Url};
+
+use crate::{
+    metrics::METRICS,
+    raw::{Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError},
+    rest::{
+        check_response, encode_path, encode_query_component, hmac_sha256, object_metadata,
+        range_header, range_response, xml_elements, xml_listed_objects, ListingSchema,
+    },
+    retries::retry,
+};
+
+/// Version of the Blob Storage REST API used by the store.
+const API_VERSION: &str = "2021-08-06";
+
+/// Elements describing objects in listing responses.
+const LISTING_SCHEMA: ListingSchema = ListingSchema {
+    entry: "Blob",
+    key: "Name",
+    size: "Content-Length",
+    last_modified: "Last-Modified",
+};
+
+/// Object store backed by an Azure Blob Storage container. Requests are authenticated using a shared account key.
+/// Objects are stored as block blobs under the same `{bucket}/{key}` names as in [GCS](crate::gcs::GoogleCloudStorage).
+pub(crate) struct AzureBlobStorage {
+    client: Client,
+    endpoint: String,
+    account_name: String,
+    container: String,
+    access_key: Vec<u8>,
+    max_retries: u16,
+}
+
+impl fmt::Debug for AzureBlobStorage {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AzureBlobStorage")
+            .field("endpoint", &self.endpoint)
+            .field("account_name", &self.account_name)
+            .field("container", &self.container)
+            .field("max_retries", &self.max_retries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AzureBlobStorage {
+    /// Creates a store. If `endpoint` is not specified, it's set to the default public endpoint
+    /// for the account; the endpoint can be overridden e.g. to use the Azurite emulator.
+    pub fn new(
+        account_name: String,
+        container: String,
+        access_key: &str,
+        endpoint: Option<&str>,
+        max_retries: u16,
+    ) -> Self {
+        let access_key = BASE64
+            .decode(access_key)
+            .expect("Azure access key must be base64-encoded");
+        let endpoint = endpoint.map_or_else(
+            || format!("https://{account_name}.blob.core.windows.net"),
+            |endpoint| endpoint.trim_end_matches('/').to_owned(),
+        );
+        Url::parse(&endpoint)
+            .unwrap_or_else(|err| panic!("invalid Azure endpoint URL `{endpoint}`: {err}"));
+
+        Self {
+            client: Client::new(),
+            endpoint,
+            account_name,
+            container,
+            access_key,
+            max_retries,
+        }
+    }
+
+    fn object_url(&self, bucket: Bucket, key: &str) -> Url {
+        let path = encode_path(&format!("{}/{bucket}/{key}", self.container));
+        let url = format!("{}/{path}", self.endpoint);
+        Url::parse(&url).unwrap_or_else(|err| panic!("invalid Azure blob URL `{url}`: {err}"))
+    }
+
+    fn container_url(&self, query: &[(&str, &str)]) -> Url {
+        let query: Vec<_> = query
+            .iter()
+            .map(|(name, value)| format!("{name}={}", encode_query_component(value)))
+            .collect();
+        let url = format!(
+            "{}/{}?{}",
+            self.endpoint,
+            encode_path(&self.container),
+            query.join("&")
+        );
+        Url::parse(&url).unwrap_or_else(|err| panic!("invalid Azure container URL `{url}`: {err}"))
+    }
+
+    /// Computes the `Authorization` header value for a request. `query` must contain decoded URL query params;
+    /// `ms_headers` must be sorted by name.
+    fn authorization(
+        &self,
+        method: &Method,
+        url: &Url,
+        query: &[(&str, &str)],
+        content_length: usize,
+        ms_headers: &[(&str, String)],
+    ) -> String {
+        // Zero content length must be represented by an empty string.
+        let content_length = if content_length == 0 {
+            String::new()
+        } else {
+            content_length.to_string()
+        };
+        let canonical_headers: String = ms_headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{value}\n"))
+            .collect();
+        let mut canonical_resource = format!("/{}{}", self.account_name, url.path());
+        let mut query = query.to_vec();
+        query.sort_unstable();
+        for (name, value) in query {
+            canonical_resource.push_str(&format!("\n{name}:{value}"));
+        }
+        // Standard headers in the signed order: `Content-Encoding`, `Content-Language`, `Content-Length`,
+        // `Content-MD5`, `Content-Type`, `Date`, `If-Modified-Since`, `If-Match`, `If-None-Match`,
+        // `If-Unmodified-Since` and `Range`. All of them except for `Content-Length` are not sent.
+        let string_to_sign = format!(
+            "{method}\n\n\n{content_length}\n\n\n\n\n\n\n\n\n{canonical_headers}{canonical_resource}"
+        );
+        let signature = BASE64.encode(hmac_sha256(&self.access_key, string_to_sign.as_bytes()));
+        format!("SharedKey {}:{signature}", self.account_name)
+    }
+
+    fn signed_request(&self, method: Method, url: &Url, body: Option<Vec<u8>>) -> RequestBuilder {
+        self.signed_request_with_headers(method, url, &[], vec![], body)
+    }
+
+    /// Creates a signed request. `query` must correspond to the query params in `url`.
+    fn signed_request_with_headers(
+        &self,
+        method: Method,
+        url: &Url,
+        query: &[(&str, &str)],
+        mut ms_headers: Vec<(&'static str, String)>,
+        body: Option<Vec<u8>>,
+    ) -> RequestBuilder {
+        let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        if body.is_some() {
+            ms_headers.push(("x-ms-blob-type", "BlockBlob".to_owned()));
+        }
+        ms_headers.push(("x-ms-date", date));
+        ms_headers.push(("x-ms-version", API_VERSION.to_owned()));
+        ms_headers.sort_unstable();
+
+        let content_length = body.as_ref().map_or(0, Vec::len);
+        let authorization = self.authorization(&method, url, query, content_length, &ms_headers);
+        let mut request = self
+            .client
+            .request(method, url.clone())
+            .header("authorization", authorization);
+        for (name, value) in ms_headers {
+            request = request.header(name, value);
+        }
+        match body {
+            Some(body) => request.body(body),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for AzureBlobStorage {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let fetch_latency = METRICS.start_fetch(bucket);
+        let url = &self.object_url(bucket, key);
+        tracing::trace!("Fetching data from Azure for key {key} from bucket {bucket}");
+
+        let blob = retry(self.max_retries, || async move {
+            let response = self.signed_request(Method::GET, url, None).send().await?;
+            let response = check_response(response).await?;
+            Ok::<_, ObjectStoreError>(response.bytes().await?.to_vec())
+        })
+        .await;
+
+        let elapsed = fetch_latency.observe();
+        tracing::trace!(
+            "Fetched data from Azure for key {key} from bucket {bucket} and it took: {elapsed:?}"
+        );
+        blob
+    }
+
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+        let fetch_latency = METRICS.start_fetch(bucket);
+        let url = &self.object_url(bucket, key);
+        let range = &range;
+        tracing::trace!("Fetching range {range:?} from Azure for key {key} from bucket {bucket}");
+
+        let blob = retry(self.max_retries, || async move {
+            let headers = vec![("x-ms-range", range_header(range))];
+            let request = self.signed_request_with_headers(Method::GET, url, &[], headers, None);
+            range_response(request.send().await?, range.clone()).await
+        })
+        .await;
+        fetch_latency.observe();
+        blob
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        let url = &self.object_url(bucket, key);
+        retry(self.max_retries, || async move {
+            let response = self.signed_request(Method::HEAD, url, None).send().await?;
+            object_metadata(&check_response(response).await?)
+        })
+        .await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        let bucket_prefix = format!("{bucket}/");
+        let full_prefix = format!("{bucket_prefix}{prefix}");
+        let mut objects = vec![];
+        let mut marker = None::<String>;
+        loop {
+            let mut query = vec![("restype", "container"), ("comp", "list")];
+            query.push(("prefix", full_prefix.as_str()));
+            if let Some(marker) = &marker {
+                query.push(("marker", marker.as_str()));
+            }
+            let url = &self.container_url(&query);
+            let query = &query;
+            let page = retry(self.max_retries, || async move {
+                let request =
+                    self.signed_request_with_headers(Method::GET, url, query, vec![], None);
+                let response = check_response(request.send().await?).await?;
+                Ok::<_, ObjectStoreError>(response.text().await?)
+            })
+            .await?;
+
+            objects.extend(xml_listed_objects(&page, &LISTING_SCHEMA, &bucket_prefix)?);
+            // The last page has an empty `<NextMarker />` element.
+            marker = xml_elements(&page, "NextMarker")
+                .pop()
+                .filter(|marker| !marker.is_empty());
+            if marker.is_none() {
+                break;
+            }
+        }
+        objects.sort_unstable_by(|x, y| x.key.cmp(&y.key));
+        Ok(objects)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let store_latency = METRICS.start_store(bucket);
+        let url = &self.object_url(bucket, key);
+        let value = &value;
+        tracing::trace!("Storing data to Azure for key {key} from bucket {bucket}");
+
+        let result = retry(self.max_retries, || async move {
+            let request = self.signed_request(Method::PUT, url, Some(value.clone()));
+            check_response(request.send().await?).await?;
+            Ok::<_, ObjectStoreError>(())
+        })
+        .await;
+
+        let elapsed = store_latency.observe();
+        tracing::trace!(
+            "Stored data to Azure for key {key} from bucket {bucket} and it took: {elapsed:?}"
+        );
+        result
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let url = &self.object_url(bucket, key);
+        tracing::trace!("Removing data from Azure for key {key} from bucket {bucket}");
+
+        retry(self.max_retries, || async move {
+            let request = self.signed_request(Method::DELETE, url, None);
+            check_response(request.send().await?).await?;
+            Ok::<_, ObjectStoreError>(())
+        })
+        .await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{}/{bucket}", self.endpoint, self.container)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::rest::testonly;
+
+    /// Well-known account key of the Azurite emulator.
+    const TEST_ACCESS_KEY: &str =
+        "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+
+    #[test]
+    fn default_endpoint() {
+        let store = AzureBlobStorage::new(
+            "account".to_owned(),
+            "zksync".to_owned(),
+            TEST_ACCESS_KEY,
+            None,
+            0,
+        );
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::WitnessInput),
+            "https://account.blob.core.windows.net/zksync/witness_inputs"
+        );
+    }
+
+    #[tokio::test]
+    async fn azure_store_basics() {
+        let (url, state) = testonly::spawn_server();
+        let store = AzureBlobStorage::new(
+            "devstoreaccount1".to_owned(),
+            "zksync".to_owned(),
+            TEST_ACCESS_KEY,
+            Some(&url),
+            0,
+        );
+
+        store
+            .put_raw(Bucket::StorageSnapshot, "chunk_0.gzip", vec![4, 5])
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::StorageSnapshot, "chunk_0.gzip")
+            .await
+            .unwrap();
+        assert_eq!(value, [4, 5]);
+        assert!(state
+            .lock()
+            .unwrap()
+            .objects
+            .contains_key("/zksync/storage_logs_snapshots/chunk_0.gzip"));
+
+        store
+            .remove_raw(Bucket::StorageSnapshot, "chunk_0.gzip")
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::StorageSnapshot, "chunk_0.gzip")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        let state = state.lock().unwrap();
+        let put_request = &state.requests[0];
+        assert_eq!(put_request.method, Method::PUT);
+        assert_eq!(put_request.headers["x-ms-blob-type"], "BlockBlob");
+        assert_eq!(put_request.headers["x-ms-version"], API_VERSION);
+        let authorization = put_request.headers["authorization"].to_str().unwrap();
+        assert!(
+            authorization.starts_with("SharedKey devstoreaccount1:"),
+            "{authorization}"
+        );
+        let get_request = &state.requests[1];
+        assert!(!get_request.headers.contains_key("x-ms-blob-type"));
+    }
+
+    #[tokio::test]
+    async fn azure_listing_and_range_reads() {
+        let (url, state) = testonly::spawn_server();
+        let store = AzureBlobStorage::new(
+            "devstoreaccount1".to_owned(),
+            "zksync".to_owned(),
+            TEST_ACCESS_KEY,
+            Some(&url),
+            0,
+        );
+        for key in ["snapshot_l1_batch_1.gzip", "snapshot_l1_batch_2.gzip"] {
+            store
+                .put_raw(Bucket::StorageSnapshot, key, vec![0, 1, 2, 3])
+                .await
+                .unwrap();
+        }
+
+        let objects = store
+            .list_raw(Bucket::StorageSnapshot, "snapshot_")
+            .await
+            .unwrap();
+        let keys: Vec<_> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            ["snapshot_l1_batch_1.gzip", "snapshot_l1_batch_2.gzip"]
+        );
+        assert_eq!(objects[0].metadata.size, 4);
+        assert!(objects[0].metadata.last_modified.is_some());
+        let objects = store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert!(objects.is_empty());
+
+        let metadata = store
+            .head_raw(Bucket::StorageSnapshot, "snapshot_l1_batch_1.gzip")
+            .await
+            .unwrap();
+        assert_eq!(metadata.size, 4);
+        let value = store
+            .get_range_raw(Bucket::StorageSnapshot, "snapshot_l1_batch_1.gzip", 2..10)
+            .await
+            .unwrap();
+        assert_eq!(value, [2, 3]);
+
+        let state = state.lock().unwrap();
+        let range_request = state.requests.last().unwrap();
+        assert_eq!(range_request.headers["x-ms-range"], "bytes=2-9");
+    }
+}
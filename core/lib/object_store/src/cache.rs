@@ -0,0 +1,451 @@
+//! Read-through [`ObjectStore`] cache backed by a bounded local directory.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    file::FileBackedObjectStore,
+    metrics::{CacheRequestOutcome, CACHE_METRICS},
+    raw::{truncate_range, Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError},
+};
+
+/// Suffix of files holding SHA-256 checksums of the cached objects.
+const CHECKSUM_SUFFIX: &str = ".sha256";
+
+fn checksum_key(key: &str) -> String {
+    format!("{key}{CHECKSUM_SUFFIX}")
+}
+
+type CacheKey = (Bucket, String);
+
+#[derive(Debug)]
+struct CacheEntry {
+    size: u64,
+    last_access: u64,
+}
+
+/// In-memory LRU index of the cached objects.
+#[derive(Debug, Default)]
+struct CacheIndex {
+    entries: HashMap<CacheKey, CacheEntry>,
+    by_last_access: BTreeMap<u64, CacheKey>,
+    total_size: u64,
+    access_counter: u64,
+}
+
+impl CacheIndex {
+    /// Marks the entry as recently used. Returns `false` if the entry is not present.
+    fn touch(&mut self, key: &CacheKey) -> bool {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return false;
+        };
+        self.by_last_access.remove(&entry.last_access);
+        self.access_counter += 1;
+        entry.last_access = self.access_counter;
+        self.by_last_access.insert(self.access_counter, key.clone());
+        true
+    }
+
+    fn insert(&mut self, key: CacheKey, size: u64) {
+        self.remove(&key);
+        self.access_counter += 1;
+        self.by_last_access.insert(self.access_counter, key.clone());
+        let entry = CacheEntry {
+            size,
+            last_access: self.access_counter,
+        };
+        self.entries.insert(key, entry);
+        self.total_size += size;
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.by_last_access.remove(&entry.last_access);
+            self.total_size -= entry.size;
+        }
+    }
+
+    /// Removes least recently used entries until the total size of the cached objects fits into `max_size`.
+    fn evict(&mut self, max_size: u64) -> Vec<CacheKey> {
+        let mut evicted_keys = vec![];
+        while self.total_size > max_size {
+            let Some((_, key)) = self.by_last_access.pop_first() else {
+                break;
+            };
+            let entry = self
+                .entries
+                .remove(&key)
+                .expect("LRU index is inconsistent");
+            self.total_size -= entry.size;
+            evicted_keys.push(key);
+        }
+        evicted_keys
+    }
+
+    fn report_metrics(&self) {
+        CACHE_METRICS.len.set(self.entries.len() as u64);
+        CACHE_METRICS.size_bytes.set(self.total_size);
+    }
+}
+
+/// [`ObjectStore`] decorator caching objects fetched from a (remote) store in a local directory.
+///
+/// The cache directory has the same layout as [`FileBackedObjectStore`]; each object is accompanied
+/// with a file containing its SHA-256 checksum. Objects failing the checksum check are refetched.
+/// The total size of cached objects is bounded; once the bound is exceeded, least recently used objects
+/// are evicted.
+///
+/// Only [`ObjectStore::get_raw()`] populates the cache. Writes and removals go directly to the underlying
+/// store and invalidate the corresponding cached objects. Listing and metadata queries are not cached.
+#[derive(Debug)]
+pub(crate) struct CachingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    cache: FileBackedObjectStore,
+    max_size_bytes: u64,
+    index: Mutex<CacheIndex>,
+}
+
+impl CachingObjectStore {
+    pub async fn new(inner: Arc<dyn ObjectStore>, cache_dir: String, max_size_bytes: u64) -> Self {
+        let cache = FileBackedObjectStore::new(cache_dir).await;
+        let this = Self {
+            inner,
+            cache,
+            max_size_bytes,
+            index: Mutex::default(),
+        };
+        this.load_index().await;
+        this
+    }
+
+    /// Restores the LRU index from the cache directory, ordering objects by their modification time.
+    async fn load_index(&self) {
+        let mut cached_objects = vec![];
+        for bucket in Bucket::ALL {
+            let objects = match self.cache.list_raw(bucket, "").await {
+                Ok(objects) => objects,
+                Err(err) => {
+                    tracing::warn!("Failed listing cached objects in bucket `{bucket}`: {err}");
+                    continue;
+                }
+            };
+            for ListedObject { key, metadata } in objects {
+                if key.ends_with(CHECKSUM_SUFFIX) {
+                    continue;
+                }
+                cached_objects.push((metadata.last_modified, bucket, key, metadata.size));
+            }
+        }
+        cached_objects.sort_by_key(|(last_modified, ..)| *last_modified);
+
+        let evicted_keys = {
+            let mut index = self.index.lock().unwrap();
+            for (_, bucket, key, size) in cached_objects {
+                index.insert((bucket, key), size);
+            }
+            tracing::info!(
+                "Loaded {} cached objects with total size {}B",
+                index.entries.len(),
+                index.total_size
+            );
+            index.evict(self.max_size_bytes)
+        };
+        self.remove_evicted(evicted_keys).await;
+    }
+
+    async fn get_cached(&self, bucket: Bucket, key: &str) -> Option<Vec<u8>> {
+        let cache_key = (bucket, key.to_owned());
+        if !self.index.lock().unwrap().touch(&cache_key) {
+            CACHE_METRICS.requests[&(bucket.as_str(), CacheRequestOutcome::Miss)].inc();
+            return None;
+        }
+
+        let value = self.cache.get_raw(bucket, key).await;
+        let checksum = self.cache.get_raw(bucket, &checksum_key(key)).await;
+        match (value, checksum) {
+            (Ok(value), Ok(checksum)) if Sha256::digest(&value).as_slice() == checksum => {
+                CACHE_METRICS.requests[&(bucket.as_str(), CacheRequestOutcome::Hit)].inc();
+                Some(value)
+            }
+            _ => {
+                tracing::warn!(
+                    "Cached object `{key}` in bucket `{bucket}` is missing or corrupted; refetching it"
+                );
+                CACHE_METRICS.requests[&(bucket.as_str(), CacheRequestOutcome::Corrupted)].inc();
+                self.invalidate(bucket, key).await;
+                None
+            }
+        }
+    }
+
+    async fn insert_cached(&self, bucket: Bucket, key: &str, value: &[u8]) {
+        let size = value.len() as u64;
+        if size > self.max_size_bytes {
+            return;
+        }
+
+        // The checksum is written after the object, so that an interrupted write is detected as corruption.
+        let checksum = Sha256::digest(value).to_vec();
+        let write_result = async {
+            self.cache.put_raw(bucket, key, value.to_vec()).await?;
+            self.cache
+                .put_raw(bucket, &checksum_key(key), checksum)
+                .await
+        };
+        if let Err(err) = write_result.await {
+            tracing::warn!("Failed caching object `{key}` in bucket `{bucket}`: {err}");
+            self.invalidate(bucket, key).await;
+            return;
+        }
+
+        let evicted_keys = {
+            let mut index = self.index.lock().unwrap();
+            index.insert((bucket, key.to_owned()), size);
+            index.evict(self.max_size_bytes)
+        };
+        self.remove_evicted(evicted_keys).await;
+    }
+
+    async fn remove_evicted(&self, evicted_keys: Vec<CacheKey>) {
+        for (bucket, key) in evicted_keys {
+            tracing::debug!("Evicting cached object `{key}` in bucket `{bucket}`");
+            CACHE_METRICS.evictions[&bucket.as_str()].inc();
+            self.remove_files(bucket, &key).await;
+        }
+        self.index.lock().unwrap().report_metrics();
+    }
+
+    async fn invalidate(&self, bucket: Bucket, key: &str) {
+        {
+            let mut index = self.index.lock().unwrap();
+            index.remove(&(bucket, key.to_owned()));
+            index.report_metrics();
+        }
+        self.remove_files(bucket, key).await;
+    }
+
+    async fn remove_files(&self, bucket: Bucket, key: &str) {
+        for key in [key.to_owned(), checksum_key(key)] {
+            match self.cache.remove_raw(bucket, &key).await {
+                Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => { /* OK */ }
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed removing cached file `{key}` in bucket `{bucket}`: {err}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CachingObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        if let Some(value) = self.get_cached(bucket, key).await {
+            return Ok(value);
+        }
+        let value = self.inner.get_raw(bucket, key).await?;
+        self.insert_cached(bucket, key, &value).await;
+        Ok(value)
+    }
+
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        if let Some(value) = self.get_cached(bucket, key).await {
+            return Ok(value[truncate_range(range, value.len())].to_vec());
+        }
+        self.inner.get_range_raw(bucket, key, range).await
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        self.inner.head_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        self.inner.list_raw(bucket, prefix).await
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.inner.put_raw(bucket, key, value).await?;
+        self.invalidate(bucket, key).await;
+        Ok(())
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await?;
+        self.invalidate(bucket, key).await;
+        Ok(())
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::mock::MockStore;
+
+    async fn create_store(
+        dir: &TempDir,
+        max_size_bytes: u64,
+    ) -> (Arc<MockStore>, CachingObjectStore) {
+        let inner = Arc::new(MockStore::default());
+        let cache_dir = dir.path().to_str().unwrap().to_owned();
+        let store = CachingObjectStore::new(inner.clone(), cache_dir, max_size_bytes).await;
+        (inner, store)
+    }
+
+    #[tokio::test]
+    async fn caching_fetched_objects() {
+        let dir = TempDir::new("object-cache").unwrap();
+        let (inner, store) = create_store(&dir, 1_024).await;
+        inner
+            .put_raw(Bucket::WitnessInput, "test.bin", vec![1, 2, 3, 4])
+            .await
+            .unwrap();
+
+        let value = store
+            .get_raw(Bucket::WitnessInput, "test.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2, 3, 4]);
+        // The object should be served from the cache even if it's removed from the underlying store.
+        inner
+            .remove_raw(Bucket::WitnessInput, "test.bin")
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::WitnessInput, "test.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2, 3, 4]);
+        let range = store
+            .get_range_raw(Bucket::WitnessInput, "test.bin", 1..3)
+            .await
+            .unwrap();
+        assert_eq!(range, [2, 3]);
+
+        // The cache should be restored after a restart.
+        drop(store);
+        let cache_dir = dir.path().to_str().unwrap().to_owned();
+        let store = CachingObjectStore::new(inner.clone(), cache_dir, 1_024).await;
+        let value = store
+            .get_raw(Bucket::WitnessInput, "test.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2, 3, 4]);
+
+        // Writes must invalidate the cached object.
+        store
+            .put_raw(Bucket::WitnessInput, "test.bin", vec![5])
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::WitnessInput, "test.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [5]);
+    }
+
+    #[tokio::test]
+    async fn evicting_least_recently_used_objects() {
+        let dir = TempDir::new("object-cache").unwrap();
+        let (inner, store) = create_store(&dir, 10).await;
+        for key in ["1.bin", "2.bin", "3.bin"] {
+            inner
+                .put_raw(Bucket::ProverJobsFri, key, vec![0; 4])
+                .await
+                .unwrap();
+        }
+
+        store.get_raw(Bucket::ProverJobsFri, "1.bin").await.unwrap();
+        store.get_raw(Bucket::ProverJobsFri, "2.bin").await.unwrap();
+        // Mark `1.bin` as recently used.
+        store.get_raw(Bucket::ProverJobsFri, "1.bin").await.unwrap();
+        store.get_raw(Bucket::ProverJobsFri, "3.bin").await.unwrap();
+
+        let cached_objects = store
+            .cache
+            .list_raw(Bucket::ProverJobsFri, "")
+            .await
+            .unwrap();
+        let cached_keys: Vec<_> = cached_objects
+            .iter()
+            .map(|object| object.key.as_str())
+            .collect();
+        assert_eq!(
+            cached_keys,
+            ["1.bin", "1.bin.sha256", "3.bin", "3.bin.sha256"]
+        );
+        assert_eq!(store.index.lock().unwrap().total_size, 8);
+
+        // Objects larger than the cache are not cached.
+        inner
+            .put_raw(Bucket::ProverJobsFri, "large.bin", vec![0; 11])
+            .await
+            .unwrap();
+        store
+            .get_raw(Bucket::ProverJobsFri, "large.bin")
+            .await
+            .unwrap();
+        assert_eq!(store.index.lock().unwrap().entries.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn refetching_corrupted_objects() {
+        let dir = TempDir::new("object-cache").unwrap();
+        let (inner, store) = create_store(&dir, 1_024).await;
+        inner
+            .put_raw(Bucket::StorageSnapshot, "snapshot.bin", vec![1; 16])
+            .await
+            .unwrap();
+        store
+            .get_raw(Bucket::StorageSnapshot, "snapshot.bin")
+            .await
+            .unwrap();
+
+        store
+            .cache
+            .put_raw(Bucket::StorageSnapshot, "snapshot.bin", vec![2; 16])
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::StorageSnapshot, "snapshot.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [1; 16]);
+        let cached_value = store
+            .cache
+            .get_raw(Bucket::StorageSnapshot, "snapshot.bin")
+            .await
+            .unwrap();
+        assert_eq!(cached_value, [1; 16]);
+    }
+}
@@ -0,0 +1,487 @@
+//! [`ObjectStore`] wrapper transparently compressing and deduplicating stored objects.
+
+use std::{collections::HashSet, ops::Range, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::raw::{
+    truncate_range, Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError,
+};
+
+/// Header byte of compressed objects. It is followed by a zstd frame, which starts with [`ZSTD_MAGIC`].
+/// Checking both the header and the magic makes it very unlikely that a legacy uncompressed object
+/// is mistaken for a compressed one.
+const COMPRESSED_HEADER: u8 = 0xc0;
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+/// Header of references to content-addressed objects. It is followed by the SHA-256 digest of the object.
+const REFERENCE_HEADER: &[u8] = b"\xc1sha256:";
+const REFERENCE_LEN: usize = REFERENCE_HEADER.len() + 32;
+/// Prefix of keys for content-addressed objects.
+const CONTENT_KEY_PREFIX: &str = "content_";
+/// Content-addressed objects older than this interval are rewritten when a new reference to them is added,
+/// so that they are not removed by [`ObjectStore::remove_unreferenced_raw()`] concurrently.
+const CONTENT_REFRESH_INTERVAL_SECS: i64 = 3_600;
+const COMPRESSION_LEVEL: i32 = 3;
+
+fn content_key(digest: &[u8]) -> String {
+    format!("{CONTENT_KEY_PREFIX}{}.bin", hex::encode(digest))
+}
+
+fn parse_reference(value: &[u8]) -> Option<&[u8]> {
+    if value.len() == REFERENCE_LEN {
+        value.strip_prefix(REFERENCE_HEADER)
+    } else {
+        None
+    }
+}
+
+fn compress(value: &[u8]) -> Result<Vec<u8>, ObjectStoreError> {
+    let mut compressed = vec![COMPRESSED_HEADER];
+    zstd::stream::copy_encode(value, &mut compressed, COMPRESSION_LEVEL)
+        .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+    Ok(compressed)
+}
+
+fn decompress_if_needed(value: Vec<u8>) -> Result<Vec<u8>, ObjectStoreError> {
+    match value.split_first() {
+        Some((&COMPRESSED_HEADER, frame)) if frame.starts_with(&ZSTD_MAGIC) => {
+            zstd::decode_all(frame).map_err(|err| ObjectStoreError::Serialization(err.into()))
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Runs a CPU-heavy (de)compression task on a blocking thread.
+async fn run_blocking<F>(task: F) -> Result<Vec<u8>, ObjectStoreError>
+where
+    F: FnOnce() -> Result<Vec<u8>, ObjectStoreError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|err| ObjectStoreError::Other(err.into()))?
+}
+
+/// [`ObjectStore`] wrapper that compresses objects with zstd and / or stores them content-addressed
+/// for the configured buckets.
+///
+/// A content-addressed object is stored under the [`content_key()`] derived from its SHA-256 digest,
+/// and the object key points to it with a short reference. Thus, identical objects (e.g., circuit inputs
+/// repeating across L1 batches) are stored only once. Removing an object only removes the reference,
+/// since the referenced content may be shared by other objects. Content that is no longer referenced
+/// is removed by a mark-and-sweep pass in [`ObjectStore::remove_unreferenced_raw()`].
+///
+/// Reads are transparent regardless of the bucket configuration, i.e., both compressed and uncompressed objects
+/// and both references and inline objects can be read from any bucket. Range reads in buckets with compression
+/// or deduplication fetch the entire object. Metadata returned by [`ObjectStore::head_raw()`] describes
+/// the stored blob (i.e., a compressed object or a reference).
+#[derive(Debug)]
+pub(crate) struct CompressingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    compressed_buckets: HashSet<Bucket>,
+    deduplicated_buckets: HashSet<Bucket>,
+}
+
+impl CompressingObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            compressed_buckets: HashSet::new(),
+            deduplicated_buckets: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_compression(mut self, bucket: Bucket) -> Self {
+        self.compressed_buckets.insert(bucket);
+        self
+    }
+
+    #[must_use]
+    pub fn with_deduplication(mut self, bucket: Bucket) -> Self {
+        self.deduplicated_buckets.insert(bucket);
+        self
+    }
+
+    fn is_transformed(&self, bucket: Bucket) -> bool {
+        self.compressed_buckets.contains(&bucket) || self.deduplicated_buckets.contains(&bucket)
+    }
+
+    async fn encode(&self, bucket: Bucket, value: Vec<u8>) -> Result<Vec<u8>, ObjectStoreError> {
+        if self.compressed_buckets.contains(&bucket) {
+            run_blocking(move || compress(&value)).await
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CompressingObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let mut value = self.inner.get_raw(bucket, key).await?;
+        if let Some(digest) = parse_reference(&value) {
+            let content_key = content_key(digest);
+            value = self.inner.get_raw(bucket, &content_key).await?;
+        }
+        run_blocking(move || decompress_if_needed(value)).await
+    }
+
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        if !self.is_transformed(bucket) {
+            return self.inner.get_range_raw(bucket, key, range).await;
+        }
+        let value = self.get_raw(bucket, key).await?;
+        Ok(value[truncate_range(range, value.len())].to_vec())
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        self.inner.head_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        let mut objects = self.inner.list_raw(bucket, prefix).await?;
+        objects.retain(|object| !object.key.starts_with(CONTENT_KEY_PREFIX));
+        Ok(objects)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        if !self.deduplicated_buckets.contains(&bucket) {
+            let value = self.encode(bucket, value).await?;
+            return self.inner.put_raw(bucket, key, value).await;
+        }
+
+        let digest = Sha256::digest(&value);
+        let content_key = content_key(&digest);
+        let refresh_cutoff = Utc::now() - chrono::Duration::seconds(CONTENT_REFRESH_INTERVAL_SECS);
+        let needs_write = match self.inner.head_raw(bucket, &content_key).await {
+            Ok(metadata) => {
+                tracing::trace!(
+                    "Object `{key}` in bucket `{bucket}` is deduplicated as `{content_key}`"
+                );
+                // Old content is rewritten to update its modification time. Content without the timestamp
+                // is never removed as unreferenced, so it doesn't need to be refreshed.
+                metadata
+                    .last_modified
+                    .map_or(false, |last_modified| last_modified < refresh_cutoff)
+            }
+            Err(ObjectStoreError::KeyNotFound(_)) => true,
+            Err(err) => return Err(err),
+        };
+        if needs_write {
+            let value = self.encode(bucket, value).await?;
+            self.inner.put_raw(bucket, &content_key, value).await?;
+        }
+        let reference = [REFERENCE_HEADER, digest.as_slice()].concat();
+        self.inner.put_raw(bucket, key, reference).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    /// Removes content-addressed objects that are not referenced by any object in the bucket. To avoid racing
+    /// with [`ObjectStore::put_raw()`] adding a reference to the removed content, the `cutoff` is capped so that
+    /// only content not refreshed during the last two [refresh intervals](CONTENT_REFRESH_INTERVAL_SECS) is removed.
+    /// All buckets are processed regardless of the configuration, since references may remain in a bucket
+    /// after deduplication is disabled for it.
+    async fn remove_unreferenced_raw(
+        &self,
+        bucket: Bucket,
+        cutoff: DateTime<Utc>,
+    ) -> Result<usize, ObjectStoreError> {
+        let max_cutoff = Utc::now() - chrono::Duration::seconds(2 * CONTENT_REFRESH_INTERVAL_SECS);
+        let cutoff = cutoff.min(max_cutoff);
+        let (content_objects, objects): (Vec<_>, Vec<_>) = self
+            .inner
+            .list_raw(bucket, "")
+            .await?
+            .into_iter()
+            .partition(|object| object.key.starts_with(CONTENT_KEY_PREFIX));
+        if content_objects.is_empty() {
+            return Ok(0);
+        }
+
+        // Mark phase: collect content referenced by objects. Only objects with the reference length
+        // need to be fetched.
+        let mut referenced_keys = HashSet::new();
+        for object in objects {
+            if object.metadata.size != REFERENCE_LEN as u64 {
+                continue;
+            }
+            let value = match self.inner.get_raw(bucket, &object.key).await {
+                Ok(value) => value,
+                // The object was removed concurrently.
+                Err(ObjectStoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            if let Some(digest) = parse_reference(&value) {
+                referenced_keys.insert(content_key(digest));
+            }
+        }
+
+        // Sweep phase: remove old unreferenced content.
+        let is_old = |metadata: &ObjectMetadata| {
+            metadata
+                .last_modified
+                .map_or(false, |last_modified| last_modified < cutoff)
+        };
+        let mut removed_count = 0;
+        for object in content_objects {
+            if referenced_keys.contains(&object.key) || !is_old(&object.metadata) {
+                continue;
+            }
+            // Recheck the modification time in case the content was refreshed after listing.
+            match self.inner.head_raw(bucket, &object.key).await {
+                Ok(metadata) if is_old(&metadata) => {}
+                Ok(_) | Err(ObjectStoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            }
+            match self.inner.remove_raw(bucket, &object.key).await {
+                Ok(()) => {
+                    tracing::debug!(
+                        "Removed unreferenced content `{}` in bucket `{bucket}`",
+                        object.key
+                    );
+                    removed_count += 1;
+                }
+                Err(ObjectStoreError::KeyNotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(removed_count)
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockStore;
+
+    fn create_store() -> (Arc<MockStore>, CompressingObjectStore) {
+        let inner = Arc::new(MockStore::default());
+        let store = CompressingObjectStore::new(inner.clone())
+            .with_compression(Bucket::WitnessInput)
+            .with_deduplication(Bucket::ProverJobsFri)
+            .with_compression(Bucket::ProverJobsFri);
+        (inner, store)
+    }
+
+    #[tokio::test]
+    async fn compressing_objects() {
+        let (inner, store) = create_store();
+        let value = vec![42; 1_024];
+        store
+            .put_raw(Bucket::WitnessInput, "test.bin", value.clone())
+            .await
+            .unwrap();
+
+        let stored_value = inner
+            .get_raw(Bucket::WitnessInput, "test.bin")
+            .await
+            .unwrap();
+        assert!(stored_value.len() < value.len());
+        assert_eq!(stored_value[0], COMPRESSED_HEADER);
+        assert_eq!(
+            store
+                .get_raw(Bucket::WitnessInput, "test.bin")
+                .await
+                .unwrap(),
+            value
+        );
+        let range = store
+            .get_range_raw(Bucket::WitnessInput, "test.bin", 10..20)
+            .await
+            .unwrap();
+        assert_eq!(range, [42; 10]);
+    }
+
+    #[tokio::test]
+    async fn reading_legacy_uncompressed_objects() {
+        let (inner, store) = create_store();
+        let legacy_values = [vec![], vec![COMPRESSED_HEADER, 1, 2, 3], vec![1; 10]];
+        for (i, value) in legacy_values.into_iter().enumerate() {
+            let key = format!("legacy_{i}.bin");
+            inner
+                .put_raw(Bucket::WitnessInput, &key, value.clone())
+                .await
+                .unwrap();
+            let read_value = store.get_raw(Bucket::WitnessInput, &key).await.unwrap();
+            assert_eq!(read_value, value);
+        }
+    }
+
+    #[tokio::test]
+    async fn deduplicating_objects() {
+        let (inner, store) = create_store();
+        let value = vec![1; 256];
+        for key in ["1_0.bin", "2_0.bin"] {
+            store
+                .put_raw(Bucket::ProverJobsFri, key, value.clone())
+                .await
+                .unwrap();
+        }
+        store
+            .put_raw(Bucket::ProverJobsFri, "3_0.bin", vec![2; 256])
+            .await
+            .unwrap();
+
+        let stored_objects = inner.list_raw(Bucket::ProverJobsFri, "").await.unwrap();
+        let content_keys: Vec<_> = stored_objects
+            .iter()
+            .filter(|object| object.key.starts_with(CONTENT_KEY_PREFIX))
+            .collect();
+        assert_eq!(content_keys.len(), 2, "{stored_objects:?}");
+        let objects = store.list_raw(Bucket::ProverJobsFri, "").await.unwrap();
+        let keys: Vec<_> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(keys, ["1_0.bin", "2_0.bin", "3_0.bin"]);
+
+        for key in ["1_0.bin", "2_0.bin"] {
+            let read_value = store.get_raw(Bucket::ProverJobsFri, key).await.unwrap();
+            assert_eq!(read_value, value);
+        }
+        let read_value = store
+            .get_raw(Bucket::ProverJobsFri, "3_0.bin")
+            .await
+            .unwrap();
+        assert_eq!(read_value, [2; 256]);
+
+        // Removing a reference must not affect other objects with the same content.
+        store
+            .remove_raw(Bucket::ProverJobsFri, "1_0.bin")
+            .await
+            .unwrap();
+        let read_value = store
+            .get_raw(Bucket::ProverJobsFri, "2_0.bin")
+            .await
+            .unwrap();
+        assert_eq!(read_value, value);
+    }
+
+    async fn content_keys(store: &MockStore, bucket: Bucket) -> Vec<String> {
+        let objects = store.list_raw(bucket, CONTENT_KEY_PREFIX).await.unwrap();
+        objects.into_iter().map(|object| object.key).collect()
+    }
+
+    #[tokio::test]
+    async fn removing_unreferenced_content() {
+        let (inner, store) = create_store();
+        let value = vec![1; 256];
+        for key in ["1_0.bin", "2_0.bin"] {
+            store
+                .put_raw(Bucket::ProverJobsFri, key, value.clone())
+                .await
+                .unwrap();
+        }
+        store
+            .put_raw(Bucket::ProverJobsFri, "3_0.bin", vec![2; 256])
+            .await
+            .unwrap();
+        let all_content_keys = content_keys(&inner, Bucket::ProverJobsFri).await;
+        assert_eq!(all_content_keys.len(), 2);
+
+        store
+            .remove_raw(Bucket::ProverJobsFri, "1_0.bin")
+            .await
+            .unwrap();
+        store
+            .remove_raw(Bucket::ProverJobsFri, "3_0.bin")
+            .await
+            .unwrap();
+
+        // Recent content must be retained even if it's unreferenced.
+        let far_future = Utc::now() + chrono::Duration::days(1);
+        let removed_count = store
+            .remove_unreferenced_raw(Bucket::ProverJobsFri, far_future)
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 0);
+        assert_eq!(
+            content_keys(&inner, Bucket::ProverJobsFri).await,
+            all_content_keys
+        );
+
+        // Emulate old content.
+        inner
+            .set_last_modified(
+                Bucket::ProverJobsFri,
+                Utc::now() - chrono::Duration::days(1),
+            )
+            .await;
+        let removed_count = store
+            .remove_unreferenced_raw(Bucket::ProverJobsFri, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 1);
+        let remaining_content_keys = content_keys(&inner, Bucket::ProverJobsFri).await;
+        assert_eq!(remaining_content_keys.len(), 1);
+        let read_value = store
+            .get_raw(Bucket::ProverJobsFri, "2_0.bin")
+            .await
+            .unwrap();
+        assert_eq!(read_value, value);
+
+        // Adding a reference to the old content should refresh it.
+        inner
+            .set_last_modified(
+                Bucket::ProverJobsFri,
+                Utc::now() - chrono::Duration::days(1),
+            )
+            .await;
+        store
+            .put_raw(Bucket::ProverJobsFri, "4_0.bin", value.clone())
+            .await
+            .unwrap();
+        store
+            .remove_raw(Bucket::ProverJobsFri, "2_0.bin")
+            .await
+            .unwrap();
+        store
+            .remove_raw(Bucket::ProverJobsFri, "4_0.bin")
+            .await
+            .unwrap();
+        let removed_count = store
+            .remove_unreferenced_raw(Bucket::ProverJobsFri, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 0);
+        assert_eq!(
+            content_keys(&inner, Bucket::ProverJobsFri).await,
+            remaining_content_keys
+        );
+    }
+
+    #[tokio::test]
+    async fn untransformed_buckets_are_passed_through() {
+        let (inner, store) = create_store();
+        store
+            .put_raw(Bucket::ProofsFri, "proof.bin", vec![3; 32])
+            .await
+            .unwrap();
+        let stored_value = inner.get_raw(Bucket::ProofsFri, "proof.bin").await.unwrap();
+        assert_eq!(stored_value, [3; 32]);
+    }
+}
@@ -1,9 +1,13 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, io::SeekFrom, ops::Range};
 
 use async_trait::async_trait;
-use tokio::{fs, io};
+use chrono::{DateTime, Utc};
+use tokio::{
+    fs, io,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError};
 
 impl From<io::Error> for ObjectStoreError {
     fn from(err: io::Error) -> Self {
@@ -14,6 +18,13 @@ impl From<io::Error> for ObjectStoreError {
     }
 }
 
+fn object_metadata(metadata: &std::fs::Metadata) -> ObjectMetadata {
+    ObjectMetadata {
+        size: metadata.len(),
+        last_modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct FileBackedObjectStore {
     base_dir: String,
@@ -33,6 +44,9 @@ impl FileBackedObjectStore {
             Bucket::SchedulerWitnessJobsFri,
             Bucket::ProofsFri,
             Bucket::StorageSnapshot,
+            Bucket::MerkleTreeCheckpoints,
+            Bucket::DataAvailability,
+            Bucket::BlobSidecars,
         ] {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path)
@@ -56,6 +70,65 @@ impl ObjectStore for FileBackedObjectStore {
         fs::read(filename).await.map_err(From::from)
     }
 
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        let mut file = fs::File::open(filename).await?;
+        let len = file.metadata().await?.len();
+        let end = range.end.min(len);
+        let start = range.start.min(end);
+        file.seek(SeekFrom::Start(start)).await?;
+
+        let mut buffer = vec![];
+        file.take(end - start).read_to_end(&mut buffer).await?;
+        Ok(buffer)
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        let metadata = fs::metadata(filename).await?;
+        Ok(object_metadata(&metadata))
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        let mut entries = fs::read_dir(format!("{}/{bucket}", self.base_dir)).await?;
+        let mut objects = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let Some(key) = entry.file_name().to_str().map(str::to_owned) else {
+                continue;
+            };
+            if !key.starts_with(prefix) {
+                continue;
+            }
+            let metadata = match entry.metadata().await {
+                Ok(metadata) => metadata,
+                // The file was removed concurrently.
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if metadata.is_file() {
+                objects.push(ListedObject {
+                    key,
+                    metadata: object_metadata(&metadata),
+                });
+            }
+        }
+        objects.sort_unstable_by(|x, y| x.key.cmp(&y.key));
+        Ok(objects)
+    }
+
     async fn put_raw(
         &self,
         bucket: Bucket,
@@ -125,4 +198,44 @@ mod test {
             .await;
         assert!(result.is_ok(), "result must be OK");
     }
+
+    #[tokio::test]
+    async fn test_list_head_and_range() {
+        let dir = TempDir::new("test-data").unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await;
+        for key in ["witness_1.bin", "witness_2.bin", "other.bin"] {
+            object_store
+                .put_raw(Bucket::WitnessInput, key, vec![0, 1, 2, 3, 4])
+                .await
+                .unwrap();
+        }
+
+        let objects = object_store
+            .list_raw(Bucket::WitnessInput, "witness_")
+            .await
+            .unwrap();
+        let keys: Vec<_> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(keys, ["witness_1.bin", "witness_2.bin"]);
+        assert_eq!(objects[0].metadata.size, 5);
+        assert!(objects[0].metadata.last_modified.is_some());
+
+        let metadata = object_store
+            .head_raw(Bucket::WitnessInput, "other.bin")
+            .await
+            .unwrap();
+        assert_eq!(metadata.size, 5);
+        assert!(metadata.last_modified.is_some());
+
+        let bytes = object_store
+            .get_range_raw(Bucket::WitnessInput, "other.bin", 3..10)
+            .await
+            .unwrap();
+        assert_eq!(bytes, [3, 4]);
+        let err = object_store
+            .head_raw(Bucket::WitnessInput, "missing.bin")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)));
+    }
 }
@@ -1,8 +1,9 @@
 //! GCS-based [`ObjectStore`] implementation.
 
-use std::{fmt, future::Future, time::Duration};
+use std::{fmt, future::Future, ops::Range as ByteRange};
 
 use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
 use google_cloud_auth::{credentials::CredentialsFile, error::Error};
 use google_cloud_storage::{
     client::{Client, ClientConfig},
@@ -11,7 +12,9 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
+            Object,
         },
         Error as HttpError,
     },
@@ -19,34 +22,11 @@ use google_cloud_storage::{
 use http::StatusCode;
 
 use crate::{
-    metrics::GCS_METRICS,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    metrics::METRICS,
+    raw::{Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError},
+    retries::retry,
 };
 
-async fn retry<T, E, Fut, F>(max_retries: u16, mut f: F) -> Result<T, E>
-where
-    E: fmt::Display,
-    Fut: Future<Output = Result<T, E>>,
-    F: FnMut() -> Fut,
-{
-    let mut retries = 1;
-    let mut backoff = 1;
-    loop {
-        match f().await {
-            Ok(result) => return Ok(result),
-            Err(err) => {
-                tracing::warn!(%err, "Failed GCS request {retries}/{max_retries}, retrying.");
-                if retries > max_retries {
-                    return Err(err);
-                }
-                retries += 1;
-                tokio::time::sleep(Duration::from_secs(backoff)).await;
-                backoff *= 2;
-            }
-        }
-    }
-}
-
 pub struct GoogleCloudStorage {
     bucket_prefix: String,
     max_retries: u16,
@@ -152,10 +132,20 @@ impl From<HttpError> for ObjectStoreError {
     }
 }
 
+fn object_metadata(object: &Object) -> ObjectMetadata {
+    ObjectMetadata {
+        size: object.size.try_into().unwrap_or(0),
+        last_modified: object.updated.and_then(|updated| {
+            Utc.timestamp_opt(updated.unix_timestamp(), updated.nanosecond())
+                .single()
+        }),
+    }
+}
+
 #[async_trait]
 impl ObjectStore for GoogleCloudStorage {
     async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
-        let fetch_latency = GCS_METRICS.start_fetch(bucket);
+        let fetch_latency = METRICS.start_fetch(bucket);
         let filename = Self::filename(bucket.as_str(), key);
         tracing::trace!(
             "Fetching data from GCS for key {filename} from bucket {}",
@@ -180,13 +170,86 @@ impl ObjectStore for GoogleCloudStorage {
         blob.map_err(ObjectStoreError::from)
     }
 
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: ByteRange<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        let size = self.head_raw(bucket, key).await?.size;
+        let end = range.end.min(size);
+        if range.start >= end {
+            return Ok(vec![]);
+        }
+
+        let fetch_latency = METRICS.start_fetch(bucket);
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: Self::filename(bucket.as_str(), key),
+            ..GetObjectRequest::default()
+        };
+        // GCS ranges are inclusive.
+        let range = Range(Some(range.start), Some(end - 1));
+        let blob = retry(self.max_retries, || {
+            self.client.download_object(&request, &range)
+        })
+        .await;
+        fetch_latency.observe();
+        blob.map_err(ObjectStoreError::from)
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: Self::filename(bucket.as_str(), key),
+            ..GetObjectRequest::default()
+        };
+        let object = retry(self.max_retries, || self.client.get_object(&request)).await?;
+        Ok(object_metadata(&object))
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        let mut request = ListObjectsRequest {
+            bucket: self.bucket_prefix.clone(),
+            prefix: Some(format!("{bucket_prefix}{prefix}")),
+            ..ListObjectsRequest::default()
+        };
+
+        let mut listed_objects = vec![];
+        loop {
+            let response = retry(self.max_retries, || self.client.list_objects(&request)).await?;
+            let objects = response.items.unwrap_or_default();
+            listed_objects.extend(objects.iter().filter_map(|object| {
+                Some(ListedObject {
+                    key: object.name.strip_prefix(&bucket_prefix)?.to_owned(),
+                    metadata: object_metadata(object),
+                })
+            }));
+            if response.next_page_token.is_none() {
+                break;
+            }
+            request.page_token = response.next_page_token;
+        }
+        listed_objects.sort_unstable_by(|x, y| x.key.cmp(&y.key));
+        Ok(listed_objects)
+    }
+
     async fn put_raw(
         &self,
         bucket: Bucket,
         key: &str,
         value: Vec<u8>,
     ) -> Result<(), ObjectStoreError> {
-        let store_latency = GCS_METRICS.start_store(bucket);
+        let store_latency = METRICS.start_store(bucket);
         let filename = Self::filename(bucket.as_str(), key);
         tracing::trace!(
             "Storing data to GCS for key {filename} from bucket {}",
@@ -223,43 +286,3 @@ impl ObjectStore for GoogleCloudStorage {
         )
     }
 }
-
-#[cfg(test)]
-mod test {
-    use std::sync::atomic::{AtomicU16, Ordering};
-
-    use super::*;
-
-    #[tokio::test]
-    async fn test_retry_success_immediate() {
-        let result = retry(2, || async { Ok::<_, &'static str>(42) }).await;
-        assert_eq!(result, Ok(42));
-    }
-
-    #[tokio::test]
-    async fn test_retry_failure_exhausted() {
-        let result = retry(2, || async { Err::<i32, _>("oops") }).await;
-        assert_eq!(result, Err("oops"));
-    }
-
-    async fn retry_success_after_n_retries(n: u16) -> Result<u32, String> {
-        let retries = AtomicU16::new(0);
-        let result = retry(n, || async {
-            let retries = retries.fetch_add(1, Ordering::Relaxed);
-            if retries + 1 == n {
-                Ok(42)
-            } else {
-                Err("oops")
-            }
-        })
-        .await;
-
-        result.map_err(|_| "Retry failed".to_string())
-    }
-
-    #[tokio::test]
-    async fn test_retry_success_after_retry() {
-        let result = retry(2, || retry_success_after_n_retries(2)).await;
-        assert_eq!(result, Ok(42));
-    }
-}
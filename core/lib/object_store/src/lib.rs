@@ -3,6 +3,11 @@
 //!
 //! - File-based storage saving blobs as separate files in the local filesystem
 //! - GCS-based storage
+//! - S3-compatible storage (AWS S3, MinIO, Cloudflare R2 etc.)
+//! - Azure Blob Storage
+//!
+//! Any of these stores can be configured to transparently compress and / or deduplicate objects
+//! in specific buckets, and to cache fetched objects in a local directory.
 //!
 //! These implementations are not exposed externally. Instead, a store trait object
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
@@ -23,12 +28,18 @@
     clippy::doc_markdown
 )]
 
+mod azure;
+mod cache;
+mod compression;
 mod file;
 mod gcs;
 mod metrics;
 mod mock;
 mod objects;
 mod raw;
+mod rest;
+mod retries;
+mod s3;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -40,5 +51,7 @@ pub mod _reexports {
 
 pub use self::{
     objects::StoredObject,
-    raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStoreFactory},
+    raw::{
+        Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError, ObjectStoreFactory,
+    },
 };
@@ -2,22 +2,24 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, LabeledFamily, LatencyObserver, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelValue, Gauge, Histogram, LabeledFamily, LatencyObserver, Metrics,
+};
 
 use crate::Bucket;
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_object_store")]
-pub(crate) struct GcsMetrics {
-    /// Latency to fetch an object from GCS.
+pub(crate) struct ObjectStoreMetrics {
+    /// Latency to fetch an object from a remote store.
     #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
     fetching_time: LabeledFamily<&'static str, Histogram<Duration>>,
-    /// Latency to store an object in GCS.
+    /// Latency to store an object in a remote store.
     #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
     storing_time: LabeledFamily<&'static str, Histogram<Duration>>,
 }
 
-impl GcsMetrics {
+impl ObjectStoreMetrics {
     pub fn start_fetch(&self, bucket: Bucket) -> LatencyObserver<'_> {
         self.fetching_time[&bucket.as_str()].start()
     }
@@ -28,4 +30,31 @@ impl GcsMetrics {
 }
 
 #[vise::register]
-pub(crate) static GCS_METRICS: vise::Global<GcsMetrics> = vise::Global::new();
+pub(crate) static METRICS: vise::Global<ObjectStoreMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum CacheRequestOutcome {
+    Hit,
+    Miss,
+    /// The cached object has failed the integrity check and was refetched.
+    Corrupted,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_object_store_cache")]
+pub(crate) struct CacheMetrics {
+    /// Outcomes of object requests to the local cache.
+    #[metrics(labels = ["bucket", "outcome"])]
+    pub requests: LabeledFamily<(&'static str, CacheRequestOutcome), Counter, 2>,
+    /// Number of objects evicted from the local cache.
+    #[metrics(labels = ["bucket"])]
+    pub evictions: LabeledFamily<&'static str, Counter>,
+    /// Number of objects in the local cache.
+    pub len: Gauge<u64>,
+    /// Total size of objects in the local cache.
+    pub size_bytes: Gauge<u64>,
+}
+
+#[vise::register]
+pub(crate) static CACHE_METRICS: vise::Global<CacheMetrics> = vise::Global::new();
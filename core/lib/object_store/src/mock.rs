@@ -1,29 +1,109 @@
 //! Mock implementation of [`ObjectStore`].
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use tokio::sync::Mutex;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{
+    truncate_range, Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError,
+};
 
-type BucketMap = HashMap<String, Vec<u8>>;
+#[derive(Debug)]
+struct MockObject {
+    value: Vec<u8>,
+    last_modified: DateTime<Utc>,
+}
+
+impl MockObject {
+    fn metadata(&self) -> ObjectMetadata {
+        ObjectMetadata {
+            size: self.value.len() as u64,
+            last_modified: Some(self.last_modified),
+        }
+    }
+}
+
+type BucketMap = HashMap<String, MockObject>;
 
 #[derive(Debug, Default)]
 pub(crate) struct MockStore {
     inner: Mutex<HashMap<Bucket, BucketMap>>,
 }
 
-#[async_trait]
-impl ObjectStore for MockStore {
-    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+impl MockStore {
+    /// Sets the modification time for all objects in the bucket.
+    #[cfg(test)]
+    pub async fn set_last_modified(&self, bucket: Bucket, last_modified: DateTime<Utc>) {
+        let mut lock = self.inner.lock().await;
+        for object in lock.entry(bucket).or_default().values_mut() {
+            object.last_modified = last_modified;
+        }
+    }
+
+    async fn with_object<T>(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        map: impl FnOnce(&MockObject) -> T,
+    ) -> Result<T, ObjectStoreError> {
         let lock = self.inner.lock().await;
-        let maybe_bytes = lock.get(&bucket).and_then(|bucket_map| bucket_map.get(key));
-        maybe_bytes.cloned().ok_or_else(|| {
+        let maybe_object = lock.get(&bucket).and_then(|bucket_map| bucket_map.get(key));
+        maybe_object.map(map).ok_or_else(|| {
             let error_message = format!("missing key: {key} in bucket {bucket}");
             ObjectStoreError::KeyNotFound(error_message.into())
         })
     }
+}
+
+#[async_trait]
+impl ObjectStore for MockStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        self.with_object(bucket, key, |object| object.value.clone())
+            .await
+    }
+
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        self.with_object(bucket, key, |object| {
+            object.value[truncate_range(range, object.value.len())].to_vec()
+        })
+        .await
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        self.with_object(bucket, key, MockObject::metadata).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let Some(bucket_map) = lock.get(&bucket) else {
+            return Ok(vec![]);
+        };
+        let mut objects: Vec<_> = bucket_map
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, object)| ListedObject {
+                key: key.clone(),
+                metadata: object.metadata(),
+            })
+            .collect();
+        objects.sort_unstable_by(|x, y| x.key.cmp(&y.key));
+        Ok(objects)
+    }
 
     async fn put_raw(
         &self,
@@ -33,7 +113,11 @@ impl ObjectStore for MockStore {
     ) -> Result<(), ObjectStoreError> {
         let mut lock = self.inner.lock().await;
         let bucket_map = lock.entry(bucket).or_default();
-        bucket_map.insert(key.to_owned(), value);
+        let object = MockObject {
+            value,
+            last_modified: Utc::now(),
+        };
+        bucket_map.insert(key.to_owned(), object);
         Ok(())
     }
 
@@ -50,3 +134,51 @@ impl ObjectStore for MockStore {
         bucket.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn listing_and_range_reads() {
+        let store = MockStore::default();
+        for key in ["1_0.bin", "1_1.bin", "2_0.bin"] {
+            store
+                .put_raw(Bucket::ProverJobsFri, key, vec![0, 1, 2, 3])
+                .await
+                .unwrap();
+        }
+
+        let objects = store.list_raw(Bucket::ProverJobsFri, "1_").await.unwrap();
+        let keys: Vec<_> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(keys, ["1_0.bin", "1_1.bin"]);
+        assert!(objects.iter().all(|object| object.metadata.size == 4));
+        let objects = store.list_raw(Bucket::ProverJobsFri, "").await.unwrap();
+        assert_eq!(objects.len(), 3);
+        let objects = store.list_raw(Bucket::WitnessInput, "").await.unwrap();
+        assert!(objects.is_empty());
+
+        let metadata = store
+            .head_raw(Bucket::ProverJobsFri, "2_0.bin")
+            .await
+            .unwrap();
+        assert_eq!(metadata.size, 4);
+        assert!(metadata.last_modified.is_some());
+
+        let value = store
+            .get_range_raw(Bucket::ProverJobsFri, "2_0.bin", 1..3)
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2]);
+        let value = store
+            .get_range_raw(Bucket::ProverJobsFri, "2_0.bin", 2..100)
+            .await
+            .unwrap();
+        assert_eq!(value, [2, 3]);
+        let value = store
+            .get_range_raw(Bucket::ProverJobsFri, "2_0.bin", 10..100)
+            .await
+            .unwrap();
+        assert!(value.is_empty());
+    }
+}
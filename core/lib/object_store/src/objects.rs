@@ -7,6 +7,7 @@ use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use prost::Message;
 use zksync_protobuf::{decode, ProtoFmt};
 use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
     snapshots::{
         SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
     },
@@ -129,6 +130,19 @@ impl StoredObject for WitnessBlockState {
     serialize_using_bincode!();
 }
 
+/// Sidecar of the EIP-4844 commit transaction for an L1 batch, archived so that blobs are available
+/// after they expire on L1.
+impl StoredObject for EthTxBlobSidecar {
+    const BUCKET: Bucket = Bucket::BlobSidecars;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("blob_sidecar_for_l1_batch_{key}.bin")
+    }
+
+    serialize_using_bincode!();
+}
+
 impl dyn ObjectStore + '_ {
     /// Fetches the value for the given key if it exists.
     ///
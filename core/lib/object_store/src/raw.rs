@@ -1,12 +1,17 @@
-use std::{error, fmt, sync::Arc};
+use std::{error, fmt, ops::Range, str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
 use crate::{
+    azure::AzureBlobStorage,
+    cache::CachingObjectStore,
+    compression::CompressingObjectStore,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStorage, GoogleCloudStorageAuthMode},
     mock::MockStore,
+    s3::S3Storage,
 };
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
@@ -24,9 +29,29 @@ pub enum Bucket {
     SchedulerWitnessJobsFri,
     ProofsFri,
     StorageSnapshot,
+    MerkleTreeCheckpoints,
+    DataAvailability,
+    BlobSidecars,
 }
 
 impl Bucket {
+    pub(crate) const ALL: [Self; 14] = [
+        Self::ProverJobs,
+        Self::WitnessInput,
+        Self::LeafAggregationWitnessJobs,
+        Self::NodeAggregationWitnessJobs,
+        Self::SchedulerWitnessJobs,
+        Self::ProverJobsFri,
+        Self::LeafAggregationWitnessJobsFri,
+        Self::NodeAggregationWitnessJobsFri,
+        Self::SchedulerWitnessJobsFri,
+        Self::ProofsFri,
+        Self::StorageSnapshot,
+        Self::MerkleTreeCheckpoints,
+        Self::DataAvailability,
+        Self::BlobSidecars,
+    ];
+
     pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::ProverJobs => "prover_jobs",
@@ -40,6 +65,9 @@ impl Bucket {
             Self::SchedulerWitnessJobsFri => "scheduler_witness_jobs_fri",
             Self::ProofsFri => "proofs_fri",
             Self::StorageSnapshot => "storage_logs_snapshots",
+            Self::MerkleTreeCheckpoints => "merkle_tree_checkpoints",
+            Self::DataAvailability => "data_availability",
+            Self::BlobSidecars => "blob_sidecars",
         }
     }
 }
@@ -50,6 +78,42 @@ impl fmt::Display for Bucket {
     }
 }
 
+impl FromStr for Bucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|bucket| bucket.as_str() == s)
+            .ok_or_else(|| format!("unknown bucket `{s}`"))
+    }
+}
+
+/// Metadata of an object stored in an [`ObjectStore`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectMetadata {
+    /// Object size in bytes.
+    pub size: u64,
+    /// Time of the last object modification, if reported by the store.
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// Object returned by [`ObjectStore::list_raw()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedObject {
+    /// Object key in the bucket.
+    pub key: String,
+    /// Object metadata, as returned by [`ObjectStore::head_raw()`].
+    pub metadata: ObjectMetadata,
+}
+
+/// Truncates `range` so that it fits into an object with the specified length.
+pub(crate) fn truncate_range(range: Range<u64>, len: usize) -> Range<usize> {
+    let end = usize::try_from(range.end).map_or(len, |end| end.min(len));
+    let start = usize::try_from(range.start).map_or(end, |start| start.min(end));
+    start..end
+}
+
 /// Thread-safe boxed error.
 pub type BoxedError = Box<dyn error::Error + Send + Sync>;
 
@@ -100,6 +164,41 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if an object with the `key` does not exist or cannot be accessed.
     async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError>;
 
+    /// Fetches the specified byte range of the value for the given key from the given bucket.
+    /// The range is truncated to the object size; thus, if the range starts after the object end,
+    /// an empty vector is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object with the `key` does not exist or cannot be accessed.
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError>;
+
+    /// Fetches metadata for the given key from the given bucket without fetching the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object with the `key` does not exist or cannot be accessed.
+    async fn head_raw(&self, bucket: Bucket, key: &str)
+        -> Result<ObjectMetadata, ObjectStoreError>;
+
+    /// Lists objects in the given bucket with keys starting with the specified `prefix`. Objects are returned
+    /// in the lexicographic key order together with their metadata, so that callers don't need to query
+    /// metadata for each object separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bucket cannot be accessed.
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError>;
+
     /// Stores the value associating it with the key into the given bucket.
     /// If the key already exists, the value is replaced.
     ///
@@ -120,6 +219,21 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Removes auxiliary blobs in the given bucket that are not referenced by any object and were last modified
+    /// before `cutoff`. Returns the number of removed blobs. Auxiliary blobs are created by stores that
+    /// deduplicate objects; the default implementation does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing, reading or removing blobs fails.
+    async fn remove_unreferenced_raw(
+        &self,
+        _bucket: Bucket,
+        _cutoff: DateTime<Utc>,
+    ) -> Result<usize, ObjectStoreError> {
+        Ok(0)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
 
@@ -129,6 +243,31 @@ impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
         (**self).get_raw(bucket, key).await
     }
 
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        (**self).get_range_raw(bucket, key, range).await
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        (**self).head_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        (**self).list_raw(bucket, prefix).await
+    }
+
     async fn put_raw(
         &self,
         bucket: Bucket,
@@ -142,6 +281,14 @@ impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
         (**self).remove_raw(bucket, key).await
     }
 
+    async fn remove_unreferenced_raw(
+        &self,
+        bucket: Bucket,
+        cutoff: DateTime<Utc>,
+    ) -> Result<usize, ObjectStoreError> {
+        (**self).remove_unreferenced_raw(bucket, cutoff).await
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         (**self).storage_prefix_raw(bucket)
     }
@@ -165,7 +312,8 @@ impl ObjectStoreFactory {
     /// # Panics
     ///
     /// If the GCS-backed implementation is configured, this constructor will panic if called
-    /// outside the Tokio runtime.
+    /// outside the Tokio runtime. [`Self::create_store()`] will panic if the config refers
+    /// to unknown buckets.
     pub fn new(config: ObjectStoreConfig) -> Self {
         Self {
             origin: ObjectStoreOrigin::Config(config),
@@ -190,6 +338,39 @@ impl ObjectStoreFactory {
     }
 
     async fn create_from_config(config: &ObjectStoreConfig) -> Arc<dyn ObjectStore> {
+        let mut store = Self::create_backend(config).await;
+        if let Some(cache_path) = &config.local_cache_path {
+            let max_size_bytes = config.local_cache_max_size_bytes();
+            tracing::trace!(
+                "Initialized local object cache at `{cache_path}` with max size {max_size_bytes}B"
+            );
+            store =
+                Arc::new(CachingObjectStore::new(store, cache_path.clone(), max_size_bytes).await);
+        }
+        if config.compressed_buckets.is_empty() && config.deduplicated_buckets.is_empty() {
+            return store;
+        }
+
+        let parse_bucket = |name: &String| {
+            name.parse::<Bucket>()
+                .unwrap_or_else(|err| panic!("invalid object store config: {err}"))
+        };
+        let mut store = CompressingObjectStore::new(store);
+        for bucket in config.compressed_buckets.iter().map(parse_bucket) {
+            store = store.with_compression(bucket);
+        }
+        for bucket in config.deduplicated_buckets.iter().map(parse_bucket) {
+            store = store.with_deduplication(bucket);
+        }
+        tracing::trace!(
+            "Enabled compression for buckets {:?} and deduplication for buckets {:?}",
+            config.compressed_buckets,
+            config.deduplicated_buckets
+        );
+        Arc::new(store)
+    }
+
+    async fn create_backend(config: &ObjectStoreConfig) -> Arc<dyn ObjectStore> {
         match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 tracing::trace!(
@@ -235,6 +416,40 @@ impl ObjectStoreFactory {
                 .await;
                 Arc::new(store)
             }
+            ObjectStoreMode::S3 {
+                s3_endpoint,
+                s3_region,
+                s3_bucket,
+                s3_access_key_id,
+                s3_secret_access_key,
+            } => {
+                tracing::trace!("Initialized S3 Object store");
+                let store = S3Storage::new(
+                    s3_endpoint,
+                    s3_region.clone(),
+                    s3_bucket.clone(),
+                    s3_access_key_id.clone(),
+                    s3_secret_access_key.clone(),
+                    config.max_retries,
+                );
+                Arc::new(store)
+            }
+            ObjectStoreMode::AzureBlob {
+                azure_account_name,
+                azure_container,
+                azure_access_key,
+                azure_endpoint,
+            } => {
+                tracing::trace!("Initialized AzureBlob Object store");
+                let store = AzureBlobStorage::new(
+                    azure_account_name.clone(),
+                    azure_container.clone(),
+                    azure_access_key,
+                    azure_endpoint.as_deref(),
+                    config.max_retries,
+                );
+                Arc::new(store)
+            }
         }
     }
 }
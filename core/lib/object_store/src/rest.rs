@@ -0,0 +1,440 @@
+//! Helpers shared by object stores accessed via REST APIs (S3 and Azure Blob Storage).
+
+use std::{error, fmt, ops::Range};
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{
+    header::{CONTENT_LENGTH, LAST_MODIFIED},
+    Response, StatusCode,
+};
+use sha2::Sha256;
+
+use crate::raw::{truncate_range, ListedObject, ObjectMetadata, ObjectStoreError};
+
+impl From<reqwest::Error> for ObjectStoreError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.status() == Some(StatusCode::NOT_FOUND) {
+            ObjectStoreError::KeyNotFound(err.into())
+        } else {
+            ObjectStoreError::Other(err.into())
+        }
+    }
+}
+
+/// Unsuccessful HTTP response from a REST-based store.
+#[derive(Debug)]
+struct HttpStatusError {
+    status: StatusCode,
+    body: String,
+}
+
+impl fmt::Display for HttpStatusError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "HTTP status {}: {}", self.status, self.body)
+    }
+}
+
+impl error::Error for HttpStatusError {}
+
+/// Converts an unsuccessful response into an error. 404 responses are mapped to [`ObjectStoreError::KeyNotFound`].
+pub(crate) async fn check_response(response: Response) -> Result<Response, ObjectStoreError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    let err = Box::new(HttpStatusError { status, body });
+    Err(if status == StatusCode::NOT_FOUND {
+        ObjectStoreError::KeyNotFound(err)
+    } else {
+        ObjectStoreError::Other(err)
+    })
+}
+
+/// Extracts the value from a response to a range request. Some servers ignore the `Range` header
+/// and return the entire object; in this case, the requested range is sliced from the returned value.
+pub(crate) async fn range_response(
+    response: Response,
+    range: Range<u64>,
+) -> Result<Vec<u8>, ObjectStoreError> {
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // The range starts after the object end.
+        return Ok(vec![]);
+    }
+    let response = check_response(response).await?;
+    let is_partial = response.status() == StatusCode::PARTIAL_CONTENT;
+    let bytes = response.bytes().await?;
+    Ok(if is_partial {
+        bytes.to_vec()
+    } else {
+        bytes[truncate_range(range, bytes.len())].to_vec()
+    })
+}
+
+/// Formats an HTTP `Range` header value for a non-empty byte range.
+pub(crate) fn range_header(range: &Range<u64>) -> String {
+    // HTTP ranges are inclusive.
+    format!("bytes={}-{}", range.start, range.end - 1)
+}
+
+/// Extracts object metadata from a response to a `HEAD` request.
+pub(crate) fn object_metadata(response: &Response) -> Result<ObjectMetadata, ObjectStoreError> {
+    let headers = response.headers();
+    let size = headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok()?.parse().ok())
+        .ok_or_else(|| ObjectStoreError::Other("missing or invalid `Content-Length`".into()))?;
+    let last_modified = headers
+        .get(LAST_MODIFIED)
+        .and_then(|value| DateTime::parse_from_rfc2822(value.to_str().ok()?).ok())
+        .map(|time| time.with_timezone(&Utc));
+    Ok(ObjectMetadata {
+        size,
+        last_modified,
+    })
+}
+
+/// Extracts text contents of all `<{tag}>` elements in an XML document. This is only suitable for simple documents
+/// returned by listing APIs, in which the extracted elements don't have attributes or child elements.
+pub(crate) fn xml_elements(xml: &str, tag: &str) -> Vec<String> {
+    raw_xml_elements(xml, tag)
+        .into_iter()
+        .map(unescape_xml)
+        .collect()
+}
+
+/// Extracts raw (i.e., not unescaped) contents of all `<{tag}>` elements in an XML document.
+/// Unlike [`xml_elements()`], extracted elements may have child elements, but not attributes.
+fn raw_xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open_tag = format!("<{tag}>");
+    let close_tag = format!("</{tag}>");
+    let mut contents = vec![];
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open_tag) {
+        rest = &rest[start + open_tag.len()..];
+        let Some(end) = rest.find(&close_tag) else {
+            break;
+        };
+        contents.push(&rest[..end]);
+        rest = &rest[end + close_tag.len()..];
+    }
+    contents
+}
+
+/// Names of XML elements describing objects in a page returned by a listing API.
+#[derive(Debug)]
+pub(crate) struct ListingSchema {
+    /// Element wrapping a single listed object.
+    pub entry: &'static str,
+    pub key: &'static str,
+    pub size: &'static str,
+    pub last_modified: &'static str,
+}
+
+/// Extracts objects from a page returned by a listing API. Objects with keys not starting with `key_prefix`
+/// are skipped; the prefix is stripped from returned keys. The last modification time may be formatted either
+/// as per RFC 3339 (S3) or as per RFC 2822 (Azure).
+pub(crate) fn xml_listed_objects(
+    xml: &str,
+    schema: &ListingSchema,
+    key_prefix: &str,
+) -> Result<Vec<ListedObject>, ObjectStoreError> {
+    let mut objects = vec![];
+    for entry in raw_xml_elements(xml, schema.entry) {
+        let Some(full_key) = xml_elements(entry, schema.key).pop() else {
+            continue;
+        };
+        let Some(key) = full_key.strip_prefix(key_prefix) else {
+            continue;
+        };
+        let size = xml_elements(entry, schema.size)
+            .pop()
+            .and_then(|size| size.parse().ok())
+            .ok_or_else(|| {
+                let message = format!("missing or invalid size for listed object `{full_key}`");
+                ObjectStoreError::Other(message.into())
+            })?;
+        let last_modified = xml_elements(entry, schema.last_modified)
+            .pop()
+            .and_then(|time| {
+                DateTime::parse_from_rfc3339(&time)
+                    .or_else(|_| DateTime::parse_from_rfc2822(&time))
+                    .ok()
+            })
+            .map(|time| time.with_timezone(&Utc));
+        objects.push(ListedObject {
+            key: key.to_owned(),
+            metadata: ObjectMetadata {
+                size,
+                last_modified,
+            },
+        });
+    }
+    Ok(objects)
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Percent-encodes an object path as per RFC 3986, leaving `/` separators intact. Both S3 and Azure
+/// require the canonical path used in request signatures to be encoded this way.
+pub(crate) fn encode_path(path: &str) -> String {
+    percent_encode(path, true)
+}
+
+/// Percent-encodes a query parameter name or value as per RFC 3986.
+pub(crate) fn encode_query_component(component: &str) -> String {
+    percent_encode(component, false)
+}
+
+fn percent_encode(s: &str, keep_slashes: bool) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        let is_unreserved =
+            byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~');
+        if is_unreserved || (keep_slashes && byte == b'/') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// In-process HTTP server emulating basic object operations of S3 / Azure Blob Storage.
+#[cfg(test)]
+pub(crate) mod testonly {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        fmt::Write as _,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        body::Bytes,
+        extract::{Query, State},
+        http::{header, HeaderMap, HeaderValue, Method, StatusCode, Uri},
+        Router,
+    };
+
+    #[derive(Debug)]
+    pub(crate) struct RecordedRequest {
+        pub method: Method,
+        pub path: String,
+        pub headers: HeaderMap,
+    }
+
+    #[derive(Debug, Default)]
+    pub(crate) struct MockServerState {
+        /// Stored objects keyed by the URL path.
+        pub objects: BTreeMap<String, Vec<u8>>,
+        pub requests: Vec<RecordedRequest>,
+    }
+
+    impl MockServerState {
+        /// Lists object paths and sizes in a container / S3 bucket with the specified URL path.
+        fn list(&self, container_path: &str, prefix: &str) -> Vec<(String, usize)> {
+            let full_prefix = format!("{container_path}/{prefix}");
+            self.objects
+                .iter()
+                .filter(|(path, _)| path.starts_with(&full_prefix))
+                .map(|(path, value)| (path[container_path.len() + 1..].to_owned(), value.len()))
+                .collect()
+        }
+    }
+
+    /// Last modification time reported for all objects.
+    const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+    type SharedState = Arc<Mutex<MockServerState>>;
+    type MockResponse = (StatusCode, HeaderMap, Vec<u8>);
+
+    fn parse_range(headers: &HeaderMap) -> Option<(usize, usize)> {
+        let range = headers.get("x-ms-range").or(headers.get(header::RANGE))?;
+        let (start, end) = range
+            .to_str()
+            .ok()?
+            .strip_prefix("bytes=")?
+            .split_once('-')?;
+        Some((start.parse().ok()?, end.parse().ok()?))
+    }
+
+    fn list_response(
+        state: &MockServerState,
+        path: &str,
+        params: &HashMap<String, String>,
+    ) -> Vec<u8> {
+        let prefix = params.get("prefix").map_or("", String::as_str);
+        let keys = state.list(path, prefix);
+        let mut xml = String::new();
+        if params.get("comp").map(String::as_str) == Some("list") {
+            xml.push_str("<EnumerationResults><Blobs>");
+            for (key, size) in keys {
+                write!(
+                    xml,
+                    "<Blob><Name>{key}</Name><Properties>\
+                     <Last-Modified>{LAST_MODIFIED}</Last-Modified>\
+                     <Content-Length>{size}</Content-Length></Properties></Blob>"
+                )
+                .unwrap();
+            }
+            xml.push_str("</Blobs><NextMarker /></EnumerationResults>");
+        } else {
+            xml.push_str("<ListBucketResult><IsTruncated>false</IsTruncated>");
+            for (key, size) in keys {
+                write!(
+                    xml,
+                    "<Contents><Key>{key}</Key>\
+                     <LastModified>2015-10-21T07:28:00.000Z</LastModified>\
+                     <Size>{size}</Size></Contents>"
+                )
+                .unwrap();
+            }
+            xml.push_str("</ListBucketResult>");
+        }
+        xml.into_bytes()
+    }
+
+    async fn handle_request(
+        State(state): State<SharedState>,
+        method: Method,
+        uri: Uri,
+        Query(params): Query<HashMap<String, String>>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> MockResponse {
+        let path = uri.path().to_owned();
+        let mut state = state.lock().unwrap();
+        let range = parse_range(&headers);
+        state.requests.push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            headers,
+        });
+
+        let no_headers = HeaderMap::new();
+        let is_list_request = params.get("list-type").map(String::as_str) == Some("2")
+            || params.get("comp").map(String::as_str) == Some("list");
+        match method {
+            Method::GET if is_list_request => (
+                StatusCode::OK,
+                no_headers,
+                list_response(&state, &path, &params),
+            ),
+            Method::GET | Method::HEAD => {
+                let Some(object) = state.objects.get(&path) else {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        no_headers,
+                        b"object not found".to_vec(),
+                    );
+                };
+                let mut response_headers = HeaderMap::new();
+                response_headers.insert(
+                    header::LAST_MODIFIED,
+                    HeaderValue::from_static(LAST_MODIFIED),
+                );
+                if method == Method::HEAD {
+                    response_headers.insert(header::CONTENT_LENGTH, object.len().into());
+                    return (StatusCode::OK, response_headers, vec![]);
+                }
+                match range {
+                    Some((start, _)) if start >= object.len() => {
+                        (StatusCode::RANGE_NOT_SATISFIABLE, no_headers, vec![])
+                    }
+                    Some((start, end)) => {
+                        let end = (end + 1).min(object.len());
+                        let value = object[start..end].to_vec();
+                        (StatusCode::PARTIAL_CONTENT, response_headers, value)
+                    }
+                    None => (StatusCode::OK, response_headers, object.clone()),
+                }
+            }
+            Method::PUT => {
+                state.objects.insert(path, body.to_vec());
+                (StatusCode::CREATED, no_headers, vec![])
+            }
+            Method::DELETE => {
+                state.objects.remove(&path);
+                (StatusCode::ACCEPTED, no_headers, vec![])
+            }
+            _ => (StatusCode::METHOD_NOT_ALLOWED, no_headers, vec![]),
+        }
+    }
+
+    /// Spawns a server on a random local port. Returns the server URL and its state.
+    pub(crate) fn spawn_server() -> (String, SharedState) {
+        let state = SharedState::default();
+        let app = Router::new()
+            .fallback(handle_request)
+            .with_state(state.clone());
+        let server = axum::Server::bind(&([127, 0, 0, 1], 0).into()).serve(app.into_make_service());
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        (url, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_paths() {
+        assert_eq!(
+            encode_path("bucket/proofs_fri/l1_batch_1.bin"),
+            "bucket/proofs_fri/l1_batch_1.bin"
+        );
+        assert_eq!(encode_path("a b/c+d:e"), "a%20b/c%2Bd%3Ae");
+        assert_eq!(encode_query_component("a b/c"), "a%20b%2Fc");
+    }
+
+    #[test]
+    fn extracting_xml_elements() {
+        let xml = "<ListBucketResult><Contents><Key>a&amp;b</Key></Contents>\
+                   <Contents><Key>c</Key></Contents><NextMarker /></ListBucketResult>";
+        assert_eq!(xml_elements(xml, "Key"), ["a&b", "c"]);
+        assert!(xml_elements(xml, "NextMarker").is_empty());
+    }
+
+    #[test]
+    fn extracting_listed_objects() {
+        let schema = ListingSchema {
+            entry: "Contents",
+            key: "Key",
+            size: "Size",
+            last_modified: "LastModified",
+        };
+        let xml = "<ListBucketResult><Contents><Key>proofs_fri/a&amp;b</Key><Size>10</Size>\
+                   <LastModified>2015-10-21T07:28:00.000Z</LastModified></Contents>\
+                   <Contents><Key>other/c</Key><Size>1</Size></Contents>\
+                   <Contents><Key>proofs_fri/d</Key><Size>3</Size></Contents></ListBucketResult>";
+        let objects = xml_listed_objects(xml, &schema, "proofs_fri/").unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].key, "a&b");
+        assert_eq!(objects[0].metadata.size, 10);
+        let expected_time = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(
+            objects[0].metadata.last_modified,
+            Some(expected_time.into())
+        );
+        assert_eq!(objects[1].key, "d");
+        assert_eq!(objects[1].metadata.last_modified, None);
+
+        let xml =
+            "<ListBucketResult><Contents><Key>proofs_fri/a</Key></Contents></ListBucketResult>";
+        xml_listed_objects(xml, &schema, "proofs_fri/").unwrap_err();
+    }
+}
@@ -0,0 +1,433 @@
+//! [`ObjectStore`] implementation for S3-compatible storages (AWS S3, MinIO, Cloudflare R2 etc.).
+
+use std::{fmt, ops::Range};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder, Url};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    metrics::METRICS,
+    raw::{Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError},
+    rest::{
+        check_response, encode_path, encode_query_component, hmac_sha256, object_metadata,
+        range_header, range_response, xml_elements, xml_listed_objects, ListingSchema,
+    },
+    retries::retry,
+};
+
+/// Headers included into request signatures.
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// Elements describing objects in listing responses.
+const LISTING_SCHEMA: ListingSchema = ListingSchema {
+    entry: "Contents",
+    key: "Key",
+    size: "Size",
+    last_modified: "LastModified",
+};
+
+/// Object store backed by an S3-compatible storage. Requests are authenticated using AWS Signature Version 4
+/// and use path-style addressing (`{endpoint}/{s3_bucket}/{key}`), which is supported by all S3-compatible storages.
+/// Objects are stored under the same `{bucket}/{key}` keys as in [GCS](crate::gcs::GoogleCloudStorage).
+pub(crate) struct S3Storage {
+    client: Client,
+    endpoint: String,
+    region: String,
+    bucket_name: String,
+    access_key_id: String,
+    secret_access_key: String,
+    max_retries: u16,
+}
+
+impl fmt::Debug for S3Storage {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("S3Storage")
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region)
+            .field("bucket_name", &self.bucket_name)
+            .field("max_retries", &self.max_retries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl S3Storage {
+    pub fn new(
+        endpoint: &str,
+        region: String,
+        bucket_name: String,
+        access_key_id: String,
+        secret_access_key: String,
+        max_retries: u16,
+    ) -> Self {
+        Url::parse(endpoint)
+            .unwrap_or_else(|err| panic!("invalid S3 endpoint URL `{endpoint}`: {err}"));
+        Self {
+            client: Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            region,
+            bucket_name,
+            access_key_id,
+            secret_access_key,
+            max_retries,
+        }
+    }
+
+    fn object_url(&self, bucket: Bucket, key: &str) -> Url {
+        let path = encode_path(&format!("{}/{bucket}/{key}", self.bucket_name));
+        let url = format!("{}/{path}", self.endpoint);
+        Url::parse(&url).unwrap_or_else(|err| panic!("invalid S3 object URL `{url}`: {err}"))
+    }
+
+    /// Returns the URL of the S3 bucket with the specified query params. Params are sorted and encoded,
+    /// so that the URL query can be used in the request signature as is.
+    fn bucket_url(&self, mut query: Vec<(&str, &str)>) -> Url {
+        query.sort_unstable();
+        let query: Vec<_> = query
+            .into_iter()
+            .map(|(name, value)| {
+                let value = encode_query_component(value);
+                format!("{}={value}", encode_query_component(name))
+            })
+            .collect();
+        let url = format!(
+            "{}/{}?{}",
+            self.endpoint,
+            encode_path(&self.bucket_name),
+            query.join("&")
+        );
+        Url::parse(&url).unwrap_or_else(|err| panic!("invalid S3 bucket URL `{url}`: {err}"))
+    }
+
+    fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+        let key = hmac_sha256(
+            format!("AWS4{secret_access_key}").as_bytes(),
+            date.as_bytes(),
+        );
+        let key = hmac_sha256(&key, region.as_bytes());
+        let key = hmac_sha256(&key, service.as_bytes());
+        hmac_sha256(&key, b"aws4_request")
+    }
+
+    /// Computes the `Authorization` header value for a request. The URL query (if any) must be canonical,
+    /// i.e., have sorted and encoded params.
+    fn authorization(
+        &self,
+        method: &Method,
+        url: &Url,
+        payload_hash: &str,
+        amz_date: &str,
+        date: &str,
+    ) -> String {
+        let host = match url.port() {
+            Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+            None => url.host_str().unwrap_or_default().to_owned(),
+        };
+        let canonical_request = format!(
+            "{method}\n{path}\n{query}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n\
+             {SIGNED_HEADERS}\n{payload_hash}",
+            path = url.path(),
+            query = url.query().unwrap_or_default()
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = Self::signing_key(&self.secret_access_key, date, &self.region, "s3");
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+            self.access_key_id
+        )
+    }
+
+    fn signed_request(&self, method: Method, url: &Url, body: Option<Vec<u8>>) -> RequestBuilder {
+        let now: DateTime<Utc> = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(body.as_deref().unwrap_or_default()));
+        let authorization = self.authorization(&method, url, &payload_hash, &amz_date, &date);
+
+        let request = self
+            .client
+            .request(method, url.clone())
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization);
+        match body {
+            Some(body) => request.body(body),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3Storage {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let fetch_latency = METRICS.start_fetch(bucket);
+        let url = &self.object_url(bucket, key);
+        tracing::trace!("Fetching data from S3 for key {key} from bucket {bucket}");
+
+        let blob = retry(self.max_retries, || async move {
+            let response = self.signed_request(Method::GET, url, None).send().await?;
+            let response = check_response(response).await?;
+            Ok::<_, ObjectStoreError>(response.bytes().await?.to_vec())
+        })
+        .await;
+
+        let elapsed = fetch_latency.observe();
+        tracing::trace!(
+            "Fetched data from S3 for key {key} from bucket {bucket} and it took: {elapsed:?}"
+        );
+        blob
+    }
+
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+        let fetch_latency = METRICS.start_fetch(bucket);
+        let url = &self.object_url(bucket, key);
+        let range = &range;
+        tracing::trace!("Fetching range {range:?} from S3 for key {key} from bucket {bucket}");
+
+        let blob = retry(self.max_retries, || async move {
+            let request = self.signed_request(Method::GET, url, None);
+            let response = request.header("range", range_header(range)).send().await?;
+            range_response(response, range.clone()).await
+        })
+        .await;
+        fetch_latency.observe();
+        blob
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        let url = &self.object_url(bucket, key);
+        retry(self.max_retries, || async move {
+            let response = self.signed_request(Method::HEAD, url, None).send().await?;
+            object_metadata(&check_response(response).await?)
+        })
+        .await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        let bucket_prefix = format!("{bucket}/");
+        let full_prefix = format!("{bucket_prefix}{prefix}");
+        let mut objects = vec![];
+        let mut continuation_token = None::<String>;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", full_prefix.as_str())];
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", token.as_str()));
+            }
+            let url = &self.bucket_url(query);
+            let page = retry(self.max_retries, || async move {
+                let response = self.signed_request(Method::GET, url, None).send().await?;
+                Ok::<_, ObjectStoreError>(check_response(response).await?.text().await?)
+            })
+            .await?;
+
+            objects.extend(xml_listed_objects(&page, &LISTING_SCHEMA, &bucket_prefix)?);
+            continuation_token = xml_elements(&page, "NextContinuationToken").pop();
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        objects.sort_unstable_by(|x, y| x.key.cmp(&y.key));
+        Ok(objects)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let store_latency = METRICS.start_store(bucket);
+        let url = &self.object_url(bucket, key);
+        let value = &value;
+        tracing::trace!("Storing data to S3 for key {key} from bucket {bucket}");
+
+        let result = retry(self.max_retries, || async move {
+            let request = self.signed_request(Method::PUT, url, Some(value.clone()));
+            check_response(request.send().await?).await?;
+            Ok::<_, ObjectStoreError>(())
+        })
+        .await;
+
+        let elapsed = store_latency.observe();
+        tracing::trace!(
+            "Stored data to S3 for key {key} from bucket {bucket} and it took: {elapsed:?}"
+        );
+        result
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let url = &self.object_url(bucket, key);
+        tracing::trace!("Removing data from S3 for key {key} from bucket {bucket}");
+
+        retry(self.max_retries, || async move {
+            let request = self.signed_request(Method::DELETE, url, None);
+            check_response(request.send().await?).await?;
+            Ok::<_, ObjectStoreError>(())
+        })
+        .await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{}/{bucket}", self.endpoint, self.bucket_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::rest::testonly;
+
+    fn test_store(endpoint: &str) -> S3Storage {
+        S3Storage::new(
+            endpoint,
+            "us-east-1".to_owned(),
+            "zksync".to_owned(),
+            "AKIDEXAMPLE".to_owned(),
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_owned(),
+            0,
+        )
+    }
+
+    #[test]
+    fn deriving_signing_key() {
+        // Example from the AWS Signature Version 4 documentation.
+        let key = S3Storage::signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[tokio::test]
+    async fn s3_store_basics() {
+        let (url, state) = testonly::spawn_server();
+        let store = test_store(&url);
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::ProofsFri),
+            format!("{url}/zksync/proofs_fri")
+        );
+
+        store
+            .put_raw(Bucket::ProofsFri, "test-key.bin", vec![1, 2, 3])
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::ProofsFri, "test-key.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2, 3]);
+        assert!(state
+            .lock()
+            .unwrap()
+            .objects
+            .contains_key("/zksync/proofs_fri/test-key.bin"));
+
+        store
+            .remove_raw(Bucket::ProofsFri, "test-key.bin")
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::ProofsFri, "test-key.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        let state = state.lock().unwrap();
+        let put_request = &state.requests[0];
+        assert_eq!(put_request.method, Method::PUT);
+        let authorization = put_request.headers["authorization"].to_str().unwrap();
+        assert!(
+            authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"),
+            "{authorization}"
+        );
+        assert!(authorization.contains("/us-east-1/s3/aws4_request"));
+        let payload_hash = put_request.headers["x-amz-content-sha256"]
+            .to_str()
+            .unwrap();
+        assert_eq!(payload_hash, hex::encode(Sha256::digest([1, 2, 3])));
+    }
+
+    #[tokio::test]
+    async fn s3_listing_and_range_reads() {
+        let (url, _) = testonly::spawn_server();
+        let store = test_store(&url);
+        for key in ["1_0.bin", "1_1.bin", "2_0.bin"] {
+            store
+                .put_raw(Bucket::ProverJobsFri, key, vec![0, 1, 2, 3, 4])
+                .await
+                .unwrap();
+        }
+        store
+            .put_raw(Bucket::WitnessInput, "1_0.bin", vec![])
+            .await
+            .unwrap();
+
+        let objects = store.list_raw(Bucket::ProverJobsFri, "1_").await.unwrap();
+        let keys: Vec<_> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(keys, ["1_0.bin", "1_1.bin"]);
+        assert_eq!(objects[0].metadata.size, 5);
+        assert!(objects[0].metadata.last_modified.is_some());
+        let objects = store.list_raw(Bucket::WitnessInput, "").await.unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].key, "1_0.bin");
+        assert_eq!(objects[0].metadata.size, 0);
+
+        let metadata = store
+            .head_raw(Bucket::ProverJobsFri, "2_0.bin")
+            .await
+            .unwrap();
+        assert_eq!(metadata.size, 5);
+        assert!(metadata.last_modified.is_some());
+        let err = store
+            .head_raw(Bucket::ProverJobsFri, "3_0.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        let value = store
+            .get_range_raw(Bucket::ProverJobsFri, "2_0.bin", 1..3)
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2]);
+        let value = store
+            .get_range_raw(Bucket::ProverJobsFri, "2_0.bin", 3..10)
+            .await
+            .unwrap();
+        assert_eq!(value, [3, 4]);
+        let value = store
+            .get_range_raw(Bucket::ProverJobsFri, "2_0.bin", 10..20)
+            .await
+            .unwrap();
+        assert!(value.is_empty());
+    }
+}
@@ -103,6 +103,12 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("latests_values_cache_size_mb")?,
+            vm_storage_disk_cache_path: self.vm_storage_disk_cache_path.clone(),
+            vm_storage_disk_cache_size_mb: self
+                .vm_storage_disk_cache_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("vm_storage_disk_cache_size_mb")?,
             fee_history_limit: self.fee_history_limit,
             max_batch_request_size: self
                 .max_batch_request_size
@@ -168,6 +174,10 @@ impl ProtoRepr for proto::Web3JsonRpc {
             latest_values_cache_size_mb: this
                 .latest_values_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
+            vm_storage_disk_cache_path: this.vm_storage_disk_cache_path.clone(),
+            vm_storage_disk_cache_size_mb: this
+                .vm_storage_disk_cache_size_mb
+                .map(|x| x.try_into().unwrap()),
             fee_history_limit: this.fee_history_limit,
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
             max_response_body_size_mb: this
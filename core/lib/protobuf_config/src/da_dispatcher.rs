@@ -0,0 +1,53 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::proto::da_dispatcher as proto;
+
+impl proto::DataAvailabilityMode {
+    fn new(x: &configs::DataAvailabilityMode) -> Self {
+        use configs::DataAvailabilityMode as From;
+        match x {
+            From::ObjectStore => Self::ObjectStore,
+            From::Celestia => Self::Celestia,
+        }
+    }
+
+    fn parse(&self) -> configs::DataAvailabilityMode {
+        use configs::DataAvailabilityMode as To;
+        match self {
+            Self::ObjectStore => To::ObjectStore,
+            Self::Celestia => To::Celestia,
+        }
+    }
+}
+
+impl ProtoRepr for proto::DaDispatcher {
+    type Type = configs::DADispatcherConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            mode: required(&self.mode)
+                .and_then(|x| Ok(proto::DataAvailabilityMode::try_from(*x)?))
+                .context("mode")?
+                .parse(),
+            celestia_api_node_url: self.celestia_api_node_url.clone(),
+            celestia_auth_token: self.celestia_auth_token.clone(),
+            celestia_namespace: self.celestia_namespace.clone(),
+            polling_interval_ms: self.polling_interval_ms,
+            max_batches_to_dispatch: self.max_batches_to_dispatch,
+            retry_backoff_ms: self.retry_backoff_ms,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            mode: Some(proto::DataAvailabilityMode::new(&this.mode).into()),
+            celestia_api_node_url: this.celestia_api_node_url.clone(),
+            celestia_auth_token: this.celestia_auth_token.clone(),
+            celestia_namespace: this.celestia_namespace.clone(),
+            polling_interval_ms: this.polling_interval_ms,
+            max_batches_to_dispatch: this.max_batches_to_dispatch,
+            retry_backoff_ms: this.retry_backoff_ms,
+        }
+    }
+}
@@ -48,6 +48,14 @@ impl ProtoRepr for proto::MerkleTree {
             max_l1_batches_per_iter: required(&self.max_l1_batches_per_iter)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_l1_batches_per_iter")?,
+            archive_mode: self.archive_mode.unwrap_or(false),
+            archive_checkpoint_interval: *required(&self.archive_checkpoint_interval)
+                .context("archive_checkpoint_interval")?,
+            pruning_retained_versions: self.pruning_retained_versions,
+            concurrent_persistence: self.concurrent_persistence.unwrap_or(false),
+            checkpoint_path: self.checkpoint_path.clone(),
+            checkpoints_in_object_store: self.checkpoints_in_object_store.unwrap_or(false),
+            checkpoint_export_interval: self.checkpoint_export_interval,
         })
     }
 
@@ -60,6 +68,13 @@ impl ProtoRepr for proto::MerkleTree {
             memtable_capacity_mb: Some(this.memtable_capacity_mb.try_into().unwrap()),
             stalled_writes_timeout_sec: Some(this.stalled_writes_timeout_sec),
             max_l1_batches_per_iter: Some(this.max_l1_batches_per_iter.try_into().unwrap()),
+            archive_mode: Some(this.archive_mode),
+            archive_checkpoint_interval: Some(this.archive_checkpoint_interval),
+            pruning_retained_versions: this.pruning_retained_versions,
+            concurrent_persistence: Some(this.concurrent_persistence),
+            checkpoint_path: this.checkpoint_path.clone(),
+            checkpoints_in_object_store: Some(this.checkpoints_in_object_store),
+            checkpoint_export_interval: this.checkpoint_export_interval,
         }
     }
 }
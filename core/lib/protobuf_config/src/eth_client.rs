@@ -10,6 +10,12 @@ impl ProtoRepr for proto::EthClient {
         Ok(Self::Type {
             chain_id: *required(&self.chain_id).context("chain_id")?,
             web3_url: required(&self.web3_url).context("web3_url")?.clone(),
+            fallback_web3_urls: self.fallback_web3_urls.clone(),
+            read_quorum: self
+                .read_quorum
+                .map(|x| x.try_into())
+                .transpose()
+                .context("read_quorum")?,
         })
     }
 
@@ -17,6 +23,8 @@ impl ProtoRepr for proto::EthClient {
         Self {
             chain_id: Some(this.chain_id),
             web3_url: Some(this.web3_url.clone()),
+            fallback_web3_urls: this.fallback_web3_urls.clone(),
+            read_quorum: this.read_quorum.map(|x| x.try_into().unwrap()),
         }
     }
 }
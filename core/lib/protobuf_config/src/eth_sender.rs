@@ -60,12 +60,56 @@ impl proto::PubdataSendingMode {
     }
 }
 
+impl proto::ProofVerificationLayerMode {
+    fn new(x: &configs::eth_sender::ProofVerificationLayerMode) -> Self {
+        use configs::eth_sender::ProofVerificationLayerMode as From;
+        match x {
+            From::NewHorizen => Self::NewHorizen,
+            From::L1 => Self::L1,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::ProofVerificationLayerMode {
+        use configs::eth_sender::ProofVerificationLayerMode as To;
+        match self {
+            Self::NewHorizen => To::NewHorizen,
+            Self::L1 => To::L1,
+        }
+    }
+}
+
+impl proto::FeeBumpStrategyKind {
+    fn new(x: &configs::eth_sender::FeeBumpStrategyKind) -> Self {
+        use configs::eth_sender::FeeBumpStrategyKind as From;
+        match x {
+            From::Linear => Self::Linear,
+            From::Exponential => Self::Exponential,
+            From::DeadlineAware => Self::DeadlineAware,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::FeeBumpStrategyKind {
+        use configs::eth_sender::FeeBumpStrategyKind as To;
+        match self {
+            Self::Linear => To::Linear,
+            Self::Exponential => To::Exponential,
+            Self::DeadlineAware => To::DeadlineAware,
+        }
+    }
+}
+
 impl ProtoRepr for proto::EthSender {
     type Type = configs::eth_sender::ETHSenderConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
             sender: read_required_repr(&self.sender).context("sender")?,
             gas_adjuster: read_required_repr(&self.gas_adjuster).context("gas_adjuster")?,
+            fee_bump: self
+                .fee_bump
+                .as_ref()
+                .map(|x| x.read().context("fee_bump"))
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 
@@ -73,6 +117,7 @@ impl ProtoRepr for proto::EthSender {
         Self {
             sender: Some(ProtoRepr::build(&this.sender)),
             gas_adjuster: Some(ProtoRepr::build(&this.gas_adjuster)),
+            fee_bump: Some(ProtoRepr::build(&this.fee_bump)),
         }
     }
 }
@@ -128,6 +173,13 @@ impl ProtoRepr for proto::Sender {
                 .and_then(|x| Ok(proto::PubdataSendingMode::try_from(*x)?))
                 .context("pubdata_sending_mode")?
                 .parse(),
+            proof_verification_layer: self
+                .proof_verification_layer
+                .map(proto::ProofVerificationLayerMode::try_from)
+                .transpose()
+                .context("proof_verification_layer")?
+                .map(|x| x.parse())
+                .unwrap_or_default(),
         })
     }
 
@@ -159,6 +211,9 @@ impl ProtoRepr for proto::Sender {
             pubdata_sending_mode: Some(
                 proto::PubdataSendingMode::new(&this.pubdata_sending_mode).into(),
             ),
+            proof_verification_layer: Some(
+                proto::ProofVerificationLayerMode::new(&this.proof_verification_layer).into(),
+            ),
         }
     }
 }
@@ -214,3 +269,63 @@ impl ProtoRepr for proto::GasAdjuster {
         }
     }
 }
+
+impl ProtoRepr for proto::FeeBump {
+    type Type = configs::eth_sender::FeeBumpConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            commit: self
+                .commit
+                .as_ref()
+                .map(|x| x.read().context("commit"))
+                .transpose()?,
+            prove: self
+                .prove
+                .as_ref()
+                .map(|x| x.read().context("prove"))
+                .transpose()?,
+            execute: self
+                .execute
+                .as_ref()
+                .map(|x| x.read().context("execute"))
+                .transpose()?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            commit: this.commit.as_ref().map(ProtoRepr::build),
+            prove: this.prove.as_ref().map(ProtoRepr::build),
+            execute: this.execute.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
+
+impl ProtoRepr for proto::FeeBumpStrategy {
+    type Type = configs::eth_sender::FeeBumpStrategyConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            strategy: required(&self.strategy)
+                .and_then(|x| Ok(proto::FeeBumpStrategyKind::try_from(*x)?))
+                .context("strategy")?
+                .parse(),
+            priority_fee_increase_percent: *required(&self.priority_fee_increase_percent)
+                .context("priority_fee_increase_percent")?,
+            multiplier: *required(&self.multiplier).context("multiplier")?,
+            max_multiplier: *required(&self.max_multiplier).context("max_multiplier")?,
+            deadline_sec: self.deadline_sec,
+            abandon_after_sec: self.abandon_after_sec,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            strategy: Some(proto::FeeBumpStrategyKind::new(&this.strategy).into()),
+            priority_fee_increase_percent: Some(this.priority_fee_increase_percent),
+            multiplier: Some(this.multiplier),
+            max_multiplier: Some(this.max_multiplier),
+            deadline_sec: this.deadline_sec,
+            abandon_after_sec: this.abandon_after_sec,
+        }
+    }
+}
@@ -55,6 +55,8 @@ impl ProtoRepr for proto::HouseKeeper {
                 .fri_prover_job_archiver_reporting_interval_ms,
             fri_prover_job_archiver_archiving_interval_secs: self
                 .fri_prover_job_archiver_archiving_interval_secs,
+            object_store_gc_interval_ms: self.object_store_gc_interval_ms,
+            object_store_gc_retention_secs: self.object_store_gc_retention_secs,
         })
     }
 
@@ -91,6 +93,8 @@ impl ProtoRepr for proto::HouseKeeper {
                 .fri_prover_job_archiver_reporting_interval_ms,
             fri_prover_job_archiver_archiving_interval_secs: this
                 .fri_prover_job_archiver_archiving_interval_secs,
+            object_store_gc_interval_ms: this.object_store_gc_interval_ms,
+            object_store_gc_retention_secs: this.object_store_gc_retention_secs,
         }
     }
 }
@@ -9,6 +9,7 @@ mod api;
 mod chain;
 mod contract_verifier;
 mod contracts;
+mod da_dispatcher;
 mod database;
 mod eth_client;
 mod eth_sender;
@@ -24,6 +25,7 @@ mod new_horizen;
 mod object_store;
 mod observability;
 mod proof_data_handler;
+mod remote_signer;
 mod snapshots_creator;
 mod witness_generator;
 
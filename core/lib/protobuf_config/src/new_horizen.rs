@@ -1,9 +1,52 @@
 use anyhow::Context as _;
-use zksync_config::configs;
+use zksync_config::configs::{self, NhSignerConfig};
 use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::new_horizen as proto;
 
+impl proto::new_horizen::Signer {
+    fn read(&self) -> anyhow::Result<NhSignerConfig> {
+        Ok(match self {
+            Self::LocalSigner(signer) => NhSignerConfig::Local {
+                secret_uri: required(&signer.secret_uri).context("secret_uri")?.clone(),
+            },
+            Self::KeystoreSigner(signer) => NhSignerConfig::Keystore {
+                keystore_path: required(&signer.keystore_path)
+                    .context("keystore_path")?
+                    .clone(),
+                passphrase: required(&signer.passphrase).context("passphrase")?.clone(),
+            },
+            Self::JsonRpcSigner(signer) => NhSignerConfig::JsonRpc {
+                url: required(&signer.url).context("url")?.clone(),
+                account_id: required(&signer.account_id).context("account_id")?.clone(),
+            },
+        })
+    }
+
+    fn build(this: &NhSignerConfig) -> Self {
+        match this {
+            NhSignerConfig::Local { secret_uri } => {
+                Self::LocalSigner(proto::new_horizen::LocalSigner {
+                    secret_uri: Some(secret_uri.clone()),
+                })
+            }
+            NhSignerConfig::Keystore {
+                keystore_path,
+                passphrase,
+            } => Self::KeystoreSigner(proto::new_horizen::KeystoreSigner {
+                keystore_path: Some(keystore_path.clone()),
+                passphrase: Some(passphrase.clone()),
+            }),
+            NhSignerConfig::JsonRpc { url, account_id } => {
+                Self::JsonRpcSigner(proto::new_horizen::JsonRpcSigner {
+                    url: Some(url.clone()),
+                    account_id: Some(account_id.clone()),
+                })
+            }
+        }
+    }
+}
+
 impl ProtoRepr for proto::NewHorizen {
     type Type = configs::NewHorizenConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -11,16 +54,35 @@ impl ProtoRepr for proto::NewHorizen {
             url: required(&self.url)
                 .and_then(|x| Ok((*x.as_str()).try_into()?))
                 .context("url")?,
-            seed_phrase: required(&self.seed_phrase)
-                .and_then(|x| Ok((*x.as_str()).try_into()?))
-                .context("seed_phrase")?,
+            signer: self
+                .signer
+                .as_ref()
+                .map(proto::new_horizen::Signer::read)
+                .transpose()
+                .context("signer")?,
+            submission_polling_interval_ms: self.submission_polling_interval_ms,
+            submission_timeout_sec: self.submission_timeout_sec,
+            submission_max_attempts: self.submission_max_attempts,
+            submission_retry_backoff_ms: self.submission_retry_backoff_ms,
+            submission_batch_size: self.submission_batch_size,
+            max_attestation_delay_sec: self.max_attestation_delay_sec,
+            attestation_reconciliation_interval_sec: self.attestation_reconciliation_interval_sec,
+            attestation_reconciliation_block_range: self.attestation_reconciliation_block_range,
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             url: Some(this.url.as_str().into()),
-            seed_phrase: Some(this.seed_phrase.as_str().into()),
+            signer: this.signer.as_ref().map(proto::new_horizen::Signer::build),
+            submission_polling_interval_ms: this.submission_polling_interval_ms,
+            submission_timeout_sec: this.submission_timeout_sec,
+            submission_max_attempts: this.submission_max_attempts,
+            submission_retry_backoff_ms: this.submission_retry_backoff_ms,
+            submission_batch_size: this.submission_batch_size,
+            max_attestation_delay_sec: this.max_attestation_delay_sec,
+            attestation_reconciliation_interval_sec: this.attestation_reconciliation_interval_sec,
+            attestation_reconciliation_block_range: this.attestation_reconciliation_block_range,
         }
     }
 }
@@ -37,6 +37,25 @@ impl ProtoRepr for proto::ObjectStore {
                     .context("file_backed_base_path")?
                     .clone(),
             },
+            proto::object_store::Mode::S3(mode) => ObjectStoreMode::S3 {
+                s3_endpoint: required(&mode.endpoint).context("endpoint")?.clone(),
+                s3_region: required(&mode.region).context("region")?.clone(),
+                s3_bucket: required(&mode.bucket).context("bucket")?.clone(),
+                s3_access_key_id: required(&mode.access_key_id)
+                    .context("access_key_id")?
+                    .clone(),
+                s3_secret_access_key: required(&mode.secret_access_key)
+                    .context("secret_access_key")?
+                    .clone(),
+            },
+            proto::object_store::Mode::AzureBlob(mode) => ObjectStoreMode::AzureBlob {
+                azure_account_name: required(&mode.account_name)
+                    .context("account_name")?
+                    .clone(),
+                azure_container: required(&mode.container).context("container")?.clone(),
+                azure_access_key: required(&mode.access_key).context("access_key")?.clone(),
+                azure_endpoint: mode.endpoint.clone(),
+            },
         };
 
         Ok(Self::Type {
@@ -44,6 +63,10 @@ impl ProtoRepr for proto::ObjectStore {
             max_retries: required(&self.max_retries)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
+            compressed_buckets: self.compressed_buckets.clone(),
+            deduplicated_buckets: self.deduplicated_buckets.clone(),
+            local_cache_path: self.local_cache_path.clone(),
+            local_cache_max_size_bytes: self.local_cache_max_size_bytes,
         })
     }
 
@@ -75,11 +98,39 @@ impl ProtoRepr for proto::ObjectStore {
             } => proto::object_store::Mode::FileBacked(proto::object_store::FileBacked {
                 file_backed_base_path: Some(file_backed_base_path.clone()),
             }),
+            ObjectStoreMode::S3 {
+                s3_endpoint,
+                s3_region,
+                s3_bucket,
+                s3_access_key_id,
+                s3_secret_access_key,
+            } => proto::object_store::Mode::S3(proto::object_store::S3 {
+                endpoint: Some(s3_endpoint.clone()),
+                region: Some(s3_region.clone()),
+                bucket: Some(s3_bucket.clone()),
+                access_key_id: Some(s3_access_key_id.clone()),
+                secret_access_key: Some(s3_secret_access_key.clone()),
+            }),
+            ObjectStoreMode::AzureBlob {
+                azure_account_name,
+                azure_container,
+                azure_access_key,
+                azure_endpoint,
+            } => proto::object_store::Mode::AzureBlob(proto::object_store::AzureBlob {
+                account_name: Some(azure_account_name.clone()),
+                container: Some(azure_container.clone()),
+                access_key: Some(azure_access_key.clone()),
+                endpoint: azure_endpoint.clone(),
+            }),
         };
 
         Self {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
+            compressed_buckets: this.compressed_buckets.clone(),
+            deduplicated_buckets: this.deduplicated_buckets.clone(),
+            local_cache_path: this.local_cache_path.clone(),
+            local_cache_max_size_bytes: this.local_cache_max_size_bytes,
         }
     }
 }
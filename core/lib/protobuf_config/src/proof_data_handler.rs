@@ -4,6 +4,26 @@ use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::proof_data_handler as proto;
 
+impl proto::SkippedProofMode {
+    fn new(x: &configs::proof_data_handler::SkippedProofMode) -> Self {
+        use configs::proof_data_handler::SkippedProofMode as From;
+        match x {
+            From::Skip => Self::Skip,
+            From::FakeProof => Self::FakeProof,
+            From::Refuse => Self::Refuse,
+        }
+    }
+
+    fn parse(&self) -> configs::proof_data_handler::SkippedProofMode {
+        use configs::proof_data_handler::SkippedProofMode as To;
+        match self {
+            Self::Skip => To::Skip,
+            Self::FakeProof => To::FakeProof,
+            Self::Refuse => To::Refuse,
+        }
+    }
+}
+
 impl ProtoRepr for proto::ProofDataHandler {
     type Type = configs::ProofDataHandlerConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -14,6 +34,13 @@ impl ProtoRepr for proto::ProofDataHandler {
             proof_generation_timeout_in_secs: required(&self.proof_generation_timeout_in_secs)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("proof_generation_timeout_in_secs")?,
+            skipped_proof_mode: self
+                .skipped_proof_mode
+                .map(proto::SkippedProofMode::try_from)
+                .transpose()
+                .context("skipped_proof_mode")?
+                .map(|x| x.parse())
+                .unwrap_or_default(),
         })
     }
 
@@ -21,6 +48,7 @@ impl ProtoRepr for proto::ProofDataHandler {
         Self {
             http_port: Some(this.http_port.into()),
             proof_generation_timeout_in_secs: Some(this.proof_generation_timeout_in_secs.into()),
+            skipped_proof_mode: Some(proto::SkippedProofMode::new(&this.skipped_proof_mode).into()),
         }
     }
 }
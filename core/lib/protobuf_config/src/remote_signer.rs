@@ -0,0 +1,33 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::{parse_h160, proto::remote_signer as proto};
+
+impl ProtoRepr for proto::RemoteSigner {
+    type Type = configs::RemoteSignerConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            url: required(&self.url).context("url")?.clone(),
+            operator_address: required(&self.operator_address)
+                .and_then(|x| parse_h160(x))
+                .context("operator_address")?,
+            blobs_operator_address: self
+                .blobs_operator_address
+                .as_ref()
+                .map(|x| parse_h160(x))
+                .transpose()
+                .context("blobs_operator_address")?,
+            request_timeout_ms: self.request_timeout_ms,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            url: Some(this.url.clone()),
+            operator_address: Some(this.operator_address.as_bytes().into()),
+            blobs_operator_address: this.blobs_operator_address.map(|x| x.as_bytes().into()),
+            request_timeout_ms: this.request_timeout_ms,
+        }
+    }
+}
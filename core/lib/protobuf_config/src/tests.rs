@@ -20,6 +20,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::chain::CircuitBreaker>>(rng);
     test_encode_all_formats::<ReprConv<proto::contract_verifier::ContractVerifier>>(rng);
     test_encode_all_formats::<ReprConv<proto::contracts::Contracts>>(rng);
+    test_encode_all_formats::<ReprConv<proto::da_dispatcher::DaDispatcher>>(rng);
     test_encode_all_formats::<ReprConv<proto::database::MerkleTree>>(rng);
     test_encode_all_formats::<ReprConv<proto::database::Db>>(rng);
     test_encode_all_formats::<ReprConv<proto::database::Postgres>>(rng);
@@ -27,6 +28,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::eth_sender::EthSender>>(rng);
     test_encode_all_formats::<ReprConv<proto::eth_sender::Sender>>(rng);
     test_encode_all_formats::<ReprConv<proto::eth_sender::GasAdjuster>>(rng);
+    test_encode_all_formats::<ReprConv<proto::eth_sender::FeeBump>>(rng);
     test_encode_all_formats::<ReprConv<proto::eth_watch::EthWatch>>(rng);
     test_encode_all_formats::<ReprConv<proto::fri_proof_compressor::FriProofCompressor>>(rng);
     test_encode_all_formats::<ReprConv<proto::fri_prover::FriProver>>(rng);
@@ -39,6 +41,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::house_keeper::HouseKeeper>>(rng);
     test_encode_all_formats::<ReprConv<proto::object_store::ObjectStore>>(rng);
     test_encode_all_formats::<ReprConv<proto::proof_data_handler::ProofDataHandler>>(rng);
+    test_encode_all_formats::<ReprConv<proto::remote_signer::RemoteSigner>>(rng);
     test_encode_all_formats::<ReprConv<proto::snapshot_creator::SnapshotsCreator>>(rng);
     test_encode_all_formats::<ReprConv<proto::witness_generator::WitnessGenerator>>(rng);
     test_encode_all_formats::<ReprConv<proto::observability::Observability>>(rng);
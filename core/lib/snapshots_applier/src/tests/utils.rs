@@ -1,9 +1,11 @@
 //! Test utils.
 
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{collections::HashMap, fmt, ops::Range, sync::Arc};
 
 use async_trait::async_trait;
-use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError, ObjectStoreFactory};
+use zksync_object_store::{
+    Bucket, ListedObject, ObjectMetadata, ObjectStore, ObjectStoreError, ObjectStoreFactory,
+};
 use zksync_types::{
     api,
     snapshots::{
@@ -87,6 +89,33 @@ impl ObjectStore for ObjectStoreWithErrors {
         self.inner.get_raw(bucket, key).await
     }
 
+    async fn get_range_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        (self.validate_fn)(key)?;
+        self.inner.get_range_raw(bucket, key, range).await
+    }
+
+    async fn head_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectMetadata, ObjectStoreError> {
+        (self.validate_fn)(key)?;
+        self.inner.head_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<ListedObject>, ObjectStoreError> {
+        self.inner.list_raw(bucket, prefix).await
+    }
+
     async fn put_raw(
         &self,
         _bucket: Bucket,
@@ -8,6 +8,7 @@ use crate::cache::{
 #[derive(Debug, Clone)]
 pub struct LruCache<K: Eq + Hash, V> {
     name: &'static str,
+    capacity: u64,
     cache: Option<MokaBase<K, V>>,
 }
 
@@ -33,7 +34,16 @@ where
             )
         };
 
-        Self { name, cache }
+        Self {
+            name,
+            capacity,
+            cache,
+        }
+    }
+
+    /// Returns the capacity of this cache as specified during its creation.
+    pub(crate) fn capacity(&self) -> u64 {
+        self.capacity
     }
 
     /// Gets an entry and pulls it to the front if it exists.
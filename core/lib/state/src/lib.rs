@@ -20,6 +20,7 @@ use zksync_types::{
 mod cache;
 mod in_memory;
 mod postgres;
+mod recording;
 mod rocksdb;
 mod shadow_storage;
 mod storage_view;
@@ -31,6 +32,7 @@ pub use self::{
     cache::sequential_cache::SequentialCache,
     in_memory::{InMemoryStorage, IN_MEMORY_STORAGE_DEFAULT_NETWORK_ID},
     postgres::{PostgresStorage, PostgresStorageCaches, PostgresStorageCachesTask},
+    recording::{RecordingStorage, ReplayStorage, StorageAccess},
     rocksdb::{RocksdbStorage, RocksdbStorageBuilder, StateKeeperColumnFamily},
     shadow_storage::ShadowStorage,
     storage_view::{StorageView, StorageViewMetrics},
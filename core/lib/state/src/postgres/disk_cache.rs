@@ -0,0 +1,604 @@
+//! Disk-backed secondary tier for [`PostgresStorageCaches`](super::PostgresStorageCaches).
+
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_storage::{
+    db::{NamedColumnFamily, WriteBatch},
+    RocksDB,
+};
+use zksync_types::{AccountTreeId, Address, L1BatchNumber, MiniblockNumber, StorageKey, H256};
+
+use super::{
+    metrics::{DiskCacheKind, DISK_CACHE_METRICS},
+    TimestampedStorageValue,
+};
+
+/// Column families used by [`DiskCache`].
+#[derive(Debug, Clone, Copy)]
+pub(super) enum DiskCacheColumnFamily {
+    /// Cache metadata (currently, only the [`DiskCacheCheckpoint`]).
+    Meta,
+    /// Factory dependencies keyed by bytecode hash.
+    FactoryDeps,
+    /// L1 batch numbers of initial writes keyed by the storage key.
+    InitialWrites,
+    /// Storage values keyed by the hashed storage key.
+    Values,
+}
+
+impl NamedColumnFamily for DiskCacheColumnFamily {
+    const DB_NAME: &'static str = "vm_storage_cache";
+    const ALL: &'static [Self] = &[
+        Self::Meta,
+        Self::FactoryDeps,
+        Self::InitialWrites,
+        Self::Values,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Meta => "meta",
+            Self::FactoryDeps => "factory_deps",
+            Self::InitialWrites => "initial_writes",
+            Self::Values => "values",
+        }
+    }
+}
+
+/// Miniblock that the disk cache is synchronized with.
+///
+/// Cached values are valid for `miniblock`. Cached initial writes are only persisted for L1 batches
+/// preceding `l1_batch_bound`; all such L1 batches contain only miniblocks preceding `miniblock`. Thus, if `miniblock`
+/// is present in Postgres with the same hash, all cached data is known not to be affected by a revert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct DiskCacheCheckpoint {
+    miniblock: MiniblockNumber,
+    miniblock_hash: H256,
+    l1_batch_bound: L1BatchNumber,
+}
+
+impl DiskCacheCheckpoint {
+    const SERIALIZED_LEN: usize = 40;
+
+    /// Loads the checkpoint for the specified miniblock from Postgres. Returns `None` if the miniblock
+    /// is not present in Postgres (e.g., if it was reverted, or if it's the snapshot miniblock).
+    pub(super) async fn load(
+        connection: &mut Connection<'_, Core>,
+        miniblock: MiniblockNumber,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(header) = connection
+            .blocks_dal()
+            .get_miniblock_header(miniblock)
+            .await
+            .with_context(|| format!("failed getting header for miniblock #{miniblock}"))?
+        else {
+            return Ok(None);
+        };
+        let resolved = connection
+            .storage_web3_dal()
+            .resolve_l1_batch_number_of_miniblock(miniblock)
+            .await
+            .with_context(|| {
+                format!("failed resolving L1 batch number for miniblock #{miniblock}")
+            })?;
+        Ok(Some(Self {
+            miniblock,
+            miniblock_hash: header.hash,
+            l1_batch_bound: resolved.expected_l1_batch(),
+        }))
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(Self::SERIALIZED_LEN);
+        buffer.extend_from_slice(&self.miniblock.0.to_be_bytes());
+        buffer.extend_from_slice(self.miniblock_hash.as_bytes());
+        buffer.extend_from_slice(&self.l1_batch_bound.0.to_be_bytes());
+        buffer
+    }
+
+    fn deserialize(bytes: &[u8]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            bytes.len() == Self::SERIALIZED_LEN,
+            "unexpected disk cache checkpoint length: {}",
+            bytes.len()
+        );
+        Ok(Self {
+            miniblock: MiniblockNumber(u32::from_be_bytes(bytes[..4].try_into().unwrap())),
+            miniblock_hash: H256::from_slice(&bytes[4..36]),
+            l1_batch_bound: L1BatchNumber(u32::from_be_bytes(bytes[36..].try_into().unwrap())),
+        })
+    }
+}
+
+/// RocksDB-backed cache for data stored in [`PostgresStorageCaches`](super::PostgresStorageCaches).
+///
+/// Factory dependencies are content-addressable and are never invalidated. Initial writes and values
+/// are tied to a [`DiskCacheCheckpoint`], which is advanced together with the storage values cache,
+/// and are invalidated on startup if a revert is detected.
+///
+/// Inserted data is not written to RocksDB immediately; instead, it's accumulated and written in batches.
+/// Pending writes are also persisted together with checkpoint updates and when the cache is dropped.
+///
+/// The cache size is bounded. Once the estimated size exceeds the limit, entire kinds of cached data
+/// are evicted (values first, since they are invalidated most often; then initial writes and factory deps),
+/// and the freed space is reclaimed by compaction.
+///
+/// Read and write errors are logged and otherwise ignored (i.e., a read error is treated as a cache miss).
+/// Errors updating the checkpoint are propagated since they could lead to stale values being served.
+#[derive(Debug, Clone)]
+pub(super) struct DiskCache {
+    db: RocksDB<DiskCacheColumnFamily>,
+    /// Exclusive upper bound for L1 batch numbers of initial writes that can be persisted.
+    /// Mirrors [`DiskCacheCheckpoint::l1_batch_bound`] of the persisted checkpoint.
+    l1_batch_bound: Arc<AtomicU32>,
+    /// Miniblock that cached values are valid for. Values loaded for earlier miniblocks are not persisted.
+    values_valid_for: Arc<AtomicU32>,
+    pending_writes: Arc<Mutex<PendingWrites>>,
+    /// Maximum estimated size of the cache in bytes.
+    max_size: u64,
+    /// Prevents concurrent evictions, which could evict more data than necessary.
+    eviction_lock: Arc<Mutex<()>>,
+}
+
+impl DiskCache {
+    const CHECKPOINT_KEY: &'static [u8] = b"checkpoint";
+    const MIN_KEY: &'static [u8] = &[];
+    /// Exclusive upper bound for all keys in the DB (the longest key is 52 bytes long).
+    const MAX_KEY: &'static [u8] = &[0xff; 64];
+    /// Maximum number of miniblocks for which modified keys are loaded from Postgres on startup.
+    /// If the cache is further behind, cached values are dropped.
+    const MAX_MINIBLOCKS_TO_RECONCILE: u32 = 1_000;
+    /// Number of pending writes after which they are written to RocksDB.
+    const WRITE_BATCH_SIZE: usize = 1_024;
+    /// Order in which kinds of cached data are evicted if the cache exceeds its size limit.
+    const EVICTION_ORDER: [DiskCacheKind; 3] = [
+        DiskCacheKind::Values,
+        DiskCacheKind::InitialWrites,
+        DiskCacheKind::FactoryDeps,
+    ];
+
+    /// Opens the cache at the specified path. `max_size` is the maximum estimated size of the cache in bytes.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    pub(super) fn open(path: &Path, max_size: u64) -> anyhow::Result<Self> {
+        let db = RocksDB::new(path).with_context(|| {
+            format!(
+                "failed opening VM storage disk cache at `{}`",
+                path.display()
+            )
+        })?;
+        let pending_writes = PendingWrites {
+            db: db.clone(),
+            entries: Vec::with_capacity(Self::WRITE_BATCH_SIZE),
+        };
+        let this = Self {
+            db,
+            l1_batch_bound: Arc::default(),
+            values_valid_for: Arc::default(),
+            pending_writes: Arc::new(Mutex::new(pending_writes)),
+            max_size,
+            eviction_lock: Arc::default(),
+        };
+        if let Some(checkpoint) = this.checkpoint()? {
+            this.l1_batch_bound
+                .store(checkpoint.l1_batch_bound.0, Ordering::SeqCst);
+            this.values_valid_for
+                .store(checkpoint.miniblock.0, Ordering::SeqCst);
+        }
+        Ok(this)
+    }
+
+    pub(super) fn checkpoint(&self) -> anyhow::Result<Option<DiskCacheCheckpoint>> {
+        let raw = self
+            .db
+            .get_cf(DiskCacheColumnFamily::Meta, Self::CHECKPOINT_KEY)
+            .context("failed reading disk cache checkpoint")?;
+        raw.as_deref()
+            .map(DiskCacheCheckpoint::deserialize)
+            .transpose()
+    }
+
+    /// Brings the cache in sync with the latest sealed miniblock in Postgres. Returns the miniblock that the cached
+    /// values are valid for.
+    ///
+    /// This method is blocking in part and should be called on startup.
+    pub(super) async fn reconcile(
+        &self,
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<MiniblockNumber> {
+        let latency = DISK_CACHE_METRICS.reconcile_latency.start();
+        let latest_miniblock = connection
+            .blocks_dal()
+            .get_sealed_miniblock_number()
+            .await
+            .context("failed getting sealed miniblock number")?
+            .unwrap_or(MiniblockNumber(0));
+        let latest_checkpoint = DiskCacheCheckpoint::load(connection, latest_miniblock).await?;
+
+        let stored_checkpoint = self.checkpoint()?;
+        let current_checkpoint = if let Some(stored) = &stored_checkpoint {
+            DiskCacheCheckpoint::load(connection, stored.miniblock).await?
+        } else {
+            None
+        };
+        let consistent_miniblock = match (&stored_checkpoint, &current_checkpoint) {
+            (Some(stored), Some(current)) if stored.miniblock_hash == current.miniblock_hash => {
+                Some(stored.miniblock)
+            }
+            _ => None,
+        };
+
+        let modified_keys = if let Some(stored_miniblock) = consistent_miniblock {
+            let lag = latest_miniblock.0.saturating_sub(stored_miniblock.0);
+            if lag > Self::MAX_MINIBLOCKS_TO_RECONCILE {
+                tracing::info!(
+                    "VM storage disk cache is too far behind (cached miniblock is {stored_miniblock}; \
+                     latest miniblock is {latest_miniblock}); dropping cached values"
+                );
+                None
+            } else {
+                let miniblocks = (stored_miniblock + 1)..=latest_miniblock;
+                let modified_keys = connection
+                    .storage_logs_dal()
+                    .modified_keys_in_miniblocks(miniblocks.clone())
+                    .await
+                    .with_context(|| {
+                        format!("failed loading modified keys for miniblocks {miniblocks:?}")
+                    })?;
+                Some(modified_keys)
+            }
+        } else {
+            if stored_checkpoint.is_some() {
+                tracing::info!(
+                    "VM storage disk cache checkpoint {stored_checkpoint:?} is inconsistent with Postgres \
+                     (current: {current_checkpoint:?}); dropping cached initial writes and values"
+                );
+            }
+            self.clear_initial_writes()?;
+            None
+        };
+
+        if let Some(modified_keys) = modified_keys {
+            tracing::info!(
+                "Removing {} keys modified since the VM storage disk cache checkpoint",
+                modified_keys.len()
+            );
+            self.advance(latest_miniblock, latest_checkpoint.as_ref(), &modified_keys)?;
+        } else {
+            self.clear_values(latest_miniblock, latest_checkpoint.as_ref())?;
+        }
+        let elapsed = latency.observe();
+        tracing::info!(
+            "Reconciled VM storage disk cache with Postgres in {elapsed:?}; the cache is valid for miniblock #{latest_miniblock}"
+        );
+        Ok(latest_miniblock)
+    }
+
+    /// Removes `modified_keys` from the cached values and updates the checkpoint, so that cached values are valid
+    /// for `miniblock`. Once this method is called, values loaded for earlier miniblocks are no longer persisted.
+    ///
+    /// `checkpoint` may be `None` if the target miniblock is not present in Postgres (e.g., it's the snapshot miniblock).
+    /// In this case, the checkpoint is removed so that the cached data is not trusted after a restart.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    pub(super) fn advance(
+        &self,
+        miniblock: MiniblockNumber,
+        checkpoint: Option<&DiskCacheCheckpoint>,
+        modified_keys: &[H256],
+    ) -> anyhow::Result<()> {
+        let mut pending_writes = self.lock_pending_writes();
+        // Updated while holding the pending writes lock, so that stale values cannot be inserted after the modified keys
+        // are removed.
+        self.values_valid_for.store(miniblock.0, Ordering::SeqCst);
+        let mut batch = self.db.new_write_batch();
+        // Pending writes must precede removing modified keys in the batch; otherwise, stale values could be persisted.
+        pending_writes.move_to_batch(&mut batch);
+        for key in modified_keys {
+            batch.delete_cf(DiskCacheColumnFamily::Values, key.as_bytes());
+        }
+        self.write_checkpoint(batch, checkpoint)
+    }
+
+    /// Removes all cached values and updates the checkpoint, so that cached values are valid for `miniblock`.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    pub(super) fn clear_values(
+        &self,
+        miniblock: MiniblockNumber,
+        checkpoint: Option<&DiskCacheCheckpoint>,
+    ) -> anyhow::Result<()> {
+        let mut pending_writes = self.lock_pending_writes();
+        self.values_valid_for.store(miniblock.0, Ordering::SeqCst);
+        let mut batch = self.db.new_write_batch();
+        pending_writes.move_to_batch(&mut batch);
+        batch.delete_range_cf(DiskCacheColumnFamily::Values, Self::MIN_KEY..Self::MAX_KEY);
+        self.write_checkpoint(batch, checkpoint)?;
+        DISK_CACHE_METRICS.values_emptied.inc();
+        Ok(())
+    }
+
+    fn clear_initial_writes(&self) -> anyhow::Result<()> {
+        let mut batch = self.db.new_write_batch();
+        batch.delete_range_cf(
+            DiskCacheColumnFamily::InitialWrites,
+            Self::MIN_KEY..Self::MAX_KEY,
+        );
+        batch.delete_cf(DiskCacheColumnFamily::Meta, Self::CHECKPOINT_KEY);
+        self.db
+            .write(batch)
+            .context("failed clearing initial writes in VM storage disk cache")?;
+        self.l1_batch_bound.store(0, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn write_checkpoint(
+        &self,
+        mut batch: WriteBatch<'_, DiskCacheColumnFamily>,
+        checkpoint: Option<&DiskCacheCheckpoint>,
+    ) -> anyhow::Result<()> {
+        if let Some(checkpoint) = checkpoint {
+            batch.put_cf(
+                DiskCacheColumnFamily::Meta,
+                Self::CHECKPOINT_KEY,
+                &checkpoint.serialize(),
+            );
+        } else {
+            batch.delete_cf(DiskCacheColumnFamily::Meta, Self::CHECKPOINT_KEY);
+        }
+        self.db
+            .write(batch)
+            .context("failed updating VM storage disk cache checkpoint")?;
+
+        // The bound must be updated *after* the checkpoint is persisted; otherwise, we could persist initial writes
+        // that are not covered by the persisted checkpoint.
+        let bound = checkpoint.map_or(L1BatchNumber(0), |checkpoint| checkpoint.l1_batch_bound);
+        self.l1_batch_bound.store(bound.0, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn get(&self, kind: DiskCacheKind, key: &[u8]) -> Option<Vec<u8>> {
+        let cf = kind.column_family();
+        let value = self.db.get_cf(cf, key).unwrap_or_else(|err| {
+            tracing::warn!("Failed reading from VM storage disk cache ({cf:?}): {err}");
+            DISK_CACHE_METRICS.errors.inc();
+            None
+        });
+        DISK_CACHE_METRICS.report_request(kind, value.is_some());
+        value
+    }
+
+    fn lock_pending_writes(&self) -> MutexGuard<'_, PendingWrites> {
+        self.pending_writes
+            .lock()
+            .expect("pending VM storage disk cache writes are poisoned")
+    }
+
+    fn put(&self, kind: DiskCacheKind, key: Vec<u8>, value: Vec<u8>) {
+        self.push_pending_write(self.lock_pending_writes(), kind, key, value);
+    }
+
+    fn push_pending_write(
+        &self,
+        mut pending_writes: MutexGuard<'_, PendingWrites>,
+        kind: DiskCacheKind,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) {
+        pending_writes
+            .entries
+            .push((kind.column_family(), key, value));
+        if pending_writes.entries.len() < Self::WRITE_BATCH_SIZE {
+            return;
+        }
+        pending_writes.flush();
+        drop(pending_writes);
+        self.enforce_size_limit();
+    }
+
+    fn estimated_size(&self) -> u64 {
+        DiskCacheColumnFamily::ALL
+            .iter()
+            .map(|&cf| self.db.estimated_size(cf))
+            .sum()
+    }
+
+    /// Evicts cached data if the estimated cache size exceeds the limit.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    pub(super) fn enforce_size_limit(&self) {
+        let Ok(_guard) = self.eviction_lock.try_lock() else {
+            return; // Another thread is evicting data
+        };
+        let mut size = self.estimated_size();
+        for kind in Self::EVICTION_ORDER {
+            if size <= self.max_size {
+                break;
+            }
+            tracing::info!(
+                "VM storage disk cache size {size}B exceeds the limit {}B; evicting {kind:?}",
+                self.max_size
+            );
+            let cf = kind.column_family();
+            let mut batch = self.db.new_write_batch();
+            batch.delete_range_cf(cf, Self::MIN_KEY..Self::MAX_KEY);
+            if let Err(err) = self.db.write(batch) {
+                tracing::warn!("Failed evicting data from VM storage disk cache ({cf:?}): {err}");
+                DISK_CACHE_METRICS.errors.inc();
+                return;
+            }
+            self.db.compact_range_cf(cf, Self::MIN_KEY..Self::MAX_KEY);
+            DISK_CACHE_METRICS.evictions[&kind].inc();
+            size = self.estimated_size();
+        }
+        DISK_CACHE_METRICS.size.set(size);
+    }
+
+    pub(super) fn factory_dep(&self, hash: H256) -> Option<Vec<u8>> {
+        self.get(DiskCacheKind::FactoryDeps, hash.as_bytes())
+    }
+
+    pub(super) fn insert_factory_dep(&self, hash: H256, bytecode: &[u8]) {
+        self.put(
+            DiskCacheKind::FactoryDeps,
+            hash.as_bytes().to_vec(),
+            bytecode.to_vec(),
+        );
+    }
+
+    pub(super) fn initial_write(&self, key: &StorageKey) -> Option<L1BatchNumber> {
+        let raw = self.get(DiskCacheKind::InitialWrites, &serialize_storage_key(key))?;
+        deserialize_l1_batch_number(&raw)
+    }
+
+    pub(super) fn insert_initial_write(&self, key: &StorageKey, l1_batch_number: L1BatchNumber) {
+        if l1_batch_number.0 >= self.l1_batch_bound.load(Ordering::SeqCst) {
+            // The L1 batch is not covered by the checkpoint, so it could be reverted without us noticing.
+            return;
+        }
+        self.put(
+            DiskCacheKind::InitialWrites,
+            serialize_storage_key(key),
+            l1_batch_number.0.to_be_bytes().to_vec(),
+        );
+    }
+
+    /// Gets a cached value. The caller is responsible for checking that the cache is valid for the requested miniblock.
+    pub(super) fn value(&self, hashed_key: &H256) -> Option<TimestampedStorageValue> {
+        let raw = self.get(DiskCacheKind::Values, hashed_key.as_bytes())?;
+        deserialize_timestamped_value(&raw)
+    }
+
+    /// Caches a value. The value is skipped if it was loaded for a miniblock preceding the one cached values
+    /// are valid for (i.e., if the cache was advanced concurrently).
+    pub(super) fn insert_value(&self, hashed_key: &H256, value: &TimestampedStorageValue) {
+        let mut raw = Vec::with_capacity(36);
+        raw.extend_from_slice(value.value.as_bytes());
+        raw.extend_from_slice(&value.loaded_at.0.to_be_bytes());
+
+        let pending_writes = self.lock_pending_writes();
+        if value.loaded_at.0 < self.values_valid_for.load(Ordering::SeqCst) {
+            return;
+        }
+        let key = hashed_key.as_bytes().to_vec();
+        self.push_pending_write(pending_writes, DiskCacheKind::Values, key, raw);
+    }
+
+    /// Iterates over all cached factory dependencies.
+    pub(super) fn factory_deps(&self) -> impl Iterator<Item = (H256, Vec<u8>)> + '_ {
+        self.db
+            .from_iterator_cf(DiskCacheColumnFamily::FactoryDeps, Self::MIN_KEY)
+            .filter_map(|(key, value)| {
+                let hash = (key.len() == 32).then(|| H256::from_slice(&key))?;
+                Some((hash, value.into_vec()))
+            })
+    }
+
+    /// Iterates over all cached initial writes.
+    pub(super) fn initial_writes(&self) -> impl Iterator<Item = (StorageKey, L1BatchNumber)> + '_ {
+        self.db
+            .from_iterator_cf(DiskCacheColumnFamily::InitialWrites, Self::MIN_KEY)
+            .filter_map(|(key, value)| {
+                Some((
+                    deserialize_storage_key(&key)?,
+                    deserialize_l1_batch_number(&value)?,
+                ))
+            })
+    }
+
+    /// Iterates over all cached values.
+    pub(super) fn values(&self) -> impl Iterator<Item = (H256, TimestampedStorageValue)> + '_ {
+        self.db
+            .from_iterator_cf(DiskCacheColumnFamily::Values, Self::MIN_KEY)
+            .filter_map(|(key, value)| {
+                let hashed_key = (key.len() == 32).then(|| H256::from_slice(&key))?;
+                Some((hashed_key, deserialize_timestamped_value(&value)?))
+            })
+    }
+}
+
+/// Writes to [`DiskCache`] that are not yet persisted in RocksDB. Flushed when dropped.
+#[derive(Debug)]
+struct PendingWrites {
+    db: RocksDB<DiskCacheColumnFamily>,
+    entries: Vec<(DiskCacheColumnFamily, Vec<u8>, Vec<u8>)>,
+}
+
+impl PendingWrites {
+    fn move_to_batch(&mut self, batch: &mut WriteBatch<'_, DiskCacheColumnFamily>) {
+        for (cf, key, value) in self.entries.drain(..) {
+            batch.put_cf(cf, &key, &value);
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let mut batch = self.db.new_write_batch();
+        for (cf, key, value) in self.entries.drain(..) {
+            batch.put_cf(cf, &key, &value);
+        }
+        if let Err(err) = self.db.write(batch) {
+            tracing::warn!("Failed writing to VM storage disk cache: {err}");
+            DISK_CACHE_METRICS.errors.inc();
+        }
+    }
+}
+
+impl Drop for PendingWrites {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl DiskCacheKind {
+    fn column_family(self) -> DiskCacheColumnFamily {
+        match self {
+            Self::FactoryDeps => DiskCacheColumnFamily::FactoryDeps,
+            Self::InitialWrites => DiskCacheColumnFamily::InitialWrites,
+            Self::Values => DiskCacheColumnFamily::Values,
+        }
+    }
+}
+
+fn serialize_storage_key(key: &StorageKey) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(52);
+    buffer.extend_from_slice(key.address().as_bytes());
+    buffer.extend_from_slice(key.key().as_bytes());
+    buffer
+}
+
+fn deserialize_storage_key(bytes: &[u8]) -> Option<StorageKey> {
+    if bytes.len() != 52 {
+        return None;
+    }
+    let address = Address::from_slice(&bytes[..20]);
+    Some(StorageKey::new(
+        AccountTreeId::new(address),
+        H256::from_slice(&bytes[20..]),
+    ))
+}
+
+fn deserialize_l1_batch_number(bytes: &[u8]) -> Option<L1BatchNumber> {
+    let bytes: [u8; 4] = bytes.try_into().ok()?;
+    Some(L1BatchNumber(u32::from_be_bytes(bytes)))
+}
+
+fn deserialize_timestamped_value(bytes: &[u8]) -> Option<TimestampedStorageValue> {
+    if bytes.len() != 36 {
+        return None;
+    }
+    Some(TimestampedStorageValue {
+        value: H256::from_slice(&bytes[..32]),
+        loaded_at: MiniblockNumber(u32::from_be_bytes(bytes[32..].try_into().unwrap())),
+    })
+}
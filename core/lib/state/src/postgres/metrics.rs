@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -52,3 +55,55 @@ pub(super) struct PostgresStorageMetrics {
 
 #[vise::register]
 pub(super) static STORAGE_METRICS: vise::Global<PostgresStorageMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum DiskCacheKind {
+    FactoryDeps,
+    InitialWrites,
+    Values,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum DiskCacheOutcome {
+    Hit,
+    Miss,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_disk_cache")]
+pub(super) struct DiskCacheMetrics {
+    /// Number of hits / misses for the disk cache.
+    #[metrics(labels = ["kind", "outcome"])]
+    pub requests: LabeledFamily<(DiskCacheKind, DiskCacheOutcome), Counter, 2>,
+    /// Number of entries loaded from the disk cache into the in-memory caches on startup.
+    #[metrics(labels = ["kind"])]
+    pub warmed_up_entries: LabeledFamily<DiskCacheKind, Gauge<usize>>,
+    /// Number of RocksDB errors encountered when reading from / writing to the disk cache.
+    pub errors: Counter,
+    /// Number of times cached values were dropped because the cache was too far behind or inconsistent.
+    pub values_emptied: Counter,
+    /// Estimated size of the disk cache in bytes.
+    pub size: Gauge<u64>,
+    /// Number of times cached data of a certain kind was evicted because the cache exceeded its size limit.
+    #[metrics(labels = ["kind"])]
+    pub evictions: LabeledFamily<DiskCacheKind, Counter>,
+    /// Latency of reconciling the disk cache with Postgres on startup.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub reconcile_latency: Histogram<Duration>,
+}
+
+impl DiskCacheMetrics {
+    pub fn report_request(&self, kind: DiskCacheKind, hit: bool) {
+        let outcome = if hit {
+            DiskCacheOutcome::Hit
+        } else {
+            DiskCacheOutcome::Miss
+        };
+        self.requests[&(kind, outcome)].inc();
+    }
+}
+
+#[vise::register]
+pub(super) static DISK_CACHE_METRICS: vise::Global<DiskCacheMetrics> = vise::Global::new();
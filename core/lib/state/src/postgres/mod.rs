@@ -1,5 +1,6 @@
 use std::{
     mem,
+    path::PathBuf,
     sync::{Arc, RwLock},
 };
 
@@ -14,12 +15,19 @@ use tokio::{
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_types::{L1BatchNumber, MiniblockNumber, StorageKey, StorageValue, H256};
 
-use self::metrics::{Method, ValuesUpdateStage, CACHE_METRICS, STORAGE_METRICS};
+use self::{
+    disk_cache::{DiskCache, DiskCacheCheckpoint},
+    metrics::{
+        DiskCacheKind, Method, ValuesUpdateStage, CACHE_METRICS, DISK_CACHE_METRICS,
+        STORAGE_METRICS,
+    },
+};
 use crate::{
     cache::{lru_cache::LruCache, CacheValue},
     ReadStorage,
 };
 
+mod disk_cache;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -77,6 +85,8 @@ struct ValuesCacheInner {
     /// be taken into account).
     valid_for: MiniblockNumber,
     values: LruCache<H256, TimestampedStorageValue>,
+    /// Secondary disk-backed tier. If present, it's always valid for the same miniblock as `values`.
+    disk: Option<DiskCache>,
 }
 
 /// Cache for the VM storage. Only caches values for a single VM storage snapshot, which logically
@@ -91,21 +101,52 @@ struct ValuesCacheInner {
 /// loading or storing values in it. This is easiest to achieve using an `RwLock`. Note that
 /// almost all cache ops require only shared access to the lock (including cache updates!); we only
 /// need exclusive access when we are updating the `valid_for` miniblock. Further, the update itself
-/// doesn't grab the lock until *after* the Postgres data has been loaded. (This works because we
+/// doesn't grab the lock until *after* the Postgres data has been loaded and the disk tier (if any) has been updated. (This works because we
 /// know statically that there is a single thread updating the cache; hence, we have no contention
 /// over updating the cache.) To summarize, `RwLock` should see barely any contention.
 #[derive(Debug, Clone)]
 struct ValuesCache(Arc<RwLock<ValuesCacheInner>>);
 
 impl ValuesCache {
-    fn new(capacity: u64) -> Self {
+    fn new(capacity: u64, disk: Option<(DiskCache, MiniblockNumber)>) -> Self {
+        let (disk, valid_for) = match disk {
+            Some((disk, valid_for)) => (Some(disk), valid_for),
+            None => (None, MiniblockNumber(0)),
+        };
         let inner = ValuesCacheInner {
-            valid_for: MiniblockNumber(0),
+            valid_for,
             values: LruCache::new("values_cache", capacity),
+            disk,
         };
         Self(Arc::new(RwLock::new(inner)))
     }
 
+    /// Loads values from the disk cache (if any) into memory until the in-memory cache is full.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    fn warm_up(&self) {
+        let lock = self.0.read().expect("values cache is poisoned");
+        let Some(disk) = &lock.disk else {
+            return;
+        };
+        let capacity = lock.values.capacity();
+        let mut total_weight = 0_u64;
+        let mut entry_count = 0;
+        for (hashed_key, value) in disk.values() {
+            total_weight += u64::from(value.cache_weight());
+            if total_weight > capacity {
+                break;
+            }
+            lock.values.insert(hashed_key, value);
+            entry_count += 1;
+        }
+        DISK_CACHE_METRICS.warmed_up_entries[&DiskCacheKind::Values].set(entry_count);
+        tracing::info!(
+            "Warmed up storage values cache with {entry_count} entries from disk; cache is valid for miniblock #{}",
+            lock.valid_for
+        );
+    }
+
     /// *NB.* The returned value should be considered immediately stale; at best, it can be
     /// the lower boundary on the current `valid_for` value.
     fn valid_for(&self) -> MiniblockNumber {
@@ -122,7 +163,13 @@ impl ValuesCache {
             return None;
         }
 
-        let timestamped_value = lock.values.get(&key.hashed_key())?;
+        let hashed_key = key.hashed_key();
+        let timestamped_value = lock.values.get(&hashed_key).or_else(|| {
+            // The disk cache is valid for the same miniblock as the in-memory one, so it can be used as a fallback.
+            let value = lock.disk.as_ref()?.value(&hashed_key)?;
+            lock.values.insert(hashed_key, value);
+            Some(value)
+        })?;
         if timestamped_value.loaded_at <= miniblock_number {
             Some(timestamped_value.value)
         } else {
@@ -134,13 +181,15 @@ impl ValuesCache {
     fn insert(&self, miniblock_number: MiniblockNumber, key: StorageKey, value: StorageValue) {
         let lock = self.0.read().expect("values cache is poisoned");
         if lock.valid_for == miniblock_number {
-            lock.values.insert(
-                key.hashed_key(),
-                TimestampedStorageValue {
-                    value,
-                    loaded_at: miniblock_number,
-                },
-            );
+            let hashed_key = key.hashed_key();
+            let value = TimestampedStorageValue {
+                value,
+                loaded_at: miniblock_number,
+            };
+            if let Some(disk) = &lock.disk {
+                disk.insert_value(&hashed_key, &value);
+            }
+            lock.values.insert(hashed_key, value);
         } else {
             CACHE_METRICS.stale_values.inc();
         }
@@ -158,6 +207,18 @@ impl ValuesCache {
             "Updating storage values cache from miniblock {from_miniblock} to {to_miniblock}"
         );
 
+        let disk = self
+            .0
+            .read()
+            .map_err(|_| anyhow::anyhow!("values cache is poisoned"))?
+            .disk
+            .clone();
+        let disk_checkpoint = if disk.is_some() {
+            DiskCacheCheckpoint::load(connection, to_miniblock).await?
+        } else {
+            None
+        };
+
         if to_miniblock.0 - from_miniblock.0 > MAX_MINIBLOCKS_LAG {
             // We can spend too much time loading data from Postgres, so we opt for an easier "update" route:
             // evict *everything* from cache and call it a day. This should not happen too often in practice.
@@ -165,6 +226,15 @@ impl ValuesCache {
                 "Storage values cache is too far behind (current miniblock is {from_miniblock}; \
                  requested update to {to_miniblock}); resetting the cache"
             );
+            if let Some(disk) = disk {
+                // RocksDB writes are performed before taking the write lock. Until the lock is taken, the disk tier
+                // only contains values valid both for `from_miniblock` and `to_miniblock`, so it's safe to read from it.
+                tokio::task::spawn_blocking(move || {
+                    disk.clear_values(to_miniblock, disk_checkpoint.as_ref())
+                })
+                .await
+                .context("panicked clearing values in VM storage disk cache")??;
+            }
             let mut lock = self
                 .0
                 .write()
@@ -202,13 +272,24 @@ impl ValuesCache {
 
             let update_latency =
                 CACHE_METRICS.values_update[&ValuesUpdateStage::RemoveStaleKeys].start();
+            let modified_keys = if let Some(disk) = disk {
+                // See the comment above on why it's safe to write to RocksDB before taking the write lock.
+                tokio::task::spawn_blocking(move || {
+                    disk.advance(to_miniblock, disk_checkpoint.as_ref(), &modified_keys)?;
+                    anyhow::Ok(modified_keys)
+                })
+                .await
+                .context("panicked advancing VM storage disk cache")??
+            } else {
+                modified_keys
+            };
             let mut lock = self
                 .0
                 .write()
                 .map_err(|_| anyhow::anyhow!("values cache is poisoned"))?;
             // The code below holding onto the write `lock` is the only code that can theoretically poison the `RwLock`
             // (other than emptying the cache above). Thus, it's kept as simple and tight as possible.
-            // E.g., we load data from Postgres beforehand.
+            // E.g., we load data from Postgres and update the disk tier beforehand.
             anyhow::ensure!(
                 lock.valid_for == from_miniblock,
                 "sanity check failed: values cache was expected to be valid for miniblock #{from_miniblock}, but it's actually \
@@ -245,6 +326,9 @@ struct ValuesCacheAndUpdater {
 /// - Cache for L1 batch numbers of initial writes for storage keys (never invalidated, except after
 ///   reverting L1 batch execution)
 /// - Cache of the VM storage snapshot corresponding to the latest sealed miniblock
+///
+/// Optionally, the caches can be backed by a secondary disk tier (see [`Self::configure_disk_cache()`]),
+/// which persists cached data across restarts.
 #[derive(Debug, Clone)]
 pub struct PostgresStorageCaches {
     factory_deps: FactoryDepsCache,
@@ -258,6 +342,8 @@ pub struct PostgresStorageCaches {
     // it wasn't written to at the point that interests us.
     negative_initial_writes: InitialWritesCache,
     values: Option<ValuesCacheAndUpdater>,
+    /// Secondary disk tier together with the miniblock its values are valid for.
+    disk: Option<(DiskCache, MiniblockNumber)>,
 }
 
 impl PostgresStorageCaches {
@@ -281,9 +367,119 @@ impl PostgresStorageCaches {
                 initial_writes_capacity / 2,
             ),
             values: None,
+            disk: None,
         }
     }
 
+    /// Configures a secondary disk tier for the caches persisted in RocksDB at the specified `path`.
+    /// If the estimated size of the disk tier exceeds `max_size` bytes, cached data is evicted from it.
+    ///
+    /// On configuration, the disk tier is reconciled with Postgres: cached values modified after the persisted
+    /// miniblock are removed, and if a revert is detected, all cached data except for factory deps is dropped.
+    /// Afterwards, in-memory caches for factory deps and initial writes are warmed up from disk. The values cache
+    /// is warmed up by [`PostgresStorageCachesTask`] once it's started.
+    ///
+    /// This method must be called before [`Self::configure_storage_values_cache()`].
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the values cache is already configured.
+    /// - Propagates RocksDB and Postgres errors.
+    pub async fn configure_disk_cache(
+        &mut self,
+        path: PathBuf,
+        max_size: u64,
+        connection_pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.values.is_none(),
+            "disk cache must be configured before the storage values cache"
+        );
+        tracing::info!("Initializing VM storage disk cache at `{}`", path.display());
+
+        let disk = tokio::task::spawn_blocking(move || DiskCache::open(&path, max_size))
+            .await
+            .context("panicked opening VM storage disk cache")??;
+        let mut connection = connection_pool
+            .connection_tagged("vm_storage_disk_cache")
+            .await?;
+        let valid_for = disk.reconcile(&mut connection).await?;
+        drop(connection);
+
+        let this = self.clone();
+        let disk_for_warm_up = disk.clone();
+        tokio::task::spawn_blocking(move || {
+            disk_for_warm_up.enforce_size_limit();
+            this.warm_up(&disk_for_warm_up);
+        })
+        .await
+        .context("panicked warming up caches from disk")?;
+        self.disk = Some((disk, valid_for));
+        Ok(())
+    }
+
+    fn warm_up(&self, disk: &DiskCache) {
+        let capacity = self.factory_deps.capacity();
+        let mut total_weight = 0_u64;
+        let mut entry_count = 0;
+        for (hash, bytecode) in disk.factory_deps() {
+            total_weight += u64::from(bytecode.cache_weight());
+            if total_weight > capacity {
+                break;
+            }
+            self.factory_deps.insert(hash, bytecode);
+            entry_count += 1;
+        }
+        DISK_CACHE_METRICS.warmed_up_entries[&DiskCacheKind::FactoryDeps].set(entry_count);
+
+        let capacity = self.initial_writes.capacity();
+        let mut total_weight = 0_u64;
+        let mut entry_count = 0;
+        for (key, l1_batch_number) in disk.initial_writes() {
+            total_weight += u64::from(l1_batch_number.cache_weight());
+            if total_weight > capacity {
+                break;
+            }
+            self.initial_writes.insert(key, l1_batch_number);
+            entry_count += 1;
+        }
+        DISK_CACHE_METRICS.warmed_up_entries[&DiskCacheKind::InitialWrites].set(entry_count);
+    }
+
+    fn factory_dep(&self, hash: H256) -> Option<Vec<u8>> {
+        if let Some(dep) = self.factory_deps.get(&hash) {
+            return Some(dep);
+        }
+        let (disk, _) = self.disk.as_ref()?;
+        let dep = disk.factory_dep(hash)?;
+        self.factory_deps.insert(hash, dep.clone());
+        Some(dep)
+    }
+
+    fn insert_factory_dep(&self, hash: H256, dep: Vec<u8>) {
+        if let Some((disk, _)) = &self.disk {
+            disk.insert_factory_dep(hash, &dep);
+        }
+        self.factory_deps.insert(hash, dep);
+    }
+
+    fn initial_write(&self, key: &StorageKey) -> Option<L1BatchNumber> {
+        if let Some(l1_batch_number) = self.initial_writes.get(key) {
+            return Some(l1_batch_number);
+        }
+        let (disk, _) = self.disk.as_ref()?;
+        let l1_batch_number = disk.initial_write(key)?;
+        self.initial_writes.insert(*key, l1_batch_number);
+        Some(l1_batch_number)
+    }
+
+    fn insert_initial_write(&self, key: StorageKey, l1_batch_number: L1BatchNumber) {
+        if let Some((disk, _)) = &self.disk {
+            disk.insert_initial_write(&key, l1_batch_number);
+        }
+        self.initial_writes.insert(key, l1_batch_number);
+    }
+
     /// Configures the VM storage values cache. The returned closure is the background task that will update
     /// the cache according to [`Self::schedule_values_update()`] calls. It should be spawned on a separate thread
     /// or a blocking Tokio task.
@@ -304,7 +500,7 @@ impl PostgresStorageCaches {
         tracing::debug!("Initializing VM storage values cache with {capacity}B capacity");
 
         let (command_sender, command_receiver) = mpsc::unbounded_channel();
-        let values_cache = ValuesCache::new(capacity);
+        let values_cache = ValuesCache::new(capacity, self.disk.clone());
         self.values = Some(ValuesCacheAndUpdater {
             cache: values_cache.clone(),
             command_sender,
@@ -357,6 +553,11 @@ impl PostgresStorageCachesTask {
     /// - Propagates Postgres errors.
     /// - Propagates errors from the cache update task.
     pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let values_cache = self.values_cache.clone();
+        tokio::task::spawn_blocking(move || values_cache.warm_up())
+            .await
+            .context("panicked warming up storage values cache")?;
+
         let mut current_miniblock = self.values_cache.valid_for();
         loop {
             tokio::select! {
@@ -502,7 +703,7 @@ impl ReadStorage for PostgresStorage<'_> {
     fn is_write_initial(&mut self, key: &StorageKey) -> bool {
         let latency = STORAGE_METRICS.storage[&Method::IsWriteInitial].start();
         let caches = self.caches.as_ref();
-        let cached_value = caches.and_then(|caches| caches.initial_writes.get(key));
+        let cached_value = caches.and_then(|caches| caches.initial_write(key));
 
         if cached_value.is_none() {
             // Write is absent in positive cache, check whether it's present in the negative cache.
@@ -529,7 +730,7 @@ impl ReadStorage for PostgresStorage<'_> {
             if let Some(caches) = &self.caches {
                 if let Some(l1_batch_number) = value {
                     caches.negative_initial_writes.remove(key);
-                    caches.initial_writes.insert(*key, l1_batch_number);
+                    caches.insert_initial_write(*key, l1_batch_number);
                 } else {
                     caches
                         .negative_initial_writes
@@ -554,7 +755,7 @@ impl ReadStorage for PostgresStorage<'_> {
         let cached_value = self
             .caches
             .as_ref()
-            .and_then(|caches| caches.factory_deps.get(&hash));
+            .and_then(|caches| caches.factory_dep(hash));
 
         let result = cached_value.or_else(|| {
             let mut dal = self.connection.storage_web3_dal();
@@ -566,7 +767,7 @@ impl ReadStorage for PostgresStorage<'_> {
             if let Some(caches) = &self.caches {
                 // If we receive None, we won't cache it.
                 if let Some(dep) = value.clone() {
-                    caches.factory_deps.insert(hash, dep);
+                    caches.insert_factory_dep(hash, dep);
                 }
             };
 
@@ -1,12 +1,13 @@
 //! Tests for `PostgresStorage`.
 
-use std::{collections::HashMap, mem};
+use std::{collections::HashMap, mem, path::Path};
 
 use rand::{
     rngs::StdRng,
     seq::{IteratorRandom, SliceRandom},
     Rng, SeedableRng,
 };
+use tempfile::TempDir;
 use zksync_dal::ConnectionPool;
 use zksync_types::StorageLog;
 
@@ -604,3 +605,235 @@ async fn mini_fuzz_values_cache() {
         .await
         .unwrap();
 }
+
+fn create_caches_with_disk(
+    pool: &ConnectionPool<Core>,
+    rt_handle: &Handle,
+    path: &Path,
+) -> (PostgresStorageCaches, ValuesCache) {
+    let mut caches = PostgresStorageCaches::new(1_024 * 1_024, 1_024 * 1_024);
+    rt_handle
+        .block_on(caches.configure_disk_cache(path.to_owned(), u64::MAX, pool))
+        .unwrap();
+    let _ = caches.configure_storage_values_cache(1_024 * 1_024, pool.clone());
+    let values_cache = caches.values.as_ref().unwrap().cache.clone();
+    values_cache.warm_up();
+    (caches, values_cache)
+}
+
+fn test_disk_cache(pool: &ConnectionPool<Core>, rt_handle: Handle) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut connection = rt_handle.block_on(pool.connection()).unwrap();
+    rt_handle.block_on(prepare_postgres(&mut connection));
+    let bytecode_hash = H256::repeat_byte(0xfe);
+    let factory_deps = HashMap::from([(bytecode_hash, vec![1, 2, 3])]);
+    rt_handle
+        .block_on(
+            connection
+                .factory_deps_dal()
+                .insert_factory_deps(MiniblockNumber(0), &factory_deps),
+        )
+        .unwrap();
+    // Initial writes are only persisted for L1 batches preceding the L1 batch of the checkpoint miniblock.
+    let new_logs = gen_storage_logs(20..30);
+    rt_handle.block_on(create_miniblock(
+        &mut connection,
+        MiniblockNumber(1),
+        new_logs.clone(),
+    ));
+    rt_handle.block_on(create_l1_batch(
+        &mut connection,
+        L1BatchNumber(1),
+        &new_logs,
+    ));
+    drop(connection);
+
+    let (caches, values_cache) = create_caches_with_disk(pool, &rt_handle, temp_dir.path());
+    assert_eq!(values_cache.valid_for(), MiniblockNumber(1));
+    let connection = rt_handle.block_on(pool.connection()).unwrap();
+    let mut storage = PostgresStorage::new(rt_handle.clone(), connection, MiniblockNumber(1), true)
+        .with_caches(caches.clone());
+
+    let logs = gen_storage_logs(0..20);
+    assert_eq!(storage.load_factory_dep(bytecode_hash), Some(vec![1, 2, 3]));
+    for log in &logs {
+        assert!(!storage.is_write_initial(&log.key));
+        assert_eq!(storage.read_value(&log.key), log.value);
+    }
+    let modified_key = logs[0].key;
+    let unmodified_key = logs[1].key;
+
+    // All cache instances must be dropped to close the RocksDB instance.
+    drop(storage);
+    drop((caches, values_cache));
+
+    // Reopen the cache; it should be warmed up from disk.
+    let (caches, values_cache) = create_caches_with_disk(pool, &rt_handle, temp_dir.path());
+    assert_eq!(caches.factory_deps.get(&bytecode_hash), Some(vec![1, 2, 3]));
+    assert_eq!(
+        caches.initial_writes.get(&modified_key),
+        Some(L1BatchNumber(0))
+    );
+    values_cache
+        .assertions(MiniblockNumber(1))
+        .assert_entries(&[
+            (modified_key, Some(logs[0].value)),
+            (unmodified_key, Some(logs[1].value)),
+        ]);
+    drop((caches, values_cache));
+
+    // Add a new miniblock modifying one of the cached keys.
+    let mut connection = rt_handle.block_on(pool.connection()).unwrap();
+    let new_value = H256::repeat_byte(0x23);
+    rt_handle.block_on(create_miniblock(
+        &mut connection,
+        MiniblockNumber(2),
+        vec![StorageLog::new_write_log(modified_key, new_value)],
+    ));
+    drop(connection);
+
+    let (caches, values_cache) = create_caches_with_disk(pool, &rt_handle, temp_dir.path());
+    assert_eq!(values_cache.valid_for(), MiniblockNumber(2));
+    let (disk, _) = caches.disk.as_ref().unwrap();
+    assert!(disk.value(&modified_key.hashed_key()).is_none());
+    assert!(disk.value(&unmodified_key.hashed_key()).is_some());
+    values_cache
+        .assertions(MiniblockNumber(2))
+        .assert_entries(&[(modified_key, None), (unmodified_key, Some(logs[1].value))]);
+    drop((caches, values_cache));
+
+    // Revert all miniblocks and L1 batches after genesis.
+    let mut connection = rt_handle.block_on(pool.connection()).unwrap();
+    rt_handle.block_on(async {
+        connection
+            .storage_logs_dal()
+            .rollback_storage_logs(MiniblockNumber(0))
+            .await
+            .unwrap();
+        connection
+            .blocks_dal()
+            .delete_miniblocks(MiniblockNumber(0))
+            .await
+            .unwrap();
+        connection
+            .blocks_dal()
+            .delete_l1_batches(L1BatchNumber(0))
+            .await
+            .unwrap();
+        connection
+            .blocks_dal()
+            .delete_initial_writes(L1BatchNumber(0))
+            .await
+            .unwrap();
+    });
+    drop(connection);
+
+    let (caches, values_cache) = create_caches_with_disk(pool, &rt_handle, temp_dir.path());
+    assert_eq!(values_cache.valid_for(), MiniblockNumber(0));
+    let (disk, _) = caches.disk.as_ref().unwrap();
+    assert!(disk.value(&unmodified_key.hashed_key()).is_none());
+    assert_eq!(disk.initial_write(&unmodified_key), None);
+    // Factory deps are content-addressable and are not invalidated.
+    assert_eq!(disk.factory_dep(bytecode_hash), Some(vec![1, 2, 3]));
+}
+
+#[tokio::test]
+async fn using_disk_cache() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let handle = Handle::current();
+    tokio::task::spawn_blocking(move || test_disk_cache(&pool, handle))
+        .await
+        .unwrap();
+}
+
+fn test_value(byte: u8) -> TimestampedStorageValue {
+    TimestampedStorageValue {
+        value: H256::repeat_byte(byte),
+        loaded_at: MiniblockNumber(1),
+    }
+}
+
+#[test]
+fn disk_cache_batches_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    let disk = DiskCache::open(temp_dir.path(), u64::MAX).unwrap();
+    let hashed_key = H256::repeat_byte(1);
+    disk.insert_value(&hashed_key, &test_value(1));
+    // The value is not persisted until pending writes are flushed.
+    assert!(disk.value(&hashed_key).is_none());
+    disk.advance(MiniblockNumber(1), None, &[]).unwrap();
+    assert_eq!(disk.value(&hashed_key).unwrap().value, H256::repeat_byte(1));
+
+    // A pending write must not survive the removal of the modified key.
+    disk.insert_value(&hashed_key, &test_value(2));
+    disk.advance(MiniblockNumber(1), None, &[hashed_key])
+        .unwrap();
+    assert!(disk.value(&hashed_key).is_none());
+
+    // Pending writes are flushed when the cache is dropped.
+    disk.insert_value(&hashed_key, &test_value(3));
+    drop(disk);
+    let disk = DiskCache::open(temp_dir.path(), u64::MAX).unwrap();
+    assert_eq!(disk.value(&hashed_key).unwrap().value, H256::repeat_byte(3));
+}
+
+#[test]
+fn disk_cache_skips_stale_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let disk = DiskCache::open(temp_dir.path(), u64::MAX).unwrap();
+    let hashed_key = H256::repeat_byte(1);
+    disk.advance(MiniblockNumber(2), None, &[]).unwrap();
+
+    // The value is loaded for a miniblock preceding the one the cache was advanced to, so it may be stale.
+    disk.insert_value(&hashed_key, &test_value(1));
+    disk.advance(MiniblockNumber(2), None, &[]).unwrap();
+    assert!(disk.value(&hashed_key).is_none());
+
+    let value = TimestampedStorageValue {
+        value: H256::repeat_byte(2),
+        loaded_at: MiniblockNumber(2),
+    };
+    disk.insert_value(&hashed_key, &value);
+    disk.advance(MiniblockNumber(2), None, &[]).unwrap();
+    assert_eq!(disk.value(&hashed_key).unwrap().value, H256::repeat_byte(2));
+}
+
+fn insert_values_into_disk_cache(disk: &DiskCache, count: u32) {
+    for i in 0..count {
+        let hashed_key = H256::from_low_u64_be(i.into());
+        disk.insert_value(&hashed_key, &test_value(1));
+    }
+}
+
+#[test]
+fn disk_cache_flushes_full_write_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    let disk = DiskCache::open(temp_dir.path(), u64::MAX).unwrap();
+    insert_values_into_disk_cache(&disk, 2_000);
+    let persisted_count = disk.values().count();
+    assert!(
+        persisted_count > 0 && persisted_count < 2_000,
+        "{persisted_count}"
+    );
+    disk.advance(MiniblockNumber(1), None, &[]).unwrap();
+    assert_eq!(disk.values().count(), 2_000);
+}
+
+#[test]
+fn disk_cache_evicts_data_exceeding_size_limit() {
+    let temp_dir = TempDir::new().unwrap();
+    let disk = DiskCache::open(temp_dir.path(), 1).unwrap();
+    let bytecode_hash = H256::repeat_byte(0xfe);
+    disk.insert_factory_dep(bytecode_hash, &[1, 2, 3]);
+    disk.advance(MiniblockNumber(1), None, &[]).unwrap();
+    assert!(disk.factory_dep(bytecode_hash).is_some());
+
+    // Flushing a full write batch checks the size limit and evicts all data.
+    insert_values_into_disk_cache(&disk, 2_000);
+    assert_eq!(disk.values().count(), 0);
+    assert!(disk.factory_dep(bytecode_hash).is_none());
+
+    // Values left pending after the eviction are persisted.
+    disk.advance(MiniblockNumber(1), None, &[]).unwrap();
+    assert!(disk.values().count() > 0);
+}
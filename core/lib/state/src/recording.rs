@@ -0,0 +1,635 @@
+//! Recording and replaying storage accesses.
+//!
+//! [`RecordingStorage`] wraps a [`ReadStorage`] and writes every response of the wrapped storage
+//! into a compact binary access log. [`ReplayStorage`] serves the same requests purely from the log,
+//! which allows re-executing an L1 batch without Postgres / RocksDB (e.g., for offline debugging
+//! or deterministic VM regression tests).
+//!
+//! # Log format
+//!
+//! The log starts with a 4-byte magic `ZKSA` followed by a 1-byte format version. Each access is encoded
+//! as a 1-byte tag followed by the tag-specific payload; integers are little-endian.
+//!
+//! | Access | Tag | Payload |
+//! |:-------|:----|:--------|
+//! | `read_value` | 0 | address (20 bytes), key (32 bytes), value (32 bytes) |
+//! | `is_write_initial` | 1 | address (20 bytes), key (32 bytes), response (1 byte) |
+//! | `load_factory_dep` | 2 | hash (32 bytes), presence (1 byte), [length (`u32`), bytecode] |
+//! | `get_enumeration_index` | 3 | address (20 bytes), key (32 bytes), presence (1 byte), [index (`u64`)] |
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+    hash::Hash,
+    io::{self, Read, Write},
+};
+
+use zksync_types::{AccountTreeId, Address, StorageKey, StorageValue, H256};
+
+use crate::ReadStorage;
+
+const MAGIC: &[u8; 4] = b"ZKSA";
+const FORMAT_VERSION: u8 = 1;
+/// Maximum length of a bytecode in the log. Bytecode hashes encode the bytecode length in 32-byte words
+/// as a `u16`, so valid bytecodes are never longer than this.
+const MAX_BYTECODE_LEN: usize = (u16::MAX as usize) * 32;
+
+/// Single storage access together with the response returned by the storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageAccess {
+    /// [`ReadStorage::read_value()`] call.
+    ReadValue {
+        /// Requested key.
+        key: StorageKey,
+        /// Returned value.
+        value: StorageValue,
+    },
+    /// [`ReadStorage::is_write_initial()`] call.
+    IsWriteInitial {
+        /// Requested key.
+        key: StorageKey,
+        /// Returned flag.
+        is_initial: bool,
+    },
+    /// [`ReadStorage::load_factory_dep()`] call.
+    LoadFactoryDep {
+        /// Requested bytecode hash.
+        hash: H256,
+        /// Returned bytecode.
+        bytecode: Option<Vec<u8>>,
+    },
+    /// [`ReadStorage::get_enumeration_index()`] call.
+    GetEnumerationIndex {
+        /// Requested key.
+        key: StorageKey,
+        /// Returned enumeration index.
+        index: Option<u64>,
+    },
+}
+
+impl StorageAccess {
+    const READ_VALUE_TAG: u8 = 0;
+    const IS_WRITE_INITIAL_TAG: u8 = 1;
+    const LOAD_FACTORY_DEP_TAG: u8 = 2;
+    const GET_ENUMERATION_INDEX_TAG: u8 = 3;
+
+    /// Writes this access to the provided writer.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors returned by the writer.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        match self {
+            Self::ReadValue { key, value } => {
+                writer.write_all(&[Self::READ_VALUE_TAG])?;
+                write_key(writer, key)?;
+                writer.write_all(value.as_bytes())
+            }
+            Self::IsWriteInitial { key, is_initial } => {
+                writer.write_all(&[Self::IS_WRITE_INITIAL_TAG])?;
+                write_key(writer, key)?;
+                writer.write_all(&[u8::from(*is_initial)])
+            }
+            Self::LoadFactoryDep { hash, bytecode } => {
+                writer.write_all(&[Self::LOAD_FACTORY_DEP_TAG])?;
+                writer.write_all(hash.as_bytes())?;
+                if let Some(bytecode) = bytecode {
+                    if bytecode.len() > MAX_BYTECODE_LEN {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "bytecode is too large",
+                        ));
+                    }
+                    let len = bytecode.len() as u32;
+                    writer.write_all(&[1])?;
+                    writer.write_all(&len.to_le_bytes())?;
+                    writer.write_all(bytecode)
+                } else {
+                    writer.write_all(&[0])
+                }
+            }
+            Self::GetEnumerationIndex { key, index } => {
+                writer.write_all(&[Self::GET_ENUMERATION_INDEX_TAG])?;
+                write_key(writer, key)?;
+                if let Some(index) = index {
+                    writer.write_all(&[1])?;
+                    writer.write_all(&index.to_le_bytes())
+                } else {
+                    writer.write_all(&[0])
+                }
+            }
+        }
+    }
+
+    /// Reads the next access from the provided reader. Returns `Ok(None)` if the reader is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader returns an I/O error, or if the access is malformed or truncated.
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut tag = [0_u8];
+        match reader.read_exact(&mut tag) {
+            Ok(()) => { /* continue reading */ }
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        Ok(Some(match tag[0] {
+            Self::READ_VALUE_TAG => Self::ReadValue {
+                key: read_key(reader)?,
+                value: read_hash(reader)?,
+            },
+            Self::IS_WRITE_INITIAL_TAG => Self::IsWriteInitial {
+                key: read_key(reader)?,
+                is_initial: read_flag(reader)?,
+            },
+            Self::LOAD_FACTORY_DEP_TAG => {
+                let hash = read_hash(reader)?;
+                let bytecode = if read_flag(reader)? {
+                    let mut len = [0_u8; 4];
+                    reader.read_exact(&mut len)?;
+                    let len = u32::from_le_bytes(len) as usize;
+                    if len > MAX_BYTECODE_LEN {
+                        let message = format!("bytecode length {len} exceeds {MAX_BYTECODE_LEN}");
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+                    }
+                    // Read incrementally, so that a truncated log doesn't lead to allocating the entire buffer.
+                    let mut bytecode = Vec::new();
+                    reader.by_ref().take(len as u64).read_to_end(&mut bytecode)?;
+                    if bytecode.len() < len {
+                        return Err(io::ErrorKind::UnexpectedEof.into());
+                    }
+                    Some(bytecode)
+                } else {
+                    None
+                };
+                Self::LoadFactoryDep { hash, bytecode }
+            }
+            Self::GET_ENUMERATION_INDEX_TAG => {
+                let key = read_key(reader)?;
+                let index = if read_flag(reader)? {
+                    let mut index = [0_u8; 8];
+                    reader.read_exact(&mut index)?;
+                    Some(u64::from_le_bytes(index))
+                } else {
+                    None
+                };
+                Self::GetEnumerationIndex { key, index }
+            }
+            tag => {
+                let message = format!("unknown storage access tag: {tag}");
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+        }))
+    }
+}
+
+fn write_key(writer: &mut impl Write, key: &StorageKey) -> io::Result<()> {
+    writer.write_all(key.address().as_bytes())?;
+    writer.write_all(key.key().as_bytes())
+}
+
+fn read_key(reader: &mut impl Read) -> io::Result<StorageKey> {
+    let mut address = Address::zero();
+    reader.read_exact(address.as_bytes_mut())?;
+    let key = read_hash(reader)?;
+    Ok(StorageKey::new(AccountTreeId::new(address), key))
+}
+
+fn read_hash(reader: &mut impl Read) -> io::Result<H256> {
+    let mut hash = H256::zero();
+    reader.read_exact(hash.as_bytes_mut())?;
+    Ok(hash)
+}
+
+fn read_flag(reader: &mut impl Read) -> io::Result<bool> {
+    let mut flag = [0_u8];
+    reader.read_exact(&mut flag)?;
+    match flag[0] {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => {
+            let message = format!("invalid flag value: {other}");
+            Err(io::Error::new(io::ErrorKind::InvalidData, message))
+        }
+    }
+}
+
+/// [`ReadStorage`] wrapper that records all responses of the wrapped storage into an access log.
+/// The log can be replayed with [`ReplayStorage`].
+///
+/// Since [`ReadStorage`] methods are infallible, I/O errors encountered during recording are not propagated
+/// immediately. Instead, recording stops after the first error, and the error is returned from [`Self::finish()`].
+/// If the storage is consumed by another component (e.g., wrapped in a [`StorageView`](crate::StorageView)),
+/// the writer can be passed by mutable reference so that the log is accessible after the storage is dropped;
+/// in this case, I/O errors are only logged.
+pub struct RecordingStorage<S, W: Write> {
+    inner: S,
+    writer: W,
+    access_count: usize,
+    error: Option<io::Error>,
+}
+
+impl<S: fmt::Debug, W: Write> fmt::Debug for RecordingStorage<S, W> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RecordingStorage")
+            .field("inner", &self.inner)
+            .field("access_count", &self.access_count)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: ReadStorage, W: Write> RecordingStorage<S, W> {
+    /// Wraps the provided storage. The access log is written to `writer`; it's advisable for the writer
+    /// to be buffered.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors returned by the writer when writing the log header.
+    pub fn new(inner: S, mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        Ok(Self {
+            inner,
+            writer,
+            access_count: 0,
+            error: None,
+        })
+    }
+
+    /// Returns the number of accesses recorded so far.
+    pub fn access_count(&self) -> usize {
+        self.access_count
+    }
+
+    fn record(&mut self, access: &StorageAccess) {
+        if self.error.is_some() {
+            return;
+        }
+        match access.write_to(&mut self.writer) {
+            Ok(()) => self.access_count += 1,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed recording storage access #{}: {err}; further accesses won't be recorded",
+                    self.access_count
+                );
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Flushes the access log and returns the wrapped storage together with the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first I/O error encountered during recording, or an error flushing the writer.
+    pub fn finish(mut self) -> io::Result<(S, W)> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        self.writer.flush()?;
+        Ok((self.inner, self.writer))
+    }
+}
+
+impl<S: ReadStorage, W: Write> ReadStorage for RecordingStorage<S, W> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        let value = self.inner.read_value(key);
+        self.record(&StorageAccess::ReadValue { key: *key, value });
+        value
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        let is_initial = self.inner.is_write_initial(key);
+        self.record(&StorageAccess::IsWriteInitial {
+            key: *key,
+            is_initial,
+        });
+        is_initial
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        let bytecode = self.inner.load_factory_dep(hash);
+        self.record(&StorageAccess::LoadFactoryDep {
+            hash,
+            bytecode: bytecode.clone(),
+        });
+        bytecode
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        let index = self.inner.get_enumeration_index(key);
+        self.record(&StorageAccess::GetEnumerationIndex { key: *key, index });
+        index
+    }
+}
+
+/// [`ReadStorage`] implementation serving requests from an access log recorded by [`RecordingStorage`].
+///
+/// Responses are looked up by request, so the replayed execution may issue requests in a different order
+/// or repeat them. Requests not present in the log indicate that the replayed execution diverged
+/// from the recorded one, and lead to a panic.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayStorage {
+    values: HashMap<StorageKey, StorageValue>,
+    initial_writes: HashMap<StorageKey, bool>,
+    factory_deps: HashMap<H256, Option<Vec<u8>>>,
+    enumeration_indices: HashMap<StorageKey, Option<u64>>,
+}
+
+impl ReplayStorage {
+    /// Reads an access log from the provided reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be read, is malformed, or contains conflicting responses
+    /// for the same request.
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut header = [0_u8; 5];
+        reader.read_exact(&mut header)?;
+        if header[..4] != MAGIC[..] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid storage access log magic",
+            ));
+        }
+        if header[4] != FORMAT_VERSION {
+            let message = format!("unsupported storage access log version: {}", header[4]);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+        }
+
+        let mut this = Self::default();
+        while let Some(access) = StorageAccess::read_from(&mut reader)? {
+            this.insert(access)?;
+        }
+        Ok(this)
+    }
+
+    fn insert(&mut self, access: StorageAccess) -> io::Result<()> {
+        match access {
+            StorageAccess::ReadValue { key, value } => {
+                Self::insert_response(&mut self.values, key, value, "read_value")
+            }
+            StorageAccess::IsWriteInitial { key, is_initial } => Self::insert_response(
+                &mut self.initial_writes,
+                key,
+                is_initial,
+                "is_write_initial",
+            ),
+            StorageAccess::LoadFactoryDep { hash, bytecode } => {
+                Self::insert_response(&mut self.factory_deps, hash, bytecode, "load_factory_dep")
+            }
+            StorageAccess::GetEnumerationIndex { key, index } => Self::insert_response(
+                &mut self.enumeration_indices,
+                key,
+                index,
+                "get_enumeration_index",
+            ),
+        }
+    }
+
+    fn insert_response<K, V>(
+        responses: &mut HashMap<K, V>,
+        request: K,
+        response: V,
+        method: &str,
+    ) -> io::Result<()>
+    where
+        K: Eq + Hash + fmt::Debug,
+        V: PartialEq,
+    {
+        match responses.entry(request) {
+            Entry::Vacant(entry) => {
+                entry.insert(response);
+            }
+            Entry::Occupied(entry) => {
+                if *entry.get() != response {
+                    let message = format!(
+                        "conflicting responses for `{method}({:?})` in storage access log",
+                        entry.key()
+                    );
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn unrecorded(method: &str, request: &dyn fmt::Debug) -> ! {
+        panic!(
+            "`{method}({request:?})` is not recorded in the storage access log; \
+             replayed execution has diverged from the recorded one"
+        );
+    }
+}
+
+impl ReadStorage for &ReplayStorage {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        match self.values.get(key) {
+            Some(value) => *value,
+            None => ReplayStorage::unrecorded("read_value", key),
+        }
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        match self.initial_writes.get(key) {
+            Some(is_initial) => *is_initial,
+            None => ReplayStorage::unrecorded("is_write_initial", key),
+        }
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        match self.factory_deps.get(&hash) {
+            Some(bytecode) => bytecode.clone(),
+            None => ReplayStorage::unrecorded("load_factory_dep", &hash),
+        }
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        match self.enumeration_indices.get(key) {
+            Some(index) => *index,
+            None => ReplayStorage::unrecorded("get_enumeration_index", key),
+        }
+    }
+}
+
+impl ReadStorage for ReplayStorage {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        (&*self).read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        (&*self).is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        (&*self).load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        (&*self).get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::H160;
+
+    use super::*;
+    use crate::{InMemoryStorage, StorageView, WriteStorage};
+
+    fn test_storage() -> InMemoryStorage {
+        let mut storage = InMemoryStorage::default();
+        for i in 0..10 {
+            storage.set_value(test_key(i), H256::from_low_u64_be(i + 100));
+        }
+        storage.store_factory_dep(H256::repeat_byte(0xfe), vec![1, 2, 3, 4]);
+        storage
+    }
+
+    fn test_key(i: u64) -> StorageKey {
+        StorageKey::new(
+            AccountTreeId::new(H160::repeat_byte(1)),
+            H256::from_low_u64_be(i),
+        )
+    }
+
+    #[test]
+    fn storage_access_roundtrip() {
+        let accesses = [
+            StorageAccess::ReadValue {
+                key: test_key(1),
+                value: H256::repeat_byte(0x23),
+            },
+            StorageAccess::IsWriteInitial {
+                key: test_key(2),
+                is_initial: true,
+            },
+            StorageAccess::LoadFactoryDep {
+                hash: H256::repeat_byte(1),
+                bytecode: Some(vec![0; 64]),
+            },
+            StorageAccess::LoadFactoryDep {
+                hash: H256::repeat_byte(2),
+                bytecode: None,
+            },
+            StorageAccess::GetEnumerationIndex {
+                key: test_key(3),
+                index: Some(42),
+            },
+            StorageAccess::GetEnumerationIndex {
+                key: test_key(4),
+                index: None,
+            },
+        ];
+
+        let mut buffer = vec![];
+        for access in &accesses {
+            access.write_to(&mut buffer).unwrap();
+        }
+        let mut reader = buffer.as_slice();
+        for access in &accesses {
+            let restored = StorageAccess::read_from(&mut reader).unwrap();
+            assert_eq!(restored.as_ref(), Some(access));
+        }
+        assert_eq!(StorageAccess::read_from(&mut reader).unwrap(), None);
+
+        // Check that truncated input is detected.
+        let mut truncated = &buffer[..buffer.len() - 1];
+        let err = loop {
+            match StorageAccess::read_from(&mut truncated) {
+                Ok(Some(_)) => continue,
+                Ok(None) => panic!("truncated access was not detected"),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn oversized_bytecode_length_is_rejected() {
+        let mut buffer = vec![StorageAccess::LOAD_FACTORY_DEP_TAG];
+        buffer.extend_from_slice(H256::repeat_byte(1).as_bytes());
+        buffer.push(1);
+        buffer.extend_from_slice(&u32::MAX.to_le_bytes());
+
+        let err = StorageAccess::read_from(&mut buffer.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let access = StorageAccess::LoadFactoryDep {
+            hash: H256::repeat_byte(1),
+            bytecode: Some(vec![0; MAX_BYTECODE_LEN + 1]),
+        };
+        let err = access.write_to(&mut vec![]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn recording_and_replaying_storage() {
+        let storage = test_storage();
+        let mut log = vec![];
+        let recording = RecordingStorage::new(&storage, &mut log).unwrap();
+        let mut view = StorageView::new(recording);
+        for i in 0..15 {
+            view.read_value(&test_key(i));
+        }
+        view.set_value(test_key(20), H256::repeat_byte(1));
+        assert!(view.is_write_initial(&test_key(20)));
+        assert!(!view.is_write_initial(&test_key(3)));
+        assert_eq!(view.get_enumeration_index(&test_key(3)), Some(4));
+        assert_eq!(view.get_enumeration_index(&test_key(30)), None);
+        assert!(view.load_factory_dep(H256::repeat_byte(0xfe)).is_some());
+        assert!(view.load_factory_dep(H256::repeat_byte(0xff)).is_none());
+        let expected_reads = view.read_storage_keys().clone();
+        drop(view);
+
+        let replay = ReplayStorage::from_reader(log.as_slice()).unwrap();
+        let mut view = StorageView::new(&replay);
+        for i in (0..15).rev() {
+            let value = view.read_value(&test_key(i));
+            assert_eq!(value, expected_reads[&test_key(i)]);
+        }
+        assert!(view.is_write_initial(&test_key(20)));
+        assert!(!view.is_write_initial(&test_key(3)));
+        assert_eq!(view.get_enumeration_index(&test_key(3)), Some(4));
+        assert_eq!(view.get_enumeration_index(&test_key(30)), None);
+        assert_eq!(
+            view.load_factory_dep(H256::repeat_byte(0xfe)),
+            Some(vec![1, 2, 3, 4])
+        );
+        assert_eq!(view.load_factory_dep(H256::repeat_byte(0xff)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "not recorded in the storage access log")]
+    fn replaying_unrecorded_access() {
+        let mut recording = RecordingStorage::new(test_storage(), vec![]).unwrap();
+        recording.read_value(&test_key(1));
+        recording.is_bytecode_known(&H256::repeat_byte(0xfe));
+        assert_eq!(recording.access_count(), 2);
+        let (_, log) = recording.finish().unwrap();
+
+        let mut replay = ReplayStorage::from_reader(log.as_slice()).unwrap();
+        replay.read_value(&test_key(2));
+    }
+
+    #[test]
+    fn conflicting_responses_are_rejected() {
+        let mut log = MAGIC.to_vec();
+        log.push(FORMAT_VERSION);
+        for value in [H256::zero(), H256::repeat_byte(1)] {
+            StorageAccess::ReadValue {
+                key: test_key(1),
+                value,
+            }
+            .write_to(&mut log)
+            .unwrap();
+        }
+
+        let err = ReplayStorage::from_reader(log.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("conflicting responses"), "{err}");
+    }
+}
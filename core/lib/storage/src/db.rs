@@ -432,6 +432,31 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             .unwrap_or(0)
     }
 
+    /// Returns the estimated size of data (in bytes) in the specified column family, including the data
+    /// in memtables. Deleted data is only accounted for after it's compacted.
+    pub fn estimated_size(&self, cf: CF) -> u64 {
+        let cf = self.column_family(cf);
+        let live_data_size = self
+            .inner
+            .int_property(cf, properties::ESTIMATE_LIVE_DATA_SIZE)
+            .unwrap_or(0);
+        let mem_tables_size = self
+            .inner
+            .int_property(cf, properties::SIZE_ALL_MEM_TABLES)
+            .unwrap_or(0);
+        live_data_size + mem_tables_size
+    }
+
+    /// Compacts the specified key range in a column family, reclaiming space occupied by deleted entries.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    pub fn compact_range_cf(&self, cf: CF, keys: ops::Range<&[u8]>) {
+        let cf = self.column_family(cf);
+        self.inner
+            .db
+            .compact_range_cf(cf, Some(keys.start), Some(keys.end));
+    }
+
     pub fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, rocksdb::Error>>
     where
         K: AsRef<[u8]>,
@@ -524,6 +549,17 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         self.inner.db.get_cf(cf, key)
     }
 
+    /// Creates a consistent point-in-time checkpoint of this database in the specified directory,
+    /// which must not exist. The checkpoint can be opened as an ordinary RocksDB instance. If the directory
+    /// is on the same filesystem as the database, SST files are hard-linked rather than copied,
+    /// so creating a checkpoint is cheap.
+    ///
+    /// This method is blocking and should be wrapped in `spawn_blocking(_)` if run in the async context.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        let checkpoint = rocksdb::checkpoint::Checkpoint::new(&self.inner.db)?;
+        checkpoint.create_checkpoint(path)
+    }
+
     /// Iterates over key-value pairs in the specified column family `cf` in the lexical
     /// key order. The keys are filtered so that they start from the specified `prefix`.
     pub fn prefix_iterator_cf(
@@ -742,4 +778,32 @@ mod tests {
             .unwrap();
         assert_eq!(value, b"value2");
     }
+
+    #[test]
+    fn creating_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(&temp_dir.path().join("db"))
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let checkpoint_path = temp_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"other_value");
+        db.write(batch).unwrap();
+        drop(db);
+
+        let checkpoint = RocksDB::<NewColumnFamilies>::new(&checkpoint_path).unwrap();
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Default, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Other, b"test")
+            .unwrap();
+        assert!(value.is_none());
+    }
 }
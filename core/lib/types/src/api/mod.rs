@@ -734,3 +734,46 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+/// Boundary of a storage key range together with a Merkle proof. Unlike in [`StorageProof`], `key`
+/// is a hashed storage key as used in the Merkle tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeBoundary {
+    pub key: U256,
+    pub proof: Vec<H256>,
+    pub value: H256,
+    pub index: u64,
+}
+
+/// Existing entry inside a proven storage key range. `key` is a hashed storage key as used in the Merkle tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeEntry {
+    pub key: U256,
+    pub value: H256,
+    pub index: u64,
+}
+
+/// Proof that the hashed storage key range `start.key..=end.key` in the Merkle tree contains `entries`
+/// and no other entries. Can be used to prove absence of a key without knowing it precisely.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeProof {
+    pub start: StorageRangeBoundary,
+    pub entries: Vec<StorageRangeEntry>,
+    pub end: StorageRangeBoundary,
+}
+
+/// EIP-4844 blob published on L1 for an L1 batch together with its sidecar data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchBlob {
+    /// Zero-based index of the blob among blobs published for the L1 batch.
+    pub index: u32,
+    pub blob: Bytes,
+    pub kzg_commitment: Bytes,
+    /// Proof that `kzg_commitment` commits to `blob`.
+    pub kzg_proof: Bytes,
+    pub versioned_hash: H256,
+}
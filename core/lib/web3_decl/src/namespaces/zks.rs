@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchBlob, L1BatchDetails, L2ToL1LogProof, Proof,
+        ProtocolVersion, StorageRangeProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -118,4 +118,18 @@ pub trait ZksNamespace {
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<Proof>>;
+
+    #[method(name = "getRangeProof")]
+    async fn get_range_proof(
+        &self,
+        start_key: U256,
+        end_key: U256,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<StorageRangeProof>>;
+
+    #[method(name = "getBatchBlobs")]
+    async fn get_batch_blobs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<Vec<L1BatchBlob>>>;
 }
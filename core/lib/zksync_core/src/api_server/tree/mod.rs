@@ -34,6 +34,13 @@ struct TreeProofsResponse {
     entries: Vec<TreeEntryWithProof>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeRangeProofRequest {
+    l1_batch_number: L1BatchNumber,
+    start_key: U256,
+    end_key: U256,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TreeEntryWithProof {
     #[serde(default, skip_serializing_if = "H256::is_zero")]
@@ -61,10 +68,97 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Existing tree entry inside a proven key range.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeEntry {
+    pub key: U256,
+    pub value: H256,
+    pub index: u64,
+}
+
+/// Proof that the key range `start_key..=end_key` in the tree contains `entries` and no other entries.
+/// The range may be shorter than requested if it contains too many entries; in this case, `end_key`
+/// is the key of the last existing entry in the returned range, and the remaining entries can be requested
+/// starting from `end_key + 1`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeRangeWithProof {
+    pub start_key: U256,
+    pub start: TreeEntryWithProof,
+    /// Existing entries with keys strictly between `start_key` and `end_key`.
+    pub entries: Vec<TreeEntry>,
+    pub end_key: U256,
+    pub end: TreeEntryWithProof,
+}
+
+impl TreeRangeWithProof {
+    fn new(src: zksync_merkle_tree::TreeRangeWithProof) -> Self {
+        Self {
+            start_key: src.start.base.key,
+            start: TreeEntryWithProof::new(src.start),
+            entries: src
+                .entries
+                .into_iter()
+                .map(|entry| TreeEntry {
+                    key: entry.key,
+                    value: entry.value,
+                    index: entry.leaf_index,
+                })
+                .collect(),
+            end_key: src.end.base.key,
+            end: TreeEntryWithProof::new(src.end),
+        }
+    }
+
+    /// Converts this proof back to the Merkle tree representation, which can be verified using
+    /// [`zksync_merkle_tree::TreeRangeWithProof::verify()`] given a trusted root hash of the tree
+    /// (e.g., one from the L1 batch commitment).
+    pub fn into_tree_proof(self) -> zksync_merkle_tree::TreeRangeWithProof {
+        fn convert_entry(
+            key: U256,
+            src: TreeEntryWithProof,
+        ) -> zksync_merkle_tree::TreeEntryWithProof {
+            let mut merkle_path = src.merkle_path;
+            merkle_path.reverse(); // Convert back to the leaf-to-root enumeration direction used by the tree
+            zksync_merkle_tree::TreeEntryWithProof {
+                base: zksync_merkle_tree::TreeEntry::new(key, src.index, src.value),
+                merkle_path,
+            }
+        }
+
+        zksync_merkle_tree::TreeRangeWithProof {
+            start: convert_entry(self.start_key, self.start),
+            entries: self
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    zksync_merkle_tree::TreeEntry::new(entry.key, entry.index, entry.value)
+                })
+                .collect(),
+            end: convert_entry(self.end_key, self.end),
+        }
+    }
+}
+
+/// Maximum number of existing entries returned in a single range proof.
+const MAX_RANGE_PROOF_ENTRIES: usize = 1_000;
+
 /// Server-side tree API error.
 #[derive(Debug)]
 enum TreeApiServerError {
     NoTreeVersion(NoVersionError),
+    InvalidRange(InvalidRangeErrorData),
+}
+
+impl From<TreeApiServerError> for TreeApiError {
+    fn from(err: TreeApiServerError) -> Self {
+        match err {
+            TreeApiServerError::NoTreeVersion(err) => Self::NoVersion(err),
+            TreeApiServerError::InvalidRange(data) => Self::InvalidRange {
+                start_key: data.start_key,
+                end_key: data.end_key,
+            },
+        }
+    }
 }
 
 // Contains the same fields as `NoVersionError` and is serializable.
@@ -92,6 +186,12 @@ impl From<NoVersionErrorData> for NoVersionError {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct InvalidRangeErrorData {
+    start_key: U256,
+    end_key: U256,
+}
+
 // Loosely conforms to HTTP Problem Details RFC: <https://datatracker.ietf.org/doc/html/rfc7807>
 #[derive(Debug, Serialize)]
 struct Problem<T> {
@@ -117,6 +217,18 @@ impl IntoResponse for TreeApiServerError {
                 };
                 (StatusCode::NOT_FOUND, headers, Json(body)).into_response()
             }
+            Self::InvalidRange(data) => {
+                let body = Problem {
+                    r#type: "/errors#invalid-range",
+                    title: "Invalid key range",
+                    detail: format!(
+                        "start key {:#x} is greater than end key {:#x}",
+                        data.start_key, data.end_key
+                    ),
+                    data,
+                };
+                (StatusCode::BAD_REQUEST, headers, Json(body)).into_response()
+            }
         }
     }
 }
@@ -127,6 +239,8 @@ impl IntoResponse for TreeApiServerError {
 pub enum TreeApiError {
     #[error(transparent)]
     NoVersion(NoVersionError),
+    #[error("invalid key range: start key {start_key:#x} is greater than end key {end_key:#x}")]
+    InvalidRange { start_key: U256, end_key: U256 },
     #[error("tree API is temporarily not available because the Merkle tree isn't initialized; repeat request later")]
     NotReady,
     /// Catch-all variant for internal errors.
@@ -146,6 +260,15 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains a proof for the hashed key range `start_key..=end_key` at the specified tree version
+    /// (= L1 batch number). The returned range may be truncated if it contains too many entries.
+    async fn get_range_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<TreeRangeWithProof, TreeApiError>;
 }
 
 /// In-memory client implementation.
@@ -173,6 +296,22 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady)
         }
     }
+
+    async fn get_range_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<TreeRangeWithProof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .get_range_proof_inner(l1_batch_number, start_key, end_key)
+                .await
+                .map_err(Into::into)
+        } else {
+            Err(TreeApiError::NotReady)
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -181,6 +320,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    range_proof_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -189,6 +329,35 @@ impl TreeApiHttpClient {
             inner: reqwest::Client::new(),
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            range_proof_url: format!("{url_base}/range_proof"),
+        }
+    }
+
+    /// Converts a problem response returned by the server to the corresponding error.
+    async fn check_problem(response: reqwest::Response) -> Result<reqwest::Response, TreeApiError> {
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
+        if !is_problem {
+            return Ok(response);
+        }
+
+        if response.status() == StatusCode::NOT_FOUND {
+            // Try to parse `NoVersionError` from the response body.
+            let problem_data: NoVersionErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            Err(TreeApiError::NoVersion(problem_data.into()))
+        } else if response.status() == StatusCode::BAD_REQUEST {
+            let problem_data: InvalidRangeErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            Err(TreeApiServerError::InvalidRange(problem_data).into())
+        } else {
+            Ok(response)
         }
     }
 }
@@ -244,19 +413,7 @@ impl TreeApiClient for TreeApiHttpClient {
             .await
             .with_context(|| format!("failed requesting proofs for L1 batch #{l1_batch_number}"))?;
 
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion(problem_data.into()));
-        }
-
+        let response = Self::check_problem(response).await?;
         let response = response.error_for_status().with_context(|| {
             format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
         })?;
@@ -265,6 +422,37 @@ impl TreeApiClient for TreeApiHttpClient {
         })?;
         Ok(response.entries)
     }
+
+    async fn get_range_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<TreeRangeWithProof, TreeApiError> {
+        let response = self
+            .inner
+            .post(&self.range_proof_url)
+            .json(&TreeRangeProofRequest {
+                l1_batch_number,
+                start_key,
+                end_key,
+            })
+            .send()
+            .await
+            .with_context(|| {
+                format!("failed requesting range proof for L1 batch #{l1_batch_number}")
+            })?;
+
+        let response = Self::check_problem(response).await?;
+        let response = response.error_for_status().with_context(|| {
+            format!(
+                "requesting range proof for L1 batch #{l1_batch_number} returned non-OK response"
+            )
+        })?;
+        Ok(response.json().await.with_context(|| {
+            format!("failed deserializing range proof for L1 batch #{l1_batch_number}")
+        })?)
+    }
 }
 
 impl AsyncTreeReader {
@@ -301,6 +489,38 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_range_proof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<TreeRangeWithProof, TreeApiServerError> {
+        if start_key > end_key {
+            return Err(TreeApiServerError::InvalidRange(InvalidRangeErrorData {
+                start_key,
+                end_key,
+            }));
+        }
+        let proof = self
+            .clone()
+            .range_with_proof(l1_batch_number, start_key, end_key, MAX_RANGE_PROOF_ENTRIES)
+            .await
+            .map_err(TreeApiServerError::NoTreeVersion)?;
+        Ok(TreeRangeWithProof::new(proof))
+    }
+
+    async fn get_range_proof_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeRangeProofRequest>,
+    ) -> Result<Json<TreeRangeWithProof>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetRangeProof].start();
+        let proof = this
+            .get_range_proof_inner(request.l1_batch_number, request.start_key, request.end_key)
+            .await?;
+        latency.observe();
+        Ok(Json(proof))
+    }
+
     fn create_api_server(
         self,
         bind_address: &SocketAddr,
@@ -311,6 +531,7 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route("/range_proof", routing::post(Self::get_range_proof_handler))
             .with_state(self);
 
         let server = axum::Server::try_bind(bind_address)
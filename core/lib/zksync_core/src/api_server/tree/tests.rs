@@ -4,6 +4,7 @@ use std::net::Ipv4Addr;
 
 use assert_matches::assert_matches;
 use tempfile::TempDir;
+use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_dal::{ConnectionPool, Core};
 
 use super::*;
@@ -68,6 +69,27 @@ async fn merkle_tree_api() {
     assert_eq!(err.version_count, 6);
     assert_eq!(err.missing_version, 10);
 
+    let range_proof = api_client
+        .get_range_proof(L1BatchNumber(5), U256::zero(), U256::MAX)
+        .await
+        .unwrap();
+    assert_eq!(range_proof.start_key, U256::zero());
+    assert!(!range_proof.entries.is_empty());
+    range_proof
+        .into_tree_proof()
+        .verify(&Blake2Hasher, tree_info.root_hash);
+
+    let err = api_client
+        .get_range_proof(L1BatchNumber(5), U256::MAX, U256::zero())
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::InvalidRange { .. });
+    let err = api_client
+        .get_range_proof(L1BatchNumber(10), U256::zero(), U256::MAX)
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion(_));
+
     // Stop the calculator and the tree API server.
     stop_sender.send_replace(true);
     api_server_task.await.unwrap().unwrap();
@@ -101,4 +123,12 @@ async fn local_merkle_tree_client() {
     };
     assert_eq!(err.version_count, 6);
     assert_eq!(err.missing_version, 10);
+
+    let range_proof = tree_reader
+        .get_range_proof(L1BatchNumber(5), U256::zero(), U256::MAX)
+        .await
+        .unwrap();
+    range_proof
+        .into_tree_proof()
+        .verify(&Blake2Hasher, tree_info.root_hash);
 }
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchBlob, L1BatchDetails, L2ToL1LogProof, Proof,
+        ProtocolVersion, StorageRangeProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -167,4 +167,24 @@ impl ZksNamespaceServer for ZksNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_range_proof(
+        &self,
+        start_key: U256,
+        end_key: U256,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<StorageRangeProof>> {
+        self.get_range_proof_impl(start_key, end_key, l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_batch_blobs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<Vec<L1BatchBlob>>> {
+        self.get_batch_blobs_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
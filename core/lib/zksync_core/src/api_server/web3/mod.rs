@@ -11,6 +11,7 @@ use tokio::{
 use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_object_store::ObjectStore;
 use zksync_types::MiniblockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
@@ -128,6 +129,7 @@ struct OptionalApiParams {
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
+    blob_store: Option<Arc<dyn ObjectStore>>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -259,6 +261,13 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables `zks_getBatchBlobs` method that returns EIP-4844 blobs archived by the ETH sender
+    /// in the specified object store.
+    pub fn with_blob_store(mut self, blob_store: Arc<dyn ObjectStore>) -> Self {
+        self.optional.blob_store = Some(blob_store);
+        self
+    }
+
     #[cfg(test)]
     fn with_pub_sub_events(mut self, sender: mpsc::UnboundedSender<PubSubEvent>) -> Self {
         self.optional.pub_sub_events_sender = Some(sender);
@@ -336,6 +345,7 @@ impl ApiServer {
             mempool_cache,
             last_sealed_miniblock,
             tree_api: self.optional.tree_api,
+            blob_store: self.optional.blob_store,
         })
     }
 
@@ -3,12 +3,15 @@ use std::{collections::HashMap, convert::TryInto};
 use anyhow::Context as _;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_mini_merkle_tree::MiniMerkleTree;
+use zksync_object_store::ObjectStoreError;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchBlob, L1BatchDetails, L2ToL1LogProof,
+        Proof, ProtocolVersion, StorageProof, StorageRangeBoundary, StorageRangeEntry,
+        StorageRangeProof, TransactionDetails,
     },
+    eth_sender::EthTxBlobSidecar,
     fee::Fee,
     fee_model::FeeParams,
     l1::L1Tx,
@@ -509,17 +512,7 @@ impl ZksNamespace {
         let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
         let proofs = match proofs_result {
             Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
+            Err(err) => return Self::handle_tree_api_error(err, l1_batch_number),
         };
 
         let storage_proof = proofs
@@ -538,4 +531,113 @@ impl ZksNamespace {
             storage_proof,
         }))
     }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn get_range_proof_impl(
+        &self,
+        start_key: U256,
+        end_key: U256,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<StorageRangeProof>, Web3Error> {
+        if start_key > end_key {
+            return Err(Web3Error::InvalidKeyRange);
+        }
+        self.state.start_info.ensure_not_pruned(l1_batch_number)?;
+        let tree_api = self
+            .state
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::TreeApiUnavailable)?;
+        let proof_result = tree_api
+            .get_range_proof(l1_batch_number, start_key, end_key)
+            .await;
+        let proof = match proof_result {
+            Ok(proof) => proof,
+            Err(err) => return Self::handle_tree_api_error(err, l1_batch_number),
+        };
+
+        let entries = proof
+            .entries
+            .into_iter()
+            .map(|entry| StorageRangeEntry {
+                key: entry.key,
+                value: entry.value,
+                index: entry.index,
+            })
+            .collect();
+        Ok(Some(StorageRangeProof {
+            start: StorageRangeBoundary {
+                key: proof.start_key,
+                proof: proof.start.merkle_path,
+                value: proof.start.value,
+                index: proof.start.index,
+            },
+            entries,
+            end: StorageRangeBoundary {
+                key: proof.end_key,
+                proof: proof.end.merkle_path,
+                value: proof.end.value,
+                index: proof.end.index,
+            },
+        }))
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_batch_blobs_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<Vec<L1BatchBlob>>, Web3Error> {
+        let blob_store = self
+            .state
+            .blob_store
+            .as_deref()
+            .ok_or(Web3Error::NotImplemented)?;
+        let sidecar = match blob_store.get::<EthTxBlobSidecar>(l1_batch_number).await {
+            Ok(sidecar) => sidecar,
+            Err(ObjectStoreError::KeyNotFound(_)) => return Ok(None),
+            Err(err) => {
+                let err = anyhow::Error::from(err).context(format!(
+                    "failed loading blob sidecar for L1 batch #{l1_batch_number}"
+                ));
+                return Err(Web3Error::InternalError(err));
+            }
+        };
+
+        let EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar) = sidecar;
+        let blobs = sidecar
+            .blobs
+            .into_iter()
+            .zip(0..)
+            .map(|(blob, index)| L1BatchBlob {
+                index,
+                blob: blob.blob.into(),
+                kzg_commitment: blob.commitment.into(),
+                kzg_proof: blob.proof.into(),
+                versioned_hash: H256::from_slice(&blob.versioned_hash),
+            })
+            .collect();
+        Ok(Some(blobs))
+    }
+
+    /// Converts a tree API error into the RPC result. Returns `Ok(None)` if the requested L1 batch
+    /// is not yet processed by the Merkle tree.
+    fn handle_tree_api_error<T>(
+        err: TreeApiError,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<T>, Web3Error> {
+        match err {
+            TreeApiError::NotReady => Err(Web3Error::TreeApiUnavailable),
+            TreeApiError::NoVersion(err) => {
+                if err.missing_version > err.version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            TreeApiError::InvalidRange { .. } => Err(Web3Error::InvalidKeyRange),
+            TreeApiError::Internal(err) => Err(Web3Error::InternalError(err)),
+        }
+    }
 }
@@ -0,0 +1,385 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::DADispatcherConfig;
+use zksync_types::L1BatchNumber;
+
+use super::{DataAvailabilityClient, DataAvailabilityError, DispatchResponse, InclusionData};
+
+/// Size of the user-specified part of a Celestia namespace ID.
+const NAMESPACE_ID_LEN: usize = 10;
+/// Number of leading zero bytes in version-0 Celestia namespace IDs.
+const NAMESPACE_ID_ZERO_PREFIX_LEN: usize = 18;
+/// Maximum size of a blob that fits into a Celestia block with the default (64x64) max square size.
+const MAX_BLOB_SIZE: usize = 1_973_786;
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'static str,
+    params: &'a serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<serde_json::Value>,
+    error: Option<JsonRpcError>,
+}
+
+/// Blob as represented in the Celestia node API. Binary fields are base64-encoded.
+#[derive(Debug, Serialize, Deserialize)]
+struct CelestiaBlob {
+    namespace: String,
+    data: String,
+    share_version: u32,
+    /// Commitment is computed by the node on submission, so it's omitted in submitted blobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    commitment: Option<String>,
+}
+
+/// Error returned by a Celestia node API call.
+#[derive(Debug)]
+enum CallError {
+    Transport(anyhow::Error),
+    Rpc(JsonRpcError),
+}
+
+impl CallError {
+    /// Converts this error treating JSON-RPC errors as blob rejections.
+    fn into_rejection(self) -> DataAvailabilityError {
+        match self {
+            Self::Transport(err) => DataAvailabilityError::Transport(err),
+            Self::Rpc(err) => DataAvailabilityError::Rejected(format!(
+                "Celestia node returned error {}: {}",
+                err.code, err.message
+            )),
+        }
+    }
+
+    fn into_transport(self) -> DataAvailabilityError {
+        DataAvailabilityError::Transport(match self {
+            Self::Transport(err) => err,
+            Self::Rpc(err) => {
+                anyhow::anyhow!("Celestia node returned error {}: {}", err.code, err.message)
+            }
+        })
+    }
+}
+
+/// DA client posting blobs to Celestia via the JSON-RPC API of a Celestia light node.
+///
+/// A blob ID has the `{height}:{commitment}` format, where `height` is the Celestia block height
+/// including the blob and `commitment` is the hex-encoded blob commitment. Since Celestia has single-slot
+/// finality, a blob is included as soon as its submission succeeds; the inclusion data is the JSON-encoded
+/// blob inclusion proof returned by the node.
+#[derive(Debug, Clone)]
+pub struct CelestiaClient {
+    url: String,
+    auth_token: Option<String>,
+    /// Base64-encoded full (29-byte) namespace.
+    namespace: String,
+    client: reqwest::Client,
+}
+
+impl CelestiaClient {
+    pub fn new(
+        url: String,
+        auth_token: Option<String>,
+        namespace_id: [u8; NAMESPACE_ID_LEN],
+    ) -> Self {
+        // Version-0 namespace: version byte, 18 zero bytes and the user-specified ID.
+        let mut namespace = vec![0_u8; 1 + NAMESPACE_ID_ZERO_PREFIX_LEN];
+        namespace.extend_from_slice(&namespace_id);
+        Self {
+            url,
+            auth_token,
+            namespace: BASE64.encode(namespace),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn from_config(config: &DADispatcherConfig) -> anyhow::Result<Self> {
+        let url = config
+            .celestia_api_node_url
+            .clone()
+            .context("Celestia API node URL is not configured")?;
+        let namespace = config
+            .celestia_namespace
+            .as_deref()
+            .context("Celestia namespace is not configured")?;
+        let namespace = hex::decode(namespace.strip_prefix("0x").unwrap_or(namespace))
+            .context("Celestia namespace is not hex-encoded")?;
+        let namespace_id = namespace.try_into().map_err(|_| {
+            anyhow::anyhow!("Celestia namespace must be {NAMESPACE_ID_LEN} bytes long")
+        })?;
+        Ok(Self::new(
+            url,
+            config.celestia_auth_token.clone(),
+            namespace_id,
+        ))
+    }
+
+    async fn call(
+        &self,
+        method: &'static str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, CallError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params: &params,
+        };
+        let mut request_builder = self.client.post(&self.url).json(&request);
+        if let Some(token) = &self.auth_token {
+            request_builder = request_builder.bearer_auth(token);
+        }
+        let response: JsonRpcResponse = request_builder
+            .send()
+            .await
+            .with_context(|| format!("failed sending `{method}` request to Celestia node"))
+            .map_err(CallError::Transport)?
+            .error_for_status()
+            .map_err(|err| CallError::Transport(err.into()))?
+            .json()
+            .await
+            .with_context(|| format!("failed parsing `{method}` response from Celestia node"))
+            .map_err(CallError::Transport)?;
+
+        if let Some(err) = response.error {
+            return Err(CallError::Rpc(err));
+        }
+        Ok(response.result.unwrap_or_default())
+    }
+
+    /// Finds the commitment of the blob with the specified data included at `height`.
+    async fn get_commitment(
+        &self,
+        height: u64,
+        encoded_data: &str,
+    ) -> Result<String, DataAvailabilityError> {
+        let blobs = self
+            .call(
+                "blob.GetAll",
+                serde_json::json!([height, [&self.namespace]]),
+            )
+            .await
+            .map_err(CallError::into_transport)?;
+        let blobs: Vec<CelestiaBlob> = serde_json::from_value(blobs)
+            .context("failed parsing blobs returned by Celestia node")
+            .map_err(DataAvailabilityError::Transport)?;
+        let commitment = blobs
+            .into_iter()
+            .find(|blob| blob.data == encoded_data)
+            .and_then(|blob| blob.commitment)
+            .with_context(|| format!("submitted blob is not found at Celestia height {height}"))
+            .map_err(DataAvailabilityError::Transport)?;
+        let commitment = BASE64
+            .decode(commitment)
+            .context("Celestia node returned malformed blob commitment")
+            .map_err(DataAvailabilityError::Transport)?;
+        Ok(hex::encode(commitment))
+    }
+
+    fn parse_blob_id(blob_id: &str) -> anyhow::Result<(u64, Vec<u8>)> {
+        let (height, commitment) = blob_id
+            .split_once(':')
+            .with_context(|| format!("malformed Celestia blob ID: {blob_id}"))?;
+        let height = height
+            .parse()
+            .with_context(|| format!("malformed height in Celestia blob ID: {blob_id}"))?;
+        let commitment = hex::decode(commitment)
+            .with_context(|| format!("malformed commitment in Celestia blob ID: {blob_id}"))?;
+        Ok((height, commitment))
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for CelestiaClient {
+    fn name(&self) -> &'static str {
+        "celestia"
+    }
+
+    async fn dispatch_blob(
+        &self,
+        _l1_batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DataAvailabilityError> {
+        // Oversized blobs would be rejected by the node anyway; checking the size locally
+        // provides a clearer error message.
+        if data.len() > MAX_BLOB_SIZE {
+            return Err(DataAvailabilityError::Rejected(format!(
+                "blob size ({} bytes) exceeds the maximum Celestia blob size ({MAX_BLOB_SIZE} bytes)",
+                data.len()
+            )));
+        }
+        let blob = CelestiaBlob {
+            namespace: self.namespace.clone(),
+            data: BASE64.encode(data),
+            share_version: 0,
+            commitment: None,
+        };
+        // Empty submit options mean that the node uses the default gas price and its default key.
+        let height = self
+            .call("blob.Submit", serde_json::json!([[&blob], {}]))
+            .await
+            .map_err(CallError::into_rejection)?;
+        let height = height
+            .as_u64()
+            .with_context(|| format!("Celestia node returned invalid height: {height}"))
+            .map_err(DataAvailabilityError::Transport)?;
+
+        let commitment = self.get_commitment(height, &blob.data).await?;
+        Ok(DispatchResponse {
+            blob_id: format!("{height}:{commitment}"),
+        })
+    }
+
+    async fn get_inclusion_data(
+        &self,
+        blob_id: &str,
+    ) -> Result<Option<InclusionData>, DataAvailabilityError> {
+        let (height, commitment) =
+            Self::parse_blob_id(blob_id).map_err(DataAvailabilityError::Transport)?;
+        let proof = self
+            .call(
+                "blob.GetProof",
+                serde_json::json!([height, &self.namespace, BASE64.encode(commitment)]),
+            )
+            .await
+            .map_err(CallError::into_transport)?;
+        if proof.is_null() {
+            return Ok(None);
+        }
+        let data = serde_json::to_vec(&proof)
+            .context("failed serializing Celestia inclusion proof")
+            .map_err(DataAvailabilityError::Transport)?;
+        Ok(Some(InclusionData { data }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+    use zksync_config::configs::DataAvailabilityMode;
+
+    use super::*;
+
+    type SubmittedBlobs = Arc<Mutex<Vec<serde_json::Value>>>;
+
+    const HEIGHT: u64 = 123;
+
+    async fn handle_request(
+        State(submitted_blobs): State<SubmittedBlobs>,
+        headers: HeaderMap,
+        Json(request): Json<serde_json::Value>,
+    ) -> Json<serde_json::Value> {
+        assert_eq!(headers["authorization"], "Bearer secret");
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap() {
+            "blob.Submit" => {
+                let mut blob = params[0][0].clone();
+                blob["commitment"] = BASE64.encode([0xcc; 32]).into();
+                submitted_blobs.lock().unwrap().push(blob);
+                serde_json::json!(HEIGHT)
+            }
+            "blob.GetAll" => {
+                assert_eq!(params[0], HEIGHT);
+                serde_json::Value::Array(submitted_blobs.lock().unwrap().clone())
+            }
+            "blob.GetProof" => {
+                assert_eq!(params[0], HEIGHT);
+                assert_eq!(params[2], BASE64.encode([0xcc; 32]));
+                serde_json::json!([{ "start": 0, "end": 1 }])
+            }
+            method => panic!("unexpected method: {method}"),
+        };
+        Json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": result,
+        }))
+    }
+
+    fn test_config(url: String) -> DADispatcherConfig {
+        DADispatcherConfig {
+            mode: DataAvailabilityMode::Celestia,
+            celestia_api_node_url: Some(url),
+            celestia_auth_token: Some("secret".to_owned()),
+            celestia_namespace: Some("000000000000007a6b73".to_owned()),
+            polling_interval_ms: None,
+            max_batches_to_dispatch: None,
+            retry_backoff_ms: None,
+        }
+    }
+
+    #[test]
+    fn namespace_encoding() {
+        let client = CelestiaClient::from_config(&test_config("http://localhost/".into())).unwrap();
+        let namespace = BASE64.decode(&client.namespace).unwrap();
+        assert_eq!(namespace.len(), 29);
+        assert!(namespace[..26].iter().all(|&byte| byte == 0));
+        assert_eq!(namespace[26..], *b"zks");
+
+        let mut config = test_config("http://localhost/".into());
+        config.celestia_namespace = Some("0x0123".to_owned());
+        CelestiaClient::from_config(&config).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn oversized_blob_is_rejected() {
+        // The client should not send any requests, so the URL is never accessed.
+        let client = CelestiaClient::from_config(&test_config("http://localhost/".into())).unwrap();
+        let err = client
+            .dispatch_blob(L1BatchNumber(1), vec![0; MAX_BLOB_SIZE + 1])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DataAvailabilityError::Rejected(msg) if msg.contains("exceeds")),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatching_blob_to_celestia() {
+        let submitted_blobs = SubmittedBlobs::default();
+        let app = Router::new()
+            .route("/", post(handle_request))
+            .with_state(submitted_blobs.clone());
+        let server = axum::Server::bind(&([127, 0, 0, 1], 0).into()).serve(app.into_make_service());
+        let url = format!("http://{}/", server.local_addr());
+        tokio::spawn(server);
+
+        let client = CelestiaClient::from_config(&test_config(url)).unwrap();
+        let response = client
+            .dispatch_blob(L1BatchNumber(1), vec![1; 64])
+            .await
+            .unwrap();
+        assert_eq!(
+            response.blob_id,
+            format!("{HEIGHT}:{}", hex::encode([0xcc; 32]))
+        );
+        let submitted_blobs = submitted_blobs.lock().unwrap().clone();
+        assert_eq!(submitted_blobs.len(), 1);
+        assert_eq!(submitted_blobs[0]["data"], BASE64.encode([1; 64]));
+        assert_eq!(submitted_blobs[0]["namespace"], client.namespace);
+
+        let inclusion = client
+            .get_inclusion_data(&response.blob_id)
+            .await
+            .unwrap()
+            .expect("blob is not included");
+        let proof: serde_json::Value = serde_json::from_slice(&inclusion.data).unwrap();
+        assert_eq!(proof, serde_json::json!([{ "start": 0, "end": 1 }]));
+    }
+}
@@ -0,0 +1,420 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use chrono::Utc;
+use tokio::{sync::watch, time::Instant};
+use zksync_config::configs::DADispatcherConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
+
+use super::{
+    metrics::{DispatchErrorKind, METRICS},
+    DataAvailabilityClient, DataAvailabilityError,
+};
+
+/// Error returned by a single iteration of the dispatcher.
+#[derive(Debug, thiserror::Error)]
+enum DispatchError {
+    #[error(transparent)]
+    Layer(#[from] DataAvailabilityError),
+    /// Non-retriable error, e.g. an error accessing Postgres.
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl DataAvailabilityError {
+    fn kind(&self) -> DispatchErrorKind {
+        match self {
+            Self::Transport(_) => DispatchErrorKind::Transport,
+            Self::Rejected(_) => DispatchErrorKind::Rejected,
+        }
+    }
+}
+
+/// Component dispatching pubdata of sealed L1 batches to the data availability (DA) layer and tracking
+/// the inclusion of dispatched blobs.
+///
+/// L1 batches are dispatched in the ascending order; the blob ID assigned by the DA layer is persisted
+/// in Postgres. Once the DA layer confirms the blob inclusion, the inclusion data is persisted as well,
+/// which allows the ETH sender to commit the L1 batch. Failed DA layer requests are retried with exponential backoff,
+/// except for blobs rejected by the DA layer: retrying those is pointless, so the dispatcher terminates with an error
+/// (which stops the node) and requires manual intervention.
+#[derive(Debug)]
+pub struct DataAvailabilityDispatcher {
+    pool: ConnectionPool<Core>,
+    client: Arc<dyn DataAvailabilityClient>,
+    config: DADispatcherConfig,
+    health_updater: HealthUpdater,
+}
+
+impl DataAvailabilityDispatcher {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        client: Arc<dyn DataAvailabilityClient>,
+        config: DADispatcherConfig,
+    ) -> Self {
+        Self {
+            pool,
+            client,
+            config,
+            health_updater: ReactiveHealthCheck::new("da_dispatcher").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.health_updater.update(HealthStatus::Ready.into());
+        let mut consecutive_failures = 0_u32;
+        while !*stop_receiver.borrow_and_update() {
+            let delay = match self.step().await {
+                Ok(true) => {
+                    consecutive_failures = 0;
+                    continue;
+                }
+                Ok(false) => {
+                    consecutive_failures = 0;
+                    self.config.polling_interval()
+                }
+                Err(DispatchError::Internal(err)) => return Err(err),
+                Err(DispatchError::Layer(err @ DataAvailabilityError::Rejected(_))) => {
+                    METRICS.dispatch_failures[&err.kind()].inc();
+                    let context = format!(
+                        "DA layer `{}` rejected a blob; this requires manual intervention",
+                        self.client.name()
+                    );
+                    return Err(anyhow::Error::from(err).context(context));
+                }
+                Err(DispatchError::Layer(err)) => {
+                    METRICS.dispatch_failures[&err.kind()].inc();
+                    consecutive_failures += 1;
+                    let delay = self.config.retry_delay(consecutive_failures);
+                    tracing::warn!(
+                        "Failed communicating with DA layer `{}`: {err}; retrying in {delay:?}",
+                        self.client.name()
+                    );
+                    delay
+                }
+            };
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(delay, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, DA dispatcher is shutting down");
+        Ok(())
+    }
+
+    /// Performs a single dispatcher iteration. Returns `false` if there was nothing to do.
+    async fn step(&self) -> Result<bool, DispatchError> {
+        let dispatched = self.dispatch().await?;
+        let included = self.poll_for_inclusion().await?;
+        Ok(dispatched || included)
+    }
+
+    /// Dispatches pubdata for the next L1 batches. Returns `false` if there are no L1 batches to dispatch.
+    async fn dispatch(&self) -> Result<bool, DispatchError> {
+        let batches = self
+            .pool
+            .connection_tagged("da_dispatcher")
+            .await?
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(self.config.max_batches_to_dispatch())
+            .await
+            .context("get_ready_for_da_dispatch_l1_batches()")?;
+
+        for batch in &batches {
+            let l1_batch_number = batch.l1_batch_number;
+            let blob_size = batch.pubdata.len();
+            let started_at = Instant::now();
+            let response = self
+                .client
+                .dispatch_blob(l1_batch_number, batch.pubdata.clone())
+                .await?;
+            METRICS.blob_dispatch_latency.observe(started_at.elapsed());
+            METRICS.blob_size.observe(blob_size);
+
+            self.pool
+                .connection_tagged("da_dispatcher")
+                .await?
+                .data_availability_dal()
+                .insert_l1_batch_da(l1_batch_number, &response.blob_id, Utc::now().naive_utc())
+                .await
+                .context("insert_l1_batch_da()")?;
+            METRICS
+                .last_dispatched_l1_batch
+                .set(l1_batch_number.0.into());
+            tracing::info!(
+                "Dispatched pubdata for L1 batch #{l1_batch_number} ({blob_size} bytes) to DA layer `{}`; blob ID: {}",
+                self.client.name(),
+                response.blob_id
+            );
+        }
+        Ok(!batches.is_empty())
+    }
+
+    /// Checks the inclusion of the oldest dispatched blob. Returns `false` if there are no blobs
+    /// awaiting inclusion or the oldest blob is not included yet.
+    async fn poll_for_inclusion(&self) -> Result<bool, DispatchError> {
+        let Some(blob) = self
+            .pool
+            .connection_tagged("da_dispatcher")
+            .await?
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .context("get_first_da_blob_awaiting_inclusion()")?
+        else {
+            return Ok(false);
+        };
+
+        let Some(inclusion_data) = self.client.get_inclusion_data(&blob.blob_id).await? else {
+            return Ok(false);
+        };
+        self.pool
+            .connection_tagged("da_dispatcher")
+            .await?
+            .data_availability_dal()
+            .save_l1_batch_inclusion_data(blob.l1_batch_number, &inclusion_data.data)
+            .await
+            .context("save_l1_batch_inclusion_data()")?;
+
+        let inclusion_latency = (Utc::now().naive_utc() - blob.sent_at)
+            .to_std()
+            .unwrap_or_default();
+        METRICS.inclusion_latency.observe(inclusion_latency);
+        METRICS
+            .last_included_l1_batch
+            .set(blob.l1_batch_number.0.into());
+        tracing::info!(
+            "Pubdata for L1 batch #{} is included on DA layer `{}` in {inclusion_latency:?}",
+            blob.l1_batch_number,
+            self.client.name()
+        );
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use zksync_config::configs::DataAvailabilityMode;
+    use zksync_dal::Connection;
+    use zksync_object_store::ObjectStoreFactory;
+    use zksync_types::{L1BatchNumber, ProtocolVersion};
+
+    use super::*;
+    use crate::{
+        data_availability::{DispatchResponse, InclusionData, ObjectStoreDAClient},
+        utils::testonly::create_l1_batch,
+    };
+
+    fn test_config() -> DADispatcherConfig {
+        DADispatcherConfig {
+            mode: DataAvailabilityMode::ObjectStore,
+            celestia_api_node_url: None,
+            celestia_auth_token: None,
+            celestia_namespace: None,
+            polling_interval_ms: Some(10),
+            max_batches_to_dispatch: Some(2),
+            retry_backoff_ms: Some(1),
+        }
+    }
+
+    async fn prepare_storage(storage: &mut Connection<'_, Core>, l1_batch_count: u32) {
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        for number in 0..=l1_batch_count {
+            let mut header = create_l1_batch(number);
+            header.pubdata_input = Some(vec![number as u8; 32]);
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn object_store_client() -> Arc<ObjectStoreDAClient> {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        Arc::new(ObjectStoreDAClient::new(store))
+    }
+
+    #[tokio::test]
+    async fn dispatching_and_polling_for_inclusion() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        prepare_storage(&mut storage, 3).await;
+
+        let client = object_store_client().await;
+        let dispatcher = DataAvailabilityDispatcher::new(pool.clone(), client, test_config());
+        // Only 2 batches should be dispatched per iteration.
+        assert!(dispatcher.dispatch().await.unwrap());
+        let blob = storage
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap()
+            .expect("no dispatched blobs");
+        assert_eq!(blob.l1_batch_number, L1BatchNumber(1));
+        let ready = storage
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(10)
+            .await
+            .unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].l1_batch_number, L1BatchNumber(3));
+
+        assert!(dispatcher.poll_for_inclusion().await.unwrap());
+        let first_without_inclusion = storage
+            .data_availability_dal()
+            .get_first_l1_batch_without_inclusion_data(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(first_without_inclusion, Some(L1BatchNumber(2)));
+
+        assert!(dispatcher.dispatch().await.unwrap());
+        assert!(!dispatcher.dispatch().await.unwrap());
+        assert!(dispatcher.poll_for_inclusion().await.unwrap());
+        assert!(dispatcher.poll_for_inclusion().await.unwrap());
+        assert!(!dispatcher.poll_for_inclusion().await.unwrap());
+        let first_without_inclusion = storage
+            .data_availability_dal()
+            .get_first_l1_batch_without_inclusion_data(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(first_without_inclusion, None);
+    }
+
+    /// DA client failing the specified number of dispatches before delegating to the wrapped client.
+    #[derive(Debug)]
+    struct FlakyClient {
+        inner: Arc<ObjectStoreDAClient>,
+        failures_left: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DataAvailabilityClient for FlakyClient {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn dispatch_blob(
+            &self,
+            l1_batch_number: L1BatchNumber,
+            data: Vec<u8>,
+        ) -> Result<DispatchResponse, DataAvailabilityError> {
+            let failures_left = self.failures_left.load(Ordering::SeqCst);
+            if failures_left > 0 {
+                self.failures_left
+                    .store(failures_left - 1, Ordering::SeqCst);
+                return Err(DataAvailabilityError::Transport(anyhow::anyhow!(
+                    "connection reset"
+                )));
+            }
+            self.inner.dispatch_blob(l1_batch_number, data).await
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            blob_id: &str,
+        ) -> Result<Option<InclusionData>, DataAvailabilityError> {
+            self.inner.get_inclusion_data(blob_id).await
+        }
+    }
+
+    /// DA client rejecting all blobs.
+    #[derive(Debug)]
+    struct RejectingClient;
+
+    #[async_trait]
+    impl DataAvailabilityClient for RejectingClient {
+        fn name(&self) -> &'static str {
+            "rejecting"
+        }
+
+        async fn dispatch_blob(
+            &self,
+            _l1_batch_number: L1BatchNumber,
+            _data: Vec<u8>,
+        ) -> Result<DispatchResponse, DataAvailabilityError> {
+            Err(DataAvailabilityError::Rejected(
+                "blob is too large".to_owned(),
+            ))
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            _blob_id: &str,
+        ) -> Result<Option<InclusionData>, DataAvailabilityError> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatcher_fails_on_rejected_blob() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        prepare_storage(&mut storage, 1).await;
+
+        let dispatcher =
+            DataAvailabilityDispatcher::new(pool.clone(), Arc::new(RejectingClient), test_config());
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = tokio::time::timeout(Duration::from_secs(10), dispatcher.run(stop_receiver))
+            .await
+            .expect("dispatcher did not terminate")
+            .unwrap_err();
+        let err = format!("{err:#}");
+        assert!(err.contains("blob is too large"), "{err}");
+
+        let ready = storage
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(10)
+            .await
+            .unwrap();
+        assert_eq!(ready.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dispatcher_retries_failed_dispatches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        prepare_storage(&mut storage, 3).await;
+
+        let client = Arc::new(FlakyClient {
+            inner: object_store_client().await,
+            failures_left: AtomicUsize::new(3),
+        });
+        let dispatcher = DataAvailabilityDispatcher::new(pool.clone(), client, test_config());
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let dispatcher_task = tokio::spawn(dispatcher.run(stop_receiver));
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let first_without_inclusion = storage
+                    .data_availability_dal()
+                    .get_first_l1_batch_without_inclusion_data(L1BatchNumber(1))
+                    .await
+                    .unwrap();
+                if first_without_inclusion.is_none() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("L1 batches were not dispatched in time");
+
+        stop_sender.send_replace(true);
+        dispatcher_task.await.unwrap().unwrap();
+    }
+}
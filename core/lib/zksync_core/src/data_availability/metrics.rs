@@ -0,0 +1,36 @@
+//! Metrics for the data availability dispatcher.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+
+/// Kind of a DA dispatcher error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum DispatchErrorKind {
+    Transport,
+    Rejected,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_da_dispatcher")]
+pub(super) struct DataAvailabilityDispatcherMetrics {
+    /// Latency of dispatching a single blob to the DA layer.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub blob_dispatch_latency: Histogram<Duration>,
+    /// Time between dispatching a blob and confirming its inclusion on the DA layer.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub inclusion_latency: Histogram<Duration>,
+    /// Size of dispatched blobs in bytes.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=16_777_216.0, 4.0))]
+    pub blob_size: Histogram<usize>,
+    /// Number of failed DA layer requests.
+    pub dispatch_failures: Family<DispatchErrorKind, Counter>,
+    /// Number of the last L1 batch dispatched to the DA layer.
+    pub last_dispatched_l1_batch: Gauge<u64>,
+    /// Number of the last L1 batch with pubdata inclusion confirmed by the DA layer.
+    pub last_included_l1_batch: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<DataAvailabilityDispatcherMetrics> = vise::Global::new();
@@ -0,0 +1,80 @@
+//! Data availability (DA) integration for Validium mode. Pubdata of sealed L1 batches is dispatched
+//! to a [`DataAvailabilityClient`] by [`DataAvailabilityDispatcher`]; once the DA layer confirms
+//! the inclusion of a blob, the corresponding L1 batch can be committed to L1.
+
+use std::{fmt, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_config::configs::{DADispatcherConfig, DataAvailabilityMode};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L1BatchNumber;
+
+pub use self::{
+    celestia::CelestiaClient, dispatcher::DataAvailabilityDispatcher,
+    object_store::ObjectStoreDAClient,
+};
+
+mod celestia;
+mod dispatcher;
+mod metrics;
+mod object_store;
+
+/// Errors returned by a [`DataAvailabilityClient`].
+#[derive(Debug, thiserror::Error)]
+pub enum DataAvailabilityError {
+    /// Error communicating with the DA layer, e.g. a network error. Such errors are retriable.
+    #[error("data availability layer transport error: {0}")]
+    Transport(#[source] anyhow::Error),
+    /// Blob was rejected by the DA layer, e.g. because it is too large.
+    #[error("blob was rejected by data availability layer: {0}")]
+    Rejected(String),
+}
+
+/// Response to a successful blob dispatch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchResponse {
+    /// ID of the blob assigned by the DA layer. Used to query the blob inclusion.
+    pub blob_id: String,
+}
+
+/// Data proving the blob inclusion on the DA layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InclusionData {
+    pub data: Vec<u8>,
+}
+
+/// Client for a data availability layer.
+#[async_trait]
+pub trait DataAvailabilityClient: 'static + fmt::Debug + Send + Sync {
+    /// Returns the name of the DA layer used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Dispatches the pubdata of an L1 batch as a blob.
+    async fn dispatch_blob(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DataAvailabilityError>;
+
+    /// Returns inclusion data for a previously dispatched blob, or `None` if the blob is not included yet.
+    async fn get_inclusion_data(
+        &self,
+        blob_id: &str,
+    ) -> Result<Option<InclusionData>, DataAvailabilityError>;
+}
+
+/// Creates a DA client specified in the config.
+pub async fn create_da_client(
+    config: &DADispatcherConfig,
+    store_factory: &ObjectStoreFactory,
+) -> anyhow::Result<Arc<dyn DataAvailabilityClient>> {
+    Ok(match config.mode {
+        DataAvailabilityMode::ObjectStore => {
+            Arc::new(ObjectStoreDAClient::new(store_factory.create_store().await))
+        }
+        DataAvailabilityMode::Celestia => Arc::new(
+            CelestiaClient::from_config(config).context("failed creating Celestia client")?,
+        ),
+    })
+}
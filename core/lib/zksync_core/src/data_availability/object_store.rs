@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+use zksync_types::{web3::signing::keccak256, L1BatchNumber};
+
+use super::{DataAvailabilityClient, DataAvailabilityError, DispatchResponse, InclusionData};
+
+/// DA client storing blobs in an [`ObjectStore`]. Blobs are considered included as soon as they are stored;
+/// the inclusion data is the keccak256 hash of the blob. Should only be used in tests and local setups.
+#[derive(Debug, Clone)]
+pub struct ObjectStoreDAClient {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl ObjectStoreDAClient {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store }
+    }
+
+    fn blob_key(l1_batch_number: L1BatchNumber, hash: &[u8; 32]) -> String {
+        format!("l1_batch_{l1_batch_number}_{}.bin", hex::encode(hash))
+    }
+
+    /// Extracts the blob hash from a blob key produced by [`Self::blob_key()`].
+    fn parse_blob_hash(blob_id: &str) -> anyhow::Result<Vec<u8>> {
+        let hash = blob_id
+            .strip_suffix(".bin")
+            .and_then(|key| key.rsplit_once('_'))
+            .with_context(|| format!("malformed blob ID: {blob_id}"))?
+            .1;
+        hex::decode(hash).with_context(|| format!("malformed blob hash in blob ID: {blob_id}"))
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for ObjectStoreDAClient {
+    fn name(&self) -> &'static str {
+        "object_store"
+    }
+
+    async fn dispatch_blob(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DataAvailabilityError> {
+        let blob_id = Self::blob_key(l1_batch_number, &keccak256(&data));
+        self.store
+            .put_raw(Bucket::DataAvailability, &blob_id, data)
+            .await
+            .map_err(|err| DataAvailabilityError::Transport(err.into()))?;
+        Ok(DispatchResponse { blob_id })
+    }
+
+    async fn get_inclusion_data(
+        &self,
+        blob_id: &str,
+    ) -> Result<Option<InclusionData>, DataAvailabilityError> {
+        let hash = Self::parse_blob_hash(blob_id).map_err(DataAvailabilityError::Transport)?;
+        match self.store.head_raw(Bucket::DataAvailability, blob_id).await {
+            Ok(_) => Ok(Some(InclusionData { data: hash })),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(DataAvailabilityError::Transport(err.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::ObjectStoreFactory;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn dispatching_blob_to_object_store() {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        let client = ObjectStoreDAClient::new(store.clone());
+
+        let response = client
+            .dispatch_blob(L1BatchNumber(1), vec![1; 64])
+            .await
+            .unwrap();
+        assert!(response.blob_id.starts_with("l1_batch_1_"));
+        let stored = store
+            .get_raw(Bucket::DataAvailability, &response.blob_id)
+            .await
+            .unwrap();
+        assert_eq!(stored, [1; 64]);
+
+        let inclusion = client
+            .get_inclusion_data(&response.blob_id)
+            .await
+            .unwrap()
+            .expect("blob is not included");
+        assert_eq!(inclusion.data, keccak256(&[1; 64]));
+
+        let missing_id = ObjectStoreDAClient::blob_key(L1BatchNumber(2), &[0; 32]);
+        let inclusion = client.get_inclusion_data(&missing_id).await.unwrap();
+        assert_eq!(inclusion, None);
+    }
+}
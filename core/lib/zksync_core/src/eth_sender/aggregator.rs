@@ -1,7 +1,6 @@
 use core::fmt::Debug;
 use std::sync::Arc;
 
-use subxt::{OnlineClient, PolkadotConfig};
 use zksync_config::configs::eth_sender::{ProofLoadingMode, ProofSendingMode, SenderConfig};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, Core, CoreDal};
@@ -20,14 +19,13 @@ use zksync_utils::h256_to_u256;
 use super::{
     aggregated_operations::AggregatedOperation,
     l1_batch_commit_data_generator::L1BatchCommitDataGenerator,
+    metrics::{AttestationPathCheck, METRICS},
     publish_criterion::{
         DataSizeCriterion, GasCriterion, L1BatchPublishCriterion, NumberCriterion,
         TimestampDeadlineCriterion,
     },
 };
-
-#[subxt::subxt(runtime_metadata_path = "../../../etc/nh/metadata.scale")]
-pub mod nh {}
+use crate::proof_verification_layer::ProofVerificationLayer;
 
 pub struct Aggregator {
     commit_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
@@ -42,7 +40,10 @@ pub struct Aggregator {
     /// transactions.
     operate_4844_mode: bool,
     pubdata_da: PubdataDA,
-    nh_client: Option<OnlineClient<PolkadotConfig>>,
+    /// If set, L1 batches are only committed after their pubdata is confirmed to be available
+    /// on the data availability layer (i.e., after the DA dispatcher saves inclusion data for them).
+    da_inclusion_required: bool,
+    proof_verification_layer: Arc<dyn ProofVerificationLayer>,
 }
 
 impl Debug for Aggregator {
@@ -55,6 +56,8 @@ impl Debug for Aggregator {
             .field("blob_store", &self.blob_store)
             .field("operate_4844_mode", &self.operate_4844_mode)
             .field("pubdata_da", &self.pubdata_da)
+            .field("da_inclusion_required", &self.da_inclusion_required)
+            .field("proof_verification_layer", &self.proof_verification_layer)
             .finish()
     }
 }
@@ -65,8 +68,9 @@ impl Aggregator {
         blob_store: Arc<dyn ObjectStore>,
         operate_4844_mode: bool,
         pubdata_da: PubdataDA,
+        da_inclusion_required: bool,
         l1_batch_commit_data_generator: Arc<dyn L1BatchCommitDataGenerator>,
-        nh_client: Option<OnlineClient<PolkadotConfig>>,
+        proof_verification_layer: Arc<dyn ProofVerificationLayer>,
     ) -> Self {
         Self {
             commit_criteria: vec![
@@ -127,7 +131,8 @@ impl Aggregator {
             blob_store,
             operate_4844_mode,
             pubdata_da,
-            nh_client,
+            da_inclusion_required,
+            proof_verification_layer,
         }
     }
 
@@ -217,7 +222,7 @@ impl Aggregator {
             .get_last_committed_to_eth_l1_batch()
             .await
             .unwrap()?;
-        let ready_for_commit_l1_batches = if protocol_version_id.is_pre_boojum() {
+        let mut ready_for_commit_l1_batches = if protocol_version_id.is_pre_boojum() {
             blocks_dal
                 .pre_boojum_get_ready_for_commit_l1_batches(
                     limit,
@@ -250,6 +255,19 @@ impl Aggregator {
                 }
             });
 
+        if self.da_inclusion_required {
+            if let Some(first_batch) = ready_for_commit_l1_batches.first() {
+                let first_batch_without_inclusion = storage
+                    .data_availability_dal()
+                    .get_first_l1_batch_without_inclusion_data(first_batch.header.number)
+                    .await
+                    .unwrap();
+                if let Some(number) = first_batch_without_inclusion {
+                    ready_for_commit_l1_batches.retain(|batch| batch.header.number < number);
+                }
+            }
+        }
+
         let batches = extract_ready_subrange(
             storage,
             &mut self.commit_criteria,
@@ -311,9 +329,9 @@ impl Aggregator {
         storage: &mut Connection<'_, Core>,
         l1_verifier_config: L1VerifierConfig,
         _proof_loading_mode: &ProofLoadingMode,
-        _blob_store: &dyn ObjectStore,
+        blob_store: &dyn ObjectStore,
         is_4844_mode: bool,
-        nh_client: Option<OnlineClient<PolkadotConfig>>,
+        proof_verification_layer: &dyn ProofVerificationLayer,
     ) -> Option<ProveBatches> {
         let previous_proven_batch_number = storage
             .blocks_dal()
@@ -356,90 +374,125 @@ impl Aggregator {
                 return None;
             }
         }
-        match storage
-            .nh_dal()
-            .get_nh_attestation_from_batch_number(batch_to_prove)
-            .await
-        {
-            Some(attestation) => {
-                tracing::info!(
-                    "Found Attestation: {:?} with ID: {:?}",
-                    attestation.proofs_attestation,
-                    attestation.attestation_id
-                );
 
-                let attestation_element = storage
-                    .proof_generation_dal()
-                    .get_nh_attestation_element_from_batch_number(batch_to_prove)
-                    .await
-                    .map_err(|err| eprintln!("Failed to get the NH attestation element: {err:?}"))
-                    .ok()?
-                    .unwrap();
-
-                let nh_runtime_api = nh_client?
-                    .runtime_api()
-                    .at_latest()
-                    .await
-                    .map_err(|err| eprintln!("Failed to create NH RuntimeAPi: {err:?}"))
-                    .ok()?;
+        let proofs = if proof_verification_layer.attests_proofs() {
+            Self::load_attestation_proof(storage, batch_to_prove, proof_verification_layer)
+                .await?
+                .into()
+        } else {
+            let proofs =
+                load_wrapped_fri_proofs_for_range(batch_to_prove, batch_to_prove, blob_store).await;
+            if proofs.is_empty() {
+                // The proof is not generated yet.
+                return None;
+            }
+            proofs.into()
+        };
 
-                let poe_merkle_path_request = nh::apis()
-                    .po_e_api()
-                    .get_proof_path(attestation.attestation_id.as_u64(), attestation_element);
+        let previous_proven_batch_metadata = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(previous_proven_batch_number)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| {
+                panic!(
+                    "L1 batch #{} with submitted proof is not complete in the DB",
+                    previous_proven_batch_number
+                );
+            });
+        let metadata_for_batch_being_proved = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(batch_to_prove)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| {
+                panic!(
+                    "L1 batch #{} with generated proof is not complete in the DB",
+                    batch_to_prove
+                );
+            });
+        Some(ProveBatches {
+            prev_l1_batch: previous_proven_batch_metadata,
+            l1_batches: vec![metadata_for_batch_being_proved],
+            proofs,
+            should_verify: true,
+        })
+    }
 
-                let poe_merkle_path_response = nh_runtime_api
-                    .call(poe_merkle_path_request)
-                    .await
-                    .map_err(|err| eprintln!("Failed to get MerklePath from NH Mainchain: {err:?}"))
-                    .ok()?
-                    .unwrap();
-                tracing::info!("Poe merkle path response: {:?}", poe_merkle_path_response);
-
-                let proofs = NewHorizenProof {
-                    attestation_id: attestation.attestation_id.as_u64(),
-                    merkle_path: poe_merkle_path_response
-                        .proof
-                        .iter()
-                        .map(|el| h256_to_u256(*el))
-                        .collect(),
-                    leaf_count: poe_merkle_path_response.number_of_leaves,
-                    index: poe_merkle_path_response.leaf_index,
-                };
-                tracing::info!("Horizen Proof {:?}", proofs);
+    /// Loads the Merkle path proving that the proof for the specified L1 batch is included
+    /// into an attestation posted on L1. Returns `None` if the attestation is not posted yet,
+    /// or if the path doesn't lead to the attestation root posted on L1.
+    async fn load_attestation_proof(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+        proof_verification_layer: &dyn ProofVerificationLayer,
+    ) -> Option<NewHorizenProof> {
+        let attestation = storage
+            .nh_dal()
+            .get_nh_attestation_from_batch_number(l1_batch_number)
+            .await?;
+        let attestation_id = attestation.attestation_id.as_u64();
+        tracing::info!(
+            "Found attestation #{attestation_id} for L1 batch #{l1_batch_number}: {:?}",
+            attestation.proofs_attestation
+        );
+
+        let attestation_element = storage
+            .proof_generation_dal()
+            .get_nh_attestation_element_from_batch_number(l1_batch_number)
+            .await
+            .unwrap()?;
 
-                let previous_proven_batch_metadata = storage
-                    .blocks_dal()
-                    .get_l1_batch_metadata(previous_proven_batch_number)
-                    .await
-                    .unwrap()
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "L1 batch #{} with submitted proof is not complete in the DB",
-                            previous_proven_batch_number
-                        );
-                    });
-                let metadata_for_batch_being_proved = storage
-                    .blocks_dal()
-                    .get_l1_batch_metadata(previous_proven_batch_number + 1)
-                    .await
-                    .unwrap()
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "L1 batch #{} with generated proof is not complete in the DB",
-                            previous_proven_batch_number + 1
-                        );
-                    });
-                Some(ProveBatches {
-                    prev_l1_batch: previous_proven_batch_metadata,
-                    l1_batches: vec![metadata_for_batch_being_proved],
-                    proofs: proofs.into(),
-                    should_verify: true,
-                })
+        let path = match proof_verification_layer
+            .get_attestation_path(attestation_id, attestation_element)
+            .await
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => {
+                tracing::warn!(
+                    "No Merkle path for L1 batch #{l1_batch_number} in attestation #{attestation_id}"
+                );
+                return None;
             }
-            None => {
+            Err(err) => {
+                tracing::warn!(
+                    "Failed getting Merkle path for L1 batch #{l1_batch_number} in attestation #{attestation_id}: {err}"
+                );
                 return None;
             }
+        };
+
+        // Sending an invalid path to L1 would revert the `proveBatches` transaction, so we check it beforehand.
+        let expected_root = attestation.proofs_attestation;
+        let computed_root = path.compute_root(attestation_element);
+        let check = match computed_root {
+            Some(root) if root == expected_root => AttestationPathCheck::Valid,
+            Some(_) => AttestationPathCheck::Mismatch,
+            None => AttestationPathCheck::Malformed,
+        };
+        METRICS.attestation_path_checks[&check].inc();
+        if check != AttestationPathCheck::Valid {
+            let err = format!(
+                "Merkle path {path:?} for element {attestation_element:?} leads to root {computed_root:?}, \
+                 while attestation #{attestation_id} posted on L1 has root {expected_root:?}"
+            );
+            tracing::error!("Invalid attestation path for L1 batch #{l1_batch_number}: {err}");
+            storage
+                .nh_proof_submissions_dal()
+                .mark_proof_submission_as_path_mismatch(l1_batch_number, &err)
+                .await
+                .unwrap();
+            return None;
         }
+
+        let proof = NewHorizenProof {
+            attestation_id,
+            merkle_path: path.merkle_path.into_iter().map(h256_to_u256).collect(),
+            leaf_count: path.leaf_count,
+            index: path.index,
+        };
+        tracing::info!("Loaded attestation proof for L1 batch #{l1_batch_number}: {proof:?}");
+        Some(proof)
     }
 
     async fn prepare_dummy_proof_operation(
@@ -448,7 +501,6 @@ impl Aggregator {
         ready_for_proof_l1_batches: Vec<L1BatchWithMetadata>,
         last_sealed_l1_batch: L1BatchNumber,
     ) -> Option<ProveBatches> {
-        println!("Prepare dummy proof operation...");
         let batches = extract_ready_subrange(
             storage,
             &mut self.proof_criteria,
@@ -456,6 +508,11 @@ impl Aggregator {
             last_sealed_l1_batch,
         )
         .await?;
+        tracing::debug!(
+            "Preparing dummy proof operation for L1 batches #{}..=#{}",
+            batches.first()?.header.number,
+            batches.last()?.header.number
+        );
 
         let prev_l1_batch_number = batches.first().map(|batch| batch.header.number - 1)?;
         let prev_batch = storage
@@ -481,14 +538,13 @@ impl Aggregator {
     ) -> Option<ProveBatches> {
         match self.config.proof_sending_mode {
             ProofSendingMode::OnlyRealProofs => {
-                //println!("Get_proof_operation => OnlyRealProofs");
                 Self::load_real_proof_operation(
                     storage,
                     l1_verifier_config,
                     &self.config.proof_loading_mode,
                     &*self.blob_store,
                     self.operate_4844_mode,
-                    self.nh_client.clone(),
+                    self.proof_verification_layer.as_ref(),
                 )
                 .await
             }
@@ -512,7 +568,7 @@ impl Aggregator {
                     &self.config.proof_loading_mode,
                     &*self.blob_store,
                     self.operate_4844_mode,
-                    self.nh_client.clone(),
+                    self.proof_verification_layer.as_ref(),
                 )
                 .await
                 {
@@ -537,6 +593,11 @@ impl Aggregator {
     pub fn pubdata_da(&self) -> PubdataDA {
         self.pubdata_da
     }
+
+    /// Returns the object store used to load proofs and archive blob sidecars.
+    pub(super) fn blob_store(&self) -> &dyn ObjectStore {
+        self.blob_store.as_ref()
+    }
 }
 
 async fn extract_ready_subrange(
@@ -564,7 +625,7 @@ async fn extract_ready_subrange(
     )
 }
 
-pub async fn _load_wrapped_fri_proofs_for_range(
+pub async fn load_wrapped_fri_proofs_for_range(
     from: L1BatchNumber,
     to: L1BatchNumber,
     blob_store: &dyn ObjectStore,
@@ -0,0 +1,317 @@
+//! Verification of EIP-4844 blob sidecars archived by [`EthTxAggregator`](super::EthTxAggregator).
+
+use std::{fmt, sync::Arc};
+
+use anyhow::Context as _;
+use sha2::{Digest, Sha256};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_eth_client::EthInterface;
+use zksync_l1_contract_interface::i_executor::commit::kzg::compute_blob_commitment;
+use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_types::{
+    eth_sender::EthTxBlobSidecar,
+    ethabi::{self, Token},
+    L1BatchNumber, ProtocolVersionId, H256,
+};
+
+use super::zksync_functions::ZkSyncFunctions;
+use crate::consistency_checker::ConsistencyChecker;
+
+/// Used by the L1 contracts to indicate that pubdata is published in blobs.
+const PUBDATA_SOURCE_BLOBS: u8 = 1;
+/// Format: opening point (16 bytes) || claimed value (32 bytes) || commitment (48 bytes)
+/// || opening proof (48 bytes).
+const BYTES_PER_PUBDATA_COMMITMENT: usize = 144;
+const KZG_COMMITMENT_RANGE: std::ops::Range<usize> = 48..96;
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Outcome of verifying archived blobs for a single L1 batch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlobArchiveStatus {
+    /// All archived blobs match the KZG commitments included on L1.
+    Verified { blob_count: usize },
+    /// There is no archived sidecar for the batch.
+    MissingSidecar,
+    /// The batch is not committed on L1 yet, so there is nothing to verify against.
+    NotCommitted,
+    /// Archived blobs do not match the data included on L1.
+    Mismatch(String),
+}
+
+impl fmt::Display for BlobArchiveStatus {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Verified { blob_count } => write!(formatter, "verified {blob_count} blob(s)"),
+            Self::MissingSidecar => formatter.write_str("blob sidecar is not archived"),
+            Self::NotCommitted => formatter.write_str("L1 batch is not committed on L1"),
+            Self::Mismatch(message) => write!(formatter, "mismatch: {message}"),
+        }
+    }
+}
+
+/// Verifies archived blob sidecars against KZG commitments published in L1 commit transactions.
+#[derive(Debug)]
+pub struct BlobArchiveVerifier {
+    pool: ConnectionPool<Core>,
+    blob_store: Arc<dyn ObjectStore>,
+    l1_client: Arc<dyn EthInterface>,
+    functions: ZkSyncFunctions,
+}
+
+impl BlobArchiveVerifier {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        blob_store: Arc<dyn ObjectStore>,
+        l1_client: Arc<dyn EthInterface>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            pool,
+            blob_store,
+            l1_client,
+            functions: ZkSyncFunctions::default(),
+        })
+    }
+
+    /// Selects the L1 commit function in the same way as `EthTxAggregator` does when encoding
+    /// commit operations.
+    fn commit_function(
+        &self,
+        protocol_version: ProtocolVersionId,
+    ) -> anyhow::Result<&ethabi::Function> {
+        if protocol_version.is_pre_shared_bridge() {
+            Ok(&self.functions.pre_shared_bridge_commit)
+        } else {
+            self.functions
+                .post_shared_bridge_commit
+                .as_ref()
+                .context("L1 contract does not have `commitBatchesSharedBridge` function")
+        }
+    }
+
+    /// Verifies archived blobs for the specified L1 batch. Returns an error only if verification
+    /// cannot be performed, e.g. because of a Postgres or L1 client error.
+    pub async fn verify_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<BlobArchiveStatus> {
+        let sidecar = match self
+            .blob_store
+            .get::<EthTxBlobSidecar>(l1_batch_number)
+            .await
+        {
+            Ok(sidecar) => sidecar,
+            Err(ObjectStoreError::KeyNotFound(_)) => return Ok(BlobArchiveStatus::MissingSidecar),
+            Err(err) => {
+                return Err(anyhow::Error::from(err).context(format!(
+                    "failed loading blob sidecar for L1 batch #{l1_batch_number}"
+                )))
+            }
+        };
+
+        let Some((commit_tx_hash, protocol_version)) = self.commit_tx_info(l1_batch_number).await?
+        else {
+            return Ok(BlobArchiveStatus::NotCommitted);
+        };
+        let commit_tx = self
+            .l1_client
+            .get_tx(commit_tx_hash, "blob_archive_verifier")
+            .await?
+            .with_context(|| format!("commit transaction {commit_tx_hash:?} not found on L1"))?;
+
+        let commitment = ConsistencyChecker::extract_commit_data(
+            &commit_tx.input.0,
+            self.commit_function(protocol_version)?,
+            l1_batch_number,
+        )
+        .with_context(|| {
+            format!("failed extracting commit data for transaction {commit_tx_hash:?}")
+        })?;
+        Ok(match verify_sidecar(&sidecar, &commitment) {
+            Ok(blob_count) => BlobArchiveStatus::Verified { blob_count },
+            Err(err) => BlobArchiveStatus::Mismatch(format!("{err:#}")),
+        })
+    }
+
+    /// Returns the hash of the confirmed commit transaction for the L1 batch together with the batch protocol version.
+    async fn commit_tx_info(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<(H256, ProtocolVersionId)>> {
+        let mut storage = self.pool.connection_tagged("blob_archive_verifier").await?;
+        let Some(storage_l1_batch) = storage
+            .blocks_dal()
+            .get_storage_l1_batch(l1_batch_number)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let Some(commit_tx_id) = storage_l1_batch.eth_commit_tx_id else {
+            return Ok(None);
+        };
+        let protocol_version = match storage_l1_batch.protocol_version {
+            Some(version) => u16::try_from(version)
+                .ok()
+                .and_then(|version| ProtocolVersionId::try_from(version).ok())
+                .with_context(|| format!("unknown protocol version {version}"))?,
+            None => ProtocolVersionId::last_potentially_undefined(),
+        };
+        let commit_tx_hash = storage
+            .eth_sender_dal()
+            .get_confirmed_tx_hash_by_eth_tx_id(commit_tx_id as u32)
+            .await?;
+        Ok(commit_tx_hash.map(|hash| (hash, protocol_version)))
+    }
+}
+
+/// Checks the sidecar against the L1 batch commitment extracted from the commit transaction.
+/// Returns the number of verified blobs.
+fn verify_sidecar(sidecar: &EthTxBlobSidecar, commitment: &Token) -> anyhow::Result<usize> {
+    let EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar) = sidecar;
+
+    let Token::Tuple(commitment) = commitment else {
+        anyhow::bail!("L1 batch commitment has unexpected shape: {commitment:?}");
+    };
+    let Some(Token::Bytes(pubdata_commitments)) = commitment.last() else {
+        anyhow::bail!("L1 batch commitment has no pubdata commitments");
+    };
+    let pubdata_commitments = match pubdata_commitments.split_first() {
+        Some((&PUBDATA_SOURCE_BLOBS, commitments)) => commitments,
+        Some((source, _)) => {
+            anyhow::bail!("L1 batch pubdata was not published in blobs (pubdata source: {source})")
+        }
+        None => anyhow::bail!("pubdata commitments are empty"),
+    };
+    anyhow::ensure!(
+        pubdata_commitments.len() % BYTES_PER_PUBDATA_COMMITMENT == 0,
+        "pubdata commitments have unexpected length {}",
+        pubdata_commitments.len()
+    );
+
+    let l1_commitment_count = pubdata_commitments.len() / BYTES_PER_PUBDATA_COMMITMENT;
+    anyhow::ensure!(
+        l1_commitment_count == sidecar.blobs.len(),
+        "blob count mismatch: {} archived, {l1_commitment_count} committed on L1",
+        sidecar.blobs.len()
+    );
+
+    let l1_commitments = pubdata_commitments.chunks(BYTES_PER_PUBDATA_COMMITMENT);
+    for (i, (blob, l1_commitment)) in sidecar.blobs.iter().zip(l1_commitments).enumerate() {
+        let l1_kzg_commitment = &l1_commitment[KZG_COMMITMENT_RANGE];
+        anyhow::ensure!(
+            blob.commitment == l1_kzg_commitment,
+            "KZG commitment mismatch for blob #{i}: archived 0x{}, committed on L1 0x{}",
+            hex::encode(&blob.commitment),
+            hex::encode(l1_kzg_commitment)
+        );
+
+        let mut versioned_hash = Sha256::digest(&blob.commitment);
+        versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+        anyhow::ensure!(
+            blob.versioned_hash == versioned_hash.as_slice(),
+            "versioned hash for blob #{i} does not match its KZG commitment"
+        );
+
+        let blob_commitment = compute_blob_commitment(&blob.blob)
+            .with_context(|| format!("archived blob #{i} is malformed"))?;
+        anyhow::ensure!(
+            blob.commitment == blob_commitment,
+            "archived data of blob #{i} does not match its KZG commitment"
+        );
+    }
+    Ok(sidecar.blobs.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_l1_contract_interface::i_executor::commit::kzg::KzgInfo;
+    use zksync_types::eth_sender::{EthTxBlobSidecarV1, SidecarBlobV1};
+
+    use super::*;
+
+    fn mock_blob(seed: u8) -> SidecarBlobV1 {
+        let kzg_info = KzgInfo::new(&[seed; 1_000]);
+        SidecarBlobV1 {
+            blob: kzg_info.blob.to_vec(),
+            commitment: kzg_info.kzg_commitment.to_vec(),
+            proof: kzg_info.blob_proof.to_vec(),
+            versioned_hash: kzg_info.versioned_hash.to_vec(),
+        }
+    }
+
+    fn mock_commitment(blobs: &[SidecarBlobV1]) -> Token {
+        let pubdata_commitments = blobs.iter().flat_map(|blob| {
+            let mut packed = vec![0_u8; BYTES_PER_PUBDATA_COMMITMENT];
+            packed[KZG_COMMITMENT_RANGE].copy_from_slice(&blob.commitment);
+            packed
+        });
+        let pubdata_commitments = std::iter::once(PUBDATA_SOURCE_BLOBS)
+            .chain(pubdata_commitments)
+            .collect();
+        Token::Tuple(vec![
+            Token::Uint(1.into()),
+            Token::Bytes(pubdata_commitments),
+        ])
+    }
+
+    #[test]
+    fn verifying_sidecar() {
+        let blobs = vec![mock_blob(1), mock_blob(2)];
+        let commitment = mock_commitment(&blobs);
+        let sidecar = EthTxBlobSidecar::from(EthTxBlobSidecarV1 { blobs });
+
+        let blob_count = verify_sidecar(&sidecar, &commitment).unwrap();
+        assert_eq!(blob_count, 2);
+    }
+
+    #[test]
+    fn verifying_sidecar_with_mismatched_commitment() {
+        let blobs = vec![mock_blob(1), mock_blob(2)];
+        let commitment = mock_commitment(&[mock_blob(1), mock_blob(3)]);
+        let sidecar = EthTxBlobSidecar::from(EthTxBlobSidecarV1 { blobs });
+
+        let err = verify_sidecar(&sidecar, &commitment).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("KZG commitment mismatch for blob #1"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn verifying_sidecar_with_mismatched_blob_count() {
+        let blobs = vec![mock_blob(1)];
+        let commitment = mock_commitment(&[mock_blob(1), mock_blob(2)]);
+        let sidecar = EthTxBlobSidecar::from(EthTxBlobSidecarV1 { blobs });
+
+        let err = verify_sidecar(&sidecar, &commitment).unwrap_err();
+        assert!(err.to_string().contains("blob count mismatch"), "{err}");
+    }
+
+    #[test]
+    fn verifying_sidecar_with_corrupted_versioned_hash() {
+        let mut blob = mock_blob(1);
+        let commitment = mock_commitment(&[blob.clone()]);
+        blob.versioned_hash[1] ^= 1;
+        let sidecar = EthTxBlobSidecar::from(EthTxBlobSidecarV1 { blobs: vec![blob] });
+
+        let err = verify_sidecar(&sidecar, &commitment).unwrap_err();
+        assert!(err.to_string().contains("versioned hash"), "{err}");
+    }
+
+    #[test]
+    fn verifying_sidecar_with_corrupted_blob_data() {
+        let mut blob = mock_blob(1);
+        let commitment = mock_commitment(&[blob.clone()]);
+        // Field elements are big-endian with the most significant byte set to 0, so this modifies
+        // the blob data without making the blob malformed.
+        blob.blob[1] ^= 1;
+        let sidecar = EthTxBlobSidecar::from(EthTxBlobSidecarV1 { blobs: vec![blob] });
+
+        let err = verify_sidecar(&sidecar, &commitment).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("archived data of blob #0 does not match"),
+            "{err}"
+        );
+    }
+}
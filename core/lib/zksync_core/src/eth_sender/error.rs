@@ -1,3 +1,4 @@
+use zksync_object_store::ObjectStoreError;
 use zksync_types::web3::contract;
 
 #[derive(Debug, thiserror::Error)]
@@ -6,4 +7,8 @@ pub enum ETHSenderError {
     EthereumGateWayError(#[from] zksync_eth_client::Error),
     #[error("Token parsing Error: {0}")]
     ParseError(#[from] contract::Error),
+    #[error("Failed archiving blob sidecar: {0}")]
+    BlobArchiveError(#[from] ObjectStoreError),
+    #[error("Operation {0} is abandoned; it must be cleared using the block reverter")]
+    OperationAbandoned(u32),
 }
@@ -546,6 +546,18 @@ impl EthTxAggregator {
             self.encode_aggregated_op(aggregated_op, contracts_are_pre_shared_bridge);
         let l1_batch_number_range = aggregated_op.l1_batch_range();
 
+        if let Some(sidecar) = &encoded_aggregated_op.sidecar {
+            // Blobs expire on L1 after a while, so we archive them to be able to serve them later.
+            // Blob commit transactions always commit a single L1 batch.
+            let l1_batch_number = *l1_batch_number_range.start();
+            let key = self
+                .aggregator
+                .blob_store()
+                .put(l1_batch_number, sidecar)
+                .await?;
+            tracing::info!("Archived blob sidecar for L1 batch #{l1_batch_number} as `{key}`");
+        }
+
         let predicted_gas_for_batches = transaction
             .blocks_dal()
             .get_l1_batches_predicted_gas(l1_batch_number_range.clone(), op_type)
@@ -1,8 +1,8 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
-use zksync_config::configs::eth_sender::SenderConfig;
+use zksync_config::configs::eth_sender::{FeeBumpConfig, SenderConfig};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
     encode_blob_tx_with_sidecar, BoundEthInterface, Error, EthInterface, ExecutedTxStatus, Options,
@@ -19,15 +19,18 @@ use zksync_types::{
 };
 use zksync_utils::time::seconds_since_epoch;
 
-use super::{metrics::METRICS, ETHSenderError};
+use super::{
+    fee_bump::{
+        strategy_from_config, EthFee, FeeBumpAttempt, FeeBumpDecision, FeeBumpStrategy,
+        LinearFeeBump,
+    },
+    metrics::METRICS,
+    ETHSenderError,
+};
 use crate::{l1_gas_price::L1TxParamsProvider, metrics::BlockL1Stage};
 
-#[derive(Debug)]
-struct EthFee {
-    base_fee_per_gas: u64,
-    priority_fee_per_gas: u64,
-    blob_base_fee_per_gas: Option<u64>,
-}
+/// Gas limit for 0-value transfers replacing transactions of abandoned operations.
+const CANCELLATION_TX_GAS: u32 = 21_000;
 
 #[derive(Debug, Clone, Copy)]
 struct OperatorNonce {
@@ -48,7 +51,12 @@ pub(super) struct L1BlockNumbers {
 /// Based on eth_tx queue the component generates new attempt with the minimum possible fee,
 /// save it to the database, and send it to Ethereum.
 /// Based on eth_tx_history queue the component can mark txs as stuck and create the new attempt
-/// with higher gas price
+/// with higher gas price, or cancel them by replacement, as decided by the [`FeeBumpStrategy`]
+/// chosen for the operation type.
+///
+/// Operations abandoned by a strategy are marked as failed once their cancellation transaction is mined,
+/// and the manager stops, since subsequent operations would revert on L1. The manager refuses to start
+/// until failed operations are cleared using the block reverter.
 #[derive(Debug)]
 pub struct EthTxManager {
     /// A gateway through which the operator normally sends all its transactions.
@@ -58,6 +66,8 @@ pub struct EthTxManager {
     ethereum_gateway_blobs: Option<Arc<dyn BoundEthInterface>>,
     config: SenderConfig,
     gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    default_fee_bump_strategy: Arc<dyn FeeBumpStrategy>,
+    fee_bump_strategies: HashMap<AggregatedActionType, Arc<dyn FeeBumpStrategy>>,
     pool: ConnectionPool<Core>,
 }
 
@@ -74,10 +84,47 @@ impl EthTxManager {
             ethereum_gateway_blobs,
             config,
             gas_adjuster,
+            default_fee_bump_strategy: Arc::new(LinearFeeBump::default()),
+            fee_bump_strategies: HashMap::new(),
             pool,
         }
     }
 
+    /// Sets the fee bump strategy for operations of the specified type. By default,
+    /// [`LinearFeeBump`] is used for all operations.
+    pub fn with_fee_bump_strategy(
+        mut self,
+        action_type: AggregatedActionType,
+        strategy: Arc<dyn FeeBumpStrategy>,
+    ) -> Self {
+        self.fee_bump_strategies.insert(action_type, strategy);
+        self
+    }
+
+    /// Sets fee bump strategies for all operation types configured in `config`.
+    pub fn with_fee_bump_config(mut self, config: &FeeBumpConfig) -> anyhow::Result<Self> {
+        let configured_strategies = [
+            (AggregatedActionType::Commit, &config.commit),
+            (AggregatedActionType::PublishProofOnchain, &config.prove),
+            (AggregatedActionType::Execute, &config.execute),
+        ];
+        for (action_type, strategy_config) in configured_strategies {
+            if let Some(strategy_config) = strategy_config {
+                let strategy = strategy_from_config(strategy_config)
+                    .with_context(|| format!("invalid fee bump strategy for {action_type}"))?;
+                self = self.with_fee_bump_strategy(action_type, strategy);
+            }
+        }
+        Ok(self)
+    }
+
+    fn fee_bump_strategy(&self, action_type: AggregatedActionType) -> &dyn FeeBumpStrategy {
+        self.fee_bump_strategies
+            .get(&action_type)
+            .unwrap_or(&self.default_fee_bump_strategy)
+            .as_ref()
+    }
+
     async fn get_tx_status(
         &self,
         tx_hash: H256,
@@ -121,110 +168,78 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         tx: &EthTx,
         time_in_mempool: u32,
-    ) -> Result<EthFee, ETHSenderError> {
-        let base_fee_per_gas = self.gas_adjuster.get_base_fee(0);
-        let priority_fee_per_gas = self.gas_adjuster.get_priority_fee();
-        let blob_base_fee_per_gas = Some(self.gas_adjuster.get_blob_base_fee());
-
-        if tx.blob_sidecar.is_some() {
-            if time_in_mempool != 0 {
-                // for blob transactions on re-sending need to double all gas prices
-                let previous_sent_tx = storage
-                    .eth_sender_dal()
-                    .get_last_sent_eth_tx(tx.id)
-                    .await
-                    .unwrap()
-                    .unwrap();
-                return Ok(EthFee {
-                    base_fee_per_gas: std::cmp::max(
-                        previous_sent_tx.base_fee_per_gas * 2,
-                        base_fee_per_gas,
-                    ),
-                    priority_fee_per_gas: std::cmp::max(
-                        previous_sent_tx.priority_fee_per_gas * 2,
-                        priority_fee_per_gas,
-                    ),
-                    blob_base_fee_per_gas: std::cmp::max(
-                        previous_sent_tx.blob_base_fee_per_gas.map(|v| v * 2),
-                        blob_base_fee_per_gas,
-                    ),
-                });
-            }
-            return Ok(EthFee {
-                base_fee_per_gas,
-                priority_fee_per_gas,
-                blob_base_fee_per_gas,
-            });
-        }
-
-        let base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
-
-        let priority_fee_per_gas = if time_in_mempool != 0 {
-            METRICS.transaction_resent.inc();
-            let priority_fee_per_gas = self
-                .increase_priority_fee(storage, tx.id, base_fee_per_gas)
-                .await?;
-            tracing::info!(
-                "Resending operation {} with base fee {:?} and priority fee {:?}",
-                tx.id,
-                base_fee_per_gas,
-                priority_fee_per_gas
-            );
-            priority_fee_per_gas
+    ) -> FeeBumpDecision {
+        let previous_fees = if time_in_mempool != 0 {
+            let previous_sent_tx = storage
+                .eth_sender_dal()
+                .get_last_sent_eth_tx(tx.id)
+                .await
+                .unwrap();
+            previous_sent_tx.map(|previous_sent_tx| EthFee {
+                base_fee_per_gas: previous_sent_tx.base_fee_per_gas,
+                priority_fee_per_gas: previous_sent_tx.priority_fee_per_gas,
+                blob_base_fee_per_gas: previous_sent_tx.blob_base_fee_per_gas,
+            })
         } else {
-            self.gas_adjuster.get_priority_fee()
+            None
+        };
+        let attempt = FeeBumpAttempt {
+            tx_type: tx.tx_type,
+            is_blob_tx: tx.blob_sidecar.is_some(),
+            time_in_mempool,
+            age: Duration::from_secs(seconds_since_epoch().saturating_sub(tx.created_at_timestamp)),
+            previous_fees,
+        };
+        let strategy = self.fee_bump_strategy(tx.tx_type);
+        let decision = strategy.next_fees(self.gas_adjuster.as_ref(), &attempt);
+
+        let fees = match &decision {
+            FeeBumpDecision::Send(fees) => {
+                if previous_fees.is_some() {
+                    METRICS.transaction_resent.inc();
+                    tracing::info!(
+                        "Resending operation {} with base fee {:?} and priority fee {:?}",
+                        tx.id,
+                        fees.base_fee_per_gas,
+                        fees.priority_fee_per_gas
+                    );
+                }
+                fees
+            }
+            FeeBumpDecision::Cancel(fees) => {
+                METRICS.transaction_cancelled.inc();
+                tracing::warn!(
+                    "Abandoning operation {} ({}) created {:?} ago; replacing it with a cancellation transaction \
+                     with base fee {:?} and priority fee {:?}",
+                    tx.id,
+                    tx.tx_type,
+                    attempt.age,
+                    fees.base_fee_per_gas,
+                    fees.priority_fee_per_gas
+                );
+                fees
+            }
+            FeeBumpDecision::Skip => {
+                tracing::info!(
+                    "Skipping gas adjustment for operation {}, previously sent with {previous_fees:?}: \
+                     declined by fee bump strategy {strategy:?}",
+                    tx.id
+                );
+                return decision;
+            }
         };
 
         // Extra check to prevent sending transaction will extremely high priority fee.
-        if priority_fee_per_gas > self.config.max_acceptable_priority_fee_in_gwei {
+        if !attempt.is_blob_tx
+            && fees.priority_fee_per_gas > self.config.max_acceptable_priority_fee_in_gwei
+        {
             panic!(
                 "Extremely high value of priority_fee_per_gas is suggested: {}, while max acceptable is {}",
-                priority_fee_per_gas,
+                fees.priority_fee_per_gas,
                 self.config.max_acceptable_priority_fee_in_gwei
             );
         }
-
-        Ok(EthFee {
-            base_fee_per_gas,
-            blob_base_fee_per_gas: None,
-            priority_fee_per_gas,
-        })
-    }
-
-    async fn increase_priority_fee(
-        &self,
-        storage: &mut Connection<'_, Core>,
-        eth_tx_id: u32,
-        base_fee_per_gas: u64,
-    ) -> Result<u64, ETHSenderError> {
-        let previous_sent_tx = storage
-            .eth_sender_dal()
-            .get_last_sent_eth_tx(eth_tx_id)
-            .await
-            .unwrap()
-            .unwrap();
-
-        let previous_base_fee = previous_sent_tx.base_fee_per_gas;
-        let previous_priority_fee = previous_sent_tx.priority_fee_per_gas;
-        let next_block_minimal_base_fee = self.gas_adjuster.get_next_block_minimal_base_fee();
-
-        if base_fee_per_gas <= next_block_minimal_base_fee.min(previous_base_fee) {
-            // If the base fee is lower than the previous used one
-            // or is lower than the minimal possible value for the next block, sending is skipped.
-            tracing::info!(
-                "Skipping gas adjustment for operation {}, \
-                 base_fee_per_gas: suggested for resending {:?}, previously sent {:?}, next block minimum {:?}",
-                eth_tx_id,
-                base_fee_per_gas,
-                previous_base_fee,
-                next_block_minimal_base_fee
-            );
-            return Err(ETHSenderError::from(Error::from(Web3Error::Internal)));
-        }
-
-        // Increase `priority_fee_per_gas` by at least 20% to prevent "replacement transaction under-priced" error.
-        Ok((previous_priority_fee + (previous_priority_fee / 5) + 1)
-            .max(self.gas_adjuster.get_priority_fee()))
+        decision
     }
 
     pub(crate) async fn send_eth_tx(
@@ -234,38 +249,27 @@ impl EthTxManager {
         time_in_mempool: u32,
         current_block: L1BlockNumber,
     ) -> Result<H256, ETHSenderError> {
+        let (fees, signed_tx, is_cancellation) =
+            match self.calculate_fee(storage, tx, time_in_mempool).await {
+                FeeBumpDecision::Send(fees) => (fees, self.sign_tx(tx, &fees).await, false),
+                FeeBumpDecision::Cancel(fees) => {
+                    (fees, self.sign_cancellation_tx(tx, &fees).await, true)
+                }
+                FeeBumpDecision::Skip => {
+                    return Err(ETHSenderError::from(Error::from(Web3Error::Internal)));
+                }
+            };
         let EthFee {
             base_fee_per_gas,
             priority_fee_per_gas,
             blob_base_fee_per_gas,
-        } = self.calculate_fee(storage, tx, time_in_mempool).await?;
+        } = fees;
 
         METRICS.used_base_fee_per_gas.observe(base_fee_per_gas);
         METRICS
             .used_priority_fee_per_gas
             .observe(priority_fee_per_gas);
 
-        let blob_gas_price = if tx.blob_sidecar.is_some() {
-            Some(
-                blob_base_fee_per_gas
-                    .expect("always ready to query blob gas price for blob transactions; qed")
-                    .into(),
-            )
-        } else {
-            None
-        };
-
-        let mut signed_tx = self
-            .sign_tx(tx, base_fee_per_gas, priority_fee_per_gas, blob_gas_price)
-            .await;
-
-        if let Some(blob_sidecar) = &tx.blob_sidecar {
-            signed_tx.raw_tx = RawTransactionBytes::new_unchecked(encode_blob_tx_with_sidecar(
-                signed_tx.raw_tx.as_ref(),
-                blob_sidecar,
-            ));
-        }
-
         if let Some(tx_history_id) = storage
             .eth_sender_dal()
             .insert_tx_history(
@@ -275,6 +279,7 @@ impl EthTxManager {
                 blob_base_fee_per_gas,
                 signed_tx.hash,
                 signed_tx.raw_tx.as_ref(),
+                is_cancellation,
             )
             .await
             .unwrap()
@@ -511,7 +516,7 @@ impl EthTxManager {
             match self.check_all_sending_attempts(storage, &tx).await {
                 Some(tx_status) => {
                     self.apply_tx_status(storage, &tx, tx_status, l1_block_numbers.finalized)
-                        .await;
+                        .await?;
                 }
                 None => {
                     // The nonce has increased but we did not find the receipt.
@@ -528,28 +533,35 @@ impl EthTxManager {
         Ok(None)
     }
 
-    async fn sign_tx(
-        &self,
-        tx: &EthTx,
-        base_fee_per_gas: u64,
-        priority_fee_per_gas: u64,
-        blob_gas_price: Option<U256>,
-    ) -> SignedCallResult {
+    fn signing_gateway(&self, tx: &EthTx) -> &Arc<dyn BoundEthInterface> {
         // Chose the signing gateway. Use a custom one in case
         // the operator is in 4844 mode and the operation at hand is Commit.
         // then the optional gateway is used to send this transaction from a
         // custom sender account.
-        let signing_gateway = if let Some(blobs_gateway) = self.ethereum_gateway_blobs.as_ref() {
-            if tx.tx_type == AggregatedActionType::Commit {
-                blobs_gateway
-            } else {
-                &self.ethereum_gateway
-            }
+        match &self.ethereum_gateway_blobs {
+            Some(blobs_gateway) if tx.tx_type == AggregatedActionType::Commit => blobs_gateway,
+            _ => &self.ethereum_gateway,
+        }
+    }
+
+    async fn sign_tx(&self, tx: &EthTx, fees: &EthFee) -> SignedCallResult {
+        let EthFee {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas,
+        } = *fees;
+        let blob_gas_price = if tx.blob_sidecar.is_some() {
+            Some(
+                blob_base_fee_per_gas
+                    .expect("always ready to query blob gas price for blob transactions; qed")
+                    .into(),
+            )
         } else {
-            &self.ethereum_gateway
+            None
         };
 
-        signing_gateway
+        let mut signed_tx = self
+            .signing_gateway(tx)
             .sign_prepared_tx_for_addr(
                 tx.raw_tx.clone(),
                 tx.contract_address,
@@ -576,14 +588,44 @@ impl EthTxManager {
                 "eth_tx_manager",
             )
             .await
-            .expect("Failed to sign transaction")
+            .expect("Failed to sign transaction");
+
+        if let Some(blob_sidecar) = &tx.blob_sidecar {
+            signed_tx.raw_tx = RawTransactionBytes::new_unchecked(encode_blob_tx_with_sidecar(
+                signed_tx.raw_tx.as_ref(),
+                blob_sidecar,
+            ));
+        }
+        signed_tx
+    }
+
+    /// Signs a 0-value transfer to the operator itself replacing the transaction of an abandoned operation.
+    async fn sign_cancellation_tx(&self, tx: &EthTx, fees: &EthFee) -> SignedCallResult {
+        let signing_gateway = self.signing_gateway(tx);
+        signing_gateway
+            .sign_prepared_tx_for_addr(
+                vec![],
+                signing_gateway.sender_account(),
+                Options::with(|opt| {
+                    opt.gas = Some(CANCELLATION_TX_GAS.into());
+                    opt.max_fee_per_gas = Some(U256::from(
+                        fees.base_fee_per_gas + fees.priority_fee_per_gas,
+                    ));
+                    opt.max_priority_fee_per_gas = Some(U256::from(fees.priority_fee_per_gas));
+                    opt.nonce = Some(tx.nonce.0.into());
+                    opt.transaction_type = Some(EIP_1559_TX_TYPE.into());
+                }),
+                "eth_tx_manager",
+            )
+            .await
+            .expect("Failed to sign cancellation transaction")
     }
 
     async fn send_unsent_txs(
         &mut self,
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
-    ) {
+    ) -> Result<(), ETHSenderError> {
         for tx in storage.eth_sender_dal().get_unsent_txs().await.unwrap() {
             // Check already sent txs not marked as sent and mark them as sent.
             // The common reason for this behavior is that we sent tx and stop the server
@@ -606,7 +648,7 @@ impl EthTxManager {
                     .expect("Eth tx should exist");
 
                 self.apply_tx_status(storage, &eth_tx, tx_status, l1_block_numbers.finalized)
-                    .await;
+                    .await?;
             } else if let Err(error) = self
                 .send_raw_transaction(
                     storage,
@@ -619,18 +661,28 @@ impl EthTxManager {
                 tracing::warn!("Error sending transaction {tx:?}: {error}");
             }
         }
+        Ok(())
     }
 
+    /// Applies the status of a mined transaction. Returns an error if the transaction abandons its operation.
     async fn apply_tx_status(
         &self,
         storage: &mut Connection<'_, Core>,
         tx: &EthTx,
         tx_status: ExecutedTxStatus,
         finalized_block: L1BlockNumber,
-    ) {
+    ) -> Result<(), ETHSenderError> {
         let receipt_block_number = tx_status.receipt.block_number.unwrap().as_u32();
         if receipt_block_number <= finalized_block.0 {
             if tx_status.success {
+                let is_cancellation = storage
+                    .eth_sender_dal()
+                    .is_cancellation_tx(tx_status.tx_hash)
+                    .await
+                    .unwrap();
+                if is_cancellation {
+                    return Err(self.abandon_tx(storage, tx, tx_status).await);
+                }
                 self.confirm_tx(storage, tx, tx_status).await;
             } else {
                 self.fail_tx(storage, tx, tx_status).await;
@@ -642,6 +694,7 @@ impl EthTxManager {
                 tx.id,
             );
         }
+        Ok(())
     }
 
     pub async fn fail_tx(
@@ -672,6 +725,27 @@ impl EthTxManager {
         panic!("We can't operate after tx fail");
     }
 
+    async fn abandon_tx(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        tx_status: ExecutedTxStatus,
+    ) -> ETHSenderError {
+        storage
+            .eth_sender_dal()
+            .mark_failed_transaction(tx.id)
+            .await
+            .unwrap();
+        tracing::error!(
+            "Operation {} ({}) is abandoned: cancellation transaction {:?} is mined. \
+             The operation must be cleared using the block reverter",
+            tx.id,
+            tx.tx_type,
+            tx_status.tx_hash
+        );
+        ETHSenderError::OperationAbandoned(tx.id)
+    }
+
     pub async fn confirm_tx(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -726,12 +800,24 @@ impl EthTxManager {
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let pool = self.pool.clone();
         {
+            let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
+            let failed_tx_count = storage
+                .eth_sender_dal()
+                .get_number_of_failed_transactions()
+                .await?;
+            anyhow::ensure!(
+                failed_tx_count == 0,
+                "There are {failed_tx_count} failed or abandoned L1 operations; \
+                 they must be cleared using the block reverter before eth_tx_manager can be started"
+            );
+
             let l1_block_numbers = self
                 .get_l1_block_numbers()
                 .await
                 .context("get_l1_block_numbers()")?;
-            let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
-            self.send_unsent_txs(&mut storage, l1_block_numbers).await;
+            self.send_unsent_txs(&mut storage, l1_block_numbers)
+                .await
+                .context("send_unsent_txs()")?;
         }
 
         // It's mandatory to set `last_known_l1_block` to zero, otherwise the first iteration
@@ -747,6 +833,10 @@ impl EthTxManager {
 
             match self.loop_iteration(&mut storage, last_known_l1_block).await {
                 Ok(block) => last_known_l1_block = block,
+                Err(err @ ETHSenderError::OperationAbandoned(_)) => {
+                    tracing::error!("eth_tx_manager is stopped: {err}");
+                    return Err(err.into());
+                }
                 Err(e) => {
                     // Web3 API request failures can cause this,
                     // and anything more important is already properly reported.
@@ -0,0 +1,584 @@
+//! Strategies choosing fees for sending and resending transactions by [`EthTxManager`](super::EthTxManager).
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use zksync_config::configs::eth_sender::{FeeBumpStrategyConfig, FeeBumpStrategyKind};
+use zksync_types::aggregated_operations::AggregatedActionType;
+
+use crate::l1_gas_price::L1TxParamsProvider;
+
+/// Minimum fee increase (in percent) required by L1 nodes to accept a replacement transaction.
+const MIN_REPLACEMENT_FEE_INCREASE_PERCENT: u64 = 10;
+/// Minimum fee increase (in percent) required by L1 nodes to accept a replacement blob transaction.
+const MIN_BLOB_REPLACEMENT_FEE_INCREASE_PERCENT: u64 = 100;
+
+/// Fees used for a single sending attempt of an L1 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthFee {
+    pub base_fee_per_gas: u64,
+    pub priority_fee_per_gas: u64,
+    pub blob_base_fee_per_gas: Option<u64>,
+}
+
+impl EthFee {
+    fn scale(self, multiplier: f64) -> Self {
+        let scale = |fee: u64| (fee as f64 * multiplier).ceil() as u64;
+        Self {
+            base_fee_per_gas: scale(self.base_fee_per_gas),
+            priority_fee_per_gas: scale(self.priority_fee_per_gas),
+            blob_base_fee_per_gas: self.blob_base_fee_per_gas.map(scale),
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self {
+            base_fee_per_gas: self.base_fee_per_gas.max(other.base_fee_per_gas),
+            priority_fee_per_gas: self.priority_fee_per_gas.max(other.priority_fee_per_gas),
+            blob_base_fee_per_gas: self.blob_base_fee_per_gas.max(other.blob_base_fee_per_gas),
+        }
+    }
+
+    /// Returns the minimum fees for a transaction replacing a transaction with `self` fees
+    /// to be accepted by L1 mempools.
+    fn min_replacement(self, is_blob_tx: bool) -> Self {
+        let increase = |fee: u64| {
+            if is_blob_tx {
+                increase_by_percent(fee, MIN_BLOB_REPLACEMENT_FEE_INCREASE_PERCENT)
+            } else {
+                increase_by_percent(fee, MIN_REPLACEMENT_FEE_INCREASE_PERCENT) + 1
+            }
+        };
+        Self {
+            base_fee_per_gas: increase(self.base_fee_per_gas),
+            priority_fee_per_gas: increase(self.priority_fee_per_gas),
+            blob_base_fee_per_gas: self.blob_base_fee_per_gas.map(increase),
+        }
+    }
+}
+
+fn increase_by_percent(fee: u64, percent: u64) -> u64 {
+    fee.saturating_add(fee.saturating_mul(percent) / 100)
+}
+
+/// Sending attempt of an L1 transaction, for which a [`FeeBumpStrategy`] chooses fees.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeBumpAttempt {
+    /// Type of the operation sent by the transaction.
+    pub tx_type: AggregatedActionType,
+    /// Whether the transaction carries EIP-4844 blobs.
+    pub is_blob_tx: bool,
+    /// Number of L1 blocks since the first sending attempt; 0 if the transaction is sent for the first time.
+    pub time_in_mempool: u32,
+    /// Time elapsed since the operation was saved by the aggregator.
+    pub age: Duration,
+    /// Fees used by the last sending attempt, or `None` if the transaction is sent for the first time.
+    pub previous_fees: Option<EthFee>,
+}
+
+impl FeeBumpAttempt {
+    /// Returns fees suggested by the gas adjuster. The suggested base fee grows with `time_in_mempool`.
+    fn suggested_fees(
+        &self,
+        gas_adjuster: &dyn L1TxParamsProvider,
+        time_in_mempool: u32,
+    ) -> EthFee {
+        EthFee {
+            base_fee_per_gas: gas_adjuster.get_base_fee(time_in_mempool),
+            priority_fee_per_gas: gas_adjuster.get_priority_fee(),
+            blob_base_fee_per_gas: self.is_blob_tx.then(|| gas_adjuster.get_blob_base_fee()),
+        }
+    }
+
+    /// Returns the cancellation decision if the attempt is a resend of an operation that
+    /// should be abandoned after `abandon_after`.
+    fn cancellation(
+        &self,
+        gas_adjuster: &dyn L1TxParamsProvider,
+        abandon_after: Option<Duration>,
+    ) -> Option<FeeBumpDecision> {
+        let previous_fees = self.previous_fees?;
+        if self.age < abandon_after? {
+            return None;
+        }
+        if self.is_blob_tx {
+            // L1 mempools don't allow replacing blob transactions with non-blob ones.
+            tracing::warn!(
+                "{} operation should be abandoned after {:?}, but it is sent as a blob transaction \
+                 which cannot be cancelled by replacement",
+                self.tx_type,
+                self.age
+            );
+            return None;
+        }
+
+        let suggested_fees = self.suggested_fees(gas_adjuster, self.time_in_mempool);
+        let fees = suggested_fees.max(previous_fees.min_replacement(false));
+        Some(FeeBumpDecision::Cancel(fees))
+    }
+}
+
+/// Decision made by a [`FeeBumpStrategy`] for a sending attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeBumpDecision {
+    /// Send the transaction with the specified fees.
+    Send(EthFee),
+    /// Do not send the transaction during this attempt.
+    Skip,
+    /// Abandon the operation and replace its transaction with a 0-value transfer to the operator
+    /// itself, which is sent with the specified fees.
+    Cancel(EthFee),
+}
+
+/// Strategy choosing fees for sending and resending transactions by [`EthTxManager`](super::EthTxManager).
+/// Strategies can be chosen per [`AggregatedActionType`].
+pub trait FeeBumpStrategy: fmt::Debug + Send + Sync {
+    /// Chooses fees for the specified sending attempt.
+    fn next_fees(
+        &self,
+        gas_adjuster: &dyn L1TxParamsProvider,
+        attempt: &FeeBumpAttempt,
+    ) -> FeeBumpDecision;
+}
+
+/// Strategy increasing the priority fee by a fixed percentage of the previously used priority fee
+/// on each resend. The base fee follows the gas adjuster suggestion for the time spent in the mempool;
+/// resending is skipped if the suggested base fee isn't above the previously used one
+/// or the minimum base fee for the next L1 block.
+///
+/// Blob transactions have all fees doubled on each resend, as required by L1 mempools.
+///
+/// This is the default strategy used for all operations.
+#[derive(Debug, Clone)]
+pub struct LinearFeeBump {
+    priority_fee_increase_percent: u64,
+    abandon_after: Option<Duration>,
+}
+
+impl Default for LinearFeeBump {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+impl LinearFeeBump {
+    pub fn new(priority_fee_increase_percent: u64) -> Self {
+        Self {
+            priority_fee_increase_percent,
+            abandon_after: None,
+        }
+    }
+
+    /// Makes the strategy abandon operations not included on L1 after the specified time since their creation.
+    pub fn with_abandon_after(mut self, abandon_after: Duration) -> Self {
+        self.abandon_after = Some(abandon_after);
+        self
+    }
+}
+
+impl FeeBumpStrategy for LinearFeeBump {
+    fn next_fees(
+        &self,
+        gas_adjuster: &dyn L1TxParamsProvider,
+        attempt: &FeeBumpAttempt,
+    ) -> FeeBumpDecision {
+        if let Some(cancellation) = attempt.cancellation(gas_adjuster, self.abandon_after) {
+            return cancellation;
+        }
+
+        if attempt.is_blob_tx {
+            let suggested_fees = attempt.suggested_fees(gas_adjuster, 0);
+            let fees = match attempt.previous_fees {
+                Some(previous_fees) => suggested_fees.max(previous_fees.min_replacement(true)),
+                None => suggested_fees,
+            };
+            return FeeBumpDecision::Send(fees);
+        }
+
+        let suggested_fees = attempt.suggested_fees(gas_adjuster, attempt.time_in_mempool);
+        let Some(previous_fees) = attempt.previous_fees else {
+            return FeeBumpDecision::Send(suggested_fees);
+        };
+
+        let next_block_minimal_base_fee = gas_adjuster.get_next_block_minimal_base_fee();
+        if suggested_fees.base_fee_per_gas
+            <= next_block_minimal_base_fee.min(previous_fees.base_fee_per_gas)
+        {
+            return FeeBumpDecision::Skip;
+        }
+
+        let priority_fee_per_gas = increase_by_percent(
+            previous_fees.priority_fee_per_gas,
+            self.priority_fee_increase_percent,
+        ) + 1;
+        FeeBumpDecision::Send(EthFee {
+            priority_fee_per_gas: priority_fee_per_gas.max(suggested_fees.priority_fee_per_gas),
+            ..suggested_fees
+        })
+    }
+}
+
+/// Strategy multiplying the fees suggested by the gas adjuster by `multiplier^n`, where `n` is
+/// the number of L1 blocks the transaction has spent in the mempool. The multiplier is capped
+/// at `max_multiplier`; after that, fees are only increased by the minimum amount needed to replace
+/// the previous transaction.
+#[derive(Debug, Clone)]
+pub struct ExponentialFeeBump {
+    multiplier: f64,
+    max_multiplier: f64,
+    abandon_after: Option<Duration>,
+}
+
+impl ExponentialFeeBump {
+    pub fn new(multiplier: f64, max_multiplier: f64) -> Self {
+        assert!(
+            multiplier >= 1.0,
+            "fee multiplier must be at least 1, got {multiplier}"
+        );
+        assert!(
+            max_multiplier >= 1.0,
+            "max fee multiplier must be at least 1, got {max_multiplier}"
+        );
+        Self {
+            multiplier,
+            max_multiplier,
+            abandon_after: None,
+        }
+    }
+
+    /// Makes the strategy abandon operations not included on L1 after the specified time since their creation.
+    pub fn with_abandon_after(mut self, abandon_after: Duration) -> Self {
+        self.abandon_after = Some(abandon_after);
+        self
+    }
+}
+
+impl FeeBumpStrategy for ExponentialFeeBump {
+    fn next_fees(
+        &self,
+        gas_adjuster: &dyn L1TxParamsProvider,
+        attempt: &FeeBumpAttempt,
+    ) -> FeeBumpDecision {
+        if let Some(cancellation) = attempt.cancellation(gas_adjuster, self.abandon_after) {
+            return cancellation;
+        }
+
+        let exponent = i32::try_from(attempt.time_in_mempool).unwrap_or(i32::MAX);
+        let multiplier = self.multiplier.powi(exponent).min(self.max_multiplier);
+        let fees = attempt.suggested_fees(gas_adjuster, 0).scale(multiplier);
+        FeeBumpDecision::Send(match attempt.previous_fees {
+            Some(previous_fees) => fees.max(previous_fees.min_replacement(attempt.is_blob_tx)),
+            None => fees,
+        })
+    }
+}
+
+/// Strategy trying to get operations included on L1 before a deadline. The fees suggested by
+/// the gas adjuster are multiplied by a factor growing linearly with the operation age,
+/// from 1 when the operation is created to `max_multiplier` at the deadline. After the deadline,
+/// `max_multiplier` is used, and fees are increased by at least the minimum amount needed
+/// to replace the previous transaction.
+#[derive(Debug, Clone)]
+pub struct DeadlineAwareFeeBump {
+    deadline: Duration,
+    max_multiplier: f64,
+    abandon_after: Option<Duration>,
+}
+
+impl DeadlineAwareFeeBump {
+    pub fn new(deadline: Duration, max_multiplier: f64) -> Self {
+        assert!(!deadline.is_zero(), "deadline must be positive");
+        assert!(
+            max_multiplier >= 1.0,
+            "max fee multiplier must be at least 1, got {max_multiplier}"
+        );
+        Self {
+            deadline,
+            max_multiplier,
+            abandon_after: None,
+        }
+    }
+
+    /// Makes the strategy abandon operations not included on L1 after the specified time since their creation.
+    pub fn with_abandon_after(mut self, abandon_after: Duration) -> Self {
+        self.abandon_after = Some(abandon_after);
+        self
+    }
+
+    fn multiplier(&self, age: Duration) -> f64 {
+        let progress = (age.as_secs_f64() / self.deadline.as_secs_f64()).min(1.0);
+        1.0 + (self.max_multiplier - 1.0) * progress
+    }
+}
+
+impl FeeBumpStrategy for DeadlineAwareFeeBump {
+    fn next_fees(
+        &self,
+        gas_adjuster: &dyn L1TxParamsProvider,
+        attempt: &FeeBumpAttempt,
+    ) -> FeeBumpDecision {
+        if let Some(cancellation) = attempt.cancellation(gas_adjuster, self.abandon_after) {
+            return cancellation;
+        }
+
+        let fees = attempt
+            .suggested_fees(gas_adjuster, 0)
+            .scale(self.multiplier(attempt.age));
+        FeeBumpDecision::Send(match attempt.previous_fees {
+            Some(previous_fees) => fees.max(previous_fees.min_replacement(attempt.is_blob_tx)),
+            None => fees,
+        })
+    }
+}
+
+/// Creates a fee bump strategy from the configuration.
+pub(super) fn strategy_from_config(
+    config: &FeeBumpStrategyConfig,
+) -> anyhow::Result<Arc<dyn FeeBumpStrategy>> {
+    let abandon_after = config.abandon_after();
+    Ok(match config.strategy {
+        FeeBumpStrategyKind::Linear => {
+            let strategy = LinearFeeBump::new(config.priority_fee_increase_percent);
+            match abandon_after {
+                Some(abandon_after) => Arc::new(strategy.with_abandon_after(abandon_after)),
+                None => Arc::new(strategy),
+            }
+        }
+        FeeBumpStrategyKind::Exponential => {
+            anyhow::ensure!(
+                config.multiplier >= 1.0 && config.max_multiplier >= 1.0,
+                "fee multipliers must be at least 1, got multiplier={}, max_multiplier={}",
+                config.multiplier,
+                config.max_multiplier
+            );
+            let strategy = ExponentialFeeBump::new(config.multiplier, config.max_multiplier);
+            match abandon_after {
+                Some(abandon_after) => Arc::new(strategy.with_abandon_after(abandon_after)),
+                None => Arc::new(strategy),
+            }
+        }
+        FeeBumpStrategyKind::DeadlineAware => {
+            let deadline = config
+                .deadline()
+                .context("deadline is required by the deadline-aware strategy")?;
+            anyhow::ensure!(!deadline.is_zero(), "deadline must be positive");
+            anyhow::ensure!(
+                config.max_multiplier >= 1.0,
+                "max fee multiplier must be at least 1, got {}",
+                config.max_multiplier
+            );
+            let strategy = DeadlineAwareFeeBump::new(deadline, config.max_multiplier);
+            match abandon_after {
+                Some(abandon_after) => Arc::new(strategy.with_abandon_after(abandon_after)),
+                None => Arc::new(strategy),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockL1TxParams {
+        base_fee: u64,
+        priority_fee: u64,
+        blob_base_fee: u64,
+        next_block_minimal_base_fee: u64,
+    }
+
+    impl Default for MockL1TxParams {
+        fn default() -> Self {
+            Self {
+                base_fee: 100,
+                priority_fee: 10,
+                blob_base_fee: 50,
+                next_block_minimal_base_fee: 80,
+            }
+        }
+    }
+
+    impl L1TxParamsProvider for MockL1TxParams {
+        fn get_base_fee(&self, time_in_mempool: u32) -> u64 {
+            self.base_fee + u64::from(time_in_mempool)
+        }
+
+        fn get_blob_base_fee(&self) -> u64 {
+            self.blob_base_fee
+        }
+
+        fn get_priority_fee(&self) -> u64 {
+            self.priority_fee
+        }
+
+        fn get_next_block_minimal_base_fee(&self) -> u64 {
+            self.next_block_minimal_base_fee
+        }
+    }
+
+    fn attempt(time_in_mempool: u32, previous_fees: Option<EthFee>) -> FeeBumpAttempt {
+        FeeBumpAttempt {
+            tx_type: AggregatedActionType::Execute,
+            is_blob_tx: false,
+            time_in_mempool,
+            age: Duration::from_secs(60),
+            previous_fees,
+        }
+    }
+
+    fn fees(base_fee_per_gas: u64, priority_fee_per_gas: u64) -> EthFee {
+        EthFee {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas: None,
+        }
+    }
+
+    #[test]
+    fn linear_fee_bump() {
+        let params = MockL1TxParams::default();
+        let strategy = LinearFeeBump::default();
+
+        let decision = strategy.next_fees(&params, &attempt(0, None));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(100, 10)));
+        let decision = strategy.next_fees(&params, &attempt(3, Some(fees(100, 100))));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(103, 121)));
+        // The suggested priority fee is used if it's greater than the bumped one.
+        let decision = strategy.next_fees(&params, &attempt(3, Some(fees(100, 1))));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(103, 10)));
+    }
+
+    #[test]
+    fn linear_fee_bump_skips_resend_if_base_fee_dropped() {
+        let params = MockL1TxParams {
+            next_block_minimal_base_fee: 200,
+            ..MockL1TxParams::default()
+        };
+        let decision =
+            LinearFeeBump::default().next_fees(&params, &attempt(1, Some(fees(150, 10))));
+        assert_eq!(decision, FeeBumpDecision::Skip);
+    }
+
+    #[test]
+    fn linear_fee_bump_for_blob_tx() {
+        let params = MockL1TxParams::default();
+        let previous_fees = EthFee {
+            base_fee_per_gas: 100,
+            priority_fee_per_gas: 10,
+            blob_base_fee_per_gas: Some(50),
+        };
+        let attempt = FeeBumpAttempt {
+            is_blob_tx: true,
+            ..attempt(5, Some(previous_fees))
+        };
+
+        let decision = LinearFeeBump::default().next_fees(&params, &attempt);
+        let expected_fees = EthFee {
+            base_fee_per_gas: 200,
+            priority_fee_per_gas: 20,
+            blob_base_fee_per_gas: Some(100),
+        };
+        assert_eq!(decision, FeeBumpDecision::Send(expected_fees));
+    }
+
+    #[test]
+    fn exponential_fee_bump() {
+        let params = MockL1TxParams::default();
+        let strategy = ExponentialFeeBump::new(2.0, 5.0);
+
+        let decision = strategy.next_fees(&params, &attempt(0, None));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(100, 10)));
+        let decision = strategy.next_fees(&params, &attempt(2, Some(fees(200, 20))));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(400, 40)));
+        // The multiplier is capped, but fees still must be sufficient to replace the previous tx.
+        let decision = strategy.next_fees(&params, &attempt(10, Some(fees(500, 50))));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(551, 56)));
+    }
+
+    #[test]
+    fn deadline_aware_fee_bump() {
+        let params = MockL1TxParams::default();
+        let strategy = DeadlineAwareFeeBump::new(Duration::from_secs(100), 3.0);
+
+        let fresh_attempt = FeeBumpAttempt {
+            age: Duration::ZERO,
+            ..attempt(0, None)
+        };
+        let decision = strategy.next_fees(&params, &fresh_attempt);
+        assert_eq!(decision, FeeBumpDecision::Send(fees(100, 10)));
+
+        let halfway_attempt = FeeBumpAttempt {
+            age: Duration::from_secs(50),
+            ..attempt(1, Some(fees(100, 10)))
+        };
+        let decision = strategy.next_fees(&params, &halfway_attempt);
+        assert_eq!(decision, FeeBumpDecision::Send(fees(200, 20)));
+
+        let overdue_attempt = FeeBumpAttempt {
+            age: Duration::from_secs(1_000),
+            ..attempt(2, Some(fees(200, 20)))
+        };
+        let decision = strategy.next_fees(&params, &overdue_attempt);
+        assert_eq!(decision, FeeBumpDecision::Send(fees(300, 30)));
+    }
+
+    #[test]
+    fn creating_strategy_from_config() {
+        let mut config = FeeBumpStrategyConfig {
+            strategy: FeeBumpStrategyKind::DeadlineAware,
+            priority_fee_increase_percent: 20,
+            multiplier: 2.0,
+            max_multiplier: 3.0,
+            deadline_sec: None,
+            abandon_after_sec: Some(30),
+        };
+        let err = strategy_from_config(&config).unwrap_err().to_string();
+        assert!(err.contains("deadline"), "{err}");
+
+        config.deadline_sec = Some(100);
+        let strategy = strategy_from_config(&config).unwrap();
+        let decision =
+            strategy.next_fees(&MockL1TxParams::default(), &attempt(1, Some(fees(100, 10))));
+        assert_eq!(decision, FeeBumpDecision::Cancel(fees(111, 12)));
+
+        config.strategy = FeeBumpStrategyKind::Exponential;
+        config.multiplier = 0.5;
+        strategy_from_config(&config).unwrap_err();
+    }
+
+    #[test]
+    fn abandoning_operation() {
+        let params = MockL1TxParams::default();
+        let strategy =
+            ExponentialFeeBump::new(2.0, 5.0).with_abandon_after(Duration::from_secs(30));
+
+        // Operations are never abandoned on the first attempt.
+        let decision = strategy.next_fees(&params, &attempt(0, None));
+        assert_eq!(decision, FeeBumpDecision::Send(fees(100, 10)));
+
+        let decision = strategy.next_fees(&params, &attempt(1, Some(fees(200, 20))));
+        assert_eq!(decision, FeeBumpDecision::Cancel(fees(221, 23)));
+
+        let young_attempt = FeeBumpAttempt {
+            age: Duration::from_secs(10),
+            ..attempt(1, Some(fees(100, 10)))
+        };
+        let decision = strategy.next_fees(&params, &young_attempt);
+        assert_eq!(decision, FeeBumpDecision::Send(fees(200, 20)));
+
+        // Blob transactions cannot be cancelled by replacement.
+        let previous_fees = EthFee {
+            blob_base_fee_per_gas: Some(50),
+            ..fees(100, 10)
+        };
+        let blob_attempt = FeeBumpAttempt {
+            is_blob_tx: true,
+            ..attempt(1, Some(previous_fees))
+        };
+        let decision = strategy.next_fees(&params, &blob_attempt);
+        assert_matches!(decision, FeeBumpDecision::Send(_));
+    }
+}
@@ -31,6 +31,17 @@ pub(super) enum BlockNumberVariant {
     Safe,
 }
 
+/// Result of checking an NH attestation Merkle path against the attestation root posted on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "result", rename_all = "snake_case")]
+pub(super) enum AttestationPathCheck {
+    Valid,
+    /// The path has an unexpected length or index.
+    Malformed,
+    /// The root computed from the path differs from the attestation root.
+    Mismatch,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
 #[metrics(label = "type")]
 pub(super) struct ActionTypeLabel(AggregatedActionType);
@@ -84,6 +95,8 @@ pub(super) struct EthSenderMetrics {
     pub block_range_size: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of transactions resent by the Ethereum sender.
     pub transaction_resent: Counter,
+    /// Number of cancellation transactions sent by the Ethereum sender for abandoned operations.
+    pub transaction_cancelled: Counter,
     #[metrics(buckets = FEE_BUCKETS)]
     pub used_base_fee_per_gas: Histogram<u64>,
     #[metrics(buckets = FEE_BUCKETS)]
@@ -100,6 +113,8 @@ pub(super) struct EthSenderMetrics {
     pub l1_blocks_waited_in_mempool: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
+    /// Number of NH attestation Merkle paths checked before sending proofs to L1, grouped by the check result.
+    pub attestation_path_checks: Family<AttestationPathCheck, Counter>,
 }
 
 impl EthSenderMetrics {
@@ -1,8 +1,10 @@
 mod aggregated_operations;
 mod aggregator;
+mod blob_archive;
 mod error;
 mod eth_tx_aggregator;
 mod eth_tx_manager;
+mod fee_bump;
 pub mod l1_batch_commit_data_generator;
 mod metrics;
 mod publish_criterion;
@@ -12,6 +14,13 @@ mod zksync_functions;
 mod tests;
 
 pub use self::{
-    aggregator::Aggregator, error::ETHSenderError, eth_tx_aggregator::EthTxAggregator,
+    aggregator::Aggregator,
+    blob_archive::{BlobArchiveStatus, BlobArchiveVerifier},
+    error::ETHSenderError,
+    eth_tx_aggregator::EthTxAggregator,
     eth_tx_manager::EthTxManager,
+    fee_bump::{
+        DeadlineAwareFeeBump, EthFee, ExponentialFeeBump, FeeBumpAttempt, FeeBumpDecision,
+        FeeBumpStrategy, LinearFeeBump,
+    },
 };
@@ -3,23 +3,41 @@ use std::sync::Arc;
 use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
 use test_casing::{test_casing, Product};
+use tokio::sync::watch;
+use zksync_circuit_breaker::{
+    attestation_paths::AttestationPathMismatchChecker, CircuitBreaker, CircuitBreakerError,
+};
 use zksync_config::{
-    configs::eth_sender::{ProofSendingMode, PubdataSendingMode, SenderConfig},
+    configs::{
+        eth_sender::{
+            FeeBumpConfig, FeeBumpStrategyConfig, FeeBumpStrategyKind, ProofSendingMode,
+            PubdataSendingMode, SenderConfig,
+        },
+        NewHorizenConfig,
+    },
     ContractsConfig, ETHSenderConfig, GasAdjusterConfig,
 };
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_eth_client::{clients::MockEthereum, EthInterface};
-use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
+use zksync_dal::{
+    nh_proof_submissions_dal::NhProofSubmissionStatus, Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_eth_client::{clients::MockEthereum, BoundEthInterface, EthInterface};
+use zksync_l1_contract_interface::i_executor::methods::{
+    ExecuteBatches, NewHorizenProof, Proof, ProveBatches,
+};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     block::L1BatchHeader,
     commitment::{L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata},
     ethabi::Token,
     helpers::unix_timestamp_ms,
+    l1::NHAttestation,
+    protocol_version::L1VerifierConfig,
     pubdata_da::PubdataDA,
-    web3::contract::Error,
+    web3::{contract::Error, types::BlockNumber},
     Address, L1BatchNumber, L1BlockNumber, ProtocolVersionId, H256,
 };
+use zksync_utils::h256_to_u256;
 
 use super::l1_batch_commit_data_generator::{
     L1BatchCommitDataGenerator, RollupModeL1BatchCommitDataGenerator,
@@ -28,9 +46,15 @@ use super::l1_batch_commit_data_generator::{
 use crate::{
     eth_sender::{
         aggregated_operations::AggregatedOperation, eth_tx_manager::L1BlockNumbers, Aggregator,
-        ETHSenderError, EthTxAggregator, EthTxManager,
+        ETHSenderError, EthTxAggregator, EthTxManager, ExponentialFeeBump, FeeBumpStrategy,
+    },
+    eth_watch::{
+        client::{EthClient, EthHttpQueryClient, RETRY_LIMIT},
+        event_processors::{nh::NHEventProcessor, EventProcessor},
     },
     l1_gas_price::{GasAdjuster, PubdataPricing, RollupPubdataPricing, ValidiumPubdataPricing},
+    new_horizen::NhProofSubmitter,
+    proof_verification_layer::{MockProofVerificationLayer, ProofVerificationLayer},
     utils::testonly::{create_l1_batch, l1_batch_metadata_to_commitment_artifacts, DeploymentMode},
 };
 
@@ -146,8 +170,9 @@ impl EthSenderTester {
                 store_factory.create_store().await,
                 aggregator_operate_4844_mode,
                 PubdataDA::Calldata,
+                false,
                 l1_batch_commit_data_generator.clone(),
-                None,
+                Arc::new(MockProofVerificationLayer::default()),
             ),
             gateway.clone(),
             // zkSync contract address
@@ -189,6 +214,26 @@ impl EthSenderTester {
             safe: finalized,
         }
     }
+
+    fn new_manager(&self) -> EthTxManager {
+        EthTxManager::new(
+            self.conn.clone(),
+            ETHSenderConfig::for_tests().sender,
+            self.gas_adjuster.clone(),
+            self.gateway.clone(),
+            None,
+        )
+    }
+
+    fn set_fee_bump_strategy(
+        &mut self,
+        action_type: AggregatedActionType,
+        strategy: Arc<dyn FeeBumpStrategy>,
+    ) {
+        self.manager = self
+            .new_manager()
+            .with_fee_bump_strategy(action_type, strategy);
+    }
 }
 
 fn l1_batch_with_metadata(header: L1BatchHeader) -> L1BatchWithMetadata {
@@ -418,6 +463,174 @@ async fn resend_each_block(deployment_mode: DeploymentMode) -> anyhow::Result<()
     Ok(())
 }
 
+#[tokio::test]
+async fn resend_with_exponential_fee_bump() -> anyhow::Result<()> {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![7, 6, 5, 5, 5, 2, 1],
+        false,
+        false,
+        &DeploymentMode::Rollup,
+    )
+    .await;
+    tester.set_fee_bump_strategy(
+        AggregatedActionType::Execute,
+        Arc::new(ExponentialFeeBump::new(2.0, 10.0)),
+    );
+
+    // after this, median should be 6
+    tester.gateway.advance_block_number(3);
+    tester.gas_adjuster.keep_updated().await?;
+
+    let block = L1BlockNumber(tester.gateway.block_number("").await?.as_u32());
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.connection().await.unwrap(),
+            &DUMMY_OPERATION,
+            true,
+        )
+        .await?;
+    let hash = tester
+        .manager
+        .send_eth_tx(&mut tester.conn.connection().await.unwrap(), &tx, 0, block)
+        .await?;
+    let sent_tx = tester
+        .gateway
+        .get_tx(hash, "")
+        .await?
+        .expect("no transaction");
+    let priority_fee = sent_tx.max_priority_fee_per_gas.unwrap();
+    assert_eq!(sent_tx.max_fee_per_gas.unwrap() - priority_fee, 18.into()); // `6 * 3 * 2^0`
+
+    // Fees are multiplied by `2^2` after the transaction has spent 2 blocks in the mempool.
+    let resent_hash = tester
+        .manager
+        .send_eth_tx(&mut tester.conn.connection().await.unwrap(), &tx, 2, block)
+        .await?;
+    assert_eq!(tester.gateway.sent_tx_count(), 2);
+    let resent_tx = tester
+        .gateway
+        .get_tx(resent_hash, "")
+        .await?
+        .expect("no transaction");
+    assert_eq!(resent_tx.nonce, 0.into());
+    assert_eq!(
+        resent_tx.max_priority_fee_per_gas.unwrap(),
+        priority_fee * 4_u32
+    );
+    assert_eq!(
+        resent_tx.max_fee_per_gas.unwrap() - resent_tx.max_priority_fee_per_gas.unwrap(),
+        72.into()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancelling_abandoned_operation() -> anyhow::Result<()> {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![10; 100],
+        false,
+        false,
+        &DeploymentMode::Rollup,
+    )
+    .await;
+    let fee_bump_config = FeeBumpConfig {
+        execute: Some(FeeBumpStrategyConfig {
+            strategy: FeeBumpStrategyKind::Linear,
+            priority_fee_increase_percent: 20,
+            multiplier: 1.0,
+            max_multiplier: 1.0,
+            deadline_sec: None,
+            abandon_after_sec: Some(0),
+        }),
+        ..FeeBumpConfig::default()
+    };
+    tester.manager = tester
+        .new_manager()
+        .with_fee_bump_config(&fee_bump_config)?;
+
+    let block = L1BlockNumber(tester.gateway.block_number("").await?.as_u32());
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.connection().await.unwrap(),
+            &DUMMY_OPERATION,
+            true,
+        )
+        .await?;
+    // The operation is never abandoned on the first sending attempt.
+    let hash = tester
+        .manager
+        .send_eth_tx(&mut tester.conn.connection().await.unwrap(), &tx, 0, block)
+        .await?;
+    let sent_tx = tester
+        .gateway
+        .get_tx(hash, "")
+        .await?
+        .expect("no transaction");
+    assert_eq!(sent_tx.to, Some(tx.contract_address));
+
+    let cancellation_hash = tester
+        .manager
+        .send_eth_tx(&mut tester.conn.connection().await.unwrap(), &tx, 1, block)
+        .await?;
+    let cancellation_tx = tester
+        .gateway
+        .get_tx(cancellation_hash, "")
+        .await?
+        .expect("no transaction");
+    assert_eq!(cancellation_tx.nonce, sent_tx.nonce);
+    assert_eq!(cancellation_tx.to, Some(tester.gateway.sender_account()));
+    assert!(cancellation_tx.input.0.is_empty());
+    assert!(
+        cancellation_tx.max_priority_fee_per_gas.unwrap()
+            > sent_tx.max_priority_fee_per_gas.unwrap()
+    );
+
+    // Once the cancellation transaction is mined, the operation is marked as failed rather than confirmed,
+    // and the manager stops.
+    tester
+        .gateway
+        .execute_tx(cancellation_hash, true, EthSenderTester::WAIT_CONFIRMATIONS);
+    let err = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.connection().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, ETHSenderError::OperationAbandoned(id) if id == tx.id);
+
+    let mut storage = tester.storage().await;
+    assert_eq!(
+        storage
+            .eth_sender_dal()
+            .get_number_of_failed_transactions()
+            .await?,
+        1
+    );
+    let confirmed_tx_hash = storage
+        .eth_sender_dal()
+        .get_confirmed_tx_hash_by_eth_tx_id(tx.id)
+        .await?;
+    assert_eq!(confirmed_tx_hash, None);
+    drop(storage);
+
+    // The manager refuses to start until the abandoned operation is cleared.
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = tester
+        .new_manager()
+        .run(stop_receiver)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("block reverter"), "{err}");
+    Ok(())
+}
+
 // Tests that if transaction was mined, but not enough blocks has been mined since,
 // we won't mark it as confirmed but also won't resend it.
 #[test_casing(2, [DeploymentMode::Rollup, DeploymentMode::Validium])]
@@ -978,6 +1191,243 @@ async fn get_multicall_data(deployment_mode: DeploymentMode) {
     assert!(multicall_data.is_ok());
 }
 
+/// Tests the entire NH flow: committed L1 batch proofs are submitted to the (mock) NH chain,
+/// attestations are posted on L1 and picked up by the eth watcher, and finally `Aggregator`
+/// produces a `proveBatches` operation with the Merkle path for the attestation.
+#[tokio::test]
+async fn proving_l1_batch_with_nh_attestation() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        &DeploymentMode::Rollup,
+    )
+    .await;
+    let nh_contract_address = Address::repeat_byte(0x42);
+    let layer = Arc::new(
+        MockProofVerificationLayer::default()
+            .with_l1_client(tester.gateway.clone(), nh_contract_address),
+    );
+
+    submit_proofs_to_nh(&mut tester, layer.clone()).await;
+    let mut aggregator = create_nh_aggregator(layer.clone()).await;
+    let l1_verifier_config = l1_verifier_config(&tester).await;
+    let mut storage = tester.storage().await;
+
+    // The attestation is not posted yet, so there's nothing to prove.
+    let operation = aggregator
+        .get_next_ready_operation(
+            &mut storage,
+            Default::default(),
+            ProtocolVersionId::latest(),
+            l1_verifier_config,
+        )
+        .await;
+    assert!(operation.is_none(), "{operation:?}");
+
+    let attestation = layer.post_attestation().unwrap();
+    assert_eq!(attestation.elements.len(), 2);
+
+    let mut processor = NHEventProcessor::new();
+    let mut eth_client = EthHttpQueryClient::new(
+        tester.gateway.clone(),
+        Address::random(),
+        None,
+        None,
+        nh_contract_address,
+    );
+    eth_client.set_topics(vec![processor.relevant_topic()]);
+    let events = eth_client
+        .get_events(BlockNumber::Earliest, BlockNumber::Latest, RETRY_LIMIT)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    processor
+        .process_events(&mut storage, &eth_client, events)
+        .await
+        .unwrap();
+
+    let operation = aggregator
+        .get_next_ready_operation(
+            &mut storage,
+            Default::default(),
+            ProtocolVersionId::latest(),
+            l1_verifier_config,
+        )
+        .await
+        .unwrap();
+    let AggregatedOperation::PublishProofOnchain(prove_batches) = &operation else {
+        panic!("unexpected operation: {operation:?}");
+    };
+    assert_eq!(prove_batches.prev_l1_batch.header.number, L1BatchNumber(0));
+    assert_eq!(prove_batches.l1_batches.len(), 1);
+    assert_eq!(prove_batches.l1_batches[0].header.number, L1BatchNumber(1));
+    assert!(prove_batches.should_verify);
+
+    let expected_path = layer
+        .get_attestation_path(attestation.id, attestation.elements[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(expected_path.merkle_path.len(), 1);
+    assert_matches!(
+        &prove_batches.proofs,
+        Proof::NewHorizenProof(NewHorizenProof {
+            attestation_id,
+            merkle_path,
+            leaf_count: 2,
+            index: 0,
+        }) if *attestation_id == attestation.id
+            && *merkle_path == [h256_to_u256(expected_path.merkle_path[0])]
+    );
+    drop(storage);
+
+    send_operation(&mut tester, operation, true).await;
+    let last_proven_l1_batch = tester
+        .storage()
+        .await
+        .blocks_dal()
+        .get_last_l1_batch_with_prove_tx()
+        .await
+        .unwrap();
+    assert_eq!(last_proven_l1_batch, L1BatchNumber(1));
+}
+
+/// Tests that an attestation path not matching the attestation root posted on L1 is not sent to L1
+/// and trips the circuit breaker.
+#[tokio::test]
+async fn nh_attestation_path_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        &DeploymentMode::Rollup,
+    )
+    .await;
+    let layer = Arc::new(MockProofVerificationLayer::default());
+    submit_proofs_to_nh(&mut tester, layer.clone()).await;
+    let mut aggregator = create_nh_aggregator(layer.clone()).await;
+    let l1_verifier_config = l1_verifier_config(&tester).await;
+
+    let circuit_breaker = AttestationPathMismatchChecker { pool: pool.clone() };
+    circuit_breaker.check().await.unwrap();
+
+    let attestation = layer.post_attestation().unwrap();
+    let mut storage = tester.storage().await;
+    storage
+        .nh_dal()
+        .insert_nh_attestation(&NHAttestation {
+            attestation_id: attestation.id.into(),
+            proofs_attestation: H256::repeat_byte(0xff),
+        })
+        .await;
+
+    let operation = aggregator
+        .get_next_ready_operation(
+            &mut storage,
+            Default::default(),
+            ProtocolVersionId::latest(),
+            l1_verifier_config,
+        )
+        .await;
+    assert!(operation.is_none(), "{operation:?}");
+    let status = storage
+        .nh_proof_submissions_dal()
+        .get_proof_submission_status(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(status, Some(NhProofSubmissionStatus::PathMismatch));
+    assert_matches!(
+        circuit_breaker.check().await,
+        Err(CircuitBreakerError::AttestationPathMismatch(1))
+    );
+}
+
+/// Commits L1 batches #1 and #2 and submits their proofs to the specified NH layer.
+async fn submit_proofs_to_nh(tester: &mut EthSenderTester, layer: Arc<MockProofVerificationLayer>) {
+    insert_genesis_protocol_version(tester).await;
+    let genesis_l1_batch = insert_l1_batch(tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(tester, L1BatchNumber(1)).await;
+    let second_l1_batch = insert_l1_batch(tester, L1BatchNumber(2)).await;
+    commit_l1_batch(tester, genesis_l1_batch, first_l1_batch.clone(), true).await;
+    commit_l1_batch(tester, first_l1_batch, second_l1_batch, true).await;
+
+    for number in [1, 2] {
+        let l1_batch_number = L1BatchNumber(number);
+        let mut storage = tester.storage().await;
+        storage
+            .proof_generation_dal()
+            .insert_proof_generation_details(l1_batch_number, "")
+            .await;
+        storage
+            .nh_proof_submissions_dal()
+            .insert_proof_submission(l1_batch_number, &[number as u8; 32], &[number as u8; 4])
+            .await
+            .unwrap();
+    }
+
+    let submitter = NhProofSubmitter::new(
+        tester.conn.clone(),
+        layer,
+        NewHorizenConfig {
+            url: String::new(),
+            signer: None,
+            submission_polling_interval_ms: None,
+            submission_timeout_sec: None,
+            submission_max_attempts: None,
+            submission_retry_backoff_ms: None,
+            submission_batch_size: None,
+            max_attestation_delay_sec: None,
+            attestation_reconciliation_interval_sec: None,
+            attestation_reconciliation_block_range: None,
+        },
+    );
+    // Both proofs should be submitted in a single batch.
+    assert!(submitter.submit_next_proofs().await.unwrap());
+    assert!(!submitter.submit_next_proofs().await.unwrap());
+    // Mock submissions are finalized immediately, so there are no included submissions to check.
+    assert!(!submitter.check_included_proofs().await.unwrap());
+    for number in [1, 2] {
+        let status = tester
+            .storage()
+            .await
+            .nh_proof_submissions_dal()
+            .get_proof_submission_status(L1BatchNumber(number))
+            .await
+            .unwrap();
+        assert_eq!(status, Some(NhProofSubmissionStatus::Finalized));
+    }
+}
+
+async fn create_nh_aggregator(layer: Arc<MockProofVerificationLayer>) -> Aggregator {
+    Aggregator::new(
+        SenderConfig {
+            proof_sending_mode: ProofSendingMode::OnlyRealProofs,
+            aggregated_proof_sizes: vec![1],
+            ..ETHSenderConfig::for_tests().sender
+        },
+        ObjectStoreFactory::mock().create_store().await,
+        false,
+        PubdataDA::Calldata,
+        false,
+        Arc::new(RollupModeL1BatchCommitDataGenerator {}),
+        layer,
+    )
+}
+
+async fn l1_verifier_config(tester: &EthSenderTester) -> L1VerifierConfig {
+    tester
+        .storage()
+        .await
+        .protocol_versions_dal()
+        .l1_verifier_config_for_version(ProtocolVersionId::latest())
+        .await
+        .unwrap()
+}
+
 async fn insert_genesis_protocol_version(tester: &EthSenderTester) {
     tester
         .storage()
@@ -27,7 +27,7 @@ use self::{
 use crate::eth_watch::event_processors::nh::NHEventProcessor;
 
 pub mod client;
-mod event_processors;
+pub(crate) mod event_processors;
 mod metrics;
 #[cfg(test)]
 mod tests;
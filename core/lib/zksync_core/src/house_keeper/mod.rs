@@ -7,5 +7,6 @@ pub mod fri_prover_queue_monitor;
 pub mod fri_scheduler_circuit_queuer;
 pub mod fri_witness_generator_jobs_retry_manager;
 pub mod fri_witness_generator_queue_monitor;
+pub mod object_store_garbage_collector;
 pub mod periodic_job;
 pub mod waiting_to_queued_fri_witness_job_mover;
@@ -0,0 +1,337 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_object_store::{Bucket, ListedObject, ObjectStore, ObjectStoreError};
+use zksync_types::L1BatchNumber;
+
+use crate::house_keeper::periodic_job::PeriodicJob;
+
+/// Buckets with blobs keyed by L1 batch number that are collected by [`ObjectStoreGarbageCollector`].
+const COLLECTED_BUCKETS: [Bucket; 3] = [
+    Bucket::ProverJobsFri,
+    Bucket::WitnessInput,
+    Bucket::StorageSnapshot,
+];
+
+/// Removes blobs for L1 batches that are proven on L1 and were last modified before the configured retention period.
+/// Blobs for the newest snapshot and the newest complete snapshot are never removed so that nodes can recover from them.
+/// When snapshot files are removed, the corresponding snapshot is removed from Postgres as well. Afterwards, blobs
+/// no longer referenced by other blobs (e.g., deduplicated content) are removed.
+#[derive(Debug)]
+pub struct ObjectStoreGarbageCollector {
+    pool: ConnectionPool<Core>,
+    blob_store: Arc<dyn ObjectStore>,
+    retention: Duration,
+    polling_interval_ms: u64,
+}
+
+impl ObjectStoreGarbageCollector {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        blob_store: Arc<dyn ObjectStore>,
+        retention: Duration,
+        polling_interval_ms: u64,
+    ) -> Self {
+        Self {
+            pool,
+            blob_store,
+            retention,
+            polling_interval_ms,
+        }
+    }
+
+    /// Extracts the L1 batch number from a blob key. Returns `None` for keys in an unknown format;
+    /// such blobs are never collected.
+    fn l1_batch_number(bucket: Bucket, key: &str) -> Option<L1BatchNumber> {
+        let number = match bucket {
+            // `{l1_batch_number}_{sequence_number}_{circuit_id}_{aggregation_round:?}_{depth}.bin`
+            Bucket::ProverJobsFri => key.split_once('_')?.0,
+            // `witness_block_state_for_l1_batch_{l1_batch_number}.bin` etc.
+            Bucket::WitnessInput => key.strip_suffix(".bin")?.rsplit_once('_')?.1,
+            // `snapshot_l1_batch_{l1_batch_number}_{suffix}`
+            Bucket::StorageSnapshot => key.strip_prefix("snapshot_l1_batch_")?.split_once('_')?.0,
+            _ => return None,
+        };
+        number.parse().ok().map(L1BatchNumber)
+    }
+
+    async fn collect_bucket(
+        &self,
+        bucket: Bucket,
+        last_proven_l1_batch: L1BatchNumber,
+        preserved_l1_batches: &[L1BatchNumber],
+        cutoff: DateTime<Utc>,
+    ) -> anyhow::Result<usize> {
+        let objects = self
+            .blob_store
+            .list_raw(bucket, "")
+            .await
+            .with_context(|| format!("failed listing blobs in bucket `{bucket}`"))?;
+
+        let mut eligible_blobs = vec![];
+        for ListedObject { key, metadata } in objects {
+            let Some(l1_batch_number) = Self::l1_batch_number(bucket, &key) else {
+                continue;
+            };
+            if l1_batch_number > last_proven_l1_batch {
+                continue;
+            }
+            if bucket == Bucket::StorageSnapshot && preserved_l1_batches.contains(&l1_batch_number)
+            {
+                continue;
+            }
+            // Blobs without the modification timestamp are conservatively retained.
+            let Some(last_modified) = metadata.last_modified else {
+                continue;
+            };
+            if last_modified > cutoff {
+                continue;
+            }
+            eligible_blobs.push((key, l1_batch_number));
+        }
+
+        if bucket == Bucket::StorageSnapshot {
+            // Snapshot metadata is removed before snapshot files, so that the snapshot is never advertised
+            // with missing files. If removing files fails midway, the remaining files are collected on the next run.
+            let mut l1_batch_numbers: Vec<_> =
+                eligible_blobs.iter().map(|(_, number)| *number).collect();
+            l1_batch_numbers.sort_unstable();
+            l1_batch_numbers.dedup();
+            if !l1_batch_numbers.is_empty() {
+                let mut storage = self.pool.connection_tagged("house_keeper").await?;
+                storage
+                    .snapshots_dal()
+                    .delete_snapshots(&l1_batch_numbers)
+                    .await?;
+                tracing::info!("Removed snapshots for L1 batches {l1_batch_numbers:?}");
+            }
+        }
+
+        let mut removed_count = 0;
+        for (key, l1_batch_number) in eligible_blobs {
+            match self.blob_store.remove_raw(bucket, &key).await {
+                Ok(()) => {}
+                // The blob was removed concurrently, e.g. by another garbage collector instance.
+                Err(ObjectStoreError::KeyNotFound(_)) => continue,
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("failed removing blob `{key}` in bucket `{bucket}`")
+                    });
+                }
+            }
+            tracing::debug!(
+                "Removed blob `{key}` for L1 batch #{l1_batch_number} in bucket `{bucket}`"
+            );
+            removed_count += 1;
+        }
+        Ok(removed_count)
+    }
+
+    /// Removes eligible blobs from all collected buckets. Returns the total number of removed blobs.
+    pub async fn collect_garbage(&self) -> anyhow::Result<usize> {
+        let mut storage = self.pool.connection_tagged("house_keeper").await?;
+        let Some(last_proven_l1_batch) = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await?
+        else {
+            tracing::debug!(
+                "No L1 batches are proven yet; skipping object store garbage collection"
+            );
+            return Ok(0);
+        };
+        let newest_snapshot = storage
+            .snapshots_dal()
+            .get_newest_snapshot_metadata()
+            .await?;
+        let complete_snapshots = storage.snapshots_dal().get_all_complete_snapshots().await?;
+        drop(storage);
+
+        let preserved_l1_batches: Vec<_> = newest_snapshot
+            .map(|snapshot| snapshot.l1_batch_number)
+            .into_iter()
+            .chain(
+                complete_snapshots
+                    .snapshots_l1_batch_numbers
+                    .first()
+                    .copied(),
+            )
+            .collect();
+        let retention =
+            chrono::Duration::from_std(self.retention).context("retention period is too large")?;
+        let cutoff = Utc::now() - retention;
+
+        let mut total_removed_count = 0;
+        for bucket in COLLECTED_BUCKETS {
+            let mut removed_count = self
+                .collect_bucket(bucket, last_proven_l1_batch, &preserved_l1_batches, cutoff)
+                .await?;
+            // Deduplicated content may become unreferenced after removing blobs.
+            removed_count += self
+                .blob_store
+                .remove_unreferenced_raw(bucket, cutoff)
+                .await
+                .with_context(|| {
+                    format!("failed removing unreferenced blobs in bucket `{bucket}`")
+                })?;
+            metrics::counter!(
+                "server.object_store_gc.removed_blobs",
+                removed_count as u64,
+                "bucket" => bucket.to_string()
+            );
+            total_removed_count += removed_count;
+        }
+        Ok(total_removed_count)
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for ObjectStoreGarbageCollector {
+    const SERVICE_NAME: &'static str = "ObjectStoreGarbageCollector";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        let removed_count = self.collect_garbage().await?;
+        tracing::info!("Removed {removed_count} blobs from object store");
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.polling_interval_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::ObjectStoreFactory;
+    use zksync_types::snapshots::SnapshotVersion;
+
+    use super::*;
+
+    #[test]
+    fn parsing_l1_batch_numbers_from_keys() {
+        let parse = ObjectStoreGarbageCollector::l1_batch_number;
+        assert_eq!(
+            parse(Bucket::ProverJobsFri, "12_3_1_BasicCircuits_0.bin"),
+            Some(L1BatchNumber(12))
+        );
+        assert_eq!(
+            parse(
+                Bucket::WitnessInput,
+                "witness_block_state_for_l1_batch_7.bin"
+            ),
+            Some(L1BatchNumber(7))
+        );
+        assert_eq!(
+            parse(Bucket::WitnessInput, "merkel_tree_paths_8.bin"),
+            Some(L1BatchNumber(8))
+        );
+        assert_eq!(
+            parse(
+                Bucket::StorageSnapshot,
+                "snapshot_l1_batch_5_storage_logs_part_0001.proto.gzip"
+            ),
+            Some(L1BatchNumber(5))
+        );
+        assert_eq!(
+            parse(
+                Bucket::StorageSnapshot,
+                "snapshot_l1_batch_5_factory_deps.proto.gzip"
+            ),
+            Some(L1BatchNumber(5))
+        );
+        assert_eq!(parse(Bucket::WitnessInput, "unrelated.json"), None);
+        assert_eq!(parse(Bucket::ProofsFri, "l1_batch_proof_1.bin"), None);
+    }
+
+    #[tokio::test]
+    async fn collecting_bucket() {
+        let blob_store = ObjectStoreFactory::mock().create_store().await;
+        let keys = [
+            "witness_block_state_for_l1_batch_1.bin",
+            "witness_block_state_for_l1_batch_2.bin",
+            "witness_block_state_for_l1_batch_3.bin",
+        ];
+        for key in keys {
+            blob_store
+                .put_raw(Bucket::WitnessInput, key, vec![1; 8])
+                .await
+                .unwrap();
+        }
+        let snapshot_keys = [
+            "snapshot_l1_batch_1_factory_deps.proto.gzip",
+            "snapshot_l1_batch_2_factory_deps.proto.gzip",
+        ];
+        for key in snapshot_keys {
+            blob_store
+                .put_raw(Bucket::StorageSnapshot, key, vec![2; 8])
+                .await
+                .unwrap();
+        }
+
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        for (l1_batch_number, key) in (1..).zip(snapshot_keys) {
+            storage
+                .snapshots_dal()
+                .add_snapshot(
+                    SnapshotVersion::Version0,
+                    L1BatchNumber(l1_batch_number),
+                    0,
+                    key,
+                )
+                .await
+                .unwrap();
+        }
+        let gc = ObjectStoreGarbageCollector::new(
+            pool.clone(),
+            blob_store.clone(),
+            Duration::ZERO,
+            1_000,
+        );
+
+        // Blobs that are not old enough are retained.
+        let cutoff = Utc::now() - chrono::Duration::hours(1);
+        let removed_count = gc
+            .collect_bucket(Bucket::WitnessInput, L1BatchNumber(2), &[], cutoff)
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 0);
+
+        let cutoff = Utc::now();
+        let removed_count = gc
+            .collect_bucket(Bucket::WitnessInput, L1BatchNumber(2), &[], cutoff)
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 2);
+        let remaining_objects = blob_store.list_raw(Bucket::WitnessInput, "").await.unwrap();
+        assert_eq!(remaining_objects.len(), 1);
+        assert_eq!(remaining_objects[0].key, keys[2]);
+
+        let removed_count = gc
+            .collect_bucket(
+                Bucket::StorageSnapshot,
+                L1BatchNumber(2),
+                &[L1BatchNumber(2)],
+                cutoff,
+            )
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 1);
+        let remaining_objects = blob_store
+            .list_raw(Bucket::StorageSnapshot, "")
+            .await
+            .unwrap();
+        assert_eq!(remaining_objects.len(), 1);
+        assert_eq!(remaining_objects[0].key, snapshot_keys[1]);
+
+        // The snapshot with removed files must be removed from Postgres as well.
+        let mut dal = storage.snapshots_dal();
+        let snapshot = dal.get_snapshot_metadata(L1BatchNumber(1)).await.unwrap();
+        assert!(snapshot.is_none());
+        let snapshot = dal.get_snapshot_metadata(L1BatchNumber(2)).await.unwrap();
+        assert!(snapshot.is_some());
+    }
+}
@@ -5,8 +5,8 @@ use tokio::{
     sync::{watch, OnceCell},
     task::JoinHandle,
 };
-use zksync_config::{configs::eth_sender::PubdataSendingMode, GasAdjusterConfig};
-use zksync_eth_client::clients::QueryClient;
+use zksync_config::{configs::eth_sender::PubdataSendingMode, ETHClientConfig, GasAdjusterConfig};
+use zksync_eth_client::clients::l1_client;
 
 use super::PubdataPricing;
 use crate::l1_gas_price::GasAdjuster;
@@ -15,7 +15,7 @@ use crate::l1_gas_price::GasAdjuster;
 /// This is needed only for running the server.
 #[derive(Debug)]
 pub struct GasAdjusterSingleton {
-    web3_url: String,
+    eth_client_config: ETHClientConfig,
     gas_adjuster_config: GasAdjusterConfig,
     pubdata_sending_mode: PubdataSendingMode,
     singleton: OnceCell<Result<Arc<GasAdjuster>, Error>>,
@@ -34,13 +34,13 @@ impl From<anyhow::Error> for Error {
 
 impl GasAdjusterSingleton {
     pub fn new(
-        web3_url: String,
+        eth_client_config: ETHClientConfig,
         gas_adjuster_config: GasAdjusterConfig,
         pubdata_sending_mode: PubdataSendingMode,
         pubdata_pricing: Arc<dyn PubdataPricing>,
     ) -> Self {
         Self {
-            web3_url,
+            eth_client_config,
             gas_adjuster_config,
             pubdata_sending_mode,
             singleton: OnceCell::new(),
@@ -52,10 +52,9 @@ impl GasAdjusterSingleton {
         let adjuster = self
             .singleton
             .get_or_init(|| async {
-                let query_client =
-                    QueryClient::new(&self.web3_url).context("QueryClient::new()")?;
+                let eth_client = l1_client(&self.eth_client_config).context("l1_client()")?;
                 let adjuster = GasAdjuster::new(
-                    Arc::new(query_client.clone()),
+                    eth_client,
                     self.gas_adjuster_config,
                     self.pubdata_sending_mode,
                     self.pubdata_pricing.clone(),
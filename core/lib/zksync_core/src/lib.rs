@@ -12,16 +12,14 @@ use api_server::tx_sender::master_pool_sink::MasterPoolSink;
 use fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider, MainNodeFeeInputProvider};
 use prometheus_exporter::PrometheusExporterConfig;
 use prover_dal::Prover;
-use subxt::{OnlineClient, PolkadotConfig};
-use subxt_signer::{sr25519::Keypair, SecretUri};
 use temp_config_store::{Secrets, TempConfigStore};
 use tokio::{
     sync::{oneshot, watch},
     task::JoinHandle,
 };
 use zksync_circuit_breaker::{
-    l1_txs::FailedL1TransactionChecker, replication_lag::ReplicationLagChecker,
-    CircuitBreakerChecker, CircuitBreakers,
+    attestation_paths::AttestationPathMismatchChecker, l1_txs::FailedL1TransactionChecker,
+    replication_lag::ReplicationLagChecker, CircuitBreakerChecker, CircuitBreakers,
 };
 use zksync_concurrency::{ctx, scope};
 use zksync_config::{
@@ -32,14 +30,16 @@ use zksync_config::{
             OperationsManagerConfig, StateKeeperConfig,
         },
         database::{MerkleTreeConfig, MerkleTreeMode},
+        eth_sender::ProofVerificationLayerMode,
     },
-    ApiConfig, ContractsConfig, DBConfig, GenesisConfig, PostgresConfig,
+    ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, GenesisConfig,
+    PostgresConfig,
 };
 use zksync_contracts::governance_contract;
 use zksync_dal::{metrics::PostgresMetrics, ConnectionPool, Core, CoreDal};
 use zksync_db_connection::healthcheck::ConnectionPoolHealthCheck;
 use zksync_eth_client::{
-    clients::{PKSigningClient, QueryClient},
+    clients::{l1_client, PKSigningClient, Web3SignerClient},
     BoundEthInterface,
 };
 use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
@@ -59,6 +59,7 @@ use crate::{
     },
     basic_witness_input_producer::BasicWitnessInputProducer,
     commitment_generator::CommitmentGenerator,
+    data_availability::{create_da_client, DataAvailabilityDispatcher},
     eth_sender::{
         l1_batch_commit_data_generator::{
             L1BatchCommitDataGenerator, RollupModeL1BatchCommitDataGenerator,
@@ -66,7 +67,7 @@ use crate::{
         },
         Aggregator, EthTxAggregator, EthTxManager,
     },
-    eth_watch::start_eth_watch,
+    eth_watch::{client::EthHttpQueryClient, start_eth_watch},
     genesis::GenesisParams,
     house_keeper::{
         blocks_state_reporter::L1BatchMetricsReporter,
@@ -78,15 +79,16 @@ use crate::{
         fri_scheduler_circuit_queuer::SchedulerCircuitQueuer,
         fri_witness_generator_jobs_retry_manager::FriWitnessGeneratorJobRetryManager,
         fri_witness_generator_queue_monitor::FriWitnessGeneratorStatsReporter,
-        periodic_job::PeriodicJob,
+        object_store_garbage_collector::ObjectStoreGarbageCollector, periodic_job::PeriodicJob,
         waiting_to_queued_fri_witness_job_mover::WaitingToQueuedFriWitnessJobMover,
     },
     l1_gas_price::{
         GasAdjusterSingleton, PubdataPricing, RollupPubdataPricing, ValidiumPubdataPricing,
     },
-    metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig},
+    metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig, TreeCheckpointStorage},
     metrics::{InitStage, APP_METRICS},
-    proof_data_handler::NhClient,
+    new_horizen::{NhAttestationReconciler, NhClient, NhHealthCheck, NhProofSubmitter},
+    proof_verification_layer::{L1ProofVerificationLayer, ProofVerificationLayer},
     state_keeper::{
         create_state_keeper, MempoolFetcher, MempoolGuard, OutputHandler, SequencerSealer,
         StateKeeperPersistence,
@@ -100,6 +102,7 @@ pub mod block_reverter;
 pub mod commitment_generator;
 pub mod consensus;
 pub mod consistency_checker;
+pub mod data_availability;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod fee_model;
@@ -109,7 +112,9 @@ pub mod house_keeper;
 pub mod l1_gas_price;
 pub mod metadata_calculator;
 mod metrics;
+pub mod new_horizen;
 pub mod proof_data_handler;
+pub mod proof_verification_layer;
 pub mod proto;
 pub mod reorg_detector;
 pub mod state_keeper;
@@ -193,6 +198,12 @@ pub enum Component {
     Consensus,
     /// Component generating commitment for L1 batches.
     CommitmentGenerator,
+    /// Component submitting L1 batch proofs to the NewHorizen chain.
+    NhProofSubmitter,
+    /// Component restoring NewHorizen attestations missed by the Ethereum watcher.
+    NhAttestationReconciler,
+    /// Component dispatching L1 batch pubdata to the data availability layer in Validium mode.
+    DaDispatcher,
 }
 
 #[derive(Debug)]
@@ -229,6 +240,9 @@ impl FromStr for Components {
             "proof_data_handler" => Ok(Components(vec![Component::ProofDataHandler])),
             "consensus" => Ok(Components(vec![Component::Consensus])),
             "commitment_generator" => Ok(Components(vec![Component::CommitmentGenerator])),
+            "nh_proof_submitter" => Ok(Components(vec![Component::NhProofSubmitter])),
+            "nh_attestation_reconciler" => Ok(Components(vec![Component::NhAttestationReconciler])),
+            "da_dispatcher" => Ok(Components(vec![Component::DaDispatcher])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -309,7 +323,7 @@ pub async fn initialize_components(
         panic!("Circuit breaker triggered: {}", err);
     });
 
-    let query_client = QueryClient::new(&eth_client_config.web3_url).unwrap();
+    let query_client = l1_client(&eth_client_config).context("l1_client()")?;
     let gas_adjuster_config = configs.gas_adjuster_config.context("gas_adjuster_config")?;
     let state_keeper_config = configs
         .state_keeper_config
@@ -325,7 +339,7 @@ pub async fn initialize_components(
         .clone()
         .context("eth_sender_config")?;
     let mut gas_adjuster = GasAdjusterSingleton::new(
-        eth_client_config.web3_url.clone(),
+        eth_client_config.clone(),
         gas_adjuster_config,
         eth_sender_config.sender.pubdata_sending_mode,
         pubdata_pricing,
@@ -356,12 +370,24 @@ pub async fn initialize_components(
         tokio::spawn(circuit_breaker_checker.run(stop_receiver.clone())),
     ];
 
-    let nh_config = configs
-        .new_horizen_config
+    // The proof verification layer (e.g., an NH node) is only connected to if a component uses it.
+    let proof_verification_layer = if components.contains(&Component::EthTxAggregator)
+        || components.contains(&Component::NhProofSubmitter)
+    {
+        Some(
+            build_proof_verification_layer(configs)
+                .await
+                .context("build_proof_verification_layer()")?,
+        )
+    } else {
+        None
+    };
+    // Batch blobs are only served by the API if the object store is configured.
+    let blob_store_factory = configs
+        .object_store_config
         .clone()
-        .context("new_horizen_config")?;
-    let nh_url = nh_config.url;
-    let nh_client = OnlineClient::<PolkadotConfig>::from_url(nh_url.clone()).await?;
+        .map(ObjectStoreFactory::new);
+
     if components.contains(&Component::WsApi)
         || components.contains(&Component::HttpApi)
         || components.contains(&Component::ContractVerificationApi)
@@ -397,6 +423,7 @@ pub async fn initialize_components(
                     &mut task_futures,
                     stop_receiver.clone(),
                 )
+                .await
                 .context("build_storage_caches()")?,
             );
 
@@ -424,6 +451,7 @@ pub async fn initialize_components(
                 batch_fee_input_provider,
                 state_keeper_config.save_call_traces,
                 storage_caches.clone().unwrap(),
+                blob_store_factory.as_ref(),
             )
             .await
             .context("run_http_api")?;
@@ -445,6 +473,7 @@ pub async fn initialize_components(
                     &mut task_futures,
                     stop_receiver.clone(),
                 )
+                .await
                 .context("build_storage_caches()")?,
             };
 
@@ -471,6 +500,7 @@ pub async fn initialize_components(
                 replica_connection_pool.clone(),
                 stop_receiver.clone(),
                 storage_caches,
+                blob_store_factory.as_ref(),
             )
             .await
             .context("run_ws_api")?;
@@ -596,7 +626,7 @@ pub async fn initialize_components(
             start_eth_watch(
                 eth_watch_config,
                 eth_watch_pool,
-                Arc::new(query_client.clone()),
+                query_client.clone(),
                 main_zksync_contract_address,
                 governance,
                 stop_receiver.clone(),
@@ -610,6 +640,39 @@ pub async fn initialize_components(
         tracing::info!("initialized ETH-Watcher in {elapsed:?}");
     }
 
+    if components.contains(&Component::NhAttestationReconciler) {
+        let started_at = Instant::now();
+        tracing::info!("initializing NH attestation reconciler");
+        let reconciler_pool = ConnectionPool::<Core>::singleton(postgres_config.master_url()?)
+            .build()
+            .await
+            .context("failed to build nh_attestation_reconciler_pool")?;
+        let eth_watch_config = configs
+            .eth_watch_config
+            .clone()
+            .context("eth_watch_config")?;
+        let nh_config = configs
+            .new_horizen_config
+            .clone()
+            .context("new_horizen_config")?;
+        let eth_client = EthHttpQueryClient::new(
+            query_client.clone(),
+            main_zksync_contract_address,
+            None,
+            eth_watch_config.confirmations_for_eth_event,
+            nh_verifier_contract_address,
+        );
+        let reconciler = NhAttestationReconciler::new(reconciler_pool, Box::new(eth_client));
+        task_futures.push(tokio::spawn(reconciler.run(
+            nh_config.attestation_reconciliation_interval(),
+            nh_config.attestation_reconciliation_block_range(),
+            stop_receiver.clone(),
+        )));
+        let elapsed = started_at.elapsed();
+        APP_METRICS.init_latency[&InitStage::NhAttestationReconciler].set(elapsed);
+        tracing::info!("initialized NH attestation reconciler in {elapsed:?}");
+    }
+
     if components.contains(&Component::EthTxAggregator) {
         let started_at = Instant::now();
         tracing::info!("initializing ETH-TxAggregator");
@@ -622,8 +685,9 @@ pub async fn initialize_components(
             .eth_sender_config
             .clone()
             .context("eth_sender_config")?;
-        let eth_client =
-            PKSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config);
+        let (eth_client, eth_client_blobs) =
+            build_operator_clients(configs, &eth_sender, &contracts_config, &eth_client_config)
+                .await?;
         let state_keeper_config = configs
             .state_keeper_config
             .clone()
@@ -646,9 +710,11 @@ pub async fn initialize_components(
                 }
             };
 
-        let eth_client_blobs_addr =
-            PKSigningClient::from_config_blobs(&eth_sender, &contracts_config, &eth_client_config)
-                .map(|k| k.sender_account());
+        let eth_client_blobs_addr = eth_client_blobs.map(|client| client.sender_account());
+        // In Validium mode, L1 batches are only committed once their pubdata is available on the DA layer.
+        let da_inclusion_required = state_keeper_config.l1_batch_commit_data_generator_mode
+            == L1BatchCommitDataGeneratorMode::Validium
+            && configs.da_dispatcher_config.is_some();
 
         let eth_tx_aggregator_actor = EthTxAggregator::new(
             eth_sender_pool,
@@ -658,10 +724,13 @@ pub async fn initialize_components(
                 store_factory.create_store().await,
                 eth_client_blobs_addr.is_some(),
                 eth_sender.sender.pubdata_sending_mode.into(),
+                da_inclusion_required,
                 l1_batch_commit_data_generator.clone(),
-                Some(nh_client.clone()),
+                proof_verification_layer
+                    .clone()
+                    .context("proof_verification_layer")?,
             ),
-            Arc::new(eth_client),
+            eth_client,
             contracts_config.validator_timelock_addr,
             contracts_config.l1_multicall3_addr,
             main_zksync_contract_address,
@@ -693,10 +762,9 @@ pub async fn initialize_components(
             .eth_sender_config
             .clone()
             .context("eth_sender_config")?;
-        let eth_client =
-            PKSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config);
-        let eth_client_blobs =
-            PKSigningClient::from_config_blobs(&eth_sender, &contracts_config, &eth_client_config);
+        let (eth_client, eth_client_blobs) =
+            build_operator_clients(configs, &eth_sender, &contracts_config, &eth_client_config)
+                .await?;
         let eth_tx_manager_actor = EthTxManager::new(
             eth_manager_pool,
             eth_sender.sender,
@@ -704,9 +772,10 @@ pub async fn initialize_components(
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?,
-            Arc::new(eth_client),
-            eth_client_blobs.map(|c| Arc::new(c) as Arc<dyn BoundEthInterface>),
-        );
+            eth_client,
+            eth_client_blobs,
+        )
+        .with_fee_bump_config(&eth_sender.fee_bump)?;
         task_futures.extend([tokio::spawn(
             eth_tx_manager_actor.run(stop_receiver.clone()),
         )]);
@@ -751,9 +820,6 @@ pub async fn initialize_components(
     }
 
     if components.contains(&Component::ProofDataHandler) {
-        let nh_kp =
-            Keypair::from_uri(&SecretUri::from_str(nh_config.seed_phrase.as_str()).unwrap())
-                .unwrap();
         task_futures.push(tokio::spawn(proof_data_handler::run_server(
             configs
                 .proof_data_handler_config
@@ -761,11 +827,81 @@ pub async fn initialize_components(
                 .context("proof_data_handler_config")?,
             store_factory.create_store().await,
             connection_pool.clone(),
-            Some(NhClient::new(nh_client.clone(), nh_kp)),
+            configs
+                .eth_sender_config
+                .as_ref()
+                .map(|config| config.sender.proof_verification_layer)
+                .unwrap_or_default(),
             stop_receiver.clone(),
         )));
     }
 
+    if components.contains(&Component::NhProofSubmitter) {
+        let started_at = Instant::now();
+        tracing::info!("initializing NH proof submitter");
+        let nh_proof_submitter_pool =
+            ConnectionPool::<Core>::singleton(postgres_config.master_url()?)
+                .build()
+                .await
+                .context("failed to build nh_proof_submitter_pool")?;
+        let proof_verification_layer = proof_verification_layer
+            .clone()
+            .context("proof_verification_layer")?;
+        anyhow::ensure!(
+            proof_verification_layer.attests_proofs(),
+            "NH proof submitter requires a proof verification layer attesting proofs"
+        );
+        let nh_config = configs
+            .new_horizen_config
+            .clone()
+            .context("new_horizen_config")?;
+        let nh_health_check = NhHealthCheck::new(
+            proof_verification_layer.clone(),
+            nh_proof_submitter_pool.clone(),
+            nh_config.max_attestation_delay(),
+        );
+        let nh_proof_submitter =
+            NhProofSubmitter::new(nh_proof_submitter_pool, proof_verification_layer, nh_config);
+        app_health.insert_component(nh_proof_submitter.health_check());
+        app_health.insert_custom_component(Arc::new(nh_health_check));
+        task_futures.push(tokio::spawn(nh_proof_submitter.run(stop_receiver.clone())));
+        let elapsed = started_at.elapsed();
+        APP_METRICS.init_latency[&InitStage::NhProofSubmitter].set(elapsed);
+        tracing::info!("initialized NH proof submitter in {elapsed:?}");
+    }
+
+    if components.contains(&Component::DaDispatcher) {
+        let started_at = Instant::now();
+        tracing::info!("initializing DA dispatcher");
+        let state_keeper_config = configs
+            .state_keeper_config
+            .as_ref()
+            .context("state_keeper_config")?;
+        anyhow::ensure!(
+            state_keeper_config.l1_batch_commit_data_generator_mode
+                == L1BatchCommitDataGeneratorMode::Validium,
+            "DA dispatcher can only be run in Validium mode"
+        );
+        let da_dispatcher_config = configs
+            .da_dispatcher_config
+            .clone()
+            .context("da_dispatcher_config")?;
+        let da_dispatcher_pool = ConnectionPool::<Core>::singleton(postgres_config.master_url()?)
+            .build()
+            .await
+            .context("failed to build da_dispatcher_pool")?;
+        let da_client = create_da_client(&da_dispatcher_config, &store_factory)
+            .await
+            .context("create_da_client()")?;
+        let da_dispatcher =
+            DataAvailabilityDispatcher::new(da_dispatcher_pool, da_client, da_dispatcher_config);
+        app_health.insert_component(da_dispatcher.health_check());
+        task_futures.push(tokio::spawn(da_dispatcher.run(stop_receiver.clone())));
+        let elapsed = started_at.elapsed();
+        APP_METRICS.init_latency[&InitStage::DaDispatcher].set(elapsed);
+        tracing::info!("initialized DA dispatcher in {elapsed:?}");
+    }
+
     if components.contains(&Component::CommitmentGenerator) {
         let commitment_generator_pool =
             ConnectionPool::<Core>::singleton(postgres_config.master_url()?)
@@ -792,6 +928,71 @@ pub async fn initialize_components(
     Ok((task_futures, stop_sender, health_check_handle))
 }
 
+/// Builds signing L1 clients for the operator and, if configured, for the blobs operator. Transactions are signed
+/// via the remote signer if it is configured, and with the operator private keys from the environment otherwise.
+async fn build_operator_clients(
+    configs: &TempConfigStore,
+    eth_sender: &ETHSenderConfig,
+    contracts_config: &ContractsConfig,
+    eth_client_config: &ETHClientConfig,
+) -> anyhow::Result<(
+    Arc<dyn BoundEthInterface>,
+    Option<Arc<dyn BoundEthInterface>>,
+)> {
+    let Some(remote_signer_config) = &configs.remote_signer_config else {
+        let eth_client =
+            PKSigningClient::from_config(eth_sender, contracts_config, eth_client_config);
+        let eth_client_blobs =
+            PKSigningClient::from_config_blobs(eth_sender, contracts_config, eth_client_config);
+        return Ok((
+            Arc::new(eth_client),
+            eth_client_blobs.map(|c| Arc::new(c) as Arc<dyn BoundEthInterface>),
+        ));
+    };
+
+    let eth_client = Web3SignerClient::from_config(
+        eth_sender,
+        contracts_config,
+        eth_client_config,
+        remote_signer_config,
+    )
+    .await
+    .context("Web3SignerClient::from_config()")?;
+    let eth_client_blobs = Web3SignerClient::from_config_blobs(
+        eth_sender,
+        contracts_config,
+        eth_client_config,
+        remote_signer_config,
+    )
+    .await
+    .context("Web3SignerClient::from_config_blobs()")?;
+    Ok((
+        Arc::new(eth_client),
+        eth_client_blobs.map(|c| Arc::new(c) as Arc<dyn BoundEthInterface>),
+    ))
+}
+
+/// Builds the proof verification layer specified in the `eth_sender` config. The mock layer is test-only
+/// and thus cannot be configured.
+async fn build_proof_verification_layer(
+    configs: &TempConfigStore,
+) -> anyhow::Result<Arc<dyn ProofVerificationLayer>> {
+    let eth_sender = configs
+        .eth_sender_config
+        .as_ref()
+        .context("eth_sender_config")?;
+    Ok(match eth_sender.sender.proof_verification_layer {
+        ProofVerificationLayerMode::NewHorizen => {
+            let nh_config = configs
+                .new_horizen_config
+                .as_ref()
+                .context("new_horizen_config")?;
+            Arc::new(NhClient::from_config(nh_config).await?)
+        }
+        ProofVerificationLayerMode::L1 => Arc::new(L1ProofVerificationLayer),
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn add_state_keeper_to_task_futures(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
@@ -904,6 +1105,17 @@ async fn add_trees_to_task_futures(
         MerkleTreeMode::Lightweight => None,
         MerkleTreeMode::Full => Some(store_factory.create_store().await),
     };
+    let checkpoint_storage = match &db_config.merkle_tree.checkpoint_path {
+        Some(path) => {
+            let checkpoint_store = if db_config.merkle_tree.checkpoints_in_object_store {
+                Some(store_factory.create_store().await)
+            } else {
+                None
+            };
+            Some(TreeCheckpointStorage::new(path.into(), checkpoint_store))
+        }
+        None => None,
+    };
 
     run_tree(
         task_futures,
@@ -913,6 +1125,7 @@ async fn add_trees_to_task_futures(
         api_config,
         &operation_config,
         object_store,
+        checkpoint_storage,
         stop_receiver,
     )
     .await
@@ -928,6 +1141,7 @@ async fn run_tree(
     api_config: Option<&MerkleTreeApiConfig>,
     operation_manager: &OperationsManagerConfig,
     object_store: Option<Arc<dyn ObjectStore>>,
+    checkpoint_storage: Option<TreeCheckpointStorage>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let started_at = Instant::now();
@@ -939,9 +1153,13 @@ async fn run_tree(
     tracing::info!("Initializing Merkle tree in {mode_str} mode");
 
     let config = MetadataCalculatorConfig::for_main_node(merkle_tree_config, operation_manager);
-    let metadata_calculator = MetadataCalculator::new(config, object_store)
+    let pool_size = config.pool_size();
+    let mut metadata_calculator = MetadataCalculator::new(config, object_store)
         .await
         .context("failed initializing metadata_calculator")?;
+    if let Some(checkpoint_storage) = checkpoint_storage {
+        metadata_calculator = metadata_calculator.with_checkpoint_storage(checkpoint_storage);
+    }
     if let Some(api_config) = api_config {
         let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
         let tree_reader = metadata_calculator.tree_reader();
@@ -957,7 +1175,7 @@ async fn run_tree(
 
     let tree_health_check = metadata_calculator.tree_health_check();
     app_health.insert_component(tree_health_check);
-    let pool = ConnectionPool::<Core>::singleton(postgres_config.master_url()?)
+    let pool = ConnectionPool::<Core>::builder(postgres_config.master_url()?, pool_size)
         .build()
         .await
         .context("failed to build connection pool")?;
@@ -1106,6 +1324,24 @@ async fn add_house_keeper_to_task_futures(
         task_futures.push(tokio::spawn(task));
     }
 
+    if house_keeper_config.object_store_gc_enabled() {
+        let object_store_config = configs
+            .object_store_config
+            .clone()
+            .context("object_store_config")?;
+        let blob_store = ObjectStoreFactory::new(object_store_config)
+            .create_store()
+            .await;
+        let object_store_gc = ObjectStoreGarbageCollector::new(
+            connection_pool.clone(),
+            blob_store,
+            Duration::from_secs(house_keeper_config.object_store_gc_retention_secs.unwrap()),
+            house_keeper_config.object_store_gc_interval_ms.unwrap(),
+        );
+        let task = object_store_gc.run(stop_receiver.clone());
+        task_futures.push(tokio::spawn(task));
+    }
+
     let fri_prover_group_config = configs
         .fri_prover_group_config
         .clone()
@@ -1141,7 +1377,7 @@ async fn add_house_keeper_to_task_futures(
     Ok(())
 }
 
-fn build_storage_caches(
+async fn build_storage_caches(
     configs: &TempConfigStore,
     replica_connection_pool: &ConnectionPool<Core>,
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
@@ -1157,6 +1393,16 @@ fn build_storage_caches(
     let mut storage_caches =
         PostgresStorageCaches::new(factory_deps_capacity, initial_writes_capacity);
 
+    if let Some(path) = &rpc_config.vm_storage_disk_cache_path {
+        storage_caches
+            .configure_disk_cache(
+                path.into(),
+                rpc_config.vm_storage_disk_cache_size() as u64,
+                replica_connection_pool,
+            )
+            .await
+            .context("failed configuring VM storage disk cache")?;
+    }
     if values_capacity > 0 {
         let values_cache_task = storage_caches
             .configure_storage_values_cache(values_capacity, replica_connection_pool.clone());
@@ -1215,6 +1461,7 @@ async fn run_http_api(
     batch_fee_model_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     with_debug_namespace: bool,
     storage_caches: PostgresStorageCaches,
+    blob_store_factory: Option<&ObjectStoreFactory>,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1248,6 +1495,9 @@ async fn run_http_api(
             .with_tx_sender(tx_sender)
             .with_vm_barrier(vm_barrier)
             .enable_api_namespaces(namespaces);
+    if let Some(blob_store_factory) = blob_store_factory {
+        api_builder = api_builder.with_blob_store(blob_store_factory.create_store().await);
+    }
     if let Some(tree_api_url) = api_config.web3_json_rpc.tree_api_url() {
         let tree_api = Arc::new(TreeApiHttpClient::new(tree_api_url));
         api_builder = api_builder.with_tree_api(tree_api.clone());
@@ -1278,6 +1528,7 @@ async fn run_ws_api(
     replica_connection_pool: ConnectionPool<Core>,
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
+    blob_store_factory: Option<&ObjectStoreFactory>,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1314,6 +1565,9 @@ async fn run_ws_api(
             .with_tx_sender(tx_sender)
             .with_vm_barrier(vm_barrier)
             .enable_api_namespaces(namespaces);
+    if let Some(blob_store_factory) = blob_store_factory {
+        api_builder = api_builder.with_blob_store(blob_store_factory.create_store().await);
+    }
     if let Some(tree_api_url) = api_config.web3_json_rpc.tree_api_url() {
         let tree_api = Arc::new(TreeApiHttpClient::new(tree_api_url));
         api_builder = api_builder.with_tree_api(tree_api.clone());
@@ -1346,7 +1600,10 @@ async fn circuit_breakers_for_components(
             .await
             .context("failed to build a connection pool")?;
         circuit_breakers
-            .insert(Box::new(FailedL1TransactionChecker { pool }))
+            .insert(Box::new(FailedL1TransactionChecker { pool: pool.clone() }))
+            .await;
+        circuit_breakers
+            .insert(Box::new(AttestationPathMismatchChecker { pool }))
             .await;
     }
 
@@ -19,7 +19,8 @@ use zksync_health_check::{Health, HealthStatus};
 use zksync_merkle_tree::{
     domain::{TreeMetadata, ZkSyncTree, ZkSyncTreeReader},
     recovery::MerkleTreeRecovery,
-    Database, Key, NoVersionError, RocksDBWrapper, TreeEntry, TreeEntryWithProof, TreeInstruction,
+    Database, Key, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError, RocksDBWrapper,
+    TreeEntry, TreeEntryWithProof, TreeInstruction, TreeRangeWithProof,
 };
 use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries};
 use zksync_types::{block::L1BatchHeader, L1BatchNumber, StorageKey, H256};
@@ -154,6 +155,17 @@ impl AsyncTree {
         self.mode
     }
 
+    pub fn set_archive_mode(&mut self, archive_mode: bool) {
+        self.as_mut().set_archive_mode(archive_mode);
+    }
+
+    pub fn pruner(
+        &self,
+        past_versions_to_keep: u64,
+    ) -> (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle) {
+        self.as_ref().pruner(past_versions_to_keep)
+    }
+
     pub fn reader(&self) -> AsyncTreeReader {
         AsyncTreeReader {
             inner: self.inner.as_ref().expect(Self::INCONSISTENT_MSG).reader(),
@@ -228,6 +240,10 @@ pub struct AsyncTreeReader {
 }
 
 impl AsyncTreeReader {
+    pub fn set_max_replayed_versions(&mut self, max_replayed_versions: u64) {
+        self.inner.set_max_replayed_versions(max_replayed_versions);
+    }
+
     pub async fn info(self) -> MerkleTreeInfo {
         tokio::task::spawn_blocking(move || MerkleTreeInfo {
             mode: self.mode,
@@ -248,6 +264,29 @@ impl AsyncTreeReader {
             .await
             .unwrap()
     }
+
+    /// Creates a consistent RocksDB checkpoint of the tree in the specified directory, which must not exist.
+    pub async fn create_checkpoint(self, path: PathBuf) -> anyhow::Result<()> {
+        tokio::task::spawn_blocking(move || self.inner.create_checkpoint(&path))
+            .await
+            .unwrap()
+            .context("failed creating RocksDB checkpoint for Merkle tree")
+    }
+
+    pub async fn range_with_proof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeWithProof, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner
+                .range_with_proof(l1_batch_number, start_key, end_key, max_entries)
+        })
+        .await
+        .unwrap()
+    }
 }
 
 /// Lazily initialized [`AsyncTreeReader`].
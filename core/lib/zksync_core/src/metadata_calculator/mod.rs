@@ -2,6 +2,7 @@
 //! stores them in the DB.
 
 use std::{
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -16,10 +17,14 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{HealthUpdater, ReactiveHealthCheck};
 use zksync_object_store::ObjectStore;
 
-pub use self::helpers::LazyAsyncTreeReader;
 pub(crate) use self::helpers::{AsyncTreeReader, L1BatchWithLogs, MerkleTreeInfo};
+pub use self::{
+    helpers::LazyAsyncTreeReader,
+    recovery::{export_tree_checkpoint, TreeCheckpointManifest, TreeCheckpointStorage},
+};
 use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth},
+    recovery::{import_checkpoint, run_checkpoint_exporter},
     updater::TreeUpdater,
 };
 
@@ -51,6 +56,19 @@ pub struct MetadataCalculatorConfig {
     pub memtable_capacity: usize,
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     pub stalled_writes_timeout: Duration,
+    /// Whether the tree should record diff logs allowing to serve proofs for pruned L1 batches.
+    pub archive_mode: bool,
+    /// Interval between checkpoint versions retained by the pruner in the archive mode. Also bounds the number
+    /// of L1 batches replayed when serving proofs for a pruned L1 batch.
+    pub archive_checkpoint_interval: u64,
+    /// Number of latest L1 batches to retain when pruning the tree. If not set, the tree is not pruned.
+    pub pruning_retained_versions: Option<u64>,
+    /// Whether to persist results for an L1 batch concurrently with processing the next L1 batch.
+    /// Requires a connection pool with at least 2 connections; see [`Self::pool_size()`].
+    pub concurrent_persistence: bool,
+    /// Interval (in L1 batches) between tree checkpoints exported to the checkpoint storage. If not set,
+    /// checkpoints are not exported. Ignored if the calculator has no checkpoint storage.
+    pub checkpoint_export_interval: Option<u32>,
 }
 
 impl MetadataCalculatorConfig {
@@ -67,6 +85,21 @@ impl MetadataCalculatorConfig {
             block_cache_capacity: merkle_tree_config.block_cache_size(),
             memtable_capacity: merkle_tree_config.memtable_capacity(),
             stalled_writes_timeout: merkle_tree_config.stalled_writes_timeout(),
+            archive_mode: merkle_tree_config.archive_mode,
+            archive_checkpoint_interval: merkle_tree_config.archive_checkpoint_interval,
+            pruning_retained_versions: merkle_tree_config.pruning_retained_versions,
+            concurrent_persistence: merkle_tree_config.concurrent_persistence,
+            checkpoint_export_interval: merkle_tree_config.checkpoint_export_interval,
+        }
+    }
+
+    /// Returns the number of Postgres connections the calculator needs for its main loop. The pool passed
+    /// to [`MetadataCalculator::run()`] should have at least this many connections.
+    pub fn pool_size(&self) -> u32 {
+        if self.concurrent_persistence {
+            2
+        } else {
+            1
         }
     }
 }
@@ -76,6 +109,7 @@ pub struct MetadataCalculator {
     config: MetadataCalculatorConfig,
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     object_store: Option<Arc<dyn ObjectStore>>,
+    checkpoint_storage: Option<TreeCheckpointStorage>,
     delayer: Delayer,
     health_updater: HealthUpdater,
     max_l1_batches_per_iter: usize,
@@ -91,6 +125,10 @@ impl MetadataCalculator {
             config.max_l1_batches_per_iter > 0,
             "Maximum L1 batches per iteration is misconfigured to be 0; please update it to positive value"
         );
+        anyhow::ensure!(
+            config.archive_checkpoint_interval > 0,
+            "Archive checkpoint interval is misconfigured to be 0; please update it to positive value"
+        );
         if matches!(config.mode, MerkleTreeMode::Lightweight) && object_store.is_some() {
             anyhow::bail!(
                 "Cannot run lightweight tree with an object store; the tree won't produce information to be stored in the store"
@@ -101,6 +139,7 @@ impl MetadataCalculator {
         Ok(Self {
             tree_reader: watch::channel(None).0,
             object_store,
+            checkpoint_storage: None,
             delayer: Delayer::new(config.delay_interval),
             health_updater,
             max_l1_batches_per_iter: config.max_l1_batches_per_iter,
@@ -108,6 +147,16 @@ impl MetadataCalculator {
         })
     }
 
+    /// Sets the storage of Merkle tree checkpoints. If set, the calculator will bootstrap an empty tree
+    /// from the latest checkpoint in the storage instead of recovering it from a snapshot or building it from scratch.
+    /// If [`MetadataCalculatorConfig::checkpoint_export_interval`] is set, the calculator will also export
+    /// checkpoints to the storage.
+    #[must_use]
+    pub fn with_checkpoint_storage(mut self, storage: TreeCheckpointStorage) -> Self {
+        self.checkpoint_storage = Some(storage);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -151,23 +200,76 @@ impl MetadataCalculator {
         pool: ConnectionPool<Core>,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
+        if let Some(storage) = &self.checkpoint_storage {
+            self.health_updater
+                .update(MerkleTreeHealth::Initialization.into());
+            import_checkpoint(storage, Path::new(&self.config.db_path), &pool)
+                .await
+                .context("failed importing Merkle tree checkpoint")?;
+        }
         let tree = self.create_tree().await?;
         let tree = tree
             .ensure_ready(&pool, &stop_receiver, &self.health_updater)
             .await?;
-        let Some(tree) = tree else {
+        let Some(mut tree) = tree else {
             return Ok(()); // recovery was aborted because a stop signal was received
         };
-        let tree_reader = tree.reader();
+        tree.set_archive_mode(self.config.archive_mode);
+        let pruner_handle = self
+            .config
+            .pruning_retained_versions
+            .map(|retained_versions| {
+                let (mut pruner, pruner_handle) = tree.pruner(retained_versions);
+                if self.config.archive_mode {
+                    pruner.set_archive_checkpoint_interval(self.config.archive_checkpoint_interval);
+                }
+                tokio::task::spawn_blocking(move || pruner.run());
+                pruner_handle
+            });
+        let mut tree_reader = tree.reader();
+        tree_reader.set_max_replayed_versions(self.config.archive_checkpoint_interval);
         tracing::info!(
             "Merkle tree is initialized and ready to process L1 batches: {:?}",
             tree_reader.clone().info().await
         );
+        let checkpoint_exporter_handle = self
+            .checkpoint_storage
+            .zip(self.config.checkpoint_export_interval)
+            .map(|(storage, interval)| {
+                tokio::spawn(run_checkpoint_exporter(
+                    storage,
+                    tree_reader.clone(),
+                    interval,
+                    self.delayer.delay_interval(),
+                    stop_receiver.clone(),
+                ))
+            });
         self.tree_reader.send_replace(Some(tree_reader));
 
-        let updater = TreeUpdater::new(tree, self.max_l1_batches_per_iter, self.object_store);
-        updater
+        let updater = TreeUpdater::new(
+            tree,
+            self.max_l1_batches_per_iter,
+            self.object_store,
+            self.config.concurrent_persistence,
+        );
+        let result = updater
             .loop_updating_tree(self.delayer, &pool, stop_receiver, self.health_updater)
-            .await
+            .await;
+        if let Some(pruner_handle) = pruner_handle {
+            pruner_handle.abort();
+        }
+        if let Some(checkpoint_exporter_handle) = checkpoint_exporter_handle {
+            if result.is_ok() {
+                // The updater has received a stop signal, so the exporter will stop on its own
+                // once it finishes the ongoing export (if any).
+                checkpoint_exporter_handle
+                    .await
+                    .context("Merkle tree checkpoint exporter panicked")??;
+            } else {
+                // Interrupted exports are cleaned up on the next export.
+                checkpoint_exporter_handle.abort();
+            }
+        }
+        result
     }
 }
@@ -0,0 +1,577 @@
+//! Export and import of Merkle tree checkpoints.
+//!
+//! A checkpoint is a consistent RocksDB copy of the tree truncated to a certain L1 batch, together with
+//! a [`TreeCheckpointManifest`]. Importing a checkpoint allows bootstrapping an empty tree without replaying
+//! snapshot storage logs, which can take hours for large states.
+//!
+//! Checkpoints can be stored in a local directory or in an object store. In the latter case, each RocksDB file
+//! is stored as a separate blob, and the manifest is uploaded last, so that only complete checkpoints are visible
+//! to importers.
+
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use zksync_config::configs::database::MerkleTreeConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper};
+use zksync_object_store::{Bucket, ObjectStore};
+use zksync_types::{L1BatchNumber, H256};
+
+use super::get_snapshot_recovery;
+use crate::metadata_calculator::helpers::{create_db, AsyncTreeReader, GenericAsyncTree};
+
+/// Manifest of a Merkle tree checkpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeCheckpointManifest {
+    /// Latest L1 batch in the checkpointed tree.
+    pub l1_batch_number: L1BatchNumber,
+    /// Root hash of the tree after processing the L1 batch.
+    pub root_hash: H256,
+    /// Number of leaves in the tree after processing the L1 batch.
+    pub leaf_count: u64,
+    /// Names of RocksDB files constituting the checkpoint.
+    pub files: Vec<String>,
+}
+
+/// Storage of Merkle tree checkpoints.
+#[derive(Debug, Clone)]
+pub enum TreeCheckpointStorage {
+    /// Checkpoints are stored in subdirectories of the specified local directory.
+    Local(PathBuf),
+    /// Checkpoints are stored in an object store. When exporting, checkpoint files are staged in the specified
+    /// local directory; it should be on the same filesystem as the tree RocksDB, so that SST files are hard-linked
+    /// rather than copied.
+    ObjectStore {
+        store: Arc<dyn ObjectStore>,
+        staging_dir: PathBuf,
+    },
+}
+
+impl TreeCheckpointStorage {
+    const MANIFEST_FILE_NAME: &'static str = "manifest.json";
+    const DB_DIR_NAME: &'static str = "db";
+    const OBJECT_KEY_PREFIX: &'static str = "tree_checkpoint_l1_batch_";
+
+    /// Creates a storage for checkpoints in the specified local directory. If `object_store` is provided,
+    /// checkpoints are stored in it instead, and the directory is only used to stage exported checkpoints.
+    pub fn new(dir: PathBuf, object_store: Option<Arc<dyn ObjectStore>>) -> Self {
+        match object_store {
+            Some(store) => Self::ObjectStore {
+                store,
+                staging_dir: dir,
+            },
+            None => Self::Local(dir),
+        }
+    }
+
+    fn local_dir(&self) -> &Path {
+        match self {
+            Self::Local(dir) => dir,
+            Self::ObjectStore { staging_dir, .. } => staging_dir,
+        }
+    }
+
+    fn checkpoint_dir(base_dir: &Path, l1_batch_number: L1BatchNumber) -> PathBuf {
+        base_dir.join(format!("l1_batch_{l1_batch_number}"))
+    }
+
+    fn object_key(l1_batch_number: L1BatchNumber, name: &str) -> String {
+        format!("{}{l1_batch_number}_{name}", Self::OBJECT_KEY_PREFIX)
+    }
+
+    /// Exports a checkpoint of the tree as of the specified L1 batch, which must be processed by the tree.
+    /// The checkpoint is taken from the tree state flushed to RocksDB, so the tree may be concurrently updated.
+    pub async fn export(
+        &self,
+        reader: AsyncTreeReader,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<TreeCheckpointManifest> {
+        let started_at = Instant::now();
+        let checkpoint_dir = Self::checkpoint_dir(self.local_dir(), l1_batch_number);
+        if checkpoint_dir.exists() {
+            // Staged checkpoints are removed after upload, and local checkpoints get a manifest only
+            // after they are complete. Thus, a directory not covered by these cases is left by an interrupted export.
+            let is_complete = matches!(self, Self::Local(_))
+                && checkpoint_dir.join(Self::MANIFEST_FILE_NAME).exists();
+            anyhow::ensure!(
+                !is_complete,
+                "checkpoint for L1 batch #{l1_batch_number} already exists at `{}`",
+                checkpoint_dir.display()
+            );
+            tracing::info!(
+                "Removing leftover checkpoint directory `{}` from the previous export",
+                checkpoint_dir.display()
+            );
+            tokio::fs::remove_dir_all(&checkpoint_dir)
+                .await
+                .with_context(|| {
+                    format!("failed removing directory `{}`", checkpoint_dir.display())
+                })?;
+        }
+        tokio::fs::create_dir_all(&checkpoint_dir)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed creating checkpoint directory `{}`",
+                    checkpoint_dir.display()
+                )
+            })?;
+        let db_dir = checkpoint_dir.join(Self::DB_DIR_NAME);
+        reader.create_checkpoint(db_dir.clone()).await?;
+
+        let manifest = tokio::task::spawn_blocking(move || {
+            Self::truncate_checkpoint(&db_dir, l1_batch_number)
+        })
+        .await
+        .context("panicked truncating tree checkpoint")??;
+
+        match self {
+            Self::Local(_) => {
+                let manifest_path = checkpoint_dir.join(Self::MANIFEST_FILE_NAME);
+                let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+                    .context("failed serializing checkpoint manifest")?;
+                tokio::fs::write(&manifest_path, manifest_bytes)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed writing checkpoint manifest to `{}`",
+                            manifest_path.display()
+                        )
+                    })?;
+            }
+            Self::ObjectStore { store, .. } => {
+                Self::upload(store.as_ref(), &checkpoint_dir, &manifest).await?;
+                tokio::fs::remove_dir_all(&checkpoint_dir)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed removing staged checkpoint `{}`",
+                            checkpoint_dir.display()
+                        )
+                    })?;
+            }
+        }
+        tracing::info!(
+            "Exported Merkle tree checkpoint for L1 batch #{l1_batch_number} in {:?}: {manifest:?}",
+            started_at.elapsed()
+        );
+        Ok(manifest)
+    }
+
+    /// Truncates the checkpointed tree to the specified L1 batch and returns the checkpoint manifest.
+    fn truncate_checkpoint(
+        db_dir: &Path,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<TreeCheckpointManifest> {
+        let db = RocksDBWrapper::new(db_dir).context("failed opening tree checkpoint")?;
+        let mut tree = ZkSyncTree::new(db);
+        let next_l1_batch_number = tree.next_l1_batch_number();
+        anyhow::ensure!(
+            l1_batch_number < next_l1_batch_number,
+            "cannot export checkpoint for L1 batch #{l1_batch_number}: tree only contains L1 batches \
+             before #{next_l1_batch_number}"
+        );
+        // Node data for the truncated L1 batches is not removed, but it is unreachable in the checkpoint.
+        tree.revert_logs(l1_batch_number);
+        tree.save();
+        let root_hash = tree.root_hash();
+        let leaf_count = tree.reader().leaf_count();
+        drop(tree); // Close RocksDB before listing its files
+
+        let mut files = vec![];
+        let dir_entries = fs::read_dir(db_dir).context("failed listing checkpoint files")?;
+        for entry in dir_entries {
+            let entry = entry.context("failed listing checkpoint files")?;
+            anyhow::ensure!(
+                entry.file_type()?.is_file(),
+                "unexpected non-file entry in checkpoint: {:?}",
+                entry.path()
+            );
+            let file_name = entry.file_name().into_string().map_err(|name| {
+                anyhow::anyhow!("checkpoint file name {name:?} is not valid UTF-8")
+            })?;
+            files.push(file_name);
+        }
+        files.sort_unstable();
+
+        Ok(TreeCheckpointManifest {
+            l1_batch_number,
+            root_hash,
+            leaf_count,
+            files,
+        })
+    }
+
+    async fn upload(
+        store: &dyn ObjectStore,
+        checkpoint_dir: &Path,
+        manifest: &TreeCheckpointManifest,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = manifest.l1_batch_number;
+        let db_dir = checkpoint_dir.join(Self::DB_DIR_NAME);
+        for file_name in &manifest.files {
+            let contents = tokio::fs::read(db_dir.join(file_name))
+                .await
+                .with_context(|| format!("failed reading checkpoint file `{file_name}`"))?;
+            let key = Self::object_key(l1_batch_number, file_name);
+            store
+                .put_raw(Bucket::MerkleTreeCheckpoints, &key, contents)
+                .await
+                .with_context(|| format!("failed uploading checkpoint file `{key}`"))?;
+        }
+
+        // The manifest is uploaded last to signal that the checkpoint is complete.
+        let manifest_bytes =
+            serde_json::to_vec(manifest).context("failed serializing checkpoint manifest")?;
+        let key = Self::object_key(l1_batch_number, Self::MANIFEST_FILE_NAME);
+        store
+            .put_raw(Bucket::MerkleTreeCheckpoints, &key, manifest_bytes)
+            .await
+            .with_context(|| format!("failed uploading checkpoint manifest `{key}`"))?;
+        Ok(())
+    }
+
+    /// Loads the manifest for the latest complete checkpoint in this storage.
+    async fn latest_manifest(&self) -> anyhow::Result<Option<TreeCheckpointManifest>> {
+        let manifest_bytes = match self {
+            Self::Local(dir) => {
+                if !dir.exists() {
+                    return Ok(None);
+                }
+                let mut latest_l1_batch = None;
+                let dir_entries = fs::read_dir(dir)
+                    .with_context(|| format!("failed listing `{}`", dir.display()))?;
+                for entry in dir_entries {
+                    let entry =
+                        entry.with_context(|| format!("failed listing `{}`", dir.display()))?;
+                    let Some(file_name) = entry.file_name().to_str().map(str::to_owned) else {
+                        continue;
+                    };
+                    let Some(number) = file_name.strip_prefix("l1_batch_") else {
+                        continue;
+                    };
+                    let Ok(number) = number.parse::<u32>() else {
+                        continue;
+                    };
+                    let is_complete = entry.path().join(Self::MANIFEST_FILE_NAME).exists();
+                    if is_complete && latest_l1_batch.map_or(true, |latest| latest < number) {
+                        latest_l1_batch = Some(number);
+                    }
+                }
+                let Some(l1_batch_number) = latest_l1_batch else {
+                    return Ok(None);
+                };
+                let manifest_path = Self::checkpoint_dir(dir, L1BatchNumber(l1_batch_number))
+                    .join(Self::MANIFEST_FILE_NAME);
+                tokio::fs::read(&manifest_path).await.with_context(|| {
+                    format!(
+                        "failed reading checkpoint manifest `{}`",
+                        manifest_path.display()
+                    )
+                })?
+            }
+            Self::ObjectStore { store, .. } => {
+                let objects = store
+                    .list_raw(Bucket::MerkleTreeCheckpoints, Self::OBJECT_KEY_PREFIX)
+                    .await
+                    .context("failed listing tree checkpoints")?;
+                let manifest_suffix = format!("_{}", Self::MANIFEST_FILE_NAME);
+                let latest_l1_batch = objects
+                    .iter()
+                    .filter_map(|object| {
+                        let number = object
+                            .key
+                            .strip_prefix(Self::OBJECT_KEY_PREFIX)?
+                            .strip_suffix(&manifest_suffix)?;
+                        number.parse::<u32>().ok()
+                    })
+                    .max();
+                let Some(l1_batch_number) = latest_l1_batch else {
+                    return Ok(None);
+                };
+                let key =
+                    Self::object_key(L1BatchNumber(l1_batch_number), Self::MANIFEST_FILE_NAME);
+                store
+                    .get_raw(Bucket::MerkleTreeCheckpoints, &key)
+                    .await
+                    .with_context(|| format!("failed fetching checkpoint manifest `{key}`"))?
+            }
+        };
+        let manifest = serde_json::from_slice(&manifest_bytes)
+            .context("failed deserializing checkpoint manifest")?;
+        Ok(Some(manifest))
+    }
+
+    /// Fetches checkpoint files to the specified directory.
+    async fn fetch(
+        &self,
+        manifest: &TreeCheckpointManifest,
+        target_dir: &Path,
+    ) -> anyhow::Result<()> {
+        tokio::fs::create_dir_all(target_dir)
+            .await
+            .with_context(|| format!("failed creating directory `{}`", target_dir.display()))?;
+
+        let l1_batch_number = manifest.l1_batch_number;
+        for file_name in &manifest.files {
+            // Guard against path traversal in a malformed manifest.
+            anyhow::ensure!(
+                Path::new(file_name).file_name() == Some(OsStr::new(file_name)),
+                "invalid file name `{file_name}` in checkpoint manifest"
+            );
+            let target_path = target_dir.join(file_name);
+            match self {
+                Self::Local(dir) => {
+                    let source_path = Self::checkpoint_dir(dir, l1_batch_number)
+                        .join(Self::DB_DIR_NAME)
+                        .join(file_name);
+                    tokio::fs::copy(&source_path, &target_path)
+                        .await
+                        .with_context(|| {
+                            format!("failed copying checkpoint file `{}`", source_path.display())
+                        })?;
+                }
+                Self::ObjectStore { store, .. } => {
+                    let key = Self::object_key(l1_batch_number, file_name);
+                    let contents = store
+                        .get_raw(Bucket::MerkleTreeCheckpoints, &key)
+                        .await
+                        .with_context(|| format!("failed fetching checkpoint file `{key}`"))?;
+                    tokio::fs::write(&target_path, contents)
+                        .await
+                        .with_context(|| {
+                            format!("failed writing checkpoint file `{}`", target_path.display())
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Exports a checkpoint of the Merkle tree with the specified configuration as of the specified L1 batch, or the latest
+/// L1 batch processed by the tree if `l1_batch_number` is not specified. The tree RocksDB must not be used
+/// by another process (e.g., a running node) during export.
+pub async fn export_tree_checkpoint(
+    config: &MerkleTreeConfig,
+    storage: &TreeCheckpointStorage,
+    l1_batch_number: Option<L1BatchNumber>,
+) -> anyhow::Result<TreeCheckpointManifest> {
+    let db = create_db(
+        config.path.clone().into(),
+        config.block_cache_size(),
+        config.memtable_capacity(),
+        config.stalled_writes_timeout(),
+        config.multi_get_chunk_size,
+    )
+    .await
+    .with_context(|| format!("failed opening Merkle tree RocksDB at `{}`", config.path))?;
+    let GenericAsyncTree::Ready(tree) = GenericAsyncTree::new(db, config.mode).await else {
+        anyhow::bail!(
+            "Merkle tree at `{}` is empty or is being recovered; cannot export a checkpoint",
+            config.path
+        );
+    };
+    let l1_batch_number = match l1_batch_number {
+        Some(number) => number,
+        None => tree.next_l1_batch_number() - 1,
+    };
+    storage.export(tree.reader(), l1_batch_number).await
+}
+
+/// Imports the latest checkpoint from `storage` into the Merkle tree RocksDB at `db_path`. The import is only performed
+/// if the RocksDB directory is missing or empty. The checkpoint root hash is checked against Postgres, and the imported
+/// tree is checked for consistency before use.
+///
+/// Returns `true` if a checkpoint was imported.
+pub(in crate::metadata_calculator) async fn import_checkpoint(
+    storage: &TreeCheckpointStorage,
+    db_path: &Path,
+    pool: &ConnectionPool<Core>,
+) -> anyhow::Result<bool> {
+    let is_db_empty = match fs::read_dir(db_path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => true,
+        Err(err) => {
+            return Err(
+                anyhow::Error::new(err).context(format!("failed listing `{}`", db_path.display()))
+            );
+        }
+    };
+    if !is_db_empty {
+        tracing::info!(
+            "Merkle tree RocksDB at `{}` is not empty; skipping checkpoint import",
+            db_path.display()
+        );
+        return Ok(false);
+    }
+
+    let Some(manifest) = storage.latest_manifest().await? else {
+        tracing::info!("No Merkle tree checkpoints found in {storage:?}");
+        return Ok(false);
+    };
+    tracing::info!("Importing Merkle tree checkpoint with manifest {manifest:?}");
+    let started_at = Instant::now();
+    let l1_batch_number = manifest.l1_batch_number;
+    let Some(expected_root_hash) = get_expected_root_hash(pool, l1_batch_number).await? else {
+        tracing::warn!(
+            "Cannot verify Merkle tree checkpoint for L1 batch #{l1_batch_number}: Postgres doesn't contain \
+             its root hash; falling back to the usual tree initialization"
+        );
+        return Ok(false);
+    };
+    anyhow::ensure!(
+        manifest.root_hash == expected_root_hash,
+        "Root hash in tree checkpoint manifest {manifest:?} differs from the root hash {expected_root_hash:?} \
+         for L1 batch #{l1_batch_number} in Postgres"
+    );
+
+    // Fetch files to a staging directory first, so that an interrupted import doesn't leave a broken tree.
+    let mut staging_dir = db_path.as_os_str().to_owned();
+    staging_dir.push(".checkpoint");
+    let staging_dir = PathBuf::from(staging_dir);
+    if staging_dir.exists() {
+        tracing::info!(
+            "Removing leftover staging directory `{}` from the previous import",
+            staging_dir.display()
+        );
+        tokio::fs::remove_dir_all(&staging_dir)
+            .await
+            .with_context(|| format!("failed removing directory `{}`", staging_dir.display()))?;
+    }
+    storage.fetch(&manifest, &staging_dir).await?;
+
+    let verified_dir = staging_dir.clone();
+    let verified_manifest = manifest.clone();
+    tokio::task::spawn_blocking(move || verify_checkpoint(&verified_dir, &verified_manifest))
+        .await
+        .context("tree checkpoint is inconsistent")??;
+
+    if db_path.exists() {
+        tokio::fs::remove_dir(db_path)
+            .await
+            .with_context(|| format!("failed removing empty directory `{}`", db_path.display()))?;
+    }
+    tokio::fs::rename(&staging_dir, db_path)
+        .await
+        .with_context(|| {
+            format!(
+                "failed moving imported checkpoint to `{}`",
+                db_path.display()
+            )
+        })?;
+    tracing::info!(
+        "Imported Merkle tree checkpoint for L1 batch #{l1_batch_number} in {:?}",
+        started_at.elapsed()
+    );
+    Ok(true)
+}
+
+/// Returns the root hash for the specified L1 batch from Postgres, or `None` if Postgres doesn't contain it
+/// (e.g., the L1 batch precedes the snapshot Postgres was recovered from, or isn't processed by the main node yet).
+async fn get_expected_root_hash(
+    pool: &ConnectionPool<Core>,
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<Option<H256>> {
+    if let Some(snapshot_recovery) = get_snapshot_recovery(pool).await? {
+        let snapshot_l1_batch = snapshot_recovery.l1_batch_number;
+        if l1_batch_number < snapshot_l1_batch {
+            return Ok(None);
+        } else if l1_batch_number == snapshot_l1_batch {
+            return Ok(Some(snapshot_recovery.l1_batch_root_hash));
+        }
+    }
+
+    let mut storage = pool.connection_tagged("metadata_calculator").await?;
+    Ok(storage
+        .blocks_dal()
+        .get_l1_batch_state_root(l1_batch_number)
+        .await?)
+}
+
+/// Periodically exports tree checkpoints for L1 batches divisible by `interval` as the tree processes them.
+/// Export errors are logged and don't stop the exporter.
+pub(in crate::metadata_calculator) async fn run_checkpoint_exporter(
+    storage: TreeCheckpointStorage,
+    reader: AsyncTreeReader,
+    interval: u32,
+    poll_interval: Duration,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        interval > 0,
+        "Tree checkpoint export interval is misconfigured to be 0; please update it to positive value"
+    );
+    let mut last_exported_l1_batch = storage
+        .latest_manifest()
+        .await
+        .context("failed loading latest checkpoint manifest")?
+        .map(|manifest| manifest.l1_batch_number);
+
+    while !*stop_receiver.borrow() {
+        let next_l1_batch_number = reader.clone().info().await.next_l1_batch_number;
+        if let Some(latest_l1_batch) = next_l1_batch_number.0.checked_sub(1) {
+            let l1_batch_number = L1BatchNumber(latest_l1_batch - latest_l1_batch % interval);
+            let should_export = l1_batch_number > L1BatchNumber(0)
+                && last_exported_l1_batch.map_or(true, |last| last < l1_batch_number);
+            if should_export {
+                if let Err(err) = storage.export(reader.clone(), l1_batch_number).await {
+                    tracing::warn!(
+                        "Failed exporting Merkle tree checkpoint for L1 batch #{l1_batch_number}; \
+                         the next checkpoint will be exported in {interval} L1 batches: {err:#}"
+                    );
+                }
+                // Failed exports are not retried, so that a persistent error doesn't lead to busy looping.
+                last_exported_l1_batch = Some(l1_batch_number);
+            }
+        }
+
+        if tokio::time::timeout(poll_interval, stop_receiver.changed())
+            .await
+            .is_ok()
+        {
+            break;
+        }
+    }
+    tracing::info!("Stop signal received, Merkle tree checkpoint exporter is shutting down");
+    Ok(())
+}
+
+/// Checks that the checkpoint tree matches the manifest and is internally consistent.
+///
+/// # Panics
+///
+/// Panics if the tree is inconsistent.
+fn verify_checkpoint(db_dir: &Path, manifest: &TreeCheckpointManifest) -> anyhow::Result<()> {
+    let db = RocksDBWrapper::new(db_dir).context("failed opening imported tree checkpoint")?;
+    let tree = ZkSyncTree::new(db);
+    let l1_batch_number = manifest.l1_batch_number;
+    let next_l1_batch_number = tree.next_l1_batch_number();
+    anyhow::ensure!(
+        next_l1_batch_number == l1_batch_number + 1,
+        "Imported tree has unexpected next L1 batch #{next_l1_batch_number}; expected #{}",
+        l1_batch_number + 1
+    );
+    let root_hash = tree.root_hash();
+    anyhow::ensure!(
+        root_hash == manifest.root_hash,
+        "Imported tree has unexpected root hash {root_hash:?}; expected {:?}",
+        manifest.root_hash
+    );
+    let leaf_count = tree.reader().leaf_count();
+    anyhow::ensure!(
+        leaf_count == manifest.leaf_count,
+        "Imported tree has unexpected leaf count {leaf_count}; expected {}",
+        manifest.leaf_count
+    );
+    tree.verify_consistency(l1_batch_number);
+    Ok(())
+}
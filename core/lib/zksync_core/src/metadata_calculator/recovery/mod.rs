@@ -42,11 +42,14 @@ use zksync_types::{
     MiniblockNumber, H256,
 };
 
+pub use self::checkpoint::{export_tree_checkpoint, TreeCheckpointManifest, TreeCheckpointStorage};
+pub(super) use self::checkpoint::{import_checkpoint, run_checkpoint_exporter};
 use super::{
     helpers::{AsyncTree, AsyncTreeRecovery, GenericAsyncTree, MerkleTreeHealth},
     metrics::{ChunkRecoveryStage, RecoveryStage, RECOVERY_METRICS},
 };
 
+mod checkpoint;
 #[cfg(test)]
 mod tests;
 
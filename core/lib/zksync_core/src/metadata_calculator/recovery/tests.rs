@@ -13,6 +13,7 @@ use zksync_config::configs::{
 use zksync_dal::CoreDal;
 use zksync_health_check::{CheckHealth, HealthStatus, ReactiveHealthCheck};
 use zksync_merkle_tree::{domain::ZkSyncTree, TreeInstruction};
+use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{L1BatchNumber, ProtocolVersionId, StorageLog};
 
 use super::*;
@@ -21,8 +22,8 @@ use crate::{
     metadata_calculator::{
         helpers::create_db,
         tests::{
-            extend_db_state, extend_db_state_from_l1_batch, gen_storage_logs, run_calculator,
-            setup_calculator,
+            extend_db_state, extend_db_state_from_l1_batch, gen_storage_logs, reset_db_state,
+            run_calculator, setup_calculator,
         },
         MetadataCalculator, MetadataCalculatorConfig,
     },
@@ -323,3 +324,216 @@ async fn entire_recovery_workflow(case: RecoveryWorkflowCase) {
     stop_sender.send_replace(true);
     calculator_task.await.expect("calculator panicked").unwrap();
 }
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn exporting_and_importing_tree_checkpoint(use_object_store: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 5).await;
+    let final_root_hash = run_calculator(calculator, pool.clone()).await;
+
+    let checkpoint_storage = if use_object_store {
+        TreeCheckpointStorage::ObjectStore {
+            store: ObjectStoreFactory::mock().create_store().await,
+            staging_dir: temp_dir.path().join("staging"),
+        }
+    } else {
+        TreeCheckpointStorage::Local(temp_dir.path().join("checkpoints"))
+    };
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    let manifest = checkpoint_storage
+        .export(tree.reader(), L1BatchNumber(3))
+        .await
+        .unwrap();
+    // The exported checkpoint must not influence the original tree.
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+    drop(tree);
+
+    let mut storage = pool.connection().await.unwrap();
+    let expected_root_hash = storage
+        .blocks_dal()
+        .get_l1_batch_state_root(L1BatchNumber(3))
+        .await
+        .unwrap()
+        .expect("no root hash for L1 batch #3");
+    assert_eq!(manifest.l1_batch_number, L1BatchNumber(3));
+    assert_eq!(manifest.root_hash, expected_root_hash);
+    assert!(!manifest.files.is_empty());
+
+    let import_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let import_path = import_dir.path().join("tree");
+    let imported = import_checkpoint(&checkpoint_storage, &import_path, &pool)
+        .await
+        .unwrap();
+    assert!(imported);
+    let imported = import_checkpoint(&checkpoint_storage, &import_path, &pool)
+        .await
+        .unwrap();
+    assert!(!imported, "checkpoint imported into non-empty tree");
+
+    let db = create_db(import_path, 0, 16 << 20, Duration::ZERO, 500)
+        .await
+        .unwrap();
+    let tree = ZkSyncTree::new(db);
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(4));
+    assert_eq!(tree.root_hash(), expected_root_hash);
+    drop(tree);
+
+    // Check that a calculator bootstrapped from the checkpoint catches up with Postgres.
+    let (calculator, _) = setup_calculator(import_dir.path(), &pool).await;
+    let calculator = calculator.with_checkpoint_storage(checkpoint_storage);
+    let root_hash = run_calculator(calculator, pool.clone()).await;
+    assert_eq!(root_hash, final_root_hash);
+}
+
+#[tokio::test]
+async fn importing_checkpoint_with_mismatched_root_hash() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 3).await;
+    run_calculator(calculator, pool.clone()).await;
+
+    let checkpoint_storage = TreeCheckpointStorage::Local(temp_dir.path().join("checkpoints"));
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    let GenericAsyncTree::Ready(tree) = calculator.create_tree().await.unwrap() else {
+        panic!("Unexpected tree state");
+    };
+    checkpoint_storage
+        .export(tree.reader(), L1BatchNumber(2))
+        .await
+        .unwrap();
+    drop(tree);
+
+    // Tamper with the checkpoint manifest.
+    let manifest_path = temp_dir.path().join("checkpoints/l1_batch_2/manifest.json");
+    let manifest = std::fs::read(&manifest_path).unwrap();
+    let mut manifest: TreeCheckpointManifest = serde_json::from_slice(&manifest).unwrap();
+    manifest.root_hash = H256::repeat_byte(0xff);
+    std::fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+    let import_path = temp_dir.path().join("imported");
+    let err = import_checkpoint(&checkpoint_storage, &import_path, &pool)
+        .await
+        .unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("root hash"), "{err}");
+    assert!(!import_path.exists());
+}
+
+#[tokio::test]
+async fn importing_checkpoint_without_root_hash_in_postgres() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 3).await;
+    run_calculator(calculator, pool.clone()).await;
+
+    let checkpoint_storage = TreeCheckpointStorage::Local(temp_dir.path().join("checkpoints"));
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    let GenericAsyncTree::Ready(tree) = calculator.create_tree().await.unwrap() else {
+        panic!("Unexpected tree state");
+    };
+    checkpoint_storage
+        .export(tree.reader(), L1BatchNumber(2))
+        .await
+        .unwrap();
+    drop(tree);
+
+    // Emulate a checkpoint for an L1 batch not present in Postgres.
+    let manifest_path = temp_dir.path().join("checkpoints/l1_batch_2/manifest.json");
+    let manifest = std::fs::read(&manifest_path).unwrap();
+    let mut manifest: TreeCheckpointManifest = serde_json::from_slice(&manifest).unwrap();
+    manifest.l1_batch_number = L1BatchNumber(100);
+    std::fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+    let import_path = temp_dir.path().join("imported");
+    let imported = import_checkpoint(&checkpoint_storage, &import_path, &pool)
+        .await
+        .unwrap();
+    assert!(!imported);
+    assert!(!import_path.exists());
+}
+
+#[tokio::test]
+async fn exporting_checkpoint_over_partial_export() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 3).await;
+    run_calculator(calculator, pool.clone()).await;
+
+    // Emulate an interrupted export.
+    let leftover_dir = temp_dir.path().join("checkpoints/l1_batch_2/db");
+    std::fs::create_dir_all(&leftover_dir).unwrap();
+    std::fs::write(leftover_dir.join("garbage"), b"garbage").unwrap();
+
+    let checkpoint_storage = TreeCheckpointStorage::Local(temp_dir.path().join("checkpoints"));
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    let GenericAsyncTree::Ready(tree) = calculator.create_tree().await.unwrap() else {
+        panic!("Unexpected tree state");
+    };
+    let manifest = checkpoint_storage
+        .export(tree.reader(), L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert_eq!(manifest.l1_batch_number, L1BatchNumber(2));
+    assert!(!leftover_dir.join("garbage").exists());
+
+    // Exporting a complete checkpoint again is an error.
+    let err = checkpoint_storage
+        .export(tree.reader(), L1BatchNumber(2))
+        .await
+        .unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("already exists"), "{err}");
+    drop(tree);
+
+    let import_path = temp_dir.path().join("imported");
+    let imported = import_checkpoint(&checkpoint_storage, &import_path, &pool)
+        .await
+        .unwrap();
+    assert!(imported);
+}
+
+#[tokio::test]
+async fn checkpoint_exporter_basics() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 5).await;
+    run_calculator(calculator, pool.clone()).await;
+
+    let checkpoint_storage = TreeCheckpointStorage::Local(temp_dir.path().join("checkpoints"));
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    let GenericAsyncTree::Ready(tree) = calculator.create_tree().await.unwrap() else {
+        panic!("Unexpected tree state");
+    };
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let exporter_task = tokio::spawn(run_checkpoint_exporter(
+        checkpoint_storage.clone(),
+        tree.reader(),
+        2,
+        Duration::from_millis(10),
+        stop_receiver,
+    ));
+
+    let manifest = loop {
+        if let Some(manifest) = checkpoint_storage.latest_manifest().await.unwrap() {
+            break manifest;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    // The latest processed L1 batch is #5, so the checkpoint must be exported for L1 batch #4.
+    assert_eq!(manifest.l1_batch_number, L1BatchNumber(4));
+
+    stop_sender.send_replace(true);
+    exporter_task.await.unwrap().unwrap();
+    assert!(!temp_dir.path().join("checkpoints/l1_batch_2").exists());
+}
@@ -17,7 +17,7 @@ use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_prover_interface::inputs::PrepareBasicCircuitsJob;
 use zksync_types::{
     block::L1BatchHeader, AccountTreeId, Address, L1BatchNumber, MiniblockNumber, StorageKey,
-    StorageLog, H256,
+    StorageLog, H256, U256,
 };
 use zksync_utils::u32_to_h256;
 
@@ -189,6 +189,119 @@ async fn multi_l1_batch_workflow() {
     }
 }
 
+#[tokio::test]
+async fn multi_l1_batch_workflow_with_concurrent_persistence() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (mut merkle_tree_config, operation_config) =
+        create_config(temp_dir.path(), MerkleTreeMode::Full);
+    merkle_tree_config.concurrent_persistence = true;
+    let store_factory = ObjectStoreFactory::mock();
+    let object_store = store_factory.create_store().await;
+    let calculator = setup_calculator_with_options(
+        &merkle_tree_config,
+        &operation_config,
+        &pool,
+        Some(object_store.clone()),
+    )
+    .await;
+    reset_db_state(&pool, 10).await;
+    let root_hash = run_calculator(calculator, pool.clone()).await;
+    assert_eq!(root_hash, expected_tree_hash(&pool).await);
+
+    let mut storage = pool.connection().await.unwrap();
+    for l1_batch_number in 1..=10 {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let state_root = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(
+            state_root.is_some(),
+            "no metadata for L1 batch #{l1_batch_number}"
+        );
+        let job: PrepareBasicCircuitsJob = object_store.get(l1_batch_number).await.unwrap();
+        assert!(job.next_enumeration_index() > 0);
+    }
+    let state_root = storage
+        .blocks_dal()
+        .get_l1_batch_state_root(L1BatchNumber(10))
+        .await
+        .unwrap();
+    assert_eq!(state_root, Some(root_hash));
+    drop(storage);
+
+    let calculator = setup_calculator_with_options(
+        &merkle_tree_config,
+        &operation_config,
+        &pool,
+        Some(object_store),
+    )
+    .await;
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(11));
+}
+
+#[tokio::test]
+async fn concurrent_persistence_with_singleton_pool() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (mut merkle_tree_config, operation_config) =
+        create_config(temp_dir.path(), MerkleTreeMode::Full);
+    merkle_tree_config.concurrent_persistence = true;
+    let calculator =
+        setup_calculator_with_options(&merkle_tree_config, &operation_config, &pool, None).await;
+    reset_db_state(&pool, 5).await;
+    let root_hash = run_calculator(calculator, pool.clone()).await;
+    assert_eq!(root_hash, expected_tree_hash(&pool).await);
+}
+
+#[tokio::test]
+async fn serving_proofs_for_pruned_l1_batches_in_archive_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (mut merkle_tree_config, operation_config) =
+        create_config(temp_dir.path(), MerkleTreeMode::Full);
+    merkle_tree_config.archive_mode = true;
+    merkle_tree_config.archive_checkpoint_interval = 3;
+    let calculator =
+        setup_calculator_with_options(&merkle_tree_config, &operation_config, &pool, None).await;
+    reset_db_state(&pool, 10).await;
+    run_calculator(calculator, pool.clone()).await;
+
+    let calculator =
+        setup_calculator_with_options(&merkle_tree_config, &operation_config, &pool, None).await;
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    let (mut pruner, _pruner_handle) = tree.pruner(1);
+    pruner.set_archive_checkpoint_interval(3);
+    tokio::task::spawn_blocking(move || pruner.run_once())
+        .await
+        .unwrap()
+        .expect("nothing pruned");
+
+    let keys = vec![U256::zero()];
+    let mut reader = tree.reader();
+    reader.set_max_replayed_versions(3);
+    reader
+        .clone()
+        .entries_with_proofs(L1BatchNumber(5), keys.clone())
+        .await
+        .unwrap();
+    reader.set_max_replayed_versions(1);
+    let err = reader
+        .entries_with_proofs(L1BatchNumber(5), keys)
+        .await
+        .unwrap_err();
+    assert_eq!(err.missing_version, 5);
+}
+
 #[tokio::test]
 async fn running_metadata_calculator_with_additional_blocks() {
     let pool = ConnectionPool::<Core>::test_pool().await;
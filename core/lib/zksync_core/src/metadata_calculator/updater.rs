@@ -5,6 +5,7 @@ use std::{ops, sync::Arc, time::Instant};
 use anyhow::Context as _;
 use futures::{future, FutureExt};
 use tokio::sync::watch;
+use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::HealthUpdater;
 use zksync_merkle_tree::domain::TreeMetadata;
@@ -27,6 +28,7 @@ pub(super) struct TreeUpdater {
     tree: AsyncTree,
     max_l1_batches_per_iter: usize,
     object_store: Option<Arc<dyn ObjectStore>>,
+    concurrent_persistence: bool,
 }
 
 impl TreeUpdater {
@@ -34,46 +36,135 @@ impl TreeUpdater {
         tree: AsyncTree,
         max_l1_batches_per_iter: usize,
         object_store: Option<Arc<dyn ObjectStore>>,
+        concurrent_persistence: bool,
     ) -> Self {
         Self {
             tree,
             max_l1_batches_per_iter,
             object_store,
+            concurrent_persistence,
         }
     }
 
-    async fn process_l1_batch(
-        &mut self,
+    async fn compute_l1_batch(
+        tree: &mut AsyncTree,
         l1_batch: L1BatchWithLogs,
-    ) -> anyhow::Result<(L1BatchHeader, TreeMetadata, Option<String>)> {
+    ) -> anyhow::Result<(L1BatchHeader, TreeMetadata)> {
         let compute_latency = METRICS.start_stage(TreeUpdateStage::Compute);
         let l1_batch_header = l1_batch.header.clone();
-        let l1_batch_number = l1_batch_header.number;
-        let mut metadata = self.tree.process_l1_batch(l1_batch).await?;
+        let metadata = tree.process_l1_batch(l1_batch).await?;
         compute_latency.observe();
+        Ok((l1_batch_header, metadata))
+    }
 
+    /// Saves witness inputs produced by the tree to the object store, if the store is configured.
+    async fn save_witness_input(
+        object_store: Option<&dyn ObjectStore>,
+        l1_batch_number: L1BatchNumber,
+        metadata: &mut TreeMetadata,
+    ) -> anyhow::Result<Option<String>> {
         let witness_input = metadata.witness.take();
-        let object_key = if let Some(object_store) = &self.object_store {
-            let witness_input =
-                witness_input.context("no witness input provided by tree; this is a bug")?;
-            let save_witnesses_latency = METRICS.start_stage(TreeUpdateStage::SaveGcs);
-            let object_key = object_store
-                .put(l1_batch_number, &witness_input)
-                .await
-                .context("cannot save witness input to object store")?;
-            save_witnesses_latency.observe();
-
-            tracing::info!(
-                "Saved witnesses for L1 batch #{l1_batch_number} to object storage at `{object_key}`"
-            );
-            Some(object_key)
-        } else {
-            None
+        let Some(object_store) = object_store else {
+            return Ok(None);
         };
 
+        let witness_input =
+            witness_input.context("no witness input provided by tree; this is a bug")?;
+        let save_witnesses_latency = METRICS.start_stage(TreeUpdateStage::SaveGcs);
+        let object_key = object_store
+            .put(l1_batch_number, &witness_input)
+            .await
+            .context("cannot save witness input to object store")?;
+        save_witnesses_latency.observe();
+
+        tracing::info!(
+            "Saved witnesses for L1 batch #{l1_batch_number} to object storage at `{object_key}`"
+        );
+        Ok(Some(object_key))
+    }
+
+    async fn process_l1_batch(
+        &mut self,
+        l1_batch: L1BatchWithLogs,
+    ) -> anyhow::Result<(L1BatchHeader, TreeMetadata, Option<String>)> {
+        let (l1_batch_header, mut metadata) =
+            Self::compute_l1_batch(&mut self.tree, l1_batch).await?;
+        let object_key = Self::save_witness_input(
+            self.object_store.as_deref(),
+            l1_batch_header.number,
+            &mut metadata,
+        )
+        .await?;
         Ok((l1_batch_header, metadata, object_key))
     }
 
+    /// Checks tree output for an L1 batch against Postgres and saves L1 batch metadata to Postgres.
+    async fn save_l1_batch_metadata(
+        storage: &mut Connection<'_, Core>,
+        header: &L1BatchHeader,
+        metadata: &TreeMetadata,
+        object_key: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = header.number;
+        let check_consistency_latency = METRICS.start_stage(TreeUpdateStage::CheckConsistency);
+        Self::check_initial_writes_consistency(storage, l1_batch_number, &metadata.initial_writes)
+            .await?;
+        check_consistency_latency.observe();
+
+        let save_postgres_latency = METRICS.start_stage(TreeUpdateStage::SavePostgres);
+        let tree_data = L1BatchTreeData {
+            hash: metadata.root_hash,
+            rollup_last_leaf_index: metadata.rollup_last_leaf_index,
+        };
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(l1_batch_number, &tree_data)
+            .await
+            .context("failed saving tree data")?;
+        // ^ Note that `save_l1_batch_tree_data()` will not blindly overwrite changes if L1 batch
+        // metadata already exists; instead, it'll check that the old and new metadata match.
+        // That is, if we run multiple tree instances, we'll get metadata correspondence
+        // right away without having to implement dedicated code.
+
+        if let Some(object_key) = object_key {
+            storage
+                .basic_witness_input_producer_dal()
+                .create_basic_witness_input_producer_job(l1_batch_number)
+                .await
+                .context("failed to create basic_witness_input_producer job")?;
+            storage
+                .proof_generation_dal()
+                .insert_proof_generation_details(l1_batch_number, object_key)
+                .await;
+        }
+        save_postgres_latency.observe();
+        tracing::info!("Updated metadata for L1 batch #{l1_batch_number} in Postgres");
+        Ok(())
+    }
+
+    /// Persists results of processing an L1 batch by the tree: saves witness inputs to the object store
+    /// and L1 batch metadata to Postgres.
+    async fn persist_l1_batch(
+        object_store: Option<&dyn ObjectStore>,
+        storage: &mut Connection<'_, Core>,
+        (header, mut metadata): (L1BatchHeader, TreeMetadata),
+    ) -> anyhow::Result<L1BatchHeader> {
+        let object_key =
+            Self::save_witness_input(object_store, header.number, &mut metadata).await?;
+        Self::save_l1_batch_metadata(storage, &header, &metadata, object_key.as_deref()).await?;
+        Ok(header)
+    }
+
+    async fn load_l1_batch(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+        tree_mode: MerkleTreeMode,
+    ) -> anyhow::Result<Option<L1BatchWithLogs>> {
+        L1BatchWithLogs::new(storage, l1_batch_number, tree_mode)
+            .await
+            .with_context(|| format!("failed fetching tree input for L1 batch #{l1_batch_number}"))
+    }
+
     /// Processes a range of L1 batches with a single flushing of the tree updates to RocksDB at the end.
     /// This allows to save on RocksDB I/O ops.
     ///
@@ -95,11 +186,8 @@ impl TreeUpdater {
         tracing::info!("Processing L1 batches #{l1_batch_numbers:?} in {tree_mode:?} mode");
         let first_l1_batch_number = L1BatchNumber(*l1_batch_numbers.start());
         let last_l1_batch_number = L1BatchNumber(*l1_batch_numbers.end());
-        let mut l1_batch_data = L1BatchWithLogs::new(storage, first_l1_batch_number, tree_mode)
-            .await
-            .with_context(|| {
-                format!("failed fetching tree input for L1 batch #{first_l1_batch_number}")
-            })?;
+        let mut l1_batch_data =
+            Self::load_l1_batch(storage, first_l1_batch_number, tree_mode).await?;
 
         let mut total_logs = 0;
         let mut updated_headers = vec![];
@@ -113,14 +201,7 @@ impl TreeUpdater {
             let process_l1_batch_task = self.process_l1_batch(current_l1_batch_data);
             let load_next_l1_batch_task = async {
                 if l1_batch_number < last_l1_batch_number {
-                    let next_l1_batch_number = l1_batch_number + 1;
-                    L1BatchWithLogs::new(storage, next_l1_batch_number, tree_mode)
-                        .await
-                        .with_context(|| {
-                            format!(
-                                "failed fetching tree input for L1 batch #{next_l1_batch_number}"
-                            )
-                        })
+                    Self::load_l1_batch(storage, l1_batch_number + 1, tree_mode).await
                 } else {
                     Ok(None) // Don't need to load the next L1 batch after the last one we're processing.
                 }
@@ -128,44 +209,8 @@ impl TreeUpdater {
             let ((header, metadata, object_key), next_l1_batch_data) =
                 future::try_join(process_l1_batch_task, load_next_l1_batch_task).await?;
 
-            let check_consistency_latency = METRICS.start_stage(TreeUpdateStage::CheckConsistency);
-            Self::check_initial_writes_consistency(
-                storage,
-                header.number,
-                &metadata.initial_writes,
-            )
-            .await?;
-            check_consistency_latency.observe();
-
-            let save_postgres_latency = METRICS.start_stage(TreeUpdateStage::SavePostgres);
-            let tree_data = L1BatchTreeData {
-                hash: metadata.root_hash,
-                rollup_last_leaf_index: metadata.rollup_last_leaf_index,
-            };
-            storage
-                .blocks_dal()
-                .save_l1_batch_tree_data(l1_batch_number, &tree_data)
-                .await
-                .context("failed saving tree data")?;
-            // ^ Note that `save_l1_batch_tree_data()` will not blindly overwrite changes if L1 batch
-            // metadata already exists; instead, it'll check that the old and new metadata match.
-            // That is, if we run multiple tree instances, we'll get metadata correspondence
-            // right away without having to implement dedicated code.
-
-            if let Some(object_key) = &object_key {
-                storage
-                    .basic_witness_input_producer_dal()
-                    .create_basic_witness_input_producer_job(l1_batch_number)
-                    .await
-                    .context("failed to create basic_witness_input_producer job")?;
-                storage
-                    .proof_generation_dal()
-                    .insert_proof_generation_details(l1_batch_number, object_key)
-                    .await;
-            }
-            save_postgres_latency.observe();
-            tracing::info!("Updated metadata for L1 batch #{l1_batch_number} in Postgres");
-
+            Self::save_l1_batch_metadata(storage, &header, &metadata, object_key.as_deref())
+                .await?;
             updated_headers.push(header);
             l1_batch_data = next_l1_batch_data;
         }
@@ -178,9 +223,95 @@ impl TreeUpdater {
         Ok(last_l1_batch_number + 1)
     }
 
+    /// Version of [`Self::process_multiple_batches()`] persisting results concurrently with computing tree updates.
+    ///
+    /// # Implementation details
+    ///
+    /// Each iteration runs 3 stages concurrently:
+    ///
+    /// - Computing the tree update for L1 batch N (CPU-bound; runs on a blocking thread).
+    /// - Loading data for L1 batch N + 1 from Postgres.
+    /// - Persisting results for L1 batch N - 1, i.e., saving witness inputs to the object store
+    ///   and metadata to Postgres.
+    ///
+    /// Loading and persisting use separate Postgres connections. Results are persisted in the order
+    /// of L1 batch numbers, and the tree is only flushed to RocksDB after all results are persisted,
+    /// so the invariants are the same as for the sequential processing.
+    ///
+    /// Tree updates for different L1 batches are still computed one after another, since each tree version
+    /// depends on the previous one; hashing within an L1 batch is parallelized by the tree itself.
+    async fn process_multiple_batches_with_concurrent_persistence(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        persistence_storage: &mut Connection<'_, Core>,
+        l1_batch_numbers: ops::RangeInclusive<u32>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let tree_mode = self.tree.mode();
+        let start = Instant::now();
+        tracing::info!(
+            "Processing L1 batches #{l1_batch_numbers:?} in {tree_mode:?} mode with concurrent persistence"
+        );
+        let first_l1_batch_number = L1BatchNumber(*l1_batch_numbers.start());
+        let last_l1_batch_number = L1BatchNumber(*l1_batch_numbers.end());
+        let mut l1_batch_data =
+            Self::load_l1_batch(storage, first_l1_batch_number, tree_mode).await?;
+
+        let tree = &mut self.tree;
+        let object_store = self.object_store.as_deref();
+        let mut next_l1_batch_number = last_l1_batch_number + 1;
+        let mut total_logs = 0;
+        let mut updated_headers = vec![];
+        let mut pending_output = None;
+        for l1_batch_number in l1_batch_numbers {
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let Some(current_l1_batch_data) = l1_batch_data else {
+                next_l1_batch_number = l1_batch_number;
+                break;
+            };
+            total_logs += current_l1_batch_data.storage_logs.len();
+
+            let compute_task = Self::compute_l1_batch(tree, current_l1_batch_data);
+            let load_next_l1_batch_task = async {
+                if l1_batch_number < last_l1_batch_number {
+                    Self::load_l1_batch(storage, l1_batch_number + 1, tree_mode).await
+                } else {
+                    Ok(None) // Don't need to load the next L1 batch after the last one we're processing.
+                }
+            };
+            let persist_task = async {
+                if let Some(output) = pending_output.take() {
+                    Self::persist_l1_batch(object_store, persistence_storage, output)
+                        .await
+                        .map(Some)
+                } else {
+                    Ok(None)
+                }
+            };
+            let (output, next_l1_batch_data, persisted_header) =
+                future::try_join3(compute_task, load_next_l1_batch_task, persist_task).await?;
+
+            updated_headers.extend(persisted_header);
+            pending_output = Some(output);
+            l1_batch_data = next_l1_batch_data;
+        }
+        if let Some(output) = pending_output {
+            let header = Self::persist_l1_batch(object_store, persistence_storage, output).await?;
+            updated_headers.push(header);
+        }
+
+        if !updated_headers.is_empty() {
+            let save_rocksdb_latency = METRICS.start_stage(TreeUpdateStage::SaveRocksdb);
+            self.tree.save().await?;
+            save_rocksdb_latency.observe();
+            MetadataCalculator::update_metrics(&updated_headers, total_logs, start);
+        }
+        Ok(next_l1_batch_number)
+    }
+
     async fn step(
         &mut self,
         mut storage: Connection<'_, Core>,
+        persistence_storage: Option<Connection<'_, Core>>,
         next_l1_batch_to_seal: &mut L1BatchNumber,
     ) -> anyhow::Result<()> {
         let Some(last_sealed_l1_batch) = storage
@@ -200,6 +331,15 @@ impl TreeUpdater {
             tracing::trace!(
                 "No L1 batches to seal: batch numbers range to be loaded {l1_batch_numbers:?} is empty"
             );
+        } else if let Some(mut persistence_storage) = persistence_storage {
+            tracing::info!("Updating Merkle tree with L1 batches #{l1_batch_numbers:?}");
+            *next_l1_batch_to_seal = self
+                .process_multiple_batches_with_concurrent_persistence(
+                    &mut storage,
+                    &mut persistence_storage,
+                    l1_batch_numbers,
+                )
+                .await?;
         } else {
             tracing::info!("Updating Merkle tree with L1 batches #{l1_batch_numbers:?}");
             *next_l1_batch_to_seal = self
@@ -242,6 +382,15 @@ impl TreeUpdater {
         }
         let mut next_l1_batch_to_seal = tree.next_l1_batch_number();
 
+        if self.concurrent_persistence && pool.max_size() < 2 {
+            tracing::warn!(
+                "Concurrent persistence requires at least 2 Postgres connections, but the pool has {}; \
+                 falling back to sequential persistence",
+                pool.max_size()
+            );
+            self.concurrent_persistence = false;
+        }
+
         let current_db_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
         let last_l1_batch_with_metadata = storage
             .blocks_dal()
@@ -294,9 +443,15 @@ impl TreeUpdater {
                 break;
             }
             let storage = pool.connection_tagged("metadata_calculator").await?;
+            let persistence_storage = if self.concurrent_persistence {
+                Some(pool.connection_tagged("metadata_calculator").await?)
+            } else {
+                None
+            };
 
             let snapshot = *next_l1_batch_to_seal;
-            self.step(storage, &mut next_l1_batch_to_seal).await?;
+            self.step(storage, persistence_storage, &mut next_l1_batch_to_seal)
+                .await?;
             let delay = if snapshot == *next_l1_batch_to_seal {
                 tracing::trace!(
                     "Metadata calculator (next L1 batch: #{next_l1_batch_to_seal}) \
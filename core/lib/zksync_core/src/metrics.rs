@@ -19,6 +19,9 @@ pub(crate) enum InitStage {
     Tree,
     BasicWitnessInputProducer,
     Consensus,
+    NhProofSubmitter,
+    NhAttestationReconciler,
+    DaDispatcher,
 }
 
 impl fmt::Display for InitStage {
@@ -34,6 +37,9 @@ impl fmt::Display for InitStage {
             Self::Tree => formatter.write_str("tree"),
             Self::BasicWitnessInputProducer => formatter.write_str("basic_witness_input_producer"),
             Self::Consensus => formatter.write_str("consensus"),
+            Self::NhProofSubmitter => formatter.write_str("nh_proof_submitter"),
+            Self::NhAttestationReconciler => formatter.write_str("nh_attestation_reconciler"),
+            Self::DaDispatcher => formatter.write_str("da_dispatcher"),
         }
     }
 }
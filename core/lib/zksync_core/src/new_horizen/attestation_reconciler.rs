@@ -0,0 +1,296 @@
+use std::{collections::HashSet, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{l1::NHAttestation, web3::types::BlockNumber, U256};
+
+use crate::eth_watch::{
+    client::{EthClient, RETRY_LIMIT},
+    event_processors::nh::ATTESTATION_POSTED_SIGNATURE,
+};
+
+/// Outcome of a single [`NhAttestationReconciler::reconcile()`] run.
+#[derive(Debug, Default)]
+pub struct AttestationReconciliation {
+    /// IDs of attestations that include L1 batch proofs, but were not stored before the run.
+    pub missing_attestation_ids: Vec<U256>,
+    /// Attestations found on L1 and stored during the run.
+    pub restored_attestations: Vec<NHAttestation>,
+}
+
+impl AttestationReconciliation {
+    /// Returns IDs of attestations that are still missing after the run.
+    pub fn unresolved_attestation_ids(&self) -> impl Iterator<Item = U256> + '_ {
+        let restored_ids: HashSet<_> = self
+            .restored_attestations
+            .iter()
+            .map(|attestation| attestation.attestation_id)
+            .collect();
+        self.missing_attestation_ids
+            .iter()
+            .copied()
+            .filter(move |id| !restored_ids.contains(id))
+    }
+}
+
+/// Restores NH attestations missed by the Ethereum watcher, e.g. after an L1 reorg or a restart with
+/// a lookback window not covering all posted attestations. Without such attestations, proofs for
+/// the corresponding L1 batches are never sent to L1.
+///
+/// The reconciler looks for proven L1 batches referencing attestations not present in Postgres and re-scans
+/// `AttestationPosted` events emitted by the NH verifier contract in the specified L1 block range.
+/// Found attestations are inserted idempotently, so the reconciler can safely run concurrently with the watcher.
+#[derive(Debug)]
+pub struct NhAttestationReconciler {
+    pool: ConnectionPool<Core>,
+    client: Box<dyn EthClient>,
+}
+
+impl NhAttestationReconciler {
+    /// Creates a reconciler. The client must query events from the NH verifier contract.
+    pub fn new(pool: ConnectionPool<Core>, mut client: Box<dyn EthClient>) -> Self {
+        client.set_topics(vec![ATTESTATION_POSTED_SIGNATURE]);
+        Self { pool, client }
+    }
+
+    /// Runs reconciliation periodically, each time scanning `block_range` L1 blocks ending
+    /// at the last finalized block.
+    pub async fn run(
+        self,
+        interval: Duration,
+        block_range: u64,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            let to_block = self
+                .client
+                .finalized_block_number()
+                .await
+                .context("failed getting finalized L1 block number")?;
+            let from_block = to_block.saturating_sub(block_range.saturating_sub(1));
+            match self.reconcile(from_block, to_block).await {
+                Ok(reconciliation) => {
+                    let unresolved: Vec<_> = reconciliation.unresolved_attestation_ids().collect();
+                    if !unresolved.is_empty() {
+                        tracing::info!(
+                            "Attestations {unresolved:?} are not found in L1 blocks {from_block}..={to_block}; \
+                             they may be not posted yet"
+                        );
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Failed reconciling NH attestations: {err:#}");
+                }
+            }
+
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, NH attestation reconciler is shutting down");
+        Ok(())
+    }
+
+    /// Restores missing attestations posted in the specified L1 block range (inclusive).
+    pub async fn reconcile(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> anyhow::Result<AttestationReconciliation> {
+        let missing_attestation_ids = self
+            .pool
+            .connection_tagged("nh_attestation_reconciler")
+            .await?
+            .nh_dal()
+            .get_missing_nh_attestation_ids()
+            .await
+            .context("get_missing_nh_attestation_ids()")?;
+        if missing_attestation_ids.is_empty() {
+            tracing::debug!("No missing NH attestations");
+            return Ok(AttestationReconciliation::default());
+        }
+        tracing::info!(
+            "Looking for missing NH attestations {missing_attestation_ids:?} in L1 blocks {from_block}..={to_block}"
+        );
+
+        let events = self
+            .client
+            .get_events(
+                BlockNumber::Number(from_block.into()),
+                BlockNumber::Number(to_block.into()),
+                RETRY_LIMIT,
+            )
+            .await
+            .context("failed fetching `AttestationPosted` events")?;
+
+        let mut missing_ids: HashSet<_> = missing_attestation_ids.iter().copied().collect();
+        let mut restored_attestations = vec![];
+        for event in events {
+            if event.topics.first() != Some(&ATTESTATION_POSTED_SIGNATURE) {
+                continue;
+            }
+            let attestation = NHAttestation::try_from(event)
+                .context("failed parsing `AttestationPosted` event")?;
+            // `remove()` deduplicates attestations if the event was emitted several times, e.g. after a reorg.
+            if missing_ids.remove(&attestation.attestation_id) {
+                restored_attestations.push(attestation);
+            }
+        }
+
+        let mut storage = self
+            .pool
+            .connection_tagged("nh_attestation_reconciler")
+            .await?;
+        for attestation in &restored_attestations {
+            tracing::info!("Restoring missing NH attestation: {attestation}");
+            storage.nh_dal().insert_nh_attestation(attestation).await;
+        }
+
+        Ok(AttestationReconciliation {
+            missing_attestation_ids,
+            restored_attestations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{web3::types::Log, Address, L1BatchNumber, H256};
+
+    use super::*;
+    use crate::{eth_watch::client::Error, utils::testonly::create_l1_batch};
+
+    #[derive(Debug, Default)]
+    struct MockEthClient {
+        logs: Vec<Log>,
+    }
+
+    impl MockEthClient {
+        fn post_attestation(&mut self, block_number: u64, attestation_id: u64, root: H256) {
+            self.logs.push(Log {
+                address: Address::repeat_byte(0x18),
+                topics: vec![
+                    ATTESTATION_POSTED_SIGNATURE,
+                    H256::from_low_u64_be(attestation_id),
+                    root,
+                ],
+                data: vec![].into(),
+                block_hash: None,
+                block_number: Some(block_number.into()),
+                transaction_hash: None,
+                transaction_index: None,
+                log_index: None,
+                transaction_log_index: None,
+                log_type: None,
+                removed: None,
+            });
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl EthClient for MockEthClient {
+        async fn get_events(
+            &self,
+            from: BlockNumber,
+            to: BlockNumber,
+            _retries_left: usize,
+        ) -> Result<Vec<Log>, Error> {
+            let (BlockNumber::Number(from), BlockNumber::Number(to)) = (from, to) else {
+                unreachable!("unexpected block range: {from:?}..={to:?}");
+            };
+            let logs = self.logs.iter().filter(|log| {
+                let block_number = log.block_number.unwrap();
+                (from..=to).contains(&block_number)
+            });
+            Ok(logs.cloned().collect())
+        }
+
+        async fn finalized_block_number(&self) -> Result<u64, Error> {
+            Ok(100)
+        }
+
+        async fn scheduler_vk_hash(&self, _verifier_address: Address) -> Result<H256, Error> {
+            unimplemented!()
+        }
+
+        fn set_topics(&mut self, _topics: Vec<H256>) {
+            // Do nothing
+        }
+    }
+
+    async fn insert_proven_l1_batch(pool: &ConnectionPool<Core>, number: u32, attestation_id: u64) {
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(number))
+            .await
+            .unwrap();
+        storage
+            .proof_generation_dal()
+            .insert_proof_generation_details(L1BatchNumber(number), "")
+            .await;
+        storage
+            .proof_generation_dal()
+            .save_nh_attestation_element(L1BatchNumber(number), attestation_id, H256::zero())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn restoring_missing_attestations() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        pool.connection()
+            .await
+            .unwrap()
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(Default::default())
+            .await;
+        for (number, attestation_id) in [(1, 0), (2, 1), (3, 1), (4, 2)] {
+            insert_proven_l1_batch(&pool, number, attestation_id).await;
+        }
+
+        let mut client = MockEthClient::default();
+        client.post_attestation(10, 0, H256::repeat_byte(1));
+        client.post_attestation(20, 1, H256::repeat_byte(2));
+        let reconciler = NhAttestationReconciler::new(pool.clone(), Box::new(client));
+
+        let reconciliation = reconciler.reconcile(15, 100).await.unwrap();
+        assert_eq!(
+            reconciliation.missing_attestation_ids,
+            [0, 1, 2].map(U256::from)
+        );
+        let restored_ids: Vec<_> = reconciliation
+            .restored_attestations
+            .iter()
+            .map(|attestation| attestation.attestation_id)
+            .collect();
+        assert_eq!(restored_ids, [U256::from(1)]);
+        let unresolved: Vec<_> = reconciliation.unresolved_attestation_ids().collect();
+        assert_eq!(unresolved, [0, 2].map(U256::from));
+
+        let mut storage = pool.connection().await.unwrap();
+        let attestation = storage
+            .nh_dal()
+            .get_nh_attestation_from_batch_number(L1BatchNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(attestation.proofs_attestation, H256::repeat_byte(2));
+        drop(storage);
+
+        // Repeated reconciliation over the entire range should restore the remaining attestation.
+        let reconciliation = reconciler.reconcile(0, 100).await.unwrap();
+        assert_eq!(
+            reconciliation.missing_attestation_ids,
+            [0, 2].map(U256::from)
+        );
+        assert_eq!(reconciliation.restored_attestations.len(), 1);
+        assert_eq!(
+            reconciliation
+                .unresolved_attestation_ids()
+                .collect::<Vec<_>>(),
+            [U256::from(2)]
+        );
+    }
+}
@@ -0,0 +1,405 @@
+use std::{fmt, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use futures::future;
+use subxt::{
+    config::Hasher,
+    ext::scale_value::{Composite, Value},
+    tx::{TxInBlock, TxProgress, TxStatus},
+    utils::{MultiAddress, MultiSignature},
+    OnlineClient, PolkadotConfig,
+};
+use zksync_config::configs::NewHorizenConfig;
+use zksync_types::H256;
+
+use super::{
+    metrics::METRICS,
+    signer::{create_signer, NhSigner},
+};
+use crate::proof_verification_layer::{
+    AttestationPath, FinalizedProofSubmission, PendingProofSubmission, ProofSubmissionStatus,
+    ProofVerificationError, ProofVerificationLayer,
+};
+
+#[subxt::subxt(runtime_metadata_path = "../../../etc/nh/metadata.scale")]
+pub mod nh {}
+
+/// Progress of a proof submission extrinsic on the NH chain.
+type NhTxProgress = TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+type NhTxInBlock = TxInBlock<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+fn map_subxt_error(err: subxt::Error) -> ProofVerificationError {
+    match err {
+        subxt::Error::Runtime(err) => ProofVerificationError::Rejected(err.to_string()),
+        err => ProofVerificationError::Transport(err.into()),
+    }
+}
+
+/// Client for the NH chain, which verifies L1 batch proofs and aggregates them into attestations.
+#[derive(Clone)]
+pub struct NhClient {
+    client: OnlineClient<PolkadotConfig>,
+    signer: Arc<dyn NhSigner>,
+}
+
+impl fmt::Debug for NhClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("NhClient")
+            .field("signer", &self.signer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NhClient {
+    pub fn new(client: OnlineClient<PolkadotConfig>, signer: Arc<dyn NhSigner>) -> Self {
+        Self { client, signer }
+    }
+
+    /// Connects to the NH node specified in the config.
+    pub async fn from_config(config: &NewHorizenConfig) -> anyhow::Result<Self> {
+        let client = OnlineClient::<PolkadotConfig>::from_url(&config.url)
+            .await
+            .with_context(|| format!("failed connecting to NH node at {}", config.url))?;
+        let signer = config
+            .signer
+            .as_ref()
+            .context("NH signer is not configured")?;
+        let signer = create_signer(signer).context("failed creating NH signer")?;
+        Ok(Self::new(client, signer))
+    }
+
+    /// Signs and submits a proof submission extrinsic. The returned progress can be used
+    /// to track the extrinsic until it's included in a finalized block. If `nonce` is not specified,
+    /// it's fetched from the NH node.
+    async fn submit_proof_tx(
+        &self,
+        proof: &[u8],
+        pi: &[u8],
+        nonce: Option<u64>,
+    ) -> Result<NhTxProgress, ProofVerificationError> {
+        // subxt macro gets confused with VkOrHash type, so we resort to the untyped,
+        // dynamic interface.
+        let submit_proof_tx = subxt::dynamic::tx(
+            "SettlementZksyncPallet",
+            "submit_proof",
+            Composite::Named(vec![
+                (
+                    "vk_or_hash".into(),
+                    Value::variant("Vk", Composite::Unnamed(vec![Value::from_bytes(vec![])])),
+                ),
+                ("proof".into(), Value::from_bytes(proof)),
+                ("pubs".into(), Value::from_bytes(pi)),
+            ]),
+        );
+
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => self
+                .client
+                .tx()
+                .account_nonce(&self.signer.account_id())
+                .await
+                .map_err(map_subxt_error)?,
+        };
+        self.submit_tx(&submit_proof_tx, nonce).await
+    }
+
+    /// Submits a no-op `System.remark` extrinsic with the specified nonce. Used to fill the nonce gap
+    /// left by a proof submission that was accepted but then dropped by the NH node; otherwise, extrinsics
+    /// with greater nonces are stuck in the NH transaction pool.
+    async fn fill_nonce_gap(&self, nonce: u64) -> Result<(), ProofVerificationError> {
+        let remark_tx = subxt::dynamic::tx(
+            "System",
+            "remark",
+            Composite::Named(vec![("remark".into(), Value::from_bytes(Vec::<u8>::new()))]),
+        );
+        // The filler extrinsic is not tracked; if it's not included, the stuck submissions will time out and be retried.
+        self.submit_tx(&remark_tx, nonce).await?;
+        Ok(())
+    }
+
+    async fn submit_tx<Call: subxt::tx::TxPayload>(
+        &self,
+        call: &Call,
+        nonce: u64,
+    ) -> Result<NhTxProgress, ProofVerificationError> {
+        let account_id = self.signer.account_id();
+        let partial_tx = self
+            .client
+            .tx()
+            .create_partial_signed_with_nonce(call, nonce, Default::default())
+            .map_err(map_subxt_error)?;
+        // The signer may be remote, so signing is performed separately from the transaction creation.
+        let signature = self
+            .signer
+            .sign(&partial_tx.signer_payload())
+            .await
+            .map_err(ProofVerificationError::Transport)?;
+        partial_tx
+            .sign_with_address_and_signature(
+                &MultiAddress::Id(account_id),
+                &MultiSignature::Sr25519(signature),
+            )
+            .submit_and_watch()
+            .await
+            .map_err(map_subxt_error)
+    }
+
+    /// Waits until a submitted extrinsic is included in a (possibly non-finalized) block.
+    async fn wait_for_inclusion(
+        mut progress: NhTxProgress,
+    ) -> Result<PendingProofSubmission, ProofVerificationError> {
+        while let Some(status) = progress.next().await {
+            match status.map_err(map_subxt_error)? {
+                TxStatus::InBestBlock(in_block) => {
+                    let tx_hash = H256(in_block.extrinsic_hash().0);
+                    let block_hash = H256(in_block.block_hash().0);
+                    let finalization = Self::wait_for_finalization(progress);
+                    return Ok(PendingProofSubmission::new(
+                        tx_hash,
+                        block_hash,
+                        finalization,
+                    ));
+                }
+                TxStatus::InFinalizedBlock(in_block) => {
+                    // The best block notification was skipped; the submission is already finalized.
+                    let tx_hash = H256(in_block.extrinsic_hash().0);
+                    let block_hash = H256(in_block.block_hash().0);
+                    let finalized = Self::finalized_submission(in_block).await;
+                    return Ok(PendingProofSubmission::new(
+                        tx_hash,
+                        block_hash,
+                        async move { finalized },
+                    ));
+                }
+                TxStatus::Error { message }
+                | TxStatus::Invalid { message }
+                | TxStatus::Dropped { message } => {
+                    return Err(ProofVerificationError::Rejected(message));
+                }
+                _ => { /* The transaction is not included in a block yet */ }
+            }
+        }
+        Err(ProofVerificationError::Rejected(
+            "transaction status subscription was closed".to_owned(),
+        ))
+    }
+
+    async fn finalized_submission(
+        in_block: NhTxInBlock,
+    ) -> Result<FinalizedProofSubmission, ProofVerificationError> {
+        let block_hash = H256(in_block.block_hash().0);
+        let events = in_block.wait_for_success().await.map_err(map_subxt_error)?;
+        let new_element = events
+            .find_first::<nh::poe::events::NewElement>()
+            .map_err(map_subxt_error)?
+            .ok_or_else(|| {
+                ProofVerificationError::Rejected("no `NewElement` event emitted".to_owned())
+            })?;
+        Ok(FinalizedProofSubmission {
+            block_hash,
+            attestation_id: new_element.attestation_id,
+            attestation_element: new_element.value,
+        })
+    }
+
+    async fn wait_for_finalization(
+        mut progress: NhTxProgress,
+    ) -> Result<FinalizedProofSubmission, ProofVerificationError> {
+        while let Some(status) = progress.next().await {
+            match status.map_err(map_subxt_error)? {
+                TxStatus::InFinalizedBlock(in_block) => {
+                    return Self::finalized_submission(in_block).await;
+                }
+                TxStatus::Error { message }
+                | TxStatus::Invalid { message }
+                | TxStatus::Dropped { message } => {
+                    return Err(ProofVerificationError::Rejected(message));
+                }
+                _ => { /* The transaction is not finalized yet */ }
+            }
+        }
+        Err(ProofVerificationError::Rejected(
+            "transaction status subscription was closed".to_owned(),
+        ))
+    }
+}
+
+#[async_trait]
+impl ProofVerificationLayer for NhClient {
+    fn attests_proofs(&self) -> bool {
+        true
+    }
+
+    async fn check_connection(&self) -> Result<(), ProofVerificationError> {
+        self.client
+            .blocks()
+            .at_latest()
+            .await
+            .map_err(map_subxt_error)?;
+        Ok(())
+    }
+
+    async fn submit_proof(
+        &self,
+        proof: &[u8],
+        public_inputs: &[u8],
+    ) -> Result<PendingProofSubmission, ProofVerificationError> {
+        let progress = self.submit_proof_tx(proof, public_inputs, None).await?;
+        Self::wait_for_inclusion(progress).await
+    }
+
+    /// Submits all proofs before waiting for any of them to be included, so that multiple proofs
+    /// can be included into a single NH block. Extrinsics are signed with consecutive nonces, so that they are
+    /// executed in order. Each extrinsic emits its own `NewElement` event, so the returned submissions
+    /// correspond to `proofs` one-to-one.
+    ///
+    /// If an accepted extrinsic is dropped before inclusion, its nonce is filled with a no-op extrinsic,
+    /// so that the following submissions in the batch aren't blocked by the nonce gap.
+    async fn submit_proofs(
+        &self,
+        proofs: &[(&[u8], &[u8])],
+    ) -> Result<Vec<Result<PendingProofSubmission, ProofVerificationError>>, ProofVerificationError>
+    {
+        let account_id = self.signer.account_id();
+        let mut nonce = self
+            .client
+            .tx()
+            .account_nonce(&account_id)
+            .await
+            .map_err(map_subxt_error)?;
+
+        let mut progresses = Vec::with_capacity(proofs.len());
+        for &(proof, public_inputs) in proofs {
+            let progress = self
+                .submit_proof_tx(proof, public_inputs, Some(nonce))
+                .await
+                .map(|progress| (progress, nonce));
+            // If the extrinsic wasn't accepted, its nonce is reused by the next extrinsic.
+            if progress.is_ok() {
+                nonce += 1;
+            }
+            progresses.push(progress);
+        }
+
+        let next_nonce = nonce;
+        let inclusions = progresses.into_iter().map(|progress| async move {
+            let (progress, nonce) = progress?;
+            let inclusion = Self::wait_for_inclusion(progress).await;
+            if let Err(err) = &inclusion {
+                if nonce + 1 < next_nonce {
+                    tracing::warn!(
+                        "Proof submission extrinsic with nonce {nonce} was not included ({err}); \
+                         filling the nonce gap for subsequent submissions"
+                    );
+                    if let Err(err) = self.fill_nonce_gap(nonce).await {
+                        tracing::warn!("Failed filling nonce gap {nonce}: {err}");
+                    }
+                }
+            }
+            inclusion
+        });
+        Ok(future::join_all(inclusions).await)
+    }
+
+    async fn get_submission_status(
+        &self,
+        tx_hash: H256,
+        block_hash: H256,
+    ) -> Result<ProofSubmissionStatus, ProofVerificationError> {
+        let rpc = self.client.rpc();
+        let nh_block_hash = block_hash.0.into();
+        let Some(header) = rpc
+            .header(Some(nh_block_hash))
+            .await
+            .map_err(map_subxt_error)?
+        else {
+            // The block was pruned, which only happens with non-canonical blocks.
+            return Ok(ProofSubmissionStatus::Dropped);
+        };
+
+        let finalized_hash = rpc.finalized_head().await.map_err(map_subxt_error)?;
+        let finalized_header = rpc
+            .header(Some(finalized_hash))
+            .await
+            .map_err(map_subxt_error)?
+            .ok_or_else(|| {
+                let err =
+                    anyhow::anyhow!("header for finalized NH block {finalized_hash:?} is missing");
+                ProofVerificationError::Transport(err)
+            })?;
+        if header.number > finalized_header.number {
+            return Ok(ProofSubmissionStatus::NotFinalized);
+        }
+        let canonical_hash = rpc
+            .block_hash(Some(u64::from(header.number).into()))
+            .await
+            .map_err(map_subxt_error)?;
+        if canonical_hash != Some(nh_block_hash) {
+            return Ok(ProofSubmissionStatus::Dropped);
+        }
+
+        let block = self
+            .client
+            .blocks()
+            .at(nh_block_hash)
+            .await
+            .map_err(map_subxt_error)?;
+        let extrinsics = block.extrinsics().await.map_err(map_subxt_error)?;
+        for extrinsic in extrinsics.iter() {
+            let extrinsic = extrinsic.map_err(map_subxt_error)?;
+            let extrinsic_hash = <PolkadotConfig as subxt::Config>::Hasher::hash(extrinsic.bytes());
+            if extrinsic_hash.0 != tx_hash.0 {
+                continue;
+            }
+            let events = extrinsic.events().await.map_err(map_subxt_error)?;
+            let new_element = events
+                .find_first::<nh::poe::events::NewElement>()
+                .map_err(map_subxt_error)?
+                .ok_or_else(|| {
+                    ProofVerificationError::Rejected("no `NewElement` event emitted".to_owned())
+                })?;
+            return Ok(ProofSubmissionStatus::Finalized(FinalizedProofSubmission {
+                block_hash,
+                attestation_id: new_element.attestation_id,
+                attestation_element: new_element.value,
+            }));
+        }
+        Ok(ProofSubmissionStatus::Dropped)
+    }
+
+    async fn get_attestation_path(
+        &self,
+        attestation_id: u64,
+        attestation_element: H256,
+    ) -> Result<Option<AttestationPath>, ProofVerificationError> {
+        let latency = METRICS.get_proof_path_latency.start();
+        let runtime_api = self
+            .client
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(map_subxt_error)?;
+        let request = nh::apis()
+            .po_e_api()
+            .get_proof_path(attestation_id, attestation_element);
+        let response = runtime_api.call(request).await.map_err(map_subxt_error)?;
+        latency.observe();
+
+        match response {
+            Ok(path) => Ok(Some(AttestationPath {
+                merkle_path: path.proof,
+                leaf_count: path.number_of_leaves,
+                index: path.leaf_index,
+            })),
+            Err(err) => {
+                tracing::debug!(
+                    "NH has no path for element {attestation_element:?} in attestation #{attestation_id}: {err:?}"
+                );
+                Ok(None)
+            }
+        }
+    }
+}
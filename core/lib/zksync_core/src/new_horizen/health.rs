@@ -0,0 +1,138 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::Serialize;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{CheckHealth, Health, HealthStatus};
+use zksync_types::L1BatchNumber;
+
+use super::metrics::METRICS;
+use crate::proof_verification_layer::ProofVerificationLayer;
+
+#[derive(Debug, Serialize)]
+struct NhHealthDetails {
+    reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    last_proven_l1_batch: Option<L1BatchNumber>,
+    last_attested_l1_batch: Option<L1BatchNumber>,
+    attestation_lag: u64,
+    /// Time since the oldest proof submission not included into an attestation on L1 was finalized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oldest_unattested_submission_age: Option<Duration>,
+}
+
+/// Health check for the NewHorizen integration. The component is reported as affected
+/// if NH is unreachable or attestations stop being posted on L1.
+#[derive(Debug)]
+pub struct NhHealthCheck {
+    layer: Arc<dyn ProofVerificationLayer>,
+    pool: ConnectionPool<Core>,
+    max_attestation_delay: Duration,
+}
+
+impl NhHealthCheck {
+    pub fn new(
+        layer: Arc<dyn ProofVerificationLayer>,
+        pool: ConnectionPool<Core>,
+        max_attestation_delay: Duration,
+    ) -> Self {
+        Self {
+            layer,
+            pool,
+            max_attestation_delay,
+        }
+    }
+
+    async fn details(&self) -> anyhow::Result<NhHealthDetails> {
+        let connection_result = self.layer.check_connection().await;
+
+        let mut storage = self.pool.connection_tagged("nh_health_check").await?;
+        let last_proven_l1_batch = storage
+            .nh_dal()
+            .get_last_l1_batch_with_nh_proof()
+            .await
+            .context("get_last_l1_batch_with_nh_proof()")?;
+        let last_attested_l1_batch = storage
+            .nh_dal()
+            .get_last_attested_l1_batch()
+            .await
+            .context("get_last_attested_l1_batch()")?;
+        let oldest_unattested_submission_time = storage
+            .nh_proof_submissions_dal()
+            .get_oldest_unattested_submission_time()
+            .await
+            .context("get_oldest_unattested_submission_time()")?;
+        drop(storage);
+
+        let attestation_lag = match (last_proven_l1_batch, last_attested_l1_batch) {
+            (Some(proven), Some(attested)) => proven.0.saturating_sub(attested.0).into(),
+            (Some(proven), None) => u64::from(proven.0) + 1,
+            (None, _) => 0,
+        };
+        METRICS.attestation_lag.set(attestation_lag);
+
+        let oldest_unattested_submission_age = oldest_unattested_submission_time.map(|time| {
+            let age = chrono::Utc::now().naive_utc() - time;
+            age.to_std().unwrap_or_default()
+        });
+
+        Ok(NhHealthDetails {
+            reachable: connection_result.is_ok(),
+            error: connection_result.err().map(|err| err.to_string()),
+            last_proven_l1_batch,
+            last_attested_l1_batch,
+            attestation_lag,
+            oldest_unattested_submission_age,
+        })
+    }
+}
+
+#[async_trait]
+impl CheckHealth for NhHealthCheck {
+    fn name(&self) -> &'static str {
+        "new_horizen"
+    }
+
+    async fn check_health(&self) -> Health {
+        let details = match self.details().await {
+            Ok(details) => details,
+            Err(err) => {
+                tracing::warn!("Failed checking NH health: {err:#}");
+                return Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                    "error": format!("{err:#}"),
+                }));
+            }
+        };
+
+        let attestations_delayed = details
+            .oldest_unattested_submission_age
+            .map_or(false, |age| age > self.max_attestation_delay);
+        let status = if !details.reachable || attestations_delayed {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_verification_layer::MockProofVerificationLayer;
+
+    #[tokio::test]
+    async fn health_check_without_proofs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let layer = Arc::new(MockProofVerificationLayer::default());
+        let health_check = NhHealthCheck::new(layer, pool, Duration::from_secs(60));
+
+        let health = health_check.check_health().await;
+        assert_eq!(health.status(), HealthStatus::Ready);
+        let health = serde_json::to_value(health).unwrap();
+        assert_eq!(health["details"]["reachable"], true);
+        assert_eq!(health["details"]["attestation_lag"], 0);
+    }
+}
@@ -0,0 +1,37 @@
+//! Metrics for the NewHorizen integration.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+
+/// Kind of a proof submission error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum SubmissionErrorKind {
+    Transport,
+    Rejected,
+    Unsupported,
+    Timeout,
+    Internal,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_nh")]
+pub(super) struct NhMetrics {
+    /// Latency of submitting a batch of proofs until all submissions are included into (possibly non-finalized) NH blocks.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub submission_latency: Histogram<Duration>,
+    /// Time between a proof submission being included into an NH block and the block being finalized.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub finalization_time: Histogram<Duration>,
+    /// Number of failed proof submission attempts.
+    pub submission_failures: Family<SubmissionErrorKind, Counter>,
+    /// Latency of getting an attestation Merkle path from NH.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub get_proof_path_latency: Histogram<Duration>,
+    /// Number of L1 batches with proofs finalized on NH, but not included into an attestation posted on L1.
+    pub attestation_lag: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<NhMetrics> = vise::Global::new();
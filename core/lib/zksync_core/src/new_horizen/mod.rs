@@ -0,0 +1,18 @@
+//! NewHorizen (NH) integration. L1 batch proofs are submitted to the NH chain, which aggregates them
+//! into attestations posted on L1. [`NhClient`] implements the corresponding
+//! [`ProofVerificationLayer`](crate::proof_verification_layer::ProofVerificationLayer).
+
+pub use self::{
+    attestation_reconciler::{AttestationReconciliation, NhAttestationReconciler},
+    client::NhClient,
+    health::NhHealthCheck,
+    proof_submitter::NhProofSubmitter,
+    signer::{create_signer, JsonRpcNhSigner, LocalNhSigner, NhKeystore, NhSigner},
+};
+
+mod attestation_reconciler;
+mod client;
+mod health;
+mod metrics;
+mod proof_submitter;
+mod signer;
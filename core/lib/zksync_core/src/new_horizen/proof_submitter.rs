@@ -0,0 +1,385 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use futures::future;
+use tokio::{sync::watch, time::Instant};
+use zksync_config::configs::NewHorizenConfig;
+use zksync_dal::{
+    nh_proof_submissions_dal::{NhIncludedProofSubmission, NhProofSubmission},
+    ConnectionPool, Core, CoreDal,
+};
+use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::L1BatchNumber;
+
+use super::metrics::{SubmissionErrorKind, METRICS};
+use crate::proof_verification_layer::{
+    FinalizedProofSubmission, PendingProofSubmission, ProofSubmissionStatus,
+    ProofVerificationError, ProofVerificationLayer,
+};
+
+/// Error returned by a single proof submission attempt.
+#[derive(Debug, thiserror::Error)]
+enum SubmissionError {
+    #[error(transparent)]
+    Layer(#[from] ProofVerificationError),
+    #[error("proof submission was not included in a block in {0:?}")]
+    Timeout(Duration),
+    /// Non-retriable error, e.g. an error accessing Postgres.
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl SubmissionError {
+    fn kind(&self) -> SubmissionErrorKind {
+        match self {
+            Self::Layer(ProofVerificationError::Transport(_)) => SubmissionErrorKind::Transport,
+            Self::Layer(ProofVerificationError::Rejected(_)) => SubmissionErrorKind::Rejected,
+            Self::Layer(ProofVerificationError::Unsupported(_)) => SubmissionErrorKind::Unsupported,
+            Self::Timeout(_) => SubmissionErrorKind::Timeout,
+            Self::Internal(_) => SubmissionErrorKind::Internal,
+        }
+    }
+}
+
+/// Component submitting L1 batch proofs queued by the proof data handler to the proof verification layer
+/// (normally, the NH chain).
+///
+/// Queued proofs are submitted in batches ordered by L1 batch number, each proof in a separate transaction;
+/// all transactions in a batch are submitted before waiting for their inclusion. L1 batches in a submission
+/// batch are not necessarily consecutive, since submissions waiting for a retry are skipped until their
+/// retry delay elapses. A submission is tracked until the transaction is included in a finalized block;
+/// after that, the attestation ID and element are saved to Postgres, so that the proof can be sent to L1
+/// once the attestation is posted. Failed submissions are retried with exponential backoff.
+///
+/// Submissions included in a block, but not finalized within the submission timeout, are never resubmitted;
+/// instead, their finality is periodically checked by the stored transaction and block hashes. A submission
+/// is only resubmitted if its block is dropped from the NH chain.
+#[derive(Debug)]
+pub struct NhProofSubmitter {
+    pool: ConnectionPool<Core>,
+    layer: Arc<dyn ProofVerificationLayer>,
+    config: NewHorizenConfig,
+    health_updater: HealthUpdater,
+}
+
+impl NhProofSubmitter {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        layer: Arc<dyn ProofVerificationLayer>,
+        config: NewHorizenConfig,
+    ) -> Self {
+        Self {
+            pool,
+            layer,
+            config,
+            health_updater: ReactiveHealthCheck::new("nh_proof_submitter").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.health_updater.update(HealthStatus::Ready.into());
+        let polling_interval = self.config.submission_polling_interval();
+        while !*stop_receiver.borrow_and_update() {
+            let has_included_proofs = self.check_included_proofs().await?;
+            let has_pending_proofs = self.submit_next_proofs().await?;
+            if !has_included_proofs && !has_pending_proofs {
+                // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+                tokio::time::timeout(polling_interval, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
+        }
+        tracing::info!("Stop signal received, NH proof submitter is shutting down");
+        Ok(())
+    }
+
+    /// Processes the next batch of queued proof submissions. Returns `false` if there are no submissions to process.
+    pub(crate) async fn submit_next_proofs(&self) -> anyhow::Result<bool> {
+        let submissions = self
+            .pool
+            .connection_tagged("nh_proof_submitter")
+            .await?
+            .nh_proof_submissions_dal()
+            .get_next_proof_submissions(
+                self.config.submission_timeout(),
+                self.config.submission_batch_size(),
+            )
+            .await
+            .context("get_next_proof_submissions()")?;
+
+        if submissions.is_empty() {
+            return Ok(false);
+        }
+        self.process_submissions(&submissions).await?;
+        Ok(true)
+    }
+
+    /// Checks finality of the next batch of submissions included in a block. Returns `false` if there are
+    /// no submissions to check.
+    pub(crate) async fn check_included_proofs(&self) -> anyhow::Result<bool> {
+        let submissions = self
+            .pool
+            .connection_tagged("nh_proof_submitter")
+            .await?
+            .nh_proof_submissions_dal()
+            .get_included_proof_submissions(
+                self.config.submission_timeout(),
+                self.config.submission_batch_size(),
+            )
+            .await
+            .context("get_included_proof_submissions()")?;
+
+        if submissions.is_empty() {
+            return Ok(false);
+        }
+        for submission in &submissions {
+            self.check_included_proof(submission).await?;
+        }
+        Ok(true)
+    }
+
+    async fn check_included_proof(
+        &self,
+        submission: &NhIncludedProofSubmission,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = submission.l1_batch_number;
+        let status = self
+            .layer
+            .get_submission_status(submission.tx_hash, submission.block_hash)
+            .await;
+        match status {
+            Ok(ProofSubmissionStatus::Finalized(finalized)) => {
+                self.record_finalization(l1_batch_number, &finalized)
+                    .await?;
+            }
+            Ok(ProofSubmissionStatus::NotFinalized) => {
+                tracing::debug!(
+                    "Proof submission for L1 batch #{l1_batch_number} in block {:?} is not finalized yet",
+                    submission.block_hash
+                );
+            }
+            Ok(ProofSubmissionStatus::Dropped) => {
+                let err = ProofVerificationError::Rejected(format!(
+                    "block {:?} including the submission was dropped",
+                    submission.block_hash
+                ));
+                self.record_failure(l1_batch_number, submission.attempts, &err.into())
+                    .await?;
+            }
+            Err(err) => {
+                // The submission will be checked again once its lease expires.
+                let err = SubmissionError::from(err);
+                METRICS.submission_failures[&err.kind()].inc();
+                tracing::warn!(
+                    "Failed checking proof submission for L1 batch #{l1_batch_number}: {err}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn process_submissions(&self, submissions: &[NhProofSubmission]) -> anyhow::Result<()> {
+        for submission in submissions {
+            tracing::info!(
+                "Submitting proof for L1 batch #{} to NH (attempt #{})",
+                submission.l1_batch_number,
+                submission.attempts
+            );
+        }
+
+        let deadline = Instant::now() + self.config.submission_timeout();
+        let results = match self.submit(submissions, deadline).await {
+            Ok(results) => results,
+            Err(SubmissionError::Internal(err)) => return Err(err),
+            Err(err) => {
+                for submission in submissions {
+                    self.record_failure(submission.l1_batch_number, submission.attempts, &err)
+                        .await?;
+                }
+                return Ok(());
+            }
+        };
+
+        for (submission, result) in submissions.iter().zip(results) {
+            match result {
+                Ok(Some(finalized)) => {
+                    self.record_finalization(submission.l1_batch_number, &finalized)
+                        .await?;
+                }
+                Ok(None) => { /* The submission is included in a block; its finality will be checked later */
+                }
+                Err(SubmissionError::Internal(err)) => return Err(err),
+                Err(err) => {
+                    self.record_failure(submission.l1_batch_number, submission.attempts, &err)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_finalization(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        finalized: &FinalizedProofSubmission,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("nh_proof_submitter").await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .nh_proof_submissions_dal()
+            .mark_proof_submission_as_finalized(
+                l1_batch_number,
+                finalized.block_hash,
+                finalized.attestation_id,
+                finalized.attestation_element,
+            )
+            .await
+            .context("mark_proof_submission_as_finalized()")?;
+        transaction
+            .proof_generation_dal()
+            .save_nh_attestation_element(
+                l1_batch_number,
+                finalized.attestation_id,
+                finalized.attestation_element,
+            )
+            .await
+            .context("save_nh_attestation_element()")?;
+        transaction.commit().await?;
+
+        tracing::info!(
+            "Proof for L1 batch #{l1_batch_number} is finalized on NH in block {:?}; \
+             attestation ID: {}, element: {:?}",
+            finalized.block_hash,
+            finalized.attestation_id,
+            finalized.attestation_element
+        );
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        attempts: u32,
+        err: &SubmissionError,
+    ) -> anyhow::Result<()> {
+        METRICS.submission_failures[&err.kind()].inc();
+
+        let mut storage = self.pool.connection_tagged("nh_proof_submitter").await?;
+        if attempts >= self.config.submission_max_attempts() {
+            tracing::error!(
+                "Failed submitting proof for L1 batch #{l1_batch_number} to NH after {attempts} attempts: {err}"
+            );
+            storage
+                .nh_proof_submissions_dal()
+                .mark_proof_submission_as_failed(l1_batch_number, &err.to_string())
+                .await
+                .context("mark_proof_submission_as_failed()")?;
+        } else {
+            let retry_delay = self.config.submission_retry_delay(attempts);
+            tracing::warn!(
+                "Failed submitting proof for L1 batch #{l1_batch_number} to NH: {err}; \
+                 retrying in {retry_delay:?}"
+            );
+            storage
+                .nh_proof_submissions_dal()
+                .reschedule_proof_submission(l1_batch_number, &err.to_string(), retry_delay)
+                .await
+                .context("reschedule_proof_submission()")?;
+        }
+        Ok(())
+    }
+
+    /// Submits proofs and waits until each submission is finalized or `deadline` is reached.
+    /// Returns a result for each submission, or an error if none of the proofs was submitted.
+    /// A submission result is `Ok(None)` if the submission is included in a block, but not finalized.
+    async fn submit(
+        &self,
+        submissions: &[NhProofSubmission],
+        deadline: Instant,
+    ) -> Result<Vec<Result<Option<FinalizedProofSubmission>, SubmissionError>>, SubmissionError>
+    {
+        let timeout = self.config.submission_timeout();
+        let proofs: Vec<_> = submissions
+            .iter()
+            .map(|submission| {
+                (
+                    submission.proof.as_slice(),
+                    submission.public_inputs.as_slice(),
+                )
+            })
+            .collect();
+
+        let started_at = Instant::now();
+        let pending = tokio::time::timeout_at(deadline, self.layer.submit_proofs(&proofs))
+            .await
+            .map_err(|_| SubmissionError::Timeout(timeout))??;
+        METRICS.submission_latency.observe(started_at.elapsed());
+        if pending.len() != submissions.len() {
+            let err = anyhow::anyhow!(
+                "proof verification layer returned {} submissions for {} proofs",
+                pending.len(),
+                submissions.len()
+            );
+            return Err(err.into());
+        }
+
+        let finalizations = submissions
+            .iter()
+            .zip(pending)
+            .map(|(submission, pending)| {
+                self.wait_for_finalization(submission.l1_batch_number, pending, deadline)
+            });
+        Ok(future::join_all(finalizations).await)
+    }
+
+    /// Marks a submission included in a block and waits until it is finalized or `deadline` is reached.
+    /// Returns `Ok(None)` if the submission wasn't finalized; from this point on, the submission must not be
+    /// resubmitted, and its finality is checked by [`Self::check_included_proofs()`].
+    async fn wait_for_finalization(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        pending: Result<PendingProofSubmission, ProofVerificationError>,
+        deadline: Instant,
+    ) -> Result<Option<FinalizedProofSubmission>, SubmissionError> {
+        let pending = pending?;
+        tracing::debug!(
+            "Proof submission for L1 batch #{l1_batch_number} is included in block {:?}",
+            pending.block_hash
+        );
+        self.pool
+            .connection_tagged("nh_proof_submitter")
+            .await?
+            .nh_proof_submissions_dal()
+            .mark_proof_submission_as_in_block(l1_batch_number, pending.tx_hash, pending.block_hash)
+            .await
+            .context("mark_proof_submission_as_in_block()")?;
+
+        let included_at = Instant::now();
+        let finalized = tokio::time::timeout_at(deadline, pending.wait_for_finalization()).await;
+        match finalized {
+            Ok(Ok(finalized)) => {
+                METRICS.finalization_time.observe(included_at.elapsed());
+                Ok(Some(finalized))
+            }
+            Ok(Err(err)) => {
+                tracing::warn!(
+                    "Failed waiting for finalization of proof submission for L1 batch #{l1_batch_number}: {err}; \
+                     will check its status later"
+                );
+                Ok(None)
+            }
+            Err(_) => {
+                tracing::info!(
+                    "Proof submission for L1 batch #{l1_batch_number} was not finalized in {:?}; \
+                     will check its status later",
+                    self.config.submission_timeout()
+                );
+                Ok(None)
+            }
+        }
+    }
+}
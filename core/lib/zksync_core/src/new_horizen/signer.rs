@@ -0,0 +1,382 @@
+//! Signers for extrinsics submitted to the NH chain.
+
+use std::{fmt, path::Path, str::FromStr, sync::Arc};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::Context as _;
+use async_trait::async_trait;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use subxt::utils::AccountId32;
+use subxt_signer::{
+    sr25519::{self, Keypair},
+    SecretUri,
+};
+use zksync_config::configs::NhSignerConfig;
+
+/// Signer of NH extrinsics using an sr25519 key.
+#[async_trait]
+pub trait NhSigner: 'static + fmt::Debug + Send + Sync {
+    /// Returns the ID of the signing account.
+    fn account_id(&self) -> AccountId32;
+
+    /// Signs the provided payload, returning an sr25519 signature.
+    async fn sign(&self, payload: &[u8]) -> anyhow::Result<[u8; 64]>;
+}
+
+/// Creates a signer based on the provided config.
+pub fn create_signer(config: &NhSignerConfig) -> anyhow::Result<Arc<dyn NhSigner>> {
+    Ok(match config {
+        NhSignerConfig::Local { secret_uri } => {
+            Arc::new(LocalNhSigner::from_secret_uri(secret_uri)?)
+        }
+        NhSignerConfig::Keystore {
+            keystore_path,
+            passphrase,
+        } => Arc::new(LocalNhSigner::from_keystore(keystore_path, passphrase)?),
+        NhSignerConfig::JsonRpc { url, account_id } => Arc::new(JsonRpcNhSigner::new(
+            url.clone(),
+            parse_account_id(account_id)?,
+        )),
+    })
+}
+
+fn parse_keypair(secret_uri: &str) -> anyhow::Result<Keypair> {
+    let secret_uri = SecretUri::from_str(secret_uri).context("invalid NH secret URI")?;
+    Keypair::from_uri(&secret_uri).context("failed creating NH keypair")
+}
+
+/// Parses an account ID either in the SS58 format, or as a 0x-prefixed hex string.
+fn parse_account_id(account_id: &str) -> anyhow::Result<AccountId32> {
+    if let Some(hex_id) = account_id.strip_prefix("0x") {
+        let bytes = hex::decode(hex_id).context("account ID is not a valid hex string")?;
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("hex-encoded account ID must have 32 bytes"))?;
+        Ok(AccountId32(bytes))
+    } else {
+        AccountId32::from_str(account_id).context("account ID is not a valid SS58 address")
+    }
+}
+
+/// Signer holding an sr25519 keypair in memory.
+#[derive(Clone)]
+pub struct LocalNhSigner {
+    keypair: Keypair,
+}
+
+impl fmt::Debug for LocalNhSigner {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("LocalNhSigner")
+            .field("account_id", &self.account_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LocalNhSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+
+    /// Creates a signer from a secret URI, e.g. a mnemonic with an optional derivation path.
+    pub fn from_secret_uri(secret_uri: &str) -> anyhow::Result<Self> {
+        parse_keypair(secret_uri).map(Self::new)
+    }
+
+    /// Creates a signer from an encrypted [`NhKeystore`] file.
+    pub fn from_keystore(path: impl AsRef<Path>, passphrase: &str) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let keystore = NhKeystore::read(path)
+            .with_context(|| format!("failed reading NH keystore at {path:?}"))?;
+        keystore.decrypt(passphrase).map(Self::new)
+    }
+}
+
+#[async_trait]
+impl NhSigner for LocalNhSigner {
+    fn account_id(&self) -> AccountId32 {
+        self.keypair.public_key().to_account_id()
+    }
+
+    async fn sign(&self, payload: &[u8]) -> anyhow::Result<[u8; 64]> {
+        Ok(self.keypair.sign(payload).0)
+    }
+}
+
+/// Keystore holding a secret URI encrypted with a passphrase. The encryption key is derived from the passphrase
+/// using PBKDF2-HMAC-SHA256; the secret URI is encrypted using AES-256-GCM. Binary fields are hex-encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NhKeystore {
+    pub version: u32,
+    /// Account ID corresponding to the encrypted key. Used to check decryption results.
+    pub account_id: String,
+    pub kdf_rounds: u32,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl NhKeystore {
+    const VERSION: u32 = 1;
+    /// Default number of PBKDF2 rounds, as recommended by OWASP for PBKDF2-HMAC-SHA256.
+    pub const DEFAULT_KDF_ROUNDS: u32 = 600_000;
+    const SALT_LEN: usize = 32;
+    const NONCE_LEN: usize = 12;
+
+    fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> Aes256Gcm {
+        let mut key = [0_u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+    }
+
+    /// Encrypts the specified secret URI with a passphrase.
+    pub fn encrypt(secret_uri: &str, passphrase: &str, kdf_rounds: u32) -> anyhow::Result<Self> {
+        let keypair = parse_keypair(secret_uri)?;
+        let mut rng = rand::thread_rng();
+        let salt: [u8; Self::SALT_LEN] = rng.gen();
+        let nonce: [u8; Self::NONCE_LEN] = rng.gen();
+
+        let cipher = Self::derive_key(passphrase, &salt, kdf_rounds);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), secret_uri.as_bytes())
+            .map_err(|_| anyhow::anyhow!("failed encrypting NH secret URI"))?;
+        Ok(Self {
+            version: Self::VERSION,
+            account_id: hex::encode(keypair.public_key().0),
+            kdf_rounds,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the keypair stored in this keystore.
+    pub fn decrypt(&self, passphrase: &str) -> anyhow::Result<Keypair> {
+        anyhow::ensure!(
+            self.version == Self::VERSION,
+            "unsupported NH keystore version: {}",
+            self.version
+        );
+        let salt = hex::decode(&self.salt).context("invalid salt")?;
+        let nonce = hex::decode(&self.nonce).context("invalid nonce")?;
+        anyhow::ensure!(nonce.len() == Self::NONCE_LEN, "invalid nonce length");
+        let ciphertext = hex::decode(&self.ciphertext).context("invalid ciphertext")?;
+
+        let cipher = Self::derive_key(passphrase, &salt, self.kdf_rounds);
+        let secret_uri = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| {
+                anyhow::anyhow!("failed decrypting NH keystore; is the passphrase correct?")
+            })?;
+        let secret_uri =
+            String::from_utf8(secret_uri).context("decrypted secret URI is not UTF-8")?;
+        let keypair = parse_keypair(&secret_uri)?;
+        anyhow::ensure!(
+            hex::encode(keypair.public_key().0) == self.account_id,
+            "decrypted key does not correspond to keystore account {}",
+            self.account_id
+        );
+        Ok(keypair)
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents).context("failed parsing keystore JSON")
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents)
+            .with_context(|| format!("failed writing keystore to {path:?}"))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'static str,
+    params: [&'a str; 2],
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<String>,
+    error: Option<JsonRpcError>,
+}
+
+/// Remote signer accessed via JSON-RPC. The signer is expected to implement the `nh_signPayload` method
+/// accepting the hex-encoded account ID and payload (both 0x-prefixed) and returning a hex-encoded sr25519 signature.
+/// Returned signatures are verified against the account public key.
+#[derive(Debug, Clone)]
+pub struct JsonRpcNhSigner {
+    url: String,
+    account_id: AccountId32,
+    client: reqwest::Client,
+}
+
+impl JsonRpcNhSigner {
+    const SIGN_METHOD: &'static str = "nh_signPayload";
+
+    pub fn new(url: String, account_id: AccountId32) -> Self {
+        Self {
+            url,
+            account_id,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl NhSigner for JsonRpcNhSigner {
+    fn account_id(&self) -> AccountId32 {
+        self.account_id.clone()
+    }
+
+    async fn sign(&self, payload: &[u8]) -> anyhow::Result<[u8; 64]> {
+        let account_id = format!("0x{}", hex::encode(self.account_id.0));
+        let payload_hex = format!("0x{}", hex::encode(payload));
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: Self::SIGN_METHOD,
+            params: [&account_id, &payload_hex],
+        };
+        let response: JsonRpcResponse = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .context("failed sending request to NH signer")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed parsing NH signer response")?;
+
+        if let Some(err) = response.error {
+            anyhow::bail!("NH signer returned error {}: {}", err.code, err.message);
+        }
+        let signature = response.result.context("NH signer returned no signature")?;
+        let signature = hex::decode(signature.strip_prefix("0x").unwrap_or(&signature))
+            .context("NH signer returned non-hex signature")?;
+        let signature: [u8; 64] = signature
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("NH signer returned signature with invalid length"))?;
+
+        let public_key = sr25519::PublicKey(self.account_id.0);
+        anyhow::ensure!(
+            sr25519::verify(&sr25519::Signature(signature), payload, &public_key),
+            "NH signer returned invalid signature for account {}",
+            self.account_id
+        );
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::State, routing::post, Json, Router};
+    use subxt_signer::sr25519::dev;
+
+    use super::*;
+
+    const TEST_KDF_ROUNDS: u32 = 1_000;
+
+    #[tokio::test]
+    async fn local_signer_basics() {
+        let signer = LocalNhSigner::from_secret_uri("//Alice").unwrap();
+        assert_eq!(
+            signer.account_id(),
+            dev::alice().public_key().to_account_id()
+        );
+
+        let signature = signer.sign(b"payload").await.unwrap();
+        let public_key = dev::alice().public_key();
+        assert!(sr25519::verify(
+            &sr25519::Signature(signature),
+            b"payload",
+            &public_key
+        ));
+    }
+
+    #[test]
+    fn keystore_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("keystore.json");
+        let keystore = NhKeystore::encrypt("//Alice", "correct horse", TEST_KDF_ROUNDS).unwrap();
+        keystore.write(&path).unwrap();
+
+        let signer = LocalNhSigner::from_keystore(&path, "correct horse").unwrap();
+        assert_eq!(
+            signer.account_id(),
+            dev::alice().public_key().to_account_id()
+        );
+
+        let err = LocalNhSigner::from_keystore(&path, "wrong horse")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("passphrase"), "{err}");
+    }
+
+    #[test]
+    fn parsing_account_ids() {
+        let alice = dev::alice().public_key().to_account_id();
+        let hex_id = format!("0x{}", hex::encode(alice.0));
+        assert_eq!(parse_account_id(&hex_id).unwrap(), alice);
+        assert_eq!(parse_account_id(&alice.to_string()).unwrap(), alice);
+        parse_account_id("0x0123").unwrap_err();
+    }
+
+    async fn sign_payload(
+        State(keypair): State<Arc<Keypair>>,
+        Json(request): Json<serde_json::Value>,
+    ) -> Json<serde_json::Value> {
+        assert_eq!(request["method"], JsonRpcNhSigner::SIGN_METHOD);
+        let payload = request["params"][1].as_str().unwrap();
+        let payload = hex::decode(payload.strip_prefix("0x").unwrap()).unwrap();
+        let signature = keypair.sign(&payload);
+        Json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": format!("0x{}", hex::encode(signature.0)),
+        }))
+    }
+
+    #[tokio::test]
+    async fn json_rpc_signer() {
+        let app = Router::new()
+            .route("/", post(sign_payload))
+            .with_state(Arc::new(dev::alice()));
+        let server = axum::Server::bind(&([127, 0, 0, 1], 0).into()).serve(app.into_make_service());
+        let url = format!("http://{}/", server.local_addr());
+        tokio::spawn(server);
+
+        let alice = dev::alice().public_key().to_account_id();
+        let signer = JsonRpcNhSigner::new(url.clone(), alice);
+        let signature = signer.sign(b"payload").await.unwrap();
+        let public_key = dev::alice().public_key();
+        assert!(sr25519::verify(
+            &sr25519::Signature(signature),
+            b"payload",
+            &public_key
+        ));
+
+        // The server signs with a key not matching the configured account.
+        let bob = dev::bob().public_key().to_account_id();
+        let signer = JsonRpcNhSigner::new(url, bob);
+        let err = signer.sign(b"payload").await.unwrap_err().to_string();
+        assert!(err.contains("invalid signature"), "{err}");
+    }
+}
@@ -2,10 +2,9 @@ use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
 use axum::{extract::Path, routing::post, Json, Router};
-pub use request_processor::NhClient;
 use request_processor::RequestProcessor;
 use tokio::sync::watch;
-use zksync_config::configs::ProofDataHandlerConfig;
+use zksync_config::configs::{eth_sender::ProofVerificationLayerMode, ProofDataHandlerConfig};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
 use zksync_prover_interface::api::{ProofGenerationDataRequest, SubmitProofRequest};
@@ -16,13 +15,14 @@ pub async fn run_server(
     config: ProofDataHandlerConfig,
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Core>,
-    nh_client: Option<NhClient>,
+    proof_verification_layer: ProofVerificationLayerMode,
     mut stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     tracing::debug!("Starting proof data handler server on {bind_address}");
 
-    let get_proof_gen_processor = RequestProcessor::new(blob_store, pool, config, nh_client);
+    let get_proof_gen_processor =
+        RequestProcessor::new(blob_store, pool, config, proof_verification_layer);
     let submit_proof_processor = get_proof_gen_processor.clone();
     let app = Router::new()
         .route(
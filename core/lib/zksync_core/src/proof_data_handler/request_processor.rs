@@ -7,21 +7,17 @@ use axum::{
     Json,
 };
 use crypto_codegen::serialize_proof;
-use hex::encode;
-use subxt::{
-    ext::scale_value::{Composite, Value},
-    OnlineClient, PolkadotConfig,
+use zksync_config::configs::{
+    eth_sender::ProofVerificationLayerMode, proof_data_handler::SkippedProofMode,
+    ProofDataHandlerConfig,
+};
+use zksync_dal::{
+    proof_generation_dal::SkippedProofHandling, ConnectionPool, Core, CoreDal, SqlxError,
 };
-use subxt_signer::sr25519::Keypair;
-use zksync_config::configs::ProofDataHandlerConfig;
-use zksync_dal::{ConnectionPool, Core, CoreDal, SqlxError};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
-use zksync_prover_interface::{
-    api::{
-        ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
-        SubmitProofRequest, SubmitProofResponse,
-    },
-    outputs::L1BatchProofForL1,
+use zksync_prover_interface::api::{
+    ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
+    SubmitProofRequest, SubmitProofResponse,
 };
 use zksync_types::{
     basic_fri_types::Eip4844Blobs, commitment::serialize_commitments, web3::signing::keccak256,
@@ -29,70 +25,23 @@ use zksync_types::{
 };
 use zksync_utils::{u256_to_bytes_be, u256_to_h256};
 
-#[derive(Clone)]
-pub struct NhClient {
-    client: OnlineClient<PolkadotConfig>,
-    kp: Keypair,
-}
-
-impl NhClient {
-    pub fn new(client: OnlineClient<PolkadotConfig>, kp: Keypair) -> Self {
-        Self { client, kp }
-    }
-
-    async fn submit_proof(
-        &self,
-        proof: [u8; 1408],
-        pi: [u8; 32],
-    ) -> Result<(u64, H256), subxt::Error> {
-        // subxt macro gets confused with VkOrHash type, so we resort to the untyped,
-        // dynamic interface.
-        let submit_proof_tx = subxt::dynamic::tx(
-            "SettlementZksyncPallet",
-            "submit_proof",
-            Composite::Named(vec![
-                (
-                    "vk_or_hash".into(),
-                    Value::variant("Vk", Composite::Unnamed(vec![Value::from_bytes(vec![])])),
-                ),
-                ("proof".into(), Value::from_bytes(&proof)),
-                ("pubs".into(), Value::from_bytes(&pi)),
-            ]),
-        );
-
-        // Submit the submitProof extrinsic, and wait for it to be successful
-        // and in a finalized block. We get back the extrinsic events if all is well.
-        let events = self
-            .client
-            .tx()
-            .sign_and_submit_then_watch_default(&submit_proof_tx, &self.kp)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
-
-        // Find the NewElement event and print it.
-        let new_element_event = events.find_first::<nh::poe::events::NewElement>()?.unwrap();
-        println!("New Element success: {new_element_event:?}");
-        Ok((new_element_event.attestation_id, new_element_event.value))
-    }
-}
+/// Length of a serialized scheduler proof submitted to the proof verification layer.
+const SERIALIZED_PROOF_LEN: usize = 1408;
 
 #[derive(Clone)]
 pub(crate) struct RequestProcessor {
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
-    nh_client: Option<NhClient>,
+    proof_verification_layer: ProofVerificationLayerMode,
 }
 
 pub(crate) enum RequestProcessorError {
     ObjectStore(ObjectStoreError),
     Sqlx(SqlxError),
+    SkippedProofRefused(L1BatchNumber),
 }
 
-#[subxt::subxt(runtime_metadata_path = "../../../etc/nh/metadata.scale")]
-pub mod nh {}
-
 impl IntoResponse for RequestProcessorError {
     fn into_response(self) -> Response {
         let (status_code, message) = match self {
@@ -115,6 +64,13 @@ impl IntoResponse for RequestProcessorError {
                     ),
                 }
             }
+            RequestProcessorError::SkippedProofRefused(l1_batch_number) => {
+                tracing::warn!("Refused to skip proof generation for L1 batch #{l1_batch_number}");
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Proof generation cannot be skipped (L1 batch #{l1_batch_number})"),
+                )
+            }
         };
         (status_code, message).into_response()
     }
@@ -125,13 +81,13 @@ impl RequestProcessor {
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
-        nh_client: Option<NhClient>,
+        proof_verification_layer: ProofVerificationLayerMode,
     ) -> Self {
         Self {
             blob_store,
             pool,
             config,
-            nh_client,
+            proof_verification_layer,
         }
     }
 
@@ -220,7 +176,6 @@ impl RequestProcessor {
         Path(l1_batch_number): Path<u32>,
         Json(payload): Json<SubmitProofRequest>,
     ) -> Result<Json<SubmitProofResponse>, RequestProcessorError> {
-        println!("Submit proof!");
         tracing::info!("Received proof for block number: {:?}", l1_batch_number);
         let l1_batch_number = L1BatchNumber(l1_batch_number);
         match payload {
@@ -302,7 +257,7 @@ impl RequestProcessor {
                     }
                 }
                 let (input, p) = serialize_proof(&proof.scheduler_proof);
-                let proof_bytes: [u8; 1408] = p
+                let proof_bytes: [u8; SERIALIZED_PROOF_LEN] = p
                     .iter()
                     .flat_map(u256_to_bytes_be)
                     .collect::<Vec<u8>>()
@@ -315,71 +270,73 @@ impl RequestProcessor {
                     .try_into()
                     .unwrap();
 
-                println!("PROOF {:?}", encode(proof_bytes.clone()));
-                println!("PI {:?}", encode(pi_bytes.clone()));
+                tracing::debug!(
+                    "Serialized proof for L1 batch #{l1_batch_number}: {}; public inputs: {}",
+                    hex::encode(proof_bytes),
+                    hex::encode(pi_bytes)
+                );
 
-                let (attestation_id, value) = self
-                    .nh_client
-                    .as_ref()
-                    .unwrap()
-                    .submit_proof(proof_bytes, pi_bytes)
+                // The proof is submitted to NH asynchronously by `NhProofSubmitter`.
+                let mut transaction = storage
+                    .start_transaction()
                     .await
-                    .unwrap();
-
-                storage
+                    .map_err(RequestProcessorError::Sqlx)?;
+                transaction
                     .proof_generation_dal()
-                    .save_proof_artifacts_metadata(
-                        l1_batch_number,
-                        &blob_url,
-                        attestation_id,
-                        value,
-                    )
+                    .save_proof_artifacts_metadata(l1_batch_number, &blob_url)
+                    .await
+                    .map_err(RequestProcessorError::Sqlx)?;
+                if self.proof_verification_layer.attests_proofs() {
+                    transaction
+                        .nh_proof_submissions_dal()
+                        .insert_proof_submission(l1_batch_number, &proof_bytes, &pi_bytes)
+                        .await
+                        .map_err(RequestProcessorError::Sqlx)?;
+                }
+                transaction
+                    .commit()
                     .await
                     .map_err(RequestProcessorError::Sqlx)?;
             }
             SubmitProofRequest::SkippedProofGeneration => {
-                println!(
-                    "SubmitProof --> SkippedProofGeneration {}",
-                    l1_batch_number.0
-                );
-
-                // Build a submitProof extrinsic.
-
-                //Mock proof
-                let data = include_bytes!("../../../../../etc/nh/l1_batch_proof_1.bin");
-                let parsed: L1BatchProofForL1 = bincode::deserialize(data.as_slice()).unwrap();
-
-                let (input, p) = serialize_proof(&parsed.scheduler_proof);
-                let proof_bytes: [u8; 1408] = p
-                    .iter()
-                    .flat_map(u256_to_bytes_be)
-                    .collect::<Vec<u8>>()
-                    .try_into()
-                    .unwrap();
-                let pi_bytes: [u8; 32] = input
-                    .iter()
-                    .flat_map(u256_to_bytes_be)
-                    .collect::<Vec<u8>>()
-                    .try_into()
-                    .unwrap();
-
-                println!("PROOF {:?}", encode(proof_bytes.clone()));
-                println!("PI {:?}", encode(pi_bytes.clone()));
-
-                let (attestation_id, value) = self
-                    .nh_client
-                    .as_ref()
-                    .unwrap()
-                    .submit_proof(proof_bytes, pi_bytes)
-                    .await
-                    .unwrap();
+                tracing::info!("Proof generation for L1 batch #{l1_batch_number} is skipped");
+                let fake_proof = match self.config.skipped_proof_mode {
+                    SkippedProofMode::Refuse => {
+                        return Err(RequestProcessorError::SkippedProofRefused(l1_batch_number));
+                    }
+                    SkippedProofMode::FakeProof
+                        if self.proof_verification_layer.attests_proofs() =>
+                    {
+                        Some(fake_proof(l1_batch_number))
+                    }
+                    // Without an attesting layer, the proof would not be submitted anywhere.
+                    SkippedProofMode::FakeProof | SkippedProofMode::Skip => None,
+                };
+                let skipped_proof_handling = if fake_proof.is_some() {
+                    SkippedProofHandling::FakeProof
+                } else {
+                    SkippedProofHandling::NotSubmitted
+                };
 
-                self.pool
-                    .connection()
+                let mut storage = self.pool.connection().await.unwrap();
+                let mut transaction = storage
+                    .start_transaction()
                     .await
-                    .unwrap()
+                    .map_err(RequestProcessorError::Sqlx)?;
+                transaction
                     .proof_generation_dal()
-                    .mark_proof_generation_job_as_skipped(l1_batch_number, attestation_id, value)
+                    .mark_proof_generation_job_as_skipped(l1_batch_number, skipped_proof_handling)
+                    .await
+                    .map_err(RequestProcessorError::Sqlx)?;
+                if let Some((proof_bytes, pi_bytes)) = fake_proof {
+                    transaction
+                        .nh_proof_submissions_dal()
+                        .insert_proof_submission(l1_batch_number, &proof_bytes, &pi_bytes)
+                        .await
+                        .map_err(RequestProcessorError::Sqlx)?;
+                }
+                transaction
+                    .commit()
                     .await
                     .map_err(RequestProcessorError::Sqlx)?;
             }
@@ -388,3 +345,29 @@ impl RequestProcessor {
         Ok(Json(SubmitProofResponse::Success))
     }
 }
+
+/// Generates a deterministic fake proof and public inputs for the specified L1 batch. Such proofs
+/// are only accepted by a test verifier.
+fn fake_proof(l1_batch_number: L1BatchNumber) -> (Vec<u8>, [u8; 32]) {
+    let public_inputs = keccak256(&l1_batch_number.0.to_be_bytes());
+    let proof = (0..(SERIALIZED_PROOF_LEN / 32) as u32)
+        .flat_map(|i| keccak256(&[&public_inputs[..], &i.to_be_bytes()].concat()))
+        .collect();
+    (proof, public_inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_proofs_are_deterministic() {
+        let (proof, public_inputs) = fake_proof(L1BatchNumber(1));
+        assert_eq!(proof.len(), SERIALIZED_PROOF_LEN);
+        assert_eq!(fake_proof(L1BatchNumber(1)), (proof.clone(), public_inputs));
+
+        let (other_proof, other_public_inputs) = fake_proof(L1BatchNumber(2));
+        assert_ne!(other_proof, proof);
+        assert_ne!(other_public_inputs, public_inputs);
+    }
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use zksync_types::H256;
+
+use super::{
+    AttestationPath, PendingProofSubmission, ProofSubmissionStatus, ProofVerificationError,
+    ProofVerificationLayer,
+};
+
+/// Proof verification layer sending proofs directly to L1, like in vanilla zkSync. Proofs are loaded
+/// from the object store by `Aggregator` and verified by the L1 verifier contract.
+#[derive(Debug, Default)]
+pub struct L1ProofVerificationLayer;
+
+#[async_trait]
+impl ProofVerificationLayer for L1ProofVerificationLayer {
+    fn attests_proofs(&self) -> bool {
+        false
+    }
+
+    async fn check_connection(&self) -> Result<(), ProofVerificationError> {
+        Ok(())
+    }
+
+    async fn submit_proof(
+        &self,
+        _proof: &[u8],
+        _public_inputs: &[u8],
+    ) -> Result<PendingProofSubmission, ProofVerificationError> {
+        Err(ProofVerificationError::Unsupported("L1"))
+    }
+
+    async fn get_submission_status(
+        &self,
+        _tx_hash: H256,
+        _block_hash: H256,
+    ) -> Result<ProofSubmissionStatus, ProofVerificationError> {
+        Err(ProofVerificationError::Unsupported("L1"))
+    }
+
+    async fn get_attestation_path(
+        &self,
+        _attestation_id: u64,
+        _attestation_element: H256,
+    ) -> Result<Option<AttestationPath>, ProofVerificationError> {
+        Err(ProofVerificationError::Unsupported("L1"))
+    }
+}
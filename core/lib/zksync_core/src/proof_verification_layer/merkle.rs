@@ -0,0 +1,166 @@
+//! Binary Merkle tree used by NH attestations. Leaves are Keccak-256 hashes of attestation elements;
+//! a node without a sibling is promoted to the next level as is.
+
+use zksync_types::{web3::signing::keccak256, H256};
+
+use super::AttestationPath;
+
+fn hash_leaf(element: &H256) -> H256 {
+    H256(keccak256(element.as_bytes()))
+}
+
+fn hash_nodes(lhs: &H256, rhs: &H256) -> H256 {
+    let mut buffer = [0_u8; 64];
+    buffer[..32].copy_from_slice(lhs.as_bytes());
+    buffer[32..].copy_from_slice(rhs.as_bytes());
+    H256(keccak256(&buffer))
+}
+
+fn next_level(level: &[H256]) -> Vec<H256> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [lhs, rhs] => hash_nodes(lhs, rhs),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Computes the attestation root for the specified elements. Returns zero hash for an empty attestation.
+pub(crate) fn attestation_root(elements: &[H256]) -> H256 {
+    let mut level: Vec<_> = elements.iter().map(hash_leaf).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// Computes the Merkle path for the element with the specified index.
+pub(crate) fn attestation_path(elements: &[H256], index: usize) -> AttestationPath {
+    assert!(index < elements.len(), "element index is out of bounds");
+
+    let mut level: Vec<_> = elements.iter().map(hash_leaf).collect();
+    let mut position = index;
+    let mut merkle_path = vec![];
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(position ^ 1) {
+            merkle_path.push(*sibling);
+        }
+        level = next_level(&level);
+        position /= 2;
+    }
+    AttestationPath {
+        merkle_path,
+        leaf_count: elements.len().try_into().expect("too many elements"),
+        index: index.try_into().expect("too many elements"),
+    }
+}
+
+/// Computes the attestation root from the element and its Merkle path. Returns `None` if the path is malformed,
+/// e.g. has an unexpected length.
+pub(crate) fn root_from_path(element: &H256, path: &AttestationPath) -> Option<H256> {
+    if path.index >= path.leaf_count {
+        return None;
+    }
+
+    let mut hash = hash_leaf(element);
+    let mut position = path.index;
+    let mut width = path.leaf_count;
+    let mut siblings = path.merkle_path.iter();
+    while width > 1 {
+        // The node has a sibling unless it's the last node on an odd-width level.
+        if position ^ 1 < width {
+            let sibling = siblings.next()?;
+            hash = if position % 2 == 0 {
+                hash_nodes(&hash, sibling)
+            } else {
+                hash_nodes(sibling, &hash)
+            };
+        }
+        position /= 2;
+        width = (width + 1) / 2;
+    }
+    if siblings.next().is_some() {
+        return None;
+    }
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_for_small_attestations() {
+        assert_eq!(attestation_root(&[]), H256::zero());
+
+        let elements: Vec<_> = (1..=3).map(H256::repeat_byte).collect();
+        assert_eq!(attestation_root(&elements[..1]), hash_leaf(&elements[0]));
+
+        let leaves: Vec<_> = elements.iter().map(hash_leaf).collect();
+        let expected_root = hash_nodes(&hash_nodes(&leaves[0], &leaves[1]), &leaves[2]);
+        assert_eq!(attestation_root(&elements), expected_root);
+    }
+
+    #[test]
+    fn paths_skip_promoted_nodes() {
+        let elements: Vec<_> = (1..=5).map(H256::repeat_byte).collect();
+        let leaves: Vec<_> = elements.iter().map(hash_leaf).collect();
+
+        let path = attestation_path(&elements, 4);
+        assert_eq!(path.leaf_count, 5);
+        assert_eq!(path.index, 4);
+        // The last leaf is promoted twice, so it only has a sibling on the top level.
+        let left_subtree = hash_nodes(
+            &hash_nodes(&leaves[0], &leaves[1]),
+            &hash_nodes(&leaves[2], &leaves[3]),
+        );
+        assert_eq!(path.merkle_path, [left_subtree]);
+
+        let path = attestation_path(&elements, 1);
+        assert_eq!(
+            path.merkle_path,
+            [leaves[0], hash_nodes(&leaves[2], &leaves[3]), leaves[4]]
+        );
+    }
+
+    #[test]
+    fn verifying_paths() {
+        for leaf_count in 1..=9_u8 {
+            let elements: Vec<_> = (1..=leaf_count).map(H256::repeat_byte).collect();
+            let root = attestation_root(&elements);
+            for (index, element) in elements.iter().enumerate() {
+                let path = attestation_path(&elements, index);
+                assert_eq!(root_from_path(element, &path), Some(root), "{path:?}");
+                assert_ne!(root_from_path(&H256::zero(), &path), Some(root));
+            }
+        }
+    }
+
+    #[test]
+    fn verifying_malformed_paths() {
+        let elements: Vec<_> = (1..=5).map(H256::repeat_byte).collect();
+        let root = attestation_root(&elements);
+        let path = attestation_path(&elements, 1);
+
+        let mut tampered_path = path.clone();
+        tampered_path.merkle_path[1] = H256::zero();
+        assert_ne!(root_from_path(&elements[1], &tampered_path), Some(root));
+
+        let mut wrong_index_path = path.clone();
+        wrong_index_path.index = 0;
+        assert_ne!(root_from_path(&elements[1], &wrong_index_path), Some(root));
+
+        let mut short_path = path.clone();
+        short_path.merkle_path.pop();
+        assert_eq!(root_from_path(&elements[1], &short_path), None);
+
+        let mut long_path = path.clone();
+        long_path.merkle_path.push(H256::zero());
+        assert_eq!(root_from_path(&elements[1], &long_path), None);
+
+        let out_of_bounds_path = AttestationPath { index: 5, ..path };
+        assert_eq!(root_from_path(&elements[1], &out_of_bounds_path), None);
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use zksync_eth_client::clients::MockEthereum;
+use zksync_types::{
+    web3::{signing::keccak256, types::Log},
+    Address, H256,
+};
+
+use super::{
+    merkle, AttestationPath, FinalizedProofSubmission, PendingProofSubmission,
+    ProofSubmissionStatus, ProofVerificationError, ProofVerificationLayer,
+};
+use crate::eth_watch::event_processors::nh::ATTESTATION_POSTED_SIGNATURE;
+
+/// Attestation posted by [`MockProofVerificationLayer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockAttestation {
+    pub id: u64,
+    pub root: H256,
+    pub elements: Vec<H256>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    block_number: u64,
+    posted_attestations: Vec<Vec<H256>>,
+    /// Elements of the attestation that is not posted yet. Its ID is equal to the number of posted attestations.
+    pending_elements: Vec<H256>,
+    /// Accepted submissions keyed by the transaction hash.
+    submissions: HashMap<H256, FinalizedProofSubmission>,
+}
+
+/// In-process proof verification layer. Accepts all submitted proofs, finalizing them immediately,
+/// and aggregates them into attestations posted via [`Self::post_attestation()`].
+///
+/// This layer is test-only and cannot be selected via `ProofVerificationLayerMode`: attestations are only posted
+/// on a mock L1 client (see [`Self::with_l1_client()`]), so with a real L1, proofs would never be sent.
+#[derive(Debug, Default)]
+pub struct MockProofVerificationLayer {
+    state: Mutex<MockState>,
+    /// Mock L1 client and the NH verifier contract address used to emit `AttestationPosted` events.
+    l1: Option<(Arc<MockEthereum>, Address)>,
+}
+
+impl MockProofVerificationLayer {
+    /// Makes the layer emit an `AttestationPosted` event from the specified contract on the mock L1
+    /// each time an attestation is posted.
+    pub fn with_l1_client(mut self, client: Arc<MockEthereum>, contract_address: Address) -> Self {
+        self.l1 = Some((client, contract_address));
+        self
+    }
+
+    /// Computes the attestation element for the specified proof.
+    pub fn attestation_element(proof: &[u8], public_inputs: &[u8]) -> H256 {
+        H256(keccak256(&[proof, public_inputs].concat()))
+    }
+
+    /// Posts an attestation including all proofs submitted since the previous attestation.
+    /// Returns `None` if no proofs were submitted.
+    pub fn post_attestation(&self) -> Option<MockAttestation> {
+        let mut state = self.state.lock().unwrap();
+        if state.pending_elements.is_empty() {
+            return None;
+        }
+        let elements = std::mem::take(&mut state.pending_elements);
+        let attestation = MockAttestation {
+            id: state.posted_attestations.len() as u64,
+            root: merkle::attestation_root(&elements),
+            elements: elements.clone(),
+        };
+        state.posted_attestations.push(elements);
+
+        if let Some((client, contract_address)) = &self.l1 {
+            client.emit_log(Log {
+                address: *contract_address,
+                topics: vec![
+                    ATTESTATION_POSTED_SIGNATURE,
+                    H256::from_low_u64_be(attestation.id),
+                    attestation.root,
+                ],
+                data: vec![].into(),
+                block_hash: None,
+                block_number: None,
+                transaction_hash: None,
+                transaction_index: None,
+                log_index: None,
+                transaction_log_index: None,
+                log_type: None,
+                removed: None,
+            });
+        }
+        Some(attestation)
+    }
+}
+
+#[async_trait]
+impl ProofVerificationLayer for MockProofVerificationLayer {
+    fn attests_proofs(&self) -> bool {
+        true
+    }
+
+    async fn check_connection(&self) -> Result<(), ProofVerificationError> {
+        Ok(())
+    }
+
+    async fn submit_proof(
+        &self,
+        proof: &[u8],
+        public_inputs: &[u8],
+    ) -> Result<PendingProofSubmission, ProofVerificationError> {
+        let attestation_element = Self::attestation_element(proof, public_inputs);
+        let mut state = self.state.lock().unwrap();
+        state.block_number += 1;
+        let block_hash = H256::from_low_u64_be(state.block_number);
+        state.pending_elements.push(attestation_element);
+
+        let finalized = FinalizedProofSubmission {
+            block_hash,
+            attestation_id: state.posted_attestations.len() as u64,
+            attestation_element,
+        };
+        let tx_hash = H256(keccak256(attestation_element.as_bytes()));
+        state.submissions.insert(tx_hash, finalized.clone());
+        Ok(PendingProofSubmission::new(
+            tx_hash,
+            block_hash,
+            async move { Ok(finalized) },
+        ))
+    }
+
+    async fn get_submission_status(
+        &self,
+        tx_hash: H256,
+        block_hash: H256,
+    ) -> Result<ProofSubmissionStatus, ProofVerificationError> {
+        let state = self.state.lock().unwrap();
+        Ok(match state.submissions.get(&tx_hash) {
+            Some(finalized) if finalized.block_hash == block_hash => {
+                ProofSubmissionStatus::Finalized(finalized.clone())
+            }
+            _ => ProofSubmissionStatus::Dropped,
+        })
+    }
+
+    async fn get_attestation_path(
+        &self,
+        attestation_id: u64,
+        attestation_element: H256,
+    ) -> Result<Option<AttestationPath>, ProofVerificationError> {
+        let state = self.state.lock().unwrap();
+        let Some(elements) = usize::try_from(attestation_id)
+            .ok()
+            .and_then(|id| state.posted_attestations.get(id))
+        else {
+            return Ok(None);
+        };
+        let index = elements
+            .iter()
+            .position(|&elem| elem == attestation_element);
+        Ok(index.map(|index| merkle::attestation_path(elements, index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_eth_client::EthInterface;
+    use zksync_types::{l1::NHAttestation, web3::types::FilterBuilder};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn mock_layer_basics() {
+        let layer = MockProofVerificationLayer::default();
+        assert_eq!(layer.post_attestation(), None);
+
+        let mut elements = vec![];
+        for i in 0..3_u8 {
+            let submission = layer.submit_proof(&[i; 32], &[i; 4]).await.unwrap();
+            let finalized = submission.wait_for_finalization().await.unwrap();
+            assert_eq!(finalized.attestation_id, 0);
+            assert_eq!(
+                finalized.attestation_element,
+                MockProofVerificationLayer::attestation_element(&[i; 32], &[i; 4])
+            );
+            elements.push(finalized.attestation_element);
+        }
+
+        // The path is not available until the attestation is posted.
+        let path = layer.get_attestation_path(0, elements[1]).await.unwrap();
+        assert_eq!(path, None);
+
+        let attestation = layer.post_attestation().unwrap();
+        assert_eq!(attestation.id, 0);
+        assert_eq!(attestation.elements, elements);
+        assert_eq!(attestation.root, merkle::attestation_root(&elements));
+
+        let path = layer
+            .get_attestation_path(0, elements[1])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, merkle::attestation_path(&elements, 1));
+        let path = layer.get_attestation_path(0, H256::zero()).await.unwrap();
+        assert_eq!(path, None);
+
+        let submission = layer.submit_proof(&[10; 32], &[]).await.unwrap();
+        let (tx_hash, block_hash) = (submission.tx_hash, submission.block_hash);
+        let finalized = submission.wait_for_finalization().await.unwrap();
+        assert_eq!(finalized.attestation_id, 1);
+
+        let status = layer
+            .get_submission_status(tx_hash, block_hash)
+            .await
+            .unwrap();
+        assert_eq!(status, ProofSubmissionStatus::Finalized(finalized));
+        let status = layer
+            .get_submission_status(H256::zero(), block_hash)
+            .await
+            .unwrap();
+        assert_eq!(status, ProofSubmissionStatus::Dropped);
+    }
+
+    #[tokio::test]
+    async fn submitting_multiple_proofs() {
+        let layer = MockProofVerificationLayer::default();
+        let proofs = [([1_u8; 32], [1_u8; 4]), ([2; 32], [2; 4])];
+        let proofs: Vec<_> = proofs
+            .iter()
+            .map(|(proof, inputs)| (proof.as_slice(), inputs.as_slice()))
+            .collect();
+        let submissions = layer.submit_proofs(&proofs).await.unwrap();
+        assert_eq!(submissions.len(), 2);
+
+        for (submission, (proof, inputs)) in submissions.into_iter().zip(proofs) {
+            let finalized = submission.unwrap().wait_for_finalization().await.unwrap();
+            assert_eq!(
+                finalized.attestation_element,
+                MockProofVerificationLayer::attestation_element(proof, inputs)
+            );
+        }
+        let attestation = layer.post_attestation().unwrap();
+        assert_eq!(attestation.elements.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn posting_attestations_on_l1() {
+        let l1_client = Arc::new(MockEthereum::default());
+        let contract_address = Address::repeat_byte(0x23);
+        let layer = MockProofVerificationLayer::default()
+            .with_l1_client(l1_client.clone(), contract_address);
+
+        layer.submit_proof(&[1; 32], &[]).await.unwrap();
+        let attestation = layer.post_attestation().unwrap();
+
+        let logs = l1_client
+            .logs(FilterBuilder::default().build(), "test")
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, contract_address);
+        let parsed = NHAttestation::try_from(logs[0].clone()).unwrap();
+        assert_eq!(parsed.attestation_id, attestation.id.into());
+        assert_eq!(parsed.proofs_attestation, attestation.root);
+    }
+}
@@ -0,0 +1,166 @@
+//! Abstraction over the layer verifying L1 batch proofs before they are sent to L1.
+//!
+//! With NewHorizen, proofs are submitted to the NH chain, which aggregates them into attestations
+//! posted on L1; L1 then receives a Merkle path proving that the batch proof is included into
+//! an attestation. Alternatively, proofs can be sent to L1 directly, like in vanilla zkSync.
+
+use std::{fmt, future::Future};
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use zksync_types::H256;
+
+pub use self::{
+    l1::L1ProofVerificationLayer,
+    mock::{MockAttestation, MockProofVerificationLayer},
+};
+
+mod l1;
+mod merkle;
+mod mock;
+
+/// Errors returned by a [`ProofVerificationLayer`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProofVerificationError {
+    /// Error communicating with the layer, e.g. a network error. Such errors are retriable.
+    #[error("proof verification layer transport error: {0}")]
+    Transport(#[source] anyhow::Error),
+    /// Proof submission was rejected by the layer.
+    #[error("proof submission was rejected: {0}")]
+    Rejected(String),
+    /// Operation is not supported by the layer, e.g. submitting proofs for [`L1ProofVerificationLayer`].
+    #[error("operation is not supported by {0} proof verification layer")]
+    Unsupported(&'static str),
+}
+
+/// Proof submission that is accepted by the layer, but not finalized yet.
+pub struct PendingProofSubmission {
+    /// Hash of the submission transaction.
+    pub tx_hash: H256,
+    /// Hash of the (possibly non-finalized) block including the submission.
+    pub block_hash: H256,
+    finalization: BoxFuture<'static, Result<FinalizedProofSubmission, ProofVerificationError>>,
+}
+
+impl fmt::Debug for PendingProofSubmission {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("PendingProofSubmission")
+            .field("tx_hash", &self.tx_hash)
+            .field("block_hash", &self.block_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PendingProofSubmission {
+    pub fn new(
+        tx_hash: H256,
+        block_hash: H256,
+        finalization: impl Future<Output = Result<FinalizedProofSubmission, ProofVerificationError>>
+            + Send
+            + 'static,
+    ) -> Self {
+        Self {
+            tx_hash,
+            block_hash,
+            finalization: Box::pin(finalization),
+        }
+    }
+
+    /// Waits until the submission is finalized by the layer.
+    pub async fn wait_for_finalization(
+        self,
+    ) -> Result<FinalizedProofSubmission, ProofVerificationError> {
+        self.finalization.await
+    }
+}
+
+/// Finalized proof submission. The proof is included into the specified attestation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinalizedProofSubmission {
+    /// Hash of the finalized block including the submission.
+    pub block_hash: H256,
+    pub attestation_id: u64,
+    /// Element of the attestation Merkle tree corresponding to the proof.
+    pub attestation_element: H256,
+}
+
+/// Status of a proof submission previously included into a block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofSubmissionStatus {
+    /// Submission is included into a block that is not finalized yet.
+    NotFinalized,
+    /// Submission is included into a finalized block.
+    Finalized(FinalizedProofSubmission),
+    /// Submission is no longer included into the chain (e.g., its block was reorged out), so it should be resubmitted.
+    Dropped,
+}
+
+/// Merkle path proving inclusion of an element into an attestation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestationPath {
+    pub merkle_path: Vec<H256>,
+    /// Number of leaves in the attestation Merkle tree.
+    pub leaf_count: u32,
+    /// Zero-based index of the element in the attestation Merkle tree.
+    pub index: u32,
+}
+
+impl AttestationPath {
+    /// Computes the attestation root for the specified element using this path. Returns `None`
+    /// if the path is malformed.
+    pub fn compute_root(&self, attestation_element: H256) -> Option<H256> {
+        merkle::root_from_path(&attestation_element, self)
+    }
+}
+
+/// Layer verifying L1 batch proofs before they are sent to L1.
+#[async_trait]
+pub trait ProofVerificationLayer: 'static + fmt::Debug + Send + Sync {
+    /// Returns `true` if proofs are verified by this layer and L1 receives attestation inclusion paths,
+    /// or `false` if proofs are sent to L1 directly.
+    fn attests_proofs(&self) -> bool;
+
+    /// Checks that the layer is reachable.
+    async fn check_connection(&self) -> Result<(), ProofVerificationError>;
+
+    /// Submits an L1 batch proof together with its public inputs. Returns once the submission
+    /// is included into a (possibly non-finalized) block.
+    async fn submit_proof(
+        &self,
+        proof: &[u8],
+        public_inputs: &[u8],
+    ) -> Result<PendingProofSubmission, ProofVerificationError>;
+
+    /// Submits several proofs given as `(proof, public_inputs)` pairs. On success, returns a result
+    /// for each proof in the same order as `proofs`; an error is returned if none of the proofs could be submitted.
+    ///
+    /// The default implementation calls [`Self::submit_proof()`] for each proof sequentially.
+    async fn submit_proofs(
+        &self,
+        proofs: &[(&[u8], &[u8])],
+    ) -> Result<Vec<Result<PendingProofSubmission, ProofVerificationError>>, ProofVerificationError>
+    {
+        let mut results = Vec::with_capacity(proofs.len());
+        for &(proof, public_inputs) in proofs {
+            results.push(self.submit_proof(proof, public_inputs).await);
+        }
+        Ok(results)
+    }
+
+    /// Checks the status of a submission previously included into a block, identified by its transaction
+    /// and block hashes as returned in [`PendingProofSubmission`].
+    async fn get_submission_status(
+        &self,
+        tx_hash: H256,
+        block_hash: H256,
+    ) -> Result<ProofSubmissionStatus, ProofVerificationError>;
+
+    /// Returns the Merkle path for the specified attestation element, or `None` if the element
+    /// is not included into the attestation.
+    async fn get_attestation_path(
+        &self,
+        attestation_id: u64,
+        attestation_element: H256,
+    ) -> Result<Option<AttestationPath>, ProofVerificationError>;
+}
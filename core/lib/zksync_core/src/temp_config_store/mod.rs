@@ -8,8 +8,9 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, NewHorizenConfig,
-        PrometheusConfig, ProofDataHandlerConfig, WitnessGeneratorConfig,
+        DADispatcherConfig, FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig,
+        NewHorizenConfig, PrometheusConfig, ProofDataHandlerConfig, RemoteSignerConfig,
+        WitnessGeneratorConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
     GasAdjusterConfig, ObjectStoreConfig, PostgresConfig,
@@ -73,6 +74,8 @@ pub struct TempConfigStore {
     pub object_store_config: Option<ObjectStoreConfig>,
     pub consensus_config: Option<consensus::Config>,
     pub new_horizen_config: Option<NewHorizenConfig>,
+    pub da_dispatcher_config: Option<DADispatcherConfig>,
+    pub remote_signer_config: Option<RemoteSignerConfig>,
 }
 
 impl ProtoFmt for TempConfigStore {
@@ -114,6 +117,8 @@ impl ProtoFmt for TempConfigStore {
             object_store_config: read_optional_repr(&r.object_store).context("object_store")?,
             consensus_config: read_optional(&r.consensus).context("consensus")?,
             new_horizen_config: read_optional_repr(&r.new_horizen).context("new_horizen")?,
+            da_dispatcher_config: read_optional_repr(&r.da_dispatcher).context("da_dispatcher")?,
+            remote_signer_config: read_optional_repr(&r.remote_signer).context("remote_signer")?,
         })
     }
 
@@ -158,6 +163,8 @@ impl ProtoFmt for TempConfigStore {
             object_store: self.object_store_config.as_ref().map(ProtoRepr::build),
             consensus: self.consensus_config.as_ref().map(ProtoFmt::build),
             new_horizen: self.new_horizen_config.as_ref().map(ProtoRepr::build),
+            da_dispatcher: self.da_dispatcher_config.as_ref().map(ProtoRepr::build),
+            remote_signer: self.remote_signer_config.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -34,6 +34,8 @@ impl Distribution<TempConfigStore> for EncodeDist {
             object_store_config: self.sample(rng),
             consensus_config: self.sample(rng),
             new_horizen_config: None,
+            da_dispatcher_config: self.sample(rng),
+            remote_signer_config: self.sample(rng),
         }
     }
 }
@@ -11,8 +11,8 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, ObservabilityConfig,
-        ProofDataHandlerConfig,
+        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, NewHorizenConfig,
+        ObservabilityConfig, ProofDataHandlerConfig, RemoteSignerConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
     GasAdjusterConfig, ObjectStoreConfig, PostgresConfig,
@@ -36,6 +36,7 @@ use zksync_node_framework::{
         house_keeper::HouseKeeperLayer,
         l1_gas::SequencerL1GasLayer,
         metadata_calculator::MetadataCalculatorLayer,
+        new_horizen_client::NewHorizenClientLayer,
         object_store::ObjectStoreLayer,
         pk_signing_eth_client::PKSigningEthClientLayer,
         pools_layer::PoolsLayerBuilder,
@@ -84,17 +85,21 @@ impl MainNodeBuilder {
     }
 
     fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(PKSigningEthClientLayer::new(
+        let mut layer = PKSigningEthClientLayer::new(
             ETHSenderConfig::from_env()?,
             ContractsConfig::from_env()?,
             ETHClientConfig::from_env()?,
-        ));
+        );
+        if let Ok(remote_signer_config) = RemoteSignerConfig::from_env() {
+            layer = layer.with_remote_signer(remote_signer_config);
+        }
+        self.node.add_layer(layer);
         Ok(self)
     }
 
     fn add_query_eth_client_layer(mut self) -> anyhow::Result<Self> {
         let eth_client_config = ETHClientConfig::from_env()?;
-        let query_eth_client_layer = QueryEthClientLayer::new(eth_client_config.web3_url);
+        let query_eth_client_layer = QueryEthClientLayer::new(eth_client_config);
         self.node.add_layer(query_eth_client_layer);
         Ok(self)
     }
@@ -126,8 +131,15 @@ impl MainNodeBuilder {
             &merkle_tree_env_config,
             &operations_manager_env_config,
         );
-        self.node
-            .add_layer(MetadataCalculatorLayer(metadata_calculator_config));
+        let mut metadata_calculator_layer =
+            MetadataCalculatorLayer::new(metadata_calculator_config);
+        if let Some(checkpoint_path) = &merkle_tree_env_config.checkpoint_path {
+            metadata_calculator_layer = metadata_calculator_layer.with_checkpoints(
+                checkpoint_path.into(),
+                merkle_tree_env_config.checkpoints_in_object_store,
+            );
+        }
+        self.node.add_layer(metadata_calculator_layer);
         Ok(self)
     }
 
@@ -156,9 +168,23 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_new_horizen_client_layer(mut self) -> anyhow::Result<Self> {
+        // NH is only required if proofs are attested by it.
+        if ETHSenderConfig::from_env()?
+            .sender
+            .proof_verification_layer
+            .attests_proofs()
+        {
+            let config = NewHorizenConfig::from_env()?;
+            self.node.add_layer(NewHorizenClientLayer::new(config));
+        }
+        Ok(self)
+    }
+
     fn add_proof_data_handler_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(ProofDataHandlerLayer::new(
             ProofDataHandlerConfig::from_env()?,
+            ETHSenderConfig::from_env()?.sender.proof_verification_layer,
         ));
         Ok(self)
     }
@@ -177,6 +203,11 @@ impl MainNodeBuilder {
             factory_deps_cache_size: rpc_config.factory_deps_cache_size() as u64,
             initial_writes_cache_size: rpc_config.initial_writes_cache_size() as u64,
             latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
+            disk_cache_path: rpc_config
+                .vm_storage_disk_cache_path
+                .clone()
+                .map(Into::into),
+            disk_cache_size: rpc_config.vm_storage_disk_cache_size() as u64,
         };
 
         // On main node we always use master pool sink.
@@ -268,13 +299,11 @@ impl MainNodeBuilder {
         let state_keeper_config = StateKeeperConfig::from_env()?;
         let eth_sender_config = ETHSenderConfig::from_env()?;
         let contracts_config = ContractsConfig::from_env()?;
-        let eth_client_config = ETHClientConfig::from_env()?;
         let network_config = NetworkConfig::from_env()?;
 
         self.node.add_layer(EthSenderLayer::new(
             eth_sender_config,
             contracts_config,
-            eth_client_config,
             network_config,
             state_keeper_config.l1_batch_commit_data_generator_mode,
         ));
@@ -347,6 +376,7 @@ fn main() -> anyhow::Result<()> {
         .add_state_keeper_layer()?
         .add_eth_watch_layer()?
         .add_pk_signing_client_layer()?
+        .add_new_horizen_client_layer()?
         .add_eth_sender_layer()?
         .add_proof_data_handler_layer()?
         .add_healthcheck_layer()?
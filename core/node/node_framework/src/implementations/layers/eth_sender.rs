@@ -1,25 +1,30 @@
 use std::sync::Arc;
 
-use zksync_circuit_breaker::l1_txs::FailedL1TransactionChecker;
+use zksync_circuit_breaker::{
+    attestation_paths::AttestationPathMismatchChecker, l1_txs::FailedL1TransactionChecker,
+};
 use zksync_config::configs::{
     chain::{L1BatchCommitDataGeneratorMode, NetworkConfig},
-    eth_sender::ETHSenderConfig,
-    ContractsConfig, ETHClientConfig,
+    eth_sender::{ETHSenderConfig, ProofVerificationLayerMode},
+    ContractsConfig,
 };
-use zksync_core::eth_sender::{
-    l1_batch_commit_data_generator::{
-        L1BatchCommitDataGenerator, RollupModeL1BatchCommitDataGenerator,
-        ValidiumModeL1BatchCommitDataGenerator,
+use zksync_core::{
+    eth_sender::{
+        l1_batch_commit_data_generator::{
+            L1BatchCommitDataGenerator, RollupModeL1BatchCommitDataGenerator,
+            ValidiumModeL1BatchCommitDataGenerator,
+        },
+        Aggregator, EthTxAggregator, EthTxManager,
     },
-    Aggregator, EthTxAggregator, EthTxManager,
+    proof_verification_layer::{L1ProofVerificationLayer, ProofVerificationLayer},
 };
-use zksync_eth_client::{clients::PKSigningClient, BoundEthInterface};
 
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
-        eth_interface::BoundEthInterfaceResource,
+        eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
         l1_tx_params::L1TxParamsResource,
+        new_horizen::NhClientResource,
         object_store::ObjectStoreResource,
         pools::{MasterPoolResource, ReplicaPoolResource},
     },
@@ -32,27 +37,33 @@ use crate::{
 pub struct EthSenderLayer {
     eth_sender_config: ETHSenderConfig,
     contracts_config: ContractsConfig,
-    eth_client_config: ETHClientConfig,
     network_config: NetworkConfig,
     l1_batch_commit_data_generator_mode: L1BatchCommitDataGeneratorMode,
+    da_inclusion_required: bool,
 }
 
 impl EthSenderLayer {
     pub fn new(
         eth_sender_config: ETHSenderConfig,
         contracts_config: ContractsConfig,
-        eth_client_config: ETHClientConfig,
         network_config: NetworkConfig,
         l1_batch_commit_data_generator_mode: L1BatchCommitDataGeneratorMode,
     ) -> Self {
         Self {
             eth_sender_config,
             contracts_config,
-            eth_client_config,
             network_config,
             l1_batch_commit_data_generator_mode,
+            da_inclusion_required: false,
         }
     }
+
+    /// Makes the aggregator commit L1 batches only after their pubdata is confirmed to be available
+    /// on the data availability layer. Should be set if the DA dispatcher is run in Validium mode.
+    pub fn with_da_inclusion_required(mut self, da_inclusion_required: bool) -> Self {
+        self.da_inclusion_required = da_inclusion_required;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -73,13 +84,17 @@ impl WiringLayer for EthSenderLayer {
 
         let object_store = context.get_resource::<ObjectStoreResource>().await?.0;
 
+        // The blobs operator account is optional, so the resource may be missing.
+        let eth_client_blobs = context
+            .get_resource::<BoundEthInterfaceForBlobsResource>()
+            .await
+            .ok()
+            .map(|resource| resource.0);
+
         // Create and add tasks.
-        let eth_client_blobs = PKSigningClient::from_config_blobs(
-            &self.eth_sender_config,
-            &self.contracts_config,
-            &self.eth_client_config,
-        );
-        let eth_client_blobs_addr = eth_client_blobs.clone().map(|k| k.sender_account());
+        let eth_client_blobs_addr = eth_client_blobs
+            .as_ref()
+            .map(|client| client.sender_account());
 
         let l1_batch_commit_data_generator: Arc<dyn L1BatchCommitDataGenerator> =
             match self.l1_batch_commit_data_generator_mode {
@@ -91,13 +106,22 @@ impl WiringLayer for EthSenderLayer {
                 }
             };
 
+        let proof_verification_layer: Arc<dyn ProofVerificationLayer> =
+            match self.eth_sender_config.sender.proof_verification_layer {
+                ProofVerificationLayerMode::NewHorizen => {
+                    Arc::new(context.get_resource::<NhClientResource>().await?.client)
+                }
+                ProofVerificationLayerMode::L1 => Arc::new(L1ProofVerificationLayer),
+            };
+
         let aggregator = Aggregator::new(
             self.eth_sender_config.sender.clone(),
             object_store,
             eth_client_blobs_addr.is_some(),
             self.eth_sender_config.sender.pubdata_sending_mode.into(),
+            self.da_inclusion_required,
             l1_batch_commit_data_generator.clone(),
-            None,
+            proof_verification_layer,
         );
 
         let config = self.eth_sender_config.sender;
@@ -127,8 +151,10 @@ impl WiringLayer for EthSenderLayer {
             config,
             gas_adjuster,
             eth_client,
-            eth_client_blobs.map(|c| Arc::new(c) as Arc<dyn BoundEthInterface>),
-        );
+            eth_client_blobs,
+        )
+        .with_fee_bump_config(&self.eth_sender_config.fee_bump)
+        .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
 
         context.add_task(Box::new(EthTxManagerTask {
             eth_tx_manager_actor,
@@ -137,7 +163,14 @@ impl WiringLayer for EthSenderLayer {
         // Insert circuit breaker.
         let CircuitBreakersResource { breakers } = context.get_resource_or_default().await;
         breakers
-            .insert(Box::new(FailedL1TransactionChecker { pool: replica_pool }))
+            .insert(Box::new(FailedL1TransactionChecker {
+                pool: replica_pool.clone(),
+            }))
+            .await;
+        breakers
+            .insert(Box::new(AttestationPathMismatchChecker {
+                pool: replica_pool,
+            }))
             .await;
 
         Ok(())
@@ -14,13 +14,16 @@ use zksync_core::house_keeper::{
     fri_scheduler_circuit_queuer::SchedulerCircuitQueuer,
     fri_witness_generator_jobs_retry_manager::FriWitnessGeneratorJobRetryManager,
     fri_witness_generator_queue_monitor::FriWitnessGeneratorStatsReporter,
-    periodic_job::PeriodicJob,
+    object_store_garbage_collector::ObjectStoreGarbageCollector, periodic_job::PeriodicJob,
     waiting_to_queued_fri_witness_job_mover::WaitingToQueuedFriWitnessJobMover,
 };
 use zksync_dal::{metrics::PostgresMetrics, ConnectionPool, Core};
 
 use crate::{
-    implementations::resources::pools::{ProverPoolResource, ReplicaPoolResource},
+    implementations::resources::{
+        object_store::ObjectStoreResource,
+        pools::{ProverPoolResource, ReplicaPoolResource},
+    },
     service::{ServiceContext, StopReceiver},
     task::Task,
     wiring_layer::{WiringError, WiringLayer},
@@ -127,6 +130,25 @@ impl WiringLayer for HouseKeeperLayer {
             }));
         }
 
+        if self.house_keeper_config.object_store_gc_enabled() {
+            let blob_store = context.get_resource::<ObjectStoreResource>().await?.0;
+            let object_store_gc = ObjectStoreGarbageCollector::new(
+                replica_pool.clone(),
+                blob_store,
+                Duration::from_secs(
+                    self.house_keeper_config
+                        .object_store_gc_retention_secs
+                        .unwrap(),
+                ),
+                self.house_keeper_config
+                    .object_store_gc_interval_ms
+                    .unwrap(),
+            );
+            context.add_task(Box::new(ObjectStoreGarbageCollectorTask {
+                object_store_gc,
+            }));
+        }
+
         let scheduler_circuit_queuer = SchedulerCircuitQueuer::new(
             self.house_keeper_config.fri_witness_job_moving_interval_ms,
             prover_pool.clone(),
@@ -365,3 +387,19 @@ impl Task for FriProverJobArchiverTask {
         self.fri_prover_job_archiver.run(stop_receiver.0).await
     }
 }
+
+#[derive(Debug)]
+struct ObjectStoreGarbageCollectorTask {
+    object_store_gc: ObjectStoreGarbageCollector,
+}
+
+#[async_trait::async_trait]
+impl Task for ObjectStoreGarbageCollectorTask {
+    fn name(&self) -> &'static str {
+        "object_store_garbage_collector"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.object_store_gc.run(stop_receiver.0).await
+    }
+}
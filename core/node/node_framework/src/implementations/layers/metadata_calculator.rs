@@ -1,4 +1,8 @@
-use zksync_core::metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig};
+use std::path::PathBuf;
+
+use zksync_core::metadata_calculator::{
+    MetadataCalculator, MetadataCalculatorConfig, TreeCheckpointStorage,
+};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_storage::RocksDB;
 
@@ -17,11 +21,33 @@ use crate::{
 /// ## Effects
 ///
 /// - Resolves `MasterPoolResource`.
-/// - Resolves `ObjectStoreResource` (optional).
+/// - Resolves `ObjectStoreResource` (optional; required if tree checkpoints are stored in the object store).
 /// - Adds `tree_health_check` to the `ResourceCollection<HealthCheckResource>`.
 /// - Adds `metadata_calculator` to the node.
 #[derive(Debug)]
-pub struct MetadataCalculatorLayer(pub MetadataCalculatorConfig);
+pub struct MetadataCalculatorLayer {
+    config: MetadataCalculatorConfig,
+    checkpoint_path: Option<PathBuf>,
+    checkpoints_in_object_store: bool,
+}
+
+impl MetadataCalculatorLayer {
+    pub fn new(config: MetadataCalculatorConfig) -> Self {
+        Self {
+            config,
+            checkpoint_path: None,
+            checkpoints_in_object_store: false,
+        }
+    }
+
+    /// Makes the calculator bootstrap an empty tree from checkpoints in the specified directory, or in the object store
+    /// if `in_object_store` is set (the directory is then used to stage exported checkpoints).
+    pub fn with_checkpoints(mut self, checkpoint_path: PathBuf, in_object_store: bool) -> Self {
+        self.checkpoint_path = Some(checkpoint_path);
+        self.checkpoints_in_object_store = in_object_store;
+        self
+    }
+}
 
 #[derive(Debug)]
 pub struct MetadataCalculatorTask {
@@ -46,8 +72,30 @@ impl WiringLayer for MetadataCalculatorLayer {
             );
         }
 
-        let metadata_calculator =
-            MetadataCalculator::new(self.0, object_store.map(|os| os.0)).await?;
+        let checkpoint_storage = match self.checkpoint_path {
+            Some(path) => {
+                let checkpoint_store = if self.checkpoints_in_object_store {
+                    let store = object_store.as_ref().ok_or_else(|| {
+                        WiringError::Configuration(
+                            "Merkle tree checkpoints are configured to be stored in the object store, \
+                             but the object store is not provided"
+                                .to_owned(),
+                        )
+                    })?;
+                    Some(store.0.clone())
+                } else {
+                    None
+                };
+                Some(TreeCheckpointStorage::new(path, checkpoint_store))
+            }
+            None => None,
+        };
+
+        let mut metadata_calculator =
+            MetadataCalculator::new(self.config, object_store.map(|os| os.0)).await?;
+        if let Some(checkpoint_storage) = checkpoint_storage {
+            metadata_calculator = metadata_calculator.with_checkpoint_storage(checkpoint_storage);
+        }
 
         let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
         app_health.insert_component(metadata_calculator.tree_health_check());
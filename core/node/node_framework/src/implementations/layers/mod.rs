@@ -9,6 +9,7 @@ pub mod house_keeper;
 pub mod l1_batch_commit_data_generator;
 pub mod l1_gas;
 pub mod metadata_calculator;
+pub mod new_horizen_client;
 pub mod object_store;
 pub mod pk_signing_eth_client;
 pub mod pools_layer;
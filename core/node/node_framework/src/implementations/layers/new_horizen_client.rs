@@ -0,0 +1,43 @@
+use anyhow::Context as _;
+use zksync_config::configs::NewHorizenConfig;
+use zksync_core::new_horizen::NhClient;
+
+use crate::{
+    implementations::resources::new_horizen::NhClientResource,
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Connects to the NewHorizen chain.
+///
+/// ## Effects
+///
+/// - Adds `NhClientResource` to the resources.
+#[derive(Debug)]
+pub struct NewHorizenClientLayer {
+    config: NewHorizenConfig,
+}
+
+impl NewHorizenClientLayer {
+    pub fn new(config: NewHorizenConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for NewHorizenClientLayer {
+    fn layer_name(&self) -> &'static str {
+        "new_horizen_client_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let client = NhClient::from_config(&self.config)
+            .await
+            .context("NhClient::from_config()")?;
+        context.insert_resource(NhClientResource {
+            client,
+            config: self.config,
+        })?;
+        Ok(())
+    }
+}
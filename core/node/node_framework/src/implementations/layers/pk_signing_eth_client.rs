@@ -1,10 +1,14 @@
 use std::sync::Arc;
 
-use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
-use zksync_eth_client::clients::PKSigningClient;
+use zksync_config::{
+    configs::RemoteSignerConfig, ContractsConfig, ETHClientConfig, ETHSenderConfig,
+};
+use zksync_eth_client::clients::{PKSigningClient, Web3SignerClient};
 
 use crate::{
-    implementations::resources::eth_interface::BoundEthInterfaceResource,
+    implementations::resources::eth_interface::{
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource,
+    },
     service::ServiceContext,
     wiring_layer::{WiringError, WiringLayer},
 };
@@ -14,6 +18,7 @@ pub struct PKSigningEthClientLayer {
     eth_sender_config: ETHSenderConfig,
     contracts_config: ContractsConfig,
     eth_client_config: ETHClientConfig,
+    remote_signer_config: Option<RemoteSignerConfig>,
 }
 
 impl PKSigningEthClientLayer {
@@ -26,8 +31,45 @@ impl PKSigningEthClientLayer {
             eth_sender_config,
             contracts_config,
             eth_client_config,
+            remote_signer_config: None,
         }
     }
+
+    /// Makes the layer sign transactions via a remote signer instead of the operator private keys
+    /// from the environment.
+    pub fn with_remote_signer(mut self, remote_signer_config: RemoteSignerConfig) -> Self {
+        self.remote_signer_config = Some(remote_signer_config);
+        self
+    }
+
+    async fn wire_remote_signer(
+        &self,
+        remote_signer_config: &RemoteSignerConfig,
+        context: &mut ServiceContext<'_>,
+    ) -> Result<(), WiringError> {
+        let signing_client = Web3SignerClient::from_config(
+            &self.eth_sender_config,
+            &self.contracts_config,
+            &self.eth_client_config,
+            remote_signer_config,
+        )
+        .await
+        .map_err(|err| WiringError::Internal(err.into()))?;
+        context.insert_resource(BoundEthInterfaceResource(Arc::new(signing_client)))?;
+
+        let signing_client_for_blobs = Web3SignerClient::from_config_blobs(
+            &self.eth_sender_config,
+            &self.contracts_config,
+            &self.eth_client_config,
+            remote_signer_config,
+        )
+        .await
+        .map_err(|err| WiringError::Internal(err.into()))?;
+        if let Some(client) = signing_client_for_blobs {
+            context.insert_resource(BoundEthInterfaceForBlobsResource(Arc::new(client)))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -37,6 +79,12 @@ impl WiringLayer for PKSigningEthClientLayer {
     }
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        if let Some(remote_signer_config) = &self.remote_signer_config {
+            return self
+                .wire_remote_signer(remote_signer_config, &mut context)
+                .await;
+        }
+
         if self.eth_sender_config.sender.private_key().is_none() {
             return Err(WiringError::Configuration(
                 "Private key is missing in ETHSenderConfig".to_string(),
@@ -49,6 +97,15 @@ impl WiringLayer for PKSigningEthClientLayer {
             &self.eth_client_config,
         );
         context.insert_resource(BoundEthInterfaceResource(Arc::new(signing_client)))?;
+
+        let signing_client_for_blobs = PKSigningClient::from_config_blobs(
+            &self.eth_sender_config,
+            &self.contracts_config,
+            &self.eth_client_config,
+        );
+        if let Some(client) = signing_client_for_blobs {
+            context.insert_resource(BoundEthInterfaceForBlobsResource(Arc::new(client)))?;
+        }
         Ok(())
     }
 }
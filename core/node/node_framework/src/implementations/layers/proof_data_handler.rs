@@ -1,12 +1,18 @@
 use std::sync::Arc;
 
-use zksync_config::configs::ProofDataHandlerConfig;
-use zksync_core::proof_data_handler;
+use zksync_config::configs::{eth_sender::ProofVerificationLayerMode, ProofDataHandlerConfig};
+use zksync_core::{
+    new_horizen::{NhHealthCheck, NhProofSubmitter},
+    proof_data_handler,
+};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
 
 use crate::{
-    implementations::resources::{object_store::ObjectStoreResource, pools::MasterPoolResource},
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource, new_horizen::NhClientResource,
+        object_store::ObjectStoreResource, pools::MasterPoolResource,
+    },
     service::{ServiceContext, StopReceiver},
     task::Task,
     wiring_layer::{WiringError, WiringLayer},
@@ -19,15 +25,22 @@ use crate::{
 /// - Resolves `MasterPoolResource`.
 /// - Resolves `ObjectStoreResource`.
 /// - Adds `proof_data_handler` to the node.
+/// - If proofs are attested by NewHorizen, resolves `NhClientResource`, adds the NH proof submitter
+///   and NH health checks to `AppHealthCheckResource` and adds `nh_proof_submitter` to the node.
 #[derive(Debug)]
 pub struct ProofDataHandlerLayer {
     proof_data_handler_config: ProofDataHandlerConfig,
+    proof_verification_layer: ProofVerificationLayerMode,
 }
 
 impl ProofDataHandlerLayer {
-    pub fn new(proof_data_handler_config: ProofDataHandlerConfig) -> Self {
+    pub fn new(
+        proof_data_handler_config: ProofDataHandlerConfig,
+        proof_verification_layer: ProofVerificationLayerMode,
+    ) -> Self {
         Self {
             proof_data_handler_config,
+            proof_verification_layer,
         }
     }
 }
@@ -44,8 +57,26 @@ impl WiringLayer for ProofDataHandlerLayer {
 
         let object_store = context.get_resource::<ObjectStoreResource>().await?;
 
+        if self.proof_verification_layer.attests_proofs() {
+            let NhClientResource { client, config } =
+                context.get_resource::<NhClientResource>().await?;
+            let client = Arc::new(client);
+            let nh_health_check = NhHealthCheck::new(
+                client.clone(),
+                pool_resource.get_singleton().await?,
+                config.max_attestation_delay(),
+            );
+            let nh_proof_submitter =
+                NhProofSubmitter::new(pool_resource.get_singleton().await?, client, config);
+            let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+            app_health.insert_component(nh_proof_submitter.health_check());
+            app_health.insert_custom_component(Arc::new(nh_health_check));
+            context.add_task(Box::new(NhProofSubmitterTask { nh_proof_submitter }));
+        }
+
         context.add_task(Box::new(ProofDataHandlerTask {
             proof_data_handler_config: self.proof_data_handler_config,
+            proof_verification_layer: self.proof_verification_layer,
             blob_store: object_store.0,
             main_pool,
         }));
@@ -57,6 +88,7 @@ impl WiringLayer for ProofDataHandlerLayer {
 #[derive(Debug)]
 struct ProofDataHandlerTask {
     proof_data_handler_config: ProofDataHandlerConfig,
+    proof_verification_layer: ProofVerificationLayerMode,
     blob_store: Arc<dyn ObjectStore>,
     main_pool: ConnectionPool<Core>,
 }
@@ -72,9 +104,25 @@ impl Task for ProofDataHandlerTask {
             self.proof_data_handler_config,
             self.blob_store,
             self.main_pool,
-            None,
+            self.proof_verification_layer,
             stop_receiver.0,
         )
         .await
     }
 }
+
+#[derive(Debug)]
+struct NhProofSubmitterTask {
+    nh_proof_submitter: NhProofSubmitter,
+}
+
+#[async_trait::async_trait]
+impl Task for NhProofSubmitterTask {
+    fn name(&self) -> &'static str {
+        "nh_proof_submitter"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.nh_proof_submitter.run(stop_receiver.0).await
+    }
+}
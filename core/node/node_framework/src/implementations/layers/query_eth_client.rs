@@ -1,7 +1,6 @@
-use std::sync::Arc;
-
 use anyhow::Context;
-use zksync_eth_client::clients::QueryClient;
+use zksync_config::ETHClientConfig;
+use zksync_eth_client::clients::l1_client;
 
 use crate::{
     implementations::resources::eth_interface::EthInterfaceResource,
@@ -11,12 +10,12 @@ use crate::{
 
 #[derive(Debug)]
 pub struct QueryEthClientLayer {
-    web3_url: String,
+    config: ETHClientConfig,
 }
 
 impl QueryEthClientLayer {
-    pub fn new(web3_url: String) -> Self {
-        Self { web3_url }
+    pub fn new(config: ETHClientConfig) -> Self {
+        Self { config }
     }
 }
 
@@ -27,8 +26,8 @@ impl WiringLayer for QueryEthClientLayer {
     }
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
-        let query_client = QueryClient::new(&self.web3_url).context("QueryClient::new()")?;
-        context.insert_resource(EthInterfaceResource(Arc::new(query_client)))?;
+        let query_client = l1_client(&self.config).context("l1_client()")?;
+        context.insert_resource(EthInterfaceResource(query_client))?;
         Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use std::{fmt, sync::Arc};
+use std::{fmt, path::PathBuf, sync::Arc};
 
 use zksync_core::api_server::{
     execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
@@ -23,6 +23,8 @@ pub struct PostgresStorageCachesConfig {
     pub factory_deps_cache_size: u64,
     pub initial_writes_cache_size: u64,
     pub latest_values_cache_size: u64,
+    pub disk_cache_path: Option<PathBuf>,
+    pub disk_cache_size: u64,
 }
 
 #[derive(Debug)]
@@ -76,6 +78,15 @@ impl WiringLayer for TxSenderLayer {
         let mut storage_caches =
             PostgresStorageCaches::new(factory_deps_capacity, initial_writes_capacity);
 
+        if let Some(path) = self.postgres_storage_caches_config.disk_cache_path {
+            storage_caches
+                .configure_disk_cache(
+                    path,
+                    self.postgres_storage_caches_config.disk_cache_size,
+                    &replica_pool,
+                )
+                .await?;
+        }
         if values_capacity > 0 {
             let values_cache_task = storage_caches
                 .configure_storage_values_cache(values_capacity, replica_pool.clone());
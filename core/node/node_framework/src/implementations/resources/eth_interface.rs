@@ -21,3 +21,13 @@ impl Resource for BoundEthInterfaceResource {
         "common/bound_eth_interface".into()
     }
 }
+
+/// Same as [`BoundEthInterfaceResource`], but for the account used to send EIP-4844 blob transactions.
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForBlobsResource(pub Arc<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceForBlobsResource {
+    fn resource_id() -> ResourceId {
+        "common/bound_eth_interface_for_blobs".into()
+    }
+}
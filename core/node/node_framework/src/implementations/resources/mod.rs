@@ -4,6 +4,7 @@ pub mod fee_input;
 pub mod healthcheck;
 pub mod l1_batch_commit_data_generator;
 pub mod l1_tx_params;
+pub mod new_horizen;
 pub mod object_store;
 pub mod pools;
 pub mod state_keeper;
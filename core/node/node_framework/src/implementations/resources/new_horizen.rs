@@ -0,0 +1,17 @@
+use zksync_config::configs::NewHorizenConfig;
+use zksync_core::new_horizen::NhClient;
+
+use crate::resource::Resource;
+
+/// Client for the NewHorizen chain together with the config it was created from.
+#[derive(Debug, Clone)]
+pub struct NhClientResource {
+    pub client: NhClient,
+    pub config: NewHorizenConfig,
+}
+
+impl Resource for NhClientResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "common/nh_client".into()
+    }
+}
@@ -31,6 +31,10 @@ async fn prover_and_assert_base_layer(
             file_backed_base_path: "./tests/data/".to_owned(),
         },
         max_retries: 5,
+        compressed_buckets: vec![],
+        deduplicated_buckets: vec![],
+        local_cache_path: None,
+        local_cache_max_size_bytes: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
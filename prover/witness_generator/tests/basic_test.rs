@@ -36,6 +36,10 @@ async fn test_leaf_witness_gen() {
             file_backed_base_path: "./tests/data/leaf/".to_owned(),
         },
         max_retries: 5,
+        compressed_buckets: vec![],
+        deduplicated_buckets: vec![],
+        local_cache_path: None,
+        local_cache_max_size_bytes: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -76,6 +80,10 @@ async fn test_node_witness_gen() {
             file_backed_base_path: "./tests/data/node/".to_owned(),
         },
         max_retries: 5,
+        compressed_buckets: vec![],
+        deduplicated_buckets: vec![],
+        local_cache_path: None,
+        local_cache_max_size_bytes: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -117,6 +125,10 @@ async fn test_scheduler_witness_gen() {
             file_backed_base_path: "./tests/data/scheduler/".to_owned(),
         },
         max_retries: 5,
+        compressed_buckets: vec![],
+        deduplicated_buckets: vec![],
+        local_cache_path: None,
+        local_cache_max_size_bytes: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()